@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use brontes_database::cex_source::{import_cex_data, LocalCexFiles};
+use brontes_types::init_thread_pools;
+use clap::Parser;
+
+use crate::{cli::load_libmdbx, runner::CliContext};
+
+#[derive(Debug, Parser)]
+pub struct CexImport {
+    /// Start block
+    #[arg(long, short)]
+    pub start_block: u64,
+    /// End block (inclusive)
+    #[arg(long, short)]
+    pub end_block:   u64,
+    /// Symbol mapping file (.parquet or .csv) with the columns `exchange`,
+    /// `symbol_pair`, `base_address` and `quote_address`
+    #[arg(long)]
+    pub symbols:     PathBuf,
+    /// Trades file (.parquet or .csv) with the columns `exchange`, `symbol`,
+    /// `timestamp`, `side`, `price`, `amount` and optionally `trade_type`
+    #[arg(long)]
+    pub trades:      Option<PathBuf>,
+    /// Quotes file (.parquet or .csv) with the columns `exchange`, `symbol`,
+    /// `timestamp`, `ask_amount`, `ask_price`, `bid_price` and `bid_amount`
+    #[arg(long)]
+    pub quotes:      Option<PathBuf>,
+}
+
+impl CexImport {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        if self.trades.is_none() && self.quotes.is_none() {
+            eyre::bail!("at least one of --trades or --quotes must be set")
+        }
+        if self.start_block > self.end_block {
+            eyre::bail!("start block must be less than or equal to the end block")
+        }
+
+        init_thread_pools(10);
+
+        let libmdbx = load_libmdbx(&ctx.task_executor, brontes_db_path)?;
+        let source = LocalCexFiles::new(self.symbols, self.trades, self.quotes);
+
+        import_cex_data(&libmdbx, &source, self.start_block..=self.end_block)
+    }
+}
@@ -3,6 +3,7 @@ use std::{path::Path, sync::Arc};
 use brontes_database::{
     clickhouse::cex_config::CexDownloadConfig, libmdbx::initialize::LibmdbxInitializer,
 };
+use brontes_types::ChainConfig;
 use clap::Parser;
 use indicatif::{ProgressBar, ProgressDrawTarget};
 use tracing::{debug, error, info};
@@ -57,7 +58,8 @@ impl ClickhouseDownload {
             Path::new(&std::env::var("DB_PATH").expect("DB_PATH not found in .env")),
             10,
             ctx.task_executor.clone(),
-        ));
+            ChainConfig::get(),
+        )?);
         debug!(target: "brontes::db::clickhouse-download", "made tracer");
 
         let initializer = LibmdbxInitializer::new(libmdbx, clickhouse, tracer, true);
@@ -13,7 +13,8 @@ pub struct Clear {
         value_delimiter = ',',
         default_value = "CexPrice,DexPrice,CexTrades,BlockInfo,InitializedState,MevBlocks,\
                          TokenDecimals,AddressToProtocolInfo,PoolCreationBlocks,Builder,\
-                         AddressMeta,SearcherEOAs,SearcherContracts,SubGraphs,TxTraces"
+                         AddressMeta,SearcherEOAs,SearcherContracts,SubGraphs,TxTraces,RelayBids,\
+                         BuilderProposerPayments,SearcherFailedAttempts"
     )]
     pub tables:                  Vec<Tables>,
     /// Mark metadata as uninitialized in the initialized state table
@@ -66,7 +67,10 @@ impl Clear {
                 AddressMeta,
                 SearcherEOAs,
                 SearcherContracts,
-                TxTraces
+                TxTraces,
+                RelayBids,
+                BuilderProposerPayments,
+                SearcherFailedAttempts
             )
         });
 
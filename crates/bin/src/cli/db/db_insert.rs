@@ -53,6 +53,9 @@ impl Insert {
             SearcherEOAs,
             SearcherContracts,
             InitializedState,
+            RelayBids,
+            BuilderProposerPayments,
+            SearcherFailedAttempts,
             PoolCreationBlocks = &self.key,
             &self.value
         );
@@ -77,7 +77,10 @@ impl DatabaseQuery {
                     AddressMeta,
                     SearcherEOAs,
                     SearcherContracts,
-                    TxTraces
+                    TxTraces,
+                    RelayBids,
+                    BuilderProposerPayments,
+                    SearcherFailedAttempts
                 );
             } else {
                 match_table!(
@@ -97,6 +100,9 @@ impl DatabaseQuery {
                     SearcherEOAs,
                     SearcherContracts,
                     TxTraces,
+                    RelayBids,
+                    BuilderProposerPayments,
+                    SearcherFailedAttempts,
                     PoolCreationBlocks = &self.key
                 );
             }
@@ -2,7 +2,7 @@ use std::path::Path;
 
 use brontes_core::decoding::Parser as DParser;
 use brontes_metrics::ParserMetricsListener;
-use brontes_types::{init_thread_pools, UnboundedYapperReceiver};
+use brontes_types::{init_thread_pools, ChainConfig, UnboundedYapperReceiver};
 use clap::Parser;
 use futures::StreamExt;
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressState, ProgressStyle};
@@ -46,8 +46,12 @@ impl DiscoveryFill {
         let libmdbx =
             static_object(load_read_only_database(&ctx.task_executor, brontes_db_path).await?);
 
-        let tracer =
-            get_tracing_provider(Path::new(&db_path), max_tasks as u64, ctx.task_executor.clone());
+        let tracer = get_tracing_provider(
+            Path::new(&db_path),
+            max_tasks as u64,
+            ctx.task_executor.clone(),
+            ChainConfig::get(),
+        )?;
 
         let parser = static_object(DParser::new(metrics_tx, libmdbx, tracer.clone()).await);
 
@@ -1,9 +1,10 @@
 use std::path::Path;
 
 use brontes_core::decoding::Parser as DParser;
+use brontes_database::libmdbx::SearcherLabels;
 use brontes_metrics::ParserMetricsListener;
 use brontes_types::{
-    init_thread_pools, unordered_buffer_map::BrontesStreamExt, UnboundedYapperReceiver,
+    init_thread_pools, unordered_buffer_map::BrontesStreamExt, ChainConfig, UnboundedYapperReceiver,
 };
 use clap::Parser;
 use futures::StreamExt;
@@ -37,11 +38,23 @@ impl TestTraceArgs {
         ctx.task_executor
             .spawn_critical("metrics", metrics_listener);
 
-        let libmdbx =
-            static_object(load_database(&ctx.task_executor, brontes_db_path, None, None).await?);
+        let libmdbx = static_object(
+            load_database(
+                &ctx.task_executor,
+                brontes_db_path,
+                None,
+                None,
+                SearcherLabels::default(),
+            )
+            .await?,
+        );
 
-        let tracer =
-            get_tracing_provider(Path::new(&db_path), max_tasks, ctx.task_executor.clone());
+        let tracer = get_tracing_provider(
+            Path::new(&db_path),
+            max_tasks,
+            ctx.task_executor.clone(),
+            ChainConfig::get(),
+        )?;
 
         let parser = static_object(DParser::new(metrics_tx, libmdbx, tracer.clone()).await);
 
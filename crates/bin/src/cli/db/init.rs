@@ -1,7 +1,10 @@
 use std::{path::Path, sync::Arc};
 
-use brontes_database::{libmdbx::LibmdbxInit, Tables};
-use brontes_types::{db::cex::CexExchange, init_thread_pools};
+use brontes_database::{
+    libmdbx::{LibmdbxInit, SearcherLabels},
+    Tables,
+};
+use brontes_types::{db::cex::CexExchange, init_thread_pools, ChainConfig};
 use clap::Parser;
 use indicatif::MultiProgress;
 use itertools::Itertools;
@@ -70,11 +73,18 @@ impl Init {
         init_thread_pools(10);
         let task_executor = ctx.task_executor;
 
-        let libmdbx =
-            static_object(load_database(&task_executor, brontes_db_path, None, None).await?);
+        let libmdbx = static_object(
+            load_database(&task_executor, brontes_db_path, None, None, SearcherLabels::default())
+                .await?,
+        );
         let clickhouse = static_object(load_clickhouse(Default::default(), None).await?);
 
-        let tracer = Arc::new(get_tracing_provider(Path::new(&db_path), 10, task_executor.clone()));
+        let tracer = Arc::new(get_tracing_provider(
+            Path::new(&db_path),
+            10,
+            task_executor.clone(),
+            ChainConfig::get(),
+        )?);
 
         if self.init_libmdbx {
             // currently inits all tables
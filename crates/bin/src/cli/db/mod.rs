@@ -3,6 +3,7 @@ mod r2_uploader;
 mod snapshot;
 use crate::runner::CliContext;
 mod cex_data;
+mod cex_import;
 #[cfg(feature = "local-clickhouse")]
 mod clickhouse_download;
 mod db_clear;
@@ -14,6 +15,7 @@ mod discovery;
 mod ensure_test_traces;
 mod export;
 mod init;
+mod relay_bids;
 mod table_stats;
 #[cfg(feature = "local-clickhouse")]
 mod tip_tracer;
@@ -44,6 +46,13 @@ pub enum DatabaseCommands {
     /// Fetches Cex data from the Sorella DB
     #[command(name = "cex-query")]
     CexData(cex_data::CexDB),
+    /// Imports Cex trades and quotes from local parquet or csv files
+    #[command(name = "cex-import")]
+    CexImport(cex_import::CexImport),
+    /// Imports the bids the relays received from the relay apis or a local
+    /// dump
+    #[command(name = "relay-bids")]
+    RelayBids(relay_bids::RelayBidsImport),
     /// Fetch data from the api and insert it into
     /// libmdbx.
     #[command(name = "init")]
@@ -95,6 +104,8 @@ impl Database {
             DatabaseCommands::TableStats(cmd) => cmd.execute(brontes_db_path),
             DatabaseCommands::DownloadSnapshot(cmd) => cmd.execute(brontes_db_path, ctx).await,
             DatabaseCommands::CexData(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::CexImport(cmd) => cmd.execute(brontes_db_path, ctx).await,
+            DatabaseCommands::RelayBids(cmd) => cmd.execute(brontes_db_path, ctx).await,
             #[cfg(feature = "local-clickhouse")]
             DatabaseCommands::DownloadClickhouse(cmd) => cmd.execute(brontes_db_path, ctx).await,
             #[cfg(feature = "local-clickhouse")]
@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use brontes_database::relay_bids::{import_relay_bids, RelayBidSource};
+use brontes_types::block_metadata::Relays;
+use clap::Parser;
+
+use crate::{cli::load_libmdbx, runner::CliContext};
+
+#[derive(Debug, Parser)]
+pub struct RelayBidsImport {
+    /// Start block
+    #[arg(long, short)]
+    pub start_block: u64,
+    /// End block (inclusive)
+    #[arg(long, short)]
+    pub end_block:   u64,
+    /// Newline delimited json file of bids in the format of the relay data
+    /// api, if omitted the bids are fetched from all relays
+    #[arg(long, requires = "relay")]
+    pub dump:        Option<PathBuf>,
+    /// Relay that received the bids of the dump
+    #[arg(long)]
+    pub relay:       Option<Relays>,
+}
+
+impl RelayBidsImport {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        if self.start_block > self.end_block {
+            eyre::bail!("start block must be less than or equal to the end block")
+        }
+
+        let libmdbx = load_libmdbx(&ctx.task_executor, brontes_db_path)?;
+        let source = match (self.dump, self.relay) {
+            (Some(path), Some(relay)) => RelayBidSource::Dump { path, relay },
+            _ => RelayBidSource::Relays,
+        };
+
+        import_relay_bids(&libmdbx, &source, self.start_block..=self.end_block).await
+    }
+}
@@ -3,7 +3,7 @@ use std::path::Path;
 use brontes_core::decoding::Parser as DParser;
 use brontes_metrics::ParserMetricsListener;
 use brontes_types::{
-    init_thread_pools, unordered_buffer_map::BrontesStreamExt, UnboundedYapperReceiver,
+    init_thread_pools, unordered_buffer_map::BrontesStreamExt, ChainConfig, UnboundedYapperReceiver,
 };
 use clap::Parser;
 use futures::{join, StreamExt};
@@ -41,8 +41,12 @@ impl TipTraceArgs {
         let libmdbx =
             static_object(load_read_only_database(&ctx.task_executor, brontes_db_path).await?);
 
-        let tracer =
-            get_tracing_provider(Path::new(&db_path), max_tasks, ctx.task_executor.clone());
+        let tracer = get_tracing_provider(
+            Path::new(&db_path),
+            max_tasks,
+            ctx.task_executor.clone(),
+            ChainConfig::get(),
+        )?;
 
         let parser = static_object(DParser::new(metrics_tx, libmdbx, tracer.clone()).await);
         let mut end_block = parser.get_latest_block_number().unwrap();
@@ -0,0 +1,100 @@
+use std::path::{Path, PathBuf};
+
+use alloy_primitives::B256;
+use brontes_core::decoding::Parser as DParser;
+use brontes_inspect::Inspectors;
+use brontes_types::{db::cex::CexExchange, init_thread_pools, ChainConfig};
+use clap::Parser;
+use tokio::sync::mpsc::unbounded_channel;
+
+use super::{
+    config_searcher_labels, determine_max_tasks, get_env_vars, get_tracing_provider,
+    init_inspectors, load_database, load_inspector_config, run::TimeWindowArgs, static_object,
+};
+use crate::{runner::CliContext, TxExplainer};
+
+#[derive(Debug, Parser)]
+pub struct ExplainArgs {
+    /// Hash of the transaction to explain
+    pub tx_hash:          B256,
+    /// Inspectors to run. If omitted it defaults to running all inspectors
+    #[arg(long, short, value_delimiter = ',')]
+    pub inspectors:       Option<Vec<Inspectors>>,
+    /// Optional quote asset, if omitted it will default to the quote asset of
+    /// the chain config, which is USDT on mainnet
+    #[arg(long, short)]
+    pub quote_asset:      Option<String>,
+    /// Path to the inspector config toml, see `brontes run --config`
+    #[arg(long)]
+    pub config:           Option<PathBuf>,
+    /// Time window arguments for cex data
+    #[clap(flatten)]
+    pub time_window_args: TimeWindowArgs,
+    /// CEX exchanges to consider for cex-dex analysis
+    #[arg(
+        long,
+        short,
+        default_value = "Binance,Coinbase,Okex,BybitSpot,Kucoin",
+        value_delimiter = ','
+    )]
+    pub cex_exchanges:    Vec<CexExchange>,
+}
+
+impl ExplainArgs {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        let inspector_config = load_inspector_config(self.config.as_deref())?;
+        let reth_db_path = get_env_vars()?;
+        let chain = ChainConfig::get();
+        let quote_asset = self
+            .quote_asset
+            .as_deref()
+            .map(str::parse)
+            .transpose()?
+            .unwrap_or(chain.quote_asset);
+
+        let max_tasks = determine_max_tasks(None);
+        init_thread_pools(max_tasks as usize);
+
+        let libmdbx = static_object(
+            load_database(
+                &ctx.task_executor,
+                brontes_db_path,
+                None,
+                None,
+                config_searcher_labels(&inspector_config)?,
+            )
+            .await?,
+        );
+        let tracer = get_tracing_provider(
+            Path::new(&reth_db_path),
+            max_tasks,
+            ctx.task_executor.clone(),
+            chain,
+        )?;
+        // tracing metrics aren't collected for a single block
+        let (metrics_tx, _metrics_rx) = unbounded_channel();
+        let parser = static_object(DParser::new(metrics_tx, libmdbx, tracer).await);
+
+        let inspectors = init_inspectors(
+            quote_asset,
+            libmdbx,
+            self.inspectors,
+            self.cex_exchanges,
+            self.time_window_args.trade_config(),
+            false,
+            &inspector_config,
+            chain,
+        );
+
+        TxExplainer::new(
+            self.tx_hash,
+            quote_asset,
+            inspectors,
+            parser,
+            libmdbx,
+            self.time_window_args.max_window(),
+        )
+        .run()
+        .await
+    }
+}
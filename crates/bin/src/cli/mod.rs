@@ -1,8 +1,13 @@
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand};
 
 mod db;
+mod explain;
 mod misc;
+mod reinspect;
 mod run;
+mod serve;
 mod utils;
 mod version_data;
 pub use utils::*;
@@ -20,6 +25,9 @@ pub struct Args {
     /// path to the brontes libmdbx db
     #[arg(long = "brontes-db-path", global = true)]
     pub brontes_db_path: Option<String>,
+    /// path to the chain config toml, if omitted mainnet is used
+    #[arg(long = "chain-config", global = true)]
+    pub chain_config:    Option<PathBuf>,
     /// The verbosity level of the logs
     #[clap(flatten)]
     pub verbosity:       Verbosity,
@@ -29,6 +37,14 @@ pub struct Args {
     pub skip_prometheus: bool,
 }
 
+impl Args {
+    /// Whether the processed blocks are streamed to stdout, in which case
+    /// nothing else may be written there
+    pub fn streams_to_stdout(&self) -> bool {
+        matches!(&self.command, Commands::Run(run) if run.sink_args.stdout)
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Subcommand)]
 pub enum Commands {
@@ -38,4 +54,13 @@ pub enum Commands {
     /// Brontes database commands
     #[command(name = "db")]
     Database(db::Database),
+    /// Serve the brontes database over a HTTP/JSON api
+    #[command(name = "serve")]
+    Serve(serve::ServeArgs),
+    /// Re-run inspectors over already processed blocks without re-tracing
+    #[command(name = "reinspect")]
+    Reinspect(reinspect::ReinspectArgs),
+    /// Explain how a single transaction was classified and inspected
+    #[command(name = "explain")]
+    Explain(explain::ExplainArgs),
 }
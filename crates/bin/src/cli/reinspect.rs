@@ -0,0 +1,118 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use brontes_inspect::Inspectors;
+use brontes_types::{db::cex::CexExchange, init_thread_pools, ChainConfig};
+use clap::Parser;
+
+use super::{
+    config_searcher_labels, determine_max_tasks, get_env_vars, get_tracing_provider,
+    init_inspectors, load_database, load_inspector_config, run::TimeWindowArgs, static_object,
+};
+use crate::{runner::CliContext, Reinspector};
+
+#[derive(Debug, Parser)]
+pub struct ReinspectArgs {
+    /// Start Block
+    #[arg(long, short)]
+    pub start_block:      u64,
+    /// End Block, inclusive
+    #[arg(long, short)]
+    pub end_block:        u64,
+    /// Inspectors to re-run, only the bundles of these inspectors are replaced
+    #[arg(long, short, value_delimiter = ',', required = true)]
+    pub inspectors:       Vec<Inspectors>,
+    /// Optional quote asset, if omitted it will default to the quote asset of
+    /// the chain config, which is USDT on mainnet
+    #[arg(long, short)]
+    pub quote_asset:      Option<String>,
+    /// Optional Max Tasks, if omitted it will default to 80% of the number of
+    /// physical cores on your machine
+    #[arg(long, short)]
+    pub max_tasks:        Option<u64>,
+    /// Path to the inspector config toml, see `brontes run --config`
+    #[arg(long)]
+    pub config:           Option<PathBuf>,
+    /// Time window arguments for cex data
+    #[clap(flatten)]
+    pub time_window_args: TimeWindowArgs,
+    /// CEX exchanges to consider for cex-dex analysis
+    #[arg(
+        long,
+        short,
+        default_value = "Binance,Coinbase,Okex,BybitSpot,Kucoin",
+        value_delimiter = ','
+    )]
+    pub cex_exchanges:    Vec<CexExchange>,
+}
+
+impl ReinspectArgs {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        if self.start_block > self.end_block {
+            return Err(eyre::eyre!("start block must be less than end block"))
+        }
+        // sandwich and jit bundles are composed into jit sandwiches, replacing only
+        // one of them would lose the bundles of the other
+        if self.inspectors.contains(&Inspectors::Sandwich)
+            != self.inspectors.contains(&Inspectors::Jit)
+        {
+            return Err(eyre::eyre!("the Sandwich and Jit inspectors need to be re-run together"))
+        }
+
+        let inspector_config = load_inspector_config(self.config.as_deref())?;
+        let reth_db_path = get_env_vars()?;
+        let chain = ChainConfig::get();
+        let quote_asset = self
+            .quote_asset
+            .as_deref()
+            .map(str::parse)
+            .transpose()?
+            .unwrap_or(chain.quote_asset);
+
+        let max_tasks = determine_max_tasks(self.max_tasks);
+        init_thread_pools(max_tasks as usize);
+
+        let libmdbx = static_object(
+            load_database(
+                &ctx.task_executor,
+                brontes_db_path,
+                None,
+                None,
+                config_searcher_labels(&inspector_config)?,
+            )
+            .await?,
+        );
+        let tracer = Arc::new(get_tracing_provider(
+            Path::new(&reth_db_path),
+            max_tasks,
+            ctx.task_executor.clone(),
+            chain,
+        )?);
+
+        let inspectors = init_inspectors(
+            quote_asset,
+            libmdbx,
+            Some(self.inspectors.clone()),
+            self.cex_exchanges,
+            self.time_window_args.trade_config(),
+            false,
+            &inspector_config,
+            chain,
+        );
+
+        Reinspector::new(
+            self.start_block,
+            self.end_block,
+            quote_asset,
+            &self.inspectors,
+            inspectors,
+            tracer,
+            libmdbx,
+            self.time_window_args.max_window(),
+        )
+        .run()
+        .await
+    }
+}
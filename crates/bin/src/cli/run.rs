@@ -1,24 +1,34 @@
-use std::{path::Path, time::Duration};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use brontes_core::decoding::Parser as DParser;
-use brontes_database::clickhouse::cex_config::CexDownloadConfig;
+use brontes_database::{
+    clickhouse::cex_config::CexDownloadConfig,
+    parquet::{ActionParquetWriter, DEFAULT_ACTION_BLOCKS_PER_FILE},
+};
 use brontes_inspect::Inspectors;
 use brontes_metrics::ParserMetricsListener;
 use brontes_types::{
-    constants::USDT_ADDRESS_STRING,
     db::cex::{trades::CexDexTradeConfig, CexExchange},
     db_write_trigger::{backup_server_heartbeat, start_hr_monitor, HeartRateMonitor},
-    init_thread_pools, UnboundedYapperReceiver,
+    init_thread_pools, BrontesTaskExecutor, ChainConfig, UnboundedYapperReceiver,
 };
 use clap::Parser;
+use malachite::Rational;
 use tokio::sync::mpsc::unbounded_channel;
 
-use super::{determine_max_tasks, get_env_vars, load_clickhouse, load_database, static_object};
+use super::{
+    config_searcher_labels, determine_max_tasks, get_env_vars, load_clickhouse, load_database,
+    load_inspector_config, static_object,
+};
 use crate::{
     banner::rain,
     cli::{get_tracing_provider, init_inspectors, load_tip_database},
     runner::CliContext,
-    BrontesRunConfig, MevProcessor, RangeType,
+    sinks::{BackPressure, ResultSinks, StdoutSink},
+    BrontesRunConfig, CheckpointStore, MevProcessor, RangeType,
 };
 
 const SECONDS_TO_US_FLOAT: f64 = 1_000_000.0;
@@ -35,6 +45,10 @@ pub struct RunArgs {
     /// starts running at tip from where brontes was last left at.
     #[arg(long, default_value_t = false)]
     pub from_db_tip:          bool,
+    /// Resume the block ranges of a previous run from their last checkpoint,
+    /// skipping the blocks that were already processed
+    #[arg(long, default_value_t = false)]
+    pub resume:               bool,
     /// Optional Multiple Ranges, format: "start1-end1 start2-end2 ..."
     /// Use this if you want to specify the exact, non continuous block ranges
     /// you want to run
@@ -47,15 +61,34 @@ pub struct RunArgs {
     /// Optional minimum batch size
     #[arg(long, default_value = "500")]
     pub min_batch_size:       u64,
-    /// Optional quote asset, if omitted it will default to USDT
-    #[arg(long, short, default_value = USDT_ADDRESS_STRING)]
-    pub quote_asset:          String,
+    /// Optional quote asset, if omitted it will default to the quote asset of
+    /// the chain config, which is USDT on mainnet
+    #[arg(long, short)]
+    pub quote_asset:          Option<String>,
     /// Inspectors to run. If omitted it defaults to running all inspectors
     #[arg(long, short, value_delimiter = ',')]
     pub inspectors:           Option<Vec<Inspectors>>,
+    /// Path to the inspector config toml with the detection thresholds, profit
+    /// floors, stable pairs and searcher labels. If omitted the defaults are
+    /// used
+    #[arg(long)]
+    pub config:               Option<PathBuf>,
     /// Time window arguments for cex data downloads
     #[clap(flatten)]
     pub time_window_args:     TimeWindowArgs,
+    /// Sinks the results of blocks processed at tip are streamed to
+    #[clap(flatten)]
+    pub sink_args:            SinkArgs,
+    /// Write the normalized actions of every processed block to rolling
+    /// parquet files in this directory
+    #[arg(long = "action-export-dir")]
+    pub action_dir:           Option<PathBuf>,
+    /// Blocks written to one action parquet file
+    #[arg(
+        long = "action-export-blocks-per-file",
+        default_value_t = DEFAULT_ACTION_BLOCKS_PER_FILE
+    )]
+    pub action_file_blocks:   u64,
     /// CEX exchanges to consider for cex-dex analysis
     #[arg(
         long,
@@ -77,7 +110,7 @@ pub struct RunArgs {
     #[arg(long, default_value = "10")]
     pub behind_tip:           u64,
     /// Legacy, run in CLI only mode (no TUI) - will output progress bars to
-    /// stdout
+    /// stderr
     #[arg(long, default_value = "true")]
     pub cli_only:             bool,
     /// Export metrics
@@ -106,8 +139,13 @@ pub struct RunArgs {
 impl RunArgs {
     pub async fn execute(mut self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
         self.check_proper_range()?;
+        let inspector_config = load_inspector_config(self.config.as_deref())?;
+        let max_block_movement =
+            Rational::try_from_float_simplest(inspector_config.pricing.max_block_movement)
+                .map_err(|_| eyre::eyre!("pricing.max_block_movement must be a finite number"))?;
 
-        if self.waterfall {
+        // the banner is drawn on stdout, which is reserved for the results
+        if self.waterfall && !self.sink_args.stdout {
             rain();
         }
 
@@ -117,7 +155,13 @@ impl RunArgs {
         // Fetch required environment variables.
         let reth_db_path = get_env_vars()?;
         tracing::info!(target: "brontes", "got env vars");
-        let quote_asset = self.quote_asset.parse()?;
+        let chain = ChainConfig::get();
+        let quote_asset = self
+            .quote_asset
+            .as_deref()
+            .map(str::parse)
+            .transpose()?
+            .unwrap_or(chain.quote_asset);
         tracing::info!(target: "brontes", "parsed quote asset");
         let task_executor = ctx.task_executor;
 
@@ -136,8 +180,17 @@ impl RunArgs {
         let hr = self.try_start_fallback_server().await;
 
         tracing::info!(target: "brontes", "starting database initialization at: '{}'", brontes_db_path);
-        let libmdbx =
-            static_object(load_database(&task_executor, brontes_db_path, hr, self.run_id).await?);
+        let checkpoints = CheckpointStore::new(Path::new(&brontes_db_path).join("checkpoints"));
+        let libmdbx = static_object(
+            load_database(
+                &task_executor,
+                brontes_db_path,
+                hr,
+                self.run_id,
+                config_searcher_labels(&inspector_config)?,
+            )
+            .await?,
+        );
 
         let tip = static_object(load_tip_database(libmdbx)?);
         tracing::info!(target: "brontes", "initialized libmdbx database");
@@ -176,12 +229,26 @@ impl RunArgs {
             self.cex_exchanges,
             trade_config,
             self.with_metrics,
+            &inspector_config,
+            chain,
         );
 
-        let tracer =
-            get_tracing_provider(Path::new(&reth_db_path), max_tasks, task_executor.clone());
+        let tracer = get_tracing_provider(
+            Path::new(&reth_db_path),
+            max_tasks,
+            task_executor.clone(),
+            chain,
+        )?;
         let parser = static_object(DParser::new(metrics_tx, libmdbx, tracer.clone()).await);
 
+        let sinks = self.sink_args.build_sinks(&task_executor).await?;
+        let action_writer = self
+            .action_dir
+            .clone()
+            .map(|dir| ActionParquetWriter::spawn(dir, self.action_file_blocks))
+            .transpose()?
+            .map(static_object);
+
         let executor = task_executor.clone();
         let result = executor
             .clone()
@@ -191,6 +258,7 @@ impl RunArgs {
                     max_tasks,
                     self.min_batch_size,
                     quote_asset,
+                    chain,
                     self.force_dex_pricing,
                     self.force_no_dex_pricing,
                     inspectors,
@@ -202,6 +270,11 @@ impl RunArgs {
                     self.with_metrics,
                     snapshot_mode,
                     load_window,
+                    checkpoints,
+                    self.resume,
+                    sinks,
+                    max_block_movement,
+                    action_writer,
                 )
                 .build(task_executor, shutdown)
                 .await
@@ -211,6 +284,12 @@ impl RunArgs {
                 }) {
                     brontes.await;
                 }
+
+                if let Some(action_writer) = action_writer {
+                    if let Err(e) = action_writer.close().await {
+                        tracing::error!(err=%e, "failed to finish the action parquet files");
+                    }
+                }
             });
 
         result.await?;
@@ -254,11 +333,7 @@ impl RunArgs {
 
     /// the time window in seconds for downloading
     fn load_time_window(&self) -> usize {
-        self.time_window_args
-            .max_vwap_pre
-            .max(self.time_window_args.max_vwap_post)
-            .max(self.time_window_args.max_optimistic_pre)
-            .max(self.time_window_args.max_optimistic_post) as usize
+        self.time_window_args.max_window()
     }
 
     fn check_proper_range(&self) -> eyre::Result<()> {
@@ -267,6 +342,9 @@ impl RunArgs {
                 return Err(eyre::eyre!("start block must be less than end block"))
             }
         }
+        if self.resume && self.start_block.is_none() && self.ranges.is_none() {
+            return Err(eyre::eyre!("resuming needs a start block or ranges to resume"))
+        }
         Ok(())
     }
 }
@@ -295,6 +373,71 @@ fn parse_ranges(ranges: &[String]) -> Result<Vec<(u64, u64)>, String> {
         .collect()
 }
 
+#[derive(Debug, Parser)]
+pub struct SinkArgs {
+    /// Stream the results of each block processed at tip as newline delimited
+    /// json to stdout. Logs are written to stderr instead
+    #[arg(long = "stdout-sink", default_value_t = false)]
+    pub stdout:        bool,
+    /// Serve the results of each block processed at tip to the websocket
+    /// clients connected to this address
+    #[cfg(feature = "ws-sink")]
+    #[arg(long = "ws-sink")]
+    pub websocket:     Option<std::net::SocketAddr>,
+    /// Publish the results of each block processed at tip to this nats server
+    #[cfg(feature = "nats-sink")]
+    #[arg(long = "nats-sink")]
+    pub nats:          Option<String>,
+    /// The nats subject the results are published to
+    #[cfg(feature = "nats-sink")]
+    #[arg(long, default_value = "brontes.results")]
+    pub nats_subject:  String,
+    /// Produce the results of each block processed at tip to these kafka
+    /// brokers
+    #[cfg(feature = "kafka-sink")]
+    #[arg(long = "kafka-sink")]
+    pub kafka:         Option<String>,
+    /// The kafka topic the results are produced to
+    #[cfg(feature = "kafka-sink")]
+    #[arg(long, default_value = "brontes-results")]
+    pub kafka_topic:   String,
+    /// Number of blocks buffered per sink before back pressure is applied
+    #[arg(long = "sink-buffer", default_value = "1024")]
+    pub buffer:        usize,
+    /// What to do when a sink can't keep up with the processed blocks
+    #[arg(long = "sink-backpressure", value_enum, default_value_t = BackPressure::Drop)]
+    pub back_pressure: BackPressure,
+}
+
+impl SinkArgs {
+    pub async fn build_sinks(
+        &self,
+        executor: &BrontesTaskExecutor,
+    ) -> eyre::Result<Option<&'static ResultSinks>> {
+        let mut sinks = ResultSinks::new(self.buffer, self.back_pressure);
+
+        if self.stdout {
+            sinks.add_sink(StdoutSink::new(), executor);
+        }
+        #[cfg(feature = "ws-sink")]
+        if let Some(addr) = self.websocket {
+            sinks.add_sink(crate::sinks::WebSocketSink::bind(addr, executor).await?, executor);
+        }
+        #[cfg(feature = "nats-sink")]
+        if let Some(url) = &self.nats {
+            let sink = crate::sinks::NatsSink::connect(url, self.nats_subject.clone()).await?;
+            sinks.add_sink(sink, executor);
+        }
+        #[cfg(feature = "kafka-sink")]
+        if let Some(brokers) = &self.kafka {
+            let sink = crate::sinks::KafkaSink::new(brokers, self.kafka_topic.clone())?;
+            sinks.add_sink(sink, executor);
+        }
+
+        Ok((!sinks.is_empty()).then(|| static_object(sinks)))
+    }
+}
+
 #[derive(Debug, Parser)]
 pub struct TimeWindowArgs {
     /// The initial sliding time window (BEFORE) for cex prices or trades
@@ -389,7 +532,15 @@ pub struct TimeWindowArgs {
 }
 
 impl TimeWindowArgs {
-    fn trade_config(&self) -> CexDexTradeConfig {
+    /// the largest time window in seconds
+    pub(crate) fn max_window(&self) -> usize {
+        self.max_vwap_pre
+            .max(self.max_vwap_post)
+            .max(self.max_optimistic_pre)
+            .max(self.max_optimistic_post) as usize
+    }
+
+    pub(crate) fn trade_config(&self) -> CexDexTradeConfig {
         CexDexTradeConfig {
             initial_vwap_pre_block_us:  (self.initial_vwap_pre * SECONDS_TO_US_FLOAT) as u64,
             initial_vwap_post_block_us: (self.initial_vwap_post * SECONDS_TO_US_FLOAT) as u64,
@@ -0,0 +1,528 @@
+//! Read only HTTP/JSON api over the brontes libmdbx database.
+//!
+//! Routes:
+//! - `GET /mev_blocks?start_block=&end_block=` mev block summaries
+//! - `GET /bundles?start_block=&end_block=&mev_type=` bundles, `mev_type` takes
+//!   a comma separated list
+//! - `GET /searcher/eoa/{address}` and `GET /searcher/contract/{address}`
+//! - `GET /builder/{address}`
+//! - `GET /address_meta/{address}`
+//! - `GET /dex_quotes/{block}/{tx_idx}?base=&quote=`
+//!
+//! Range queries are paginated with `limit` and `offset`, where `offset` skips
+//! items of `start_block`. If there is more data the response contains the
+//! `start_block` and `offset` of the next page. Passing `stream=true` instead
+//! streams every result as newline delimited json.
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+    sync::Arc,
+};
+
+use alloy_primitives::Address;
+use brontes_database::libmdbx::LibmdbxReadWriter;
+use brontes_types::{
+    db::{mev_block::MevBlockWithClassified, traits::LibmdbxReader},
+    mev::MevType,
+    pair::Pair,
+    ToFloatNearest,
+};
+use clap::{Parser, ValueEnum};
+use eyre::{eyre, WrapErr};
+use futures::{stream, StreamExt};
+use hyper::{
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use serde::Serialize;
+use serde_json::json;
+use tracing::info;
+
+use crate::{
+    cli::{load_libmdbx, static_object},
+    runner::CliContext,
+};
+
+/// Blocks read from the db per query when walking a block range.
+const BLOCK_CHUNK: u64 = 100;
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 10_000;
+
+#[derive(Debug, Parser)]
+pub struct ServeArgs {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: IpAddr,
+    /// Port to listen on
+    #[arg(long, default_value = "6924")]
+    pub port: u16,
+}
+
+impl ServeArgs {
+    pub async fn execute(self, brontes_db_path: String, ctx: CliContext) -> eyre::Result<()> {
+        let libmdbx = static_object(load_libmdbx(&ctx.task_executor, brontes_db_path)?);
+        let addr = SocketAddr::new(self.host, self.port);
+
+        let make_svc = make_service_fn(move |_| async move {
+            Ok::<_, Infallible>(service_fn(move |req: Request<Body>| async move {
+                Ok::<_, Infallible>(handle(libmdbx, req).await)
+            }))
+        });
+        let server = Server::try_bind(&addr)
+            .wrap_err("could not bind to address")?
+            .serve(make_svc);
+
+        info!(target: "brontes::serve", %addr, "serving brontes db");
+        server.await.wrap_err("server crashed")
+    }
+}
+
+/// Error of a single request, rendered as `{"error": ..}` with the given
+/// status.
+struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn bad_request(msg: impl ToString) -> Self {
+        Self(StatusCode::BAD_REQUEST, msg.to_string())
+    }
+
+    fn not_found(msg: impl ToString) -> Self {
+        Self(StatusCode::NOT_FOUND, msg.to_string())
+    }
+}
+
+impl From<eyre::Report> for ApiError {
+    fn from(err: eyre::Report) -> Self {
+        Self(StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+    }
+}
+
+type ApiResult = Result<Response<Body>, ApiError>;
+
+async fn handle(db: &'static LibmdbxReadWriter, req: Request<Body>) -> Response<Body> {
+    if req.method() != Method::GET {
+        return error_response(ApiError(
+            StatusCode::METHOD_NOT_ALLOWED,
+            "only GET is supported".into(),
+        ))
+    }
+
+    let query = Query::parse(req.uri().query());
+    let Some(route) = Route::parse(req.uri().path()) else {
+        return error_response(ApiError::not_found(format!("unknown route {}", req.uri().path())))
+    };
+
+    let res = match route {
+        Route::MevBlocks => mev_blocks(db, query).await,
+        Route::Bundles => bundles(db, query).await,
+        Route::SearcherEoa(address) => {
+            lookup(db, address, |db, address| db.try_fetch_searcher_eoa_info(address)).await
+        }
+        Route::SearcherContract(address) => {
+            lookup(db, address, |db, address| db.try_fetch_searcher_contract_info(address)).await
+        }
+        Route::Builder(address) => {
+            lookup(db, address, |db, address| db.try_fetch_builder_info(address)).await
+        }
+        Route::AddressMeta(address) => {
+            lookup(db, address, |db, address| db.try_fetch_address_metadata(address)).await
+        }
+        Route::DexQuotes { block, tx_idx } => dex_quote(db, block, tx_idx, query).await,
+    };
+
+    res.unwrap_or_else(error_response)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Route<'a> {
+    MevBlocks,
+    Bundles,
+    SearcherEoa(&'a str),
+    SearcherContract(&'a str),
+    Builder(&'a str),
+    AddressMeta(&'a str),
+    DexQuotes { block: &'a str, tx_idx: &'a str },
+}
+
+impl<'a> Route<'a> {
+    fn parse(path: &'a str) -> Option<Self> {
+        let path = path
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>();
+
+        Some(match path[..] {
+            ["mev_blocks"] => Self::MevBlocks,
+            ["bundles"] => Self::Bundles,
+            ["searcher", "eoa", address] => Self::SearcherEoa(address),
+            ["searcher", "contract", address] => Self::SearcherContract(address),
+            ["builder", address] => Self::Builder(address),
+            ["address_meta", address] => Self::AddressMeta(address),
+            ["dex_quotes", block, tx_idx] => Self::DexQuotes { block, tx_idx },
+            _ => return None,
+        })
+    }
+}
+
+async fn lookup<T, F>(db: &'static LibmdbxReadWriter, address: &str, f: F) -> ApiResult
+where
+    T: Serialize + Send + 'static,
+    F: FnOnce(&LibmdbxReadWriter, Address) -> eyre::Result<Option<T>> + Send + 'static,
+{
+    let address = parse::<Address>("address", address)?;
+    match blocking(move || f(db, address)).await? {
+        Some(value) => json_response(&value),
+        None => Err(ApiError::not_found(format!("no entry for {address:?}"))),
+    }
+}
+
+async fn dex_quote(
+    db: &'static LibmdbxReadWriter,
+    block: &str,
+    tx_idx: &str,
+    query: Query,
+) -> ApiResult {
+    let block = parse::<u64>("block", block)?;
+    let tx_idx = parse::<usize>("tx_idx", tx_idx)?;
+    let pair = Pair(query.required("base")?, query.required("quote")?);
+
+    let quotes = blocking(move || db.get_dex_quotes(block)).await?;
+    match quotes.price_at(pair, tx_idx) {
+        Some(price) => json_response(&json!({
+            "block_number": block,
+            "tx_idx": tx_idx,
+            "pair": pair,
+            "pre_state": price.pre_state.to_float(),
+            "post_state": price.post_state.to_float(),
+            "goes_through": price.goes_through,
+            "is_transfer": price.is_transfer,
+        })),
+        None => Err(ApiError::not_found(format!(
+            "no dex quote for {pair:?} at block {block} tx {tx_idx}"
+        ))),
+    }
+}
+
+async fn mev_blocks(db: &'static LibmdbxReadWriter, query: Query) -> ApiResult {
+    range_response(db, query, |block| vec![json!(block.block)]).await
+}
+
+async fn bundles(db: &'static LibmdbxReadWriter, query: Query) -> ApiResult {
+    let mev_types = query
+        .get("mev_type")
+        .map(|types| {
+            types
+                .split(',')
+                .map(|ty| {
+                    <MevType as ValueEnum>::from_str(ty, true)
+                        .map_err(|_| ApiError::bad_request(format!("invalid mev_type {ty}")))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+
+    range_response(db, query, move |block| {
+        block
+            .mev
+            .into_iter()
+            .filter(|bundle| {
+                mev_types
+                    .as_ref()
+                    .map(|types| types.contains(&bundle.mev_type()))
+                    .unwrap_or(true)
+            })
+            .map(|bundle| json!(bundle))
+            .collect()
+    })
+    .await
+}
+
+/// Walks the requested block range in chunks, mapping every mev block to the
+/// items that are returned. Either returns a page of items or streams all of
+/// them as ndjson.
+async fn range_response<F>(db: &'static LibmdbxReadWriter, query: Query, to_items: F) -> ApiResult
+where
+    F: Fn(MevBlockWithClassified) -> Vec<serde_json::Value> + Send + Sync + 'static,
+{
+    let start_block = query.required::<u64>("start_block")?;
+    let end_block = match query.optional::<u64>("end_block")? {
+        Some(end) => end,
+        None => blocking(move || db.get_most_recent_block()).await?,
+    };
+    if start_block > end_block {
+        return Err(ApiError::bad_request("start_block must be less than or equal to end_block"))
+    }
+    let offset = query.optional::<usize>("offset")?.unwrap_or_default();
+    let stream_results = query.optional::<bool>("stream")?.unwrap_or_default();
+    let limit = match query.optional::<usize>("limit")? {
+        Some(limit) if limit > MAX_LIMIT => {
+            return Err(ApiError::bad_request(format!("limit can be at most {MAX_LIMIT}")))
+        }
+        Some(limit) => limit,
+        None if stream_results => usize::MAX,
+        None => DEFAULT_LIMIT,
+    };
+
+    let to_items = Arc::new(to_items);
+    let items = stream::unfold(Some(start_block), move |chunk_start| {
+        let to_items = to_items.clone();
+        async move {
+            let chunk_start = chunk_start?;
+            let (chunk_end, next) = chunk_range(chunk_start, end_block);
+
+            match blocking(move || db.fetch_mev_block_range(chunk_start, chunk_end)).await {
+                Ok(blocks) => Some((Ok(block_items(blocks, &*to_items)), next)),
+                // stop walking the range once we hit an error
+                Err(ApiError(_, e)) => Some((Err(e), None)),
+            }
+        }
+    })
+    .flat_map(|res| {
+        stream::iter(match res {
+            Ok(items) => items.into_iter().map(Ok).collect::<Vec<_>>(),
+            Err(e) => vec![Err(e)],
+        })
+    })
+    // the offset only skips items of the first block, so a page never re-reads
+    // more than the block it starts in
+    .filter(move |item| {
+        futures::future::ready(
+            !matches!(item, Ok(item) if item.block == start_block && item.index < offset),
+        )
+    });
+
+    if stream_results {
+        let body = items.take(limit).map(|item| {
+            item.map(|item| format!("{}\n", item.value))
+                .map_err(std::io::Error::other)
+        });
+
+        return Response::builder()
+            .header(CONTENT_TYPE, "application/x-ndjson")
+            .body(Body::wrap_stream(body))
+            .map_err(|e| eyre!(e).into())
+    }
+
+    // take one more than the limit to know if there is another page
+    let items = items
+        .take(limit + 1)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let (page, next) = split_page(items, limit);
+
+    json_response(&json!({ "items": page, "next": next }))
+}
+
+/// The inclusive end of the chunk starting at `chunk_start` and the start of
+/// the following chunk, if any.
+fn chunk_range(chunk_start: u64, end_block: u64) -> (u64, Option<u64>) {
+    let chunk_end = chunk_start.saturating_add(BLOCK_CHUNK - 1).min(end_block);
+    (chunk_end, (chunk_end < end_block).then_some(chunk_end + 1))
+}
+
+/// An item of a range query along with its position in the range.
+#[derive(Debug)]
+struct RangeItem {
+    block: u64,
+    /// Index of the item among the items of its block
+    index: usize,
+    value: serde_json::Value,
+}
+
+fn block_items<F>(blocks: Vec<MevBlockWithClassified>, to_items: &F) -> Vec<RangeItem>
+where
+    F: Fn(MevBlockWithClassified) -> Vec<serde_json::Value>,
+{
+    blocks
+        .into_iter()
+        .flat_map(|block| {
+            let block_number = block.block.block_number;
+            to_items(block)
+                .into_iter()
+                .enumerate()
+                .map(move |(index, value)| RangeItem { block: block_number, index, value })
+        })
+        .collect()
+}
+
+/// Where the next page of a range query starts.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct NextPage {
+    start_block: u64,
+    offset:      usize,
+}
+
+/// Splits off the first `limit` items, the item after them is where the next
+/// page starts.
+fn split_page(
+    mut items: Vec<RangeItem>,
+    limit: usize,
+) -> (Vec<serde_json::Value>, Option<NextPage>) {
+    let next = items
+        .get(limit)
+        .map(|item| NextPage { start_block: item.block, offset: item.index });
+    items.truncate(limit);
+
+    (items.into_iter().map(|item| item.value).collect(), next)
+}
+
+/// Runs a blocking db read off the async runtime.
+async fn blocking<T, F>(f: F) -> Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> eyre::Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| eyre!(e))?
+        .map_err(Into::into)
+}
+
+struct Query(HashMap<String, String>);
+
+impl Query {
+    fn parse(query: Option<&str>) -> Self {
+        Self(
+            form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+                .into_owned()
+                .collect(),
+        )
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    fn optional<T: FromStr>(&self, key: &str) -> Result<Option<T>, ApiError> {
+        self.get(key).map(|value| parse(key, value)).transpose()
+    }
+
+    fn required<T: FromStr>(&self, key: &str) -> Result<T, ApiError> {
+        self.optional(key)?
+            .ok_or_else(|| ApiError::bad_request(format!("missing query parameter {key}")))
+    }
+}
+
+fn parse<T: FromStr>(name: &str, value: &str) -> Result<T, ApiError> {
+    value
+        .parse()
+        .map_err(|_| ApiError::bad_request(format!("invalid {name} {value}")))
+}
+
+fn json_response<T: Serialize>(value: &T) -> ApiResult {
+    let body = serde_json::to_vec(value).map_err(|e| eyre!(e))?;
+
+    Response::builder()
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .map_err(|e| eyre!(e).into())
+}
+
+fn error_response(ApiError(status, msg): ApiError) -> Response<Body> {
+    let mut res = Response::new(Body::from(json!({ "error": msg }).to_string()));
+    *res.status_mut() = status;
+    res.headers_mut()
+        .insert(CONTENT_TYPE, "application/json".parse().unwrap());
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_parse() {
+        assert_eq!(Route::parse("/mev_blocks"), Some(Route::MevBlocks));
+        assert_eq!(Route::parse("/bundles/"), Some(Route::Bundles));
+        assert_eq!(Route::parse("/searcher/eoa/0xab"), Some(Route::SearcherEoa("0xab")));
+        assert_eq!(Route::parse("/searcher/contract/0xab"), Some(Route::SearcherContract("0xab")));
+        assert_eq!(Route::parse("/builder/0xab"), Some(Route::Builder("0xab")));
+        assert_eq!(Route::parse("/address_meta/0xab"), Some(Route::AddressMeta("0xab")));
+        assert_eq!(
+            Route::parse("/dex_quotes/18000000/4"),
+            Some(Route::DexQuotes { block: "18000000", tx_idx: "4" })
+        );
+
+        assert_eq!(Route::parse("/"), None);
+        assert_eq!(Route::parse("/searcher/0xab"), None);
+        assert_eq!(Route::parse("/dex_quotes/18000000"), None);
+    }
+
+    #[test]
+    fn test_query_parse() {
+        let query = Query::parse(Some("start_block=10&mev_type=sandwich%2Cjit&flag&name=a+b"));
+        assert_eq!(query.required::<u64>("start_block").unwrap(), 10);
+        assert_eq!(query.get("mev_type"), Some("sandwich,jit"));
+        assert_eq!(query.get("name"), Some("a b"));
+        assert_eq!(query.get("flag"), Some(""));
+        assert!(query.optional::<u64>("end_block").unwrap().is_none());
+        assert!(query.required::<u64>("end_block").is_err());
+
+        let query = Query::parse(Some("start_block=ten"));
+        assert!(query.optional::<u64>("start_block").is_err());
+        assert!(Query::parse(None).get("start_block").is_none());
+    }
+
+    #[test]
+    fn test_chunk_range_covers_range() {
+        assert_eq!(chunk_range(0, 250), (99, Some(100)));
+        assert_eq!(chunk_range(100, 250), (199, Some(200)));
+        assert_eq!(chunk_range(200, 250), (250, None));
+        assert_eq!(chunk_range(5, 5), (5, None));
+        assert_eq!(chunk_range(u64::MAX, u64::MAX), (u64::MAX, None));
+    }
+
+    fn items(blocks: &[(u64, usize)]) -> Vec<RangeItem> {
+        blocks
+            .iter()
+            .flat_map(|&(block, count)| {
+                (0..count).map(move |index| RangeItem {
+                    block,
+                    index,
+                    value: json!(format!("{block}-{index}")),
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_split_page() {
+        let (page, next) = split_page(items(&[(10, 2), (11, 3)]), 3);
+        assert_eq!(page, vec![json!("10-0"), json!("10-1"), json!("11-0")]);
+        assert_eq!(next, Some(NextPage { start_block: 11, offset: 1 }));
+
+        let (page, next) = split_page(items(&[(10, 2), (11, 1)]), 3);
+        assert_eq!(page.len(), 3);
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn test_block_items_index_per_block() {
+        let blocks = [10, 12]
+            .into_iter()
+            .map(|block_number| {
+                let mut block = MevBlockWithClassified::default();
+                block.block.block_number = block_number;
+                block
+            })
+            .collect();
+        let items = block_items(blocks, &|block: MevBlockWithClassified| {
+            vec![json!(block.block.block_number); 2]
+        });
+
+        assert_eq!(
+            items
+                .iter()
+                .map(|item| (item.block, item.index))
+                .collect::<Vec<_>>(),
+            vec![(10, 0), (10, 1), (12, 0), (12, 1)]
+        );
+    }
+}
@@ -2,7 +2,7 @@ use std::{env, path::Path};
 
 use alloy_primitives::Address;
 #[cfg(not(feature = "local-reth"))]
-use brontes_core::local_provider::LocalProvider;
+use brontes_core::rpc_provider::RpcTracingProvider;
 #[cfg(feature = "local-clickhouse")]
 use brontes_database::clickhouse::clickhouse_config;
 #[cfg(feature = "local-clickhouse")]
@@ -15,8 +15,11 @@ use brontes_database::clickhouse::ClickhouseMiddleware;
 use brontes_database::clickhouse::ReadOnlyMiddleware;
 #[cfg(feature = "local-clickhouse")]
 use brontes_database::clickhouse::{dbms::BrontesClickhouseData, ClickhouseBuffered};
-use brontes_database::{clickhouse::cex_config::CexDownloadConfig, libmdbx::LibmdbxReadWriter};
-use brontes_inspect::{Inspector, Inspectors};
+use brontes_database::{
+    clickhouse::cex_config::CexDownloadConfig,
+    libmdbx::{LibmdbxReadWriter, SearcherLabels},
+};
+use brontes_inspect::{config::InspectorConfig, Inspector, Inspectors};
 use brontes_metrics::inspectors::OutlierMetrics;
 #[cfg(feature = "local-clickhouse")]
 use brontes_types::UnboundedYapperReceiver;
@@ -27,7 +30,7 @@ use brontes_types::{
     },
     db_write_trigger::HeartRateMonitor,
     mev::Bundle,
-    BrontesTaskExecutor,
+    BrontesTaskExecutor, ChainConfig,
 };
 use itertools::Itertools;
 #[cfg(feature = "local-reth")]
@@ -41,8 +44,10 @@ pub async fn load_database(
     db_endpoint: String,
     _: Option<HeartRateMonitor>,
     _: Option<u64>,
+    searcher_labels: SearcherLabels,
 ) -> eyre::Result<LibmdbxReadWriter> {
-    LibmdbxReadWriter::init_db(db_endpoint, None, executor, true)
+    Ok(LibmdbxReadWriter::init_db(db_endpoint, None, executor, true)?
+        .with_searcher_labels(searcher_labels))
 }
 
 #[cfg(not(feature = "local-clickhouse"))]
@@ -57,8 +62,10 @@ pub async fn load_database(
     db_endpoint: String,
     hr: Option<HeartRateMonitor>,
     run_id: Option<u64>,
+    searcher_labels: SearcherLabels,
 ) -> eyre::Result<ClickhouseMiddleware<LibmdbxReadWriter>> {
-    let inner = LibmdbxReadWriter::init_db(db_endpoint, None, executor, true)?;
+    let inner = LibmdbxReadWriter::init_db(db_endpoint, None, executor, true)?
+        .with_searcher_labels(searcher_labels);
 
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
     spawn_db_writer_thread(executor, rx, hr);
@@ -120,11 +127,26 @@ pub async fn load_clickhouse(
 }
 
 #[cfg(not(feature = "local-reth"))]
-pub fn get_tracing_provider(_: &Path, _: u64, _: BrontesTaskExecutor) -> LocalProvider {
+pub fn get_tracing_provider(
+    _: &Path,
+    _: u64,
+    _: BrontesTaskExecutor,
+    _: &ChainConfig,
+) -> eyre::Result<RpcTracingProvider> {
     let db_endpoint = env::var("RETH_ENDPOINT").expect("No db Endpoint in .env");
     let db_port = env::var("RETH_PORT").expect("No DB port.env");
     let url = format!("{db_endpoint}:{db_port}");
-    LocalProvider::new(url, 5)
+    let provider = RpcTracingProvider::new(url, 5);
+
+    Ok(
+        match env::var("RPC_TRACE_BATCH_SIZE")
+            .ok()
+            .and_then(|size| size.parse().ok())
+        {
+            Some(batch_size) => provider.with_batch_size(batch_size),
+            None => provider,
+        },
+    )
 }
 
 #[cfg(feature = "local-reth")]
@@ -132,8 +154,14 @@ pub fn get_tracing_provider(
     db_path: &Path,
     tracing_tasks: u64,
     executor: BrontesTaskExecutor,
-) -> TracingClient {
-    TracingClient::new(db_path, tracing_tasks, executor.clone())
+    chain: &ChainConfig,
+) -> eyre::Result<TracingClient> {
+    Ok(TracingClient::new(
+        db_path,
+        tracing_tasks,
+        executor.clone(),
+        reth_tracing_ext::chain_spec(&chain.chain)?,
+    ))
 }
 
 pub fn determine_max_tasks(max_tasks: Option<u64>) -> u64 {
@@ -157,6 +185,8 @@ pub fn init_inspectors<DB: LibmdbxReader>(
     cex_exchanges: Vec<CexExchange>,
     trade_config: CexDexTradeConfig,
     metrics: bool,
+    config: &InspectorConfig,
+    chain: &ChainConfig,
 ) -> &'static [&'static dyn Inspector<Result = Vec<Bundle>>] {
     let mut res = Vec::new();
     let metrics = metrics.then(OutlierMetrics::new);
@@ -170,12 +200,38 @@ pub fn init_inspectors<DB: LibmdbxReader>(
             &cex_exchanges,
             trade_config,
             metrics.clone(),
+            config,
+            chain,
         ));
     }
 
     &*Box::leak(res.into_boxed_slice())
 }
 
+/// Loads and validates the inspector config passed with `--config`, the
+/// defaults are used without one
+pub fn load_inspector_config(path: Option<&Path>) -> eyre::Result<InspectorConfig> {
+    let Some(path) = path else { return Ok(InspectorConfig::default()) };
+
+    let config: InspectorConfig = toml::from_str(&std::fs::read_to_string(path)?)
+        .map_err(|e| eyre::eyre!("failed to parse inspector config {}: {e}", path.display()))?;
+    config.validate()?;
+    info!(target: "brontes", "loaded inspector config from {}", path.display());
+
+    Ok(config)
+}
+
+/// The searcher labels of the inspector config. They are applied on top of the
+/// stored searcher info for the run and never written to the db
+pub fn config_searcher_labels(config: &InspectorConfig) -> eyre::Result<SearcherLabels> {
+    let (eoas, contracts) = config.searcher_labels()?;
+
+    Ok(SearcherLabels {
+        eoas:      eoas.into_iter().collect(),
+        contracts: contracts.into_iter().collect(),
+    })
+}
+
 pub fn get_env_vars() -> eyre::Result<String> {
     let db_path = env::var("DB_PATH").map_err(|_| Box::new(std::env::VarError::NotPresent))?;
     info!("Found DB Path");
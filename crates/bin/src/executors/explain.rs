@@ -0,0 +1,530 @@
+use std::fmt::{self, Write};
+
+use alloy_primitives::{Address, B256};
+use brontes_classifier::Classifier;
+use brontes_core::decoding::{Parser, TracingProvider};
+use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
+use brontes_inspect::{
+    composer::{explain_tx_inspection, BundleOutcome, TxInspectionExplanation},
+    Inspector,
+};
+use brontes_pricing::types::DexPriceMsg;
+use brontes_types::{
+    db::{dex::PriceAt, metadata::Metadata, searcher::SearcherInfo},
+    mev::Bundle,
+    normalized_actions::{accounting::ActionAccounting, Action},
+    pair::Pair,
+    tree::{Node, Root},
+    BlockData, MultiBlockData, ToFloatNearest, TreeSearchBuilder, TxInfo,
+};
+use colored::Colorize;
+use itertools::Itertools;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+use super::shared::{multi_block_window::MultiBlockWindow, stored_metadata::StoredMetadataLoader};
+
+/// Explains how brontes sees a single transaction: the classified action tree,
+/// the tx info, the token deltas and dex prices of the transaction, and what
+/// the inspectors and composer made of it.
+///
+/// The block is traced (or loaded from the stored traces) and classified
+/// again, the stored metadata of the block is used.
+pub struct TxExplainer<T: TracingProvider, DB: LibmdbxReader + DBWriter> {
+    tx_hash:     B256,
+    quote_asset: Address,
+    inspectors:  &'static [&'static dyn Inspector<Result = Vec<Bundle>>],
+    parser:      &'static Parser<T, DB>,
+    classifier:  Classifier<'static, T, DB>,
+    pricing_rx:  UnboundedReceiver<DexPriceMsg>,
+    libmdbx:     &'static DB,
+    metadata:    StoredMetadataLoader<DB>,
+}
+
+impl<T: TracingProvider, DB: LibmdbxReader + DBWriter> TxExplainer<T, DB> {
+    pub fn new(
+        tx_hash: B256,
+        quote_asset: Address,
+        inspectors: &'static [&'static dyn Inspector<Result = Vec<Bundle>>],
+        parser: &'static Parser<T, DB>,
+        libmdbx: &'static DB,
+        cex_window_sec: usize,
+    ) -> Self {
+        // pricing is never run, the updates the classifier sends out are dropped
+        let (pricing_tx, pricing_rx) = unbounded_channel();
+        let classifier = Classifier::new(libmdbx, pricing_tx, parser.get_tracer());
+
+        Self {
+            tx_hash,
+            quote_asset,
+            inspectors,
+            parser,
+            classifier,
+            pricing_rx,
+            libmdbx,
+            metadata: StoredMetadataLoader::new(libmdbx, quote_asset, cex_window_sec),
+        }
+    }
+
+    pub async fn run(mut self) -> eyre::Result<()> {
+        let (block, _) = self
+            .parser
+            .get_tracer()
+            .block_and_tx_index(self.tx_hash)
+            .await?;
+
+        let block_window_size = self
+            .inspectors
+            .iter()
+            .map(|inspector| inspector.block_window())
+            .max()
+            .expect("no inspectors loaded");
+        let mut window = MultiBlockWindow::new(block_window_size);
+
+        // multi block inspectors need the blocks before the transaction's block
+        let mut data = None;
+        for block in block.saturating_sub(block_window_size as u64 - 1)..=block {
+            data = Some(window.new_block_data(self.load_block(block).await?));
+        }
+
+        let explanation = explain_tx(
+            self.tx_hash,
+            self.quote_asset,
+            self.inspectors,
+            data.unwrap(),
+            self.libmdbx,
+        )?;
+        print!("{explanation}");
+
+        Ok(())
+    }
+
+    async fn load_block(&mut self, block: u64) -> eyre::Result<BlockData> {
+        let (_, traces, header) = self
+            .parser
+            .execute(block, 0, None)
+            .await
+            .ok_or_else(|| eyre::eyre!("failed to trace block {block}"))?;
+
+        let tree = self
+            .classifier
+            .build_block_tree(traces, header, false)
+            .await;
+        while self.pricing_rx.try_recv().is_ok() {}
+
+        let metadata = self.metadata.load(block, tree.header.beneficiary)?;
+
+        Ok(BlockData { metadata: metadata.into(), tree: tree.into() })
+    }
+}
+
+/// Builds the explanation of `tx_hash`, which has to be in the most recent
+/// block of `data`, as it is printed by [`TxExplainer::run`].
+pub fn explain_tx<DB: LibmdbxReader>(
+    tx_hash: B256,
+    quote_asset: Address,
+    inspectors: &[&dyn Inspector<Result = Vec<Bundle>>],
+    data: MultiBlockData,
+    libmdbx: &'static DB,
+) -> eyre::Result<String> {
+    let BlockData { metadata, tree } = data.get_most_recent_block().clone();
+    let block = tree.header.number;
+    let root = tree
+        .tx_roots
+        .iter()
+        .find(|root| root.tx_hash == tx_hash)
+        .ok_or_else(|| eyre::eyre!("transaction not found in block {block}"))?;
+    let tx_info = root.get_tx_info(block, libmdbx)?;
+
+    let mut out = String::new();
+    writeln!(out, "{} {}", "Transaction".bold(), tx_hash)?;
+    writeln!(out, "  block {}, index {}\n", block, root.position)?;
+
+    write_tx_info(&mut out, &tx_info, &metadata, quote_asset)?;
+    write_action_tree(&mut out, root)?;
+    write_deltas_and_prices(&mut out, root, &tx_info, &metadata, quote_asset, libmdbx)?;
+
+    let explanation = explain_tx_inspection(inspectors, data, libmdbx, tx_hash);
+    write_inspection(&mut out, explanation)?;
+
+    Ok(out)
+}
+
+fn write_tx_info(
+    out: &mut String,
+    info: &TxInfo,
+    metadata: &Metadata,
+    quote_asset: Address,
+) -> fmt::Result {
+    let searcher = |address: Address, searcher: &Option<SearcherInfo>| match searcher {
+        Some(SearcherInfo { name, fund, .. }) => {
+            format!("{address} (searcher {}, fund: {fund:?})", name.as_deref().unwrap_or("unnamed"))
+        }
+        None => address.to_string(),
+    };
+
+    writeln!(out, "{}", "Tx Info".bold())?;
+    writeln!(out, "  eoa:                  {}", searcher(info.eoa, &info.searcher_eoa_info))?;
+    writeln!(
+        out,
+        "  mev contract:         {}",
+        info.mev_contract
+            .map(|contract| searcher(contract, &info.searcher_contract_info))
+            .unwrap_or_else(|| "None".to_string())
+    )?;
+    writeln!(out, "  contract type:        {:?}", info.contract_type)?;
+    writeln!(out, "  classified:           {}", info.is_classified)?;
+    writeln!(out, "  verified contract:    {}", info.is_verified_contract)?;
+    writeln!(out, "  private:              {}", info.is_private)?;
+    writeln!(out, "  cex dex call:         {}", info.is_cex_dex_call)?;
+
+    let gas = &info.gas_details;
+    writeln!(out, "  gas used:             {}", gas.gas_used)?;
+    writeln!(out, "  effective gas price:  {} wei", gas.effective_gas_price)?;
+    writeln!(out, "  priority fee:         {} wei", gas.priority_fee)?;
+    writeln!(out, "  coinbase transfer:    {} wei", gas.coinbase_transfer())?;
+    writeln!(
+        out,
+        "  gas paid:             {:.7} ETH (${:.2})\n",
+        gas.gas_paid() as f64 / 1e18,
+        metadata
+            .get_gas_price_usd(gas.gas_paid(), quote_asset)
+            .to_float()
+    )
+}
+
+fn write_deltas_and_prices<DB: LibmdbxReader>(
+    out: &mut String,
+    root: &Root<Action>,
+    info: &TxInfo,
+    metadata: &Metadata,
+    quote_asset: Address,
+    libmdbx: &DB,
+) -> fmt::Result {
+    let search_args =
+        TreeSearchBuilder::default().with_actions([Action::is_transfer, Action::is_eth_transfer]);
+    let deltas = root
+        .collect(&search_args)
+        .into_iter()
+        .chain(info.get_total_eth_value().iter().cloned().map(Action::from))
+        .account_for_actions();
+
+    let symbol = |token: Address| {
+        libmdbx
+            .try_fetch_token_info(token)
+            .map(|info| info.symbol.clone())
+            .unwrap_or_else(|_| token.to_string())
+    };
+
+    writeln!(out, "{}", "Token Deltas".bold())?;
+    if deltas.is_empty() {
+        writeln!(out, "  none")?;
+    }
+    for (address, token_deltas) in deltas.iter().sorted_by_key(|(address, _)| **address) {
+        writeln!(out, "  {address}")?;
+        for (token, amount) in token_deltas.iter().sorted_by_key(|(token, _)| **token) {
+            writeln!(out, "    {:>24.6} {}", amount.clone().to_float(), symbol(*token))?;
+        }
+    }
+
+    writeln!(out, "\n{}", "Dex Prices".bold())?;
+    let Some(dex_quotes) = metadata.dex_quotes.as_ref() else {
+        return writeln!(out, "  no dex prices stored for the block\n")
+    };
+    let tokens = deltas
+        .values()
+        .flat_map(|token_deltas| token_deltas.keys())
+        .filter(|token| **token != quote_asset)
+        .unique()
+        .sorted();
+    for token in tokens {
+        match dex_quotes.price_at(Pair(*token, quote_asset), root.position) {
+            Some(price) => writeln!(
+                out,
+                "  {:<12} before: {:.6}, after: {:.6}",
+                symbol(*token),
+                price.clone().get_price(PriceAt::Before).to_float(),
+                price.get_price(PriceAt::After).to_float()
+            )?,
+            None => writeln!(out, "  {:<12} no price", symbol(*token))?,
+        }
+    }
+    writeln!(out)
+}
+
+fn write_action_tree(out: &mut String, root: &Root<Action>) -> fmt::Result {
+    writeln!(out, "{}", "Action Tree".bold())?;
+    write_node(out, root, &root.head, 1)?;
+    writeln!(out)
+}
+
+fn write_node(out: &mut String, root: &Root<Action>, node: &Node, depth: usize) -> fmt::Result {
+    let indent = "  ".repeat(depth);
+    let actions = root
+        .data_store
+        .get_ref(node.data)
+        .cloned()
+        .unwrap_or_default();
+    if actions.is_empty() {
+        writeln!(out, "{indent}{:?} {}", node.trace_address, node.address)?;
+    }
+    for action in actions {
+        writeln!(out, "{indent}{:?} {}", node.trace_address, describe_action(&action))?;
+    }
+
+    for child in &node.inner {
+        write_node(out, root, child, depth + 1)?;
+    }
+
+    Ok(())
+}
+
+fn describe_action(action: &Action) -> String {
+    match action {
+        Action::Swap(swap) => swap.to_string(),
+        Action::SwapWithFee(swap) => format!(
+            "{} with a fee of {:.4} {}",
+            swap.swap,
+            swap.fee_amount.clone().to_float(),
+            swap.fee_token.symbol
+        ),
+        Action::Mint(mint) => mint.to_string(),
+        Action::Burn(burn) => burn.to_string(),
+        Action::Collect(collect) => collect.to_string(),
+        Action::Liquidation(liquidation) => liquidation.to_string(),
+        Action::OracleUpdate(update) => update.to_string(),
+        Action::Transfer(transfer) => format!(
+            "Transfer {:.4} {} from {} to {}",
+            transfer.amount.clone().to_float(),
+            transfer.token.symbol,
+            transfer.from,
+            transfer.to
+        ),
+        Action::EthTransfer(transfer) => format!(
+            "Transfer {:.6} ETH from {} to {}",
+            transfer.value.to::<u128>() as f64 / 1e18,
+            transfer.from,
+            transfer.to
+        ),
+        Action::FlashLoan(loan) => format!(
+            "Flash loan of {} from {} via {}",
+            loan.assets
+                .iter()
+                .map(|asset| asset.symbol.clone())
+                .join(", "),
+            loan.pool,
+            loan.protocol
+        ),
+        Action::Batch(batch) => format!(
+            "Batch of {} user swaps settled by {} via {}",
+            batch.user_swaps.len(),
+            batch.solver,
+            batch.protocol
+        ),
+        Action::Aggregator(aggregator) => {
+            format!("Aggregator swap for {} via {}", aggregator.recipient, aggregator.protocol)
+        }
+        Action::NewPool(pool) => format!("New pool {} via {}", pool.pool_address, pool.protocol),
+        Action::PoolConfigUpdate(update) => {
+            format!("Pool config update of {} via {}", update.pool_address, update.protocol)
+        }
+        Action::SelfDestruct(_) => format!("Self destruct, refund to {}", action.get_to_address()),
+        Action::Unclassified(_) => format!("Unclassified call to {}", action.get_to_address()),
+        Action::Revert => "Revert".to_string(),
+    }
+}
+
+fn write_inspection(out: &mut String, explanation: TxInspectionExplanation) -> fmt::Result {
+    writeln!(out, "{}", "Inspectors".bold())?;
+    if explanation.found.is_empty() {
+        writeln!(out, "  no inspector flagged the transaction")?;
+    }
+    for found in &explanation.found {
+        let outcome = match found.outcome {
+            BundleOutcome::Kept => {
+                "kept, no overlapping bundle has a higher precedence".to_string()
+            }
+            BundleOutcome::Composed(mev_type) => format!("composed into a {mev_type} bundle"),
+            BundleOutcome::Deduplicated { by, tx_hash } => {
+                format!("deduplicated by the {by} bundle of {tx_hash}")
+            }
+            BundleOutcome::Unprofitable => "dropped as it wasn't profitable".to_string(),
+        };
+        writeln!(
+            out,
+            "  {} found a {} bundle (profit ${:.2}): {}",
+            found.inspector, found.bundle.header.mev_type, found.bundle.header.profit_usd, outcome
+        )?;
+    }
+    if let Some(possible) = &explanation.possible_mev {
+        writeln!(out, "  discovery flagged the transaction as possible mev")?;
+        write!(out, "{}", possible.triggers)?;
+    }
+
+    writeln!(out, "\n{}", "Results".bold())?;
+    if explanation.results.is_empty() {
+        writeln!(out, "  the transaction isn't part of any bundle")?;
+    }
+    for bundle in explanation.results {
+        writeln!(out, "{bundle}")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Bytes, Log, U256};
+    use alloy_sol_types::{SolCall, SolEvent};
+    use brontes_classifier::{
+        erc20::transferCall,
+        test_utils::{call_trace, ClassifierTestUtils},
+        UniswapV2::{swapCall, Swap},
+    };
+    use brontes_inspect::composer::FoundBundle;
+    use brontes_types::{
+        constants::USDC_ADDRESS,
+        db::token_info::TokenInfoWithAddress,
+        mev::{BundleData, BundleHeader, Liquidation, MevType},
+        Protocol,
+    };
+
+    use super::*;
+
+    const USDC_WETH_PAIR: Address = Address::new(hex!("B4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc"));
+    const USER: Address = Address::repeat_byte(0x11);
+    const BLOCK: u64 = 19_000_000;
+
+    /// A swap of 1 weth for 3000 usdc on the uniswap v2 pool
+    async fn classified_swap(classifier_utils: &ClassifierTestUtils) -> Root<Action> {
+        let usdc = TokenInfoWithAddress::usdc();
+        let weth = TokenInfoWithAddress::weth();
+        classifier_utils.ensure_token(usdc.clone());
+        classifier_utils.ensure_token(weth.clone());
+        classifier_utils.ensure_protocol(
+            Protocol::UniswapV2,
+            USDC_WETH_PAIR,
+            usdc.address,
+            Some(weth.address),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let usdc_out = U256::from(3_000_000_000u64);
+        let swap = call_trace(
+            vec![],
+            USER,
+            USDC_WETH_PAIR,
+            swapCall {
+                amount0Out: usdc_out,
+                amount1Out: U256::ZERO,
+                to:         USER,
+                data:       Bytes::new(),
+            }
+            .abi_encode(),
+            vec![],
+            vec![Log {
+                address: USDC_WETH_PAIR,
+                data:    Swap {
+                    sender:     USER,
+                    amount0In:  U256::ZERO,
+                    amount1In:  U256::from(10).pow(U256::from(18)),
+                    amount0Out: usdc_out,
+                    amount1Out: U256::ZERO,
+                    to:         USER,
+                }
+                .encode_log_data(),
+            }],
+        );
+        let transfer = call_trace(
+            vec![0],
+            USDC_WETH_PAIR,
+            usdc.address,
+            transferCall { _0: USER, _1: usdc_out }.abi_encode(),
+            vec![],
+            vec![],
+        );
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(BLOCK, vec![vec![swap, transfer]])
+            .await;
+        tree.tx_roots.remove(0)
+    }
+
+    #[brontes_macros::test]
+    async fn test_explain_classified_tx() {
+        colored::control::set_override(false);
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let root = classified_swap(&classifier_utils).await;
+        let tx_info = root.get_tx_info(BLOCK, classifier_utils.libmdbx).unwrap();
+        let metadata = Metadata::default();
+
+        let mut out = String::new();
+        write_tx_info(&mut out, &tx_info, &metadata, USDC_ADDRESS).unwrap();
+        assert!(out.contains(&format!("eoa:                  {USER}")));
+        assert!(out.contains("classified:           true"));
+
+        let mut out = String::new();
+        write_action_tree(&mut out, &root).unwrap();
+        let lines = out.lines().collect_vec();
+        assert_eq!(lines[0], "Action Tree");
+        assert_eq!(lines[1], "  [] Swap 1.0000 WETH to 3000.0000 USDC via Uni V2");
+        assert_eq!(
+            lines[2],
+            format!("    [0] Transfer 3000.0000 USDC from {USDC_WETH_PAIR} to {USER}")
+        );
+
+        let mut out = String::new();
+        write_deltas_and_prices(
+            &mut out,
+            &root,
+            &tx_info,
+            &metadata,
+            USDC_ADDRESS,
+            classifier_utils.libmdbx,
+        )
+        .unwrap();
+        assert!(out.contains(&format!("  {USER}\n{:>28.6} USDC", 3000.0)));
+        assert!(out.contains(&format!("  {USDC_WETH_PAIR}\n{:>28.6} USDC", -3000.0)));
+        assert!(out.contains("no dex prices stored for the block"));
+    }
+
+    #[test]
+    fn test_explain_inspection() {
+        colored::control::set_override(false);
+        let bundle = Bundle {
+            header: BundleHeader {
+                profit_usd: 12.5,
+                mev_type: MevType::Liquidation,
+                ..Default::default()
+            },
+            data:   BundleData::Liquidation(Liquidation::default()),
+        };
+        let explanation = TxInspectionExplanation {
+            found:        vec![FoundBundle {
+                inspector: "Liquidation".to_string(),
+                bundle,
+                outcome: BundleOutcome::Deduplicated {
+                    by:      MevType::Sandwich,
+                    tx_hash: B256::repeat_byte(1),
+                },
+            }],
+            results:      vec![],
+            possible_mev: None,
+        };
+
+        let mut out = String::new();
+        write_inspection(&mut out, explanation).unwrap();
+        let lines = out.lines().collect_vec();
+        assert_eq!(
+            lines[1],
+            format!(
+                "  Liquidation found a Liquidation bundle (profit $12.50): deduplicated by the \
+                 Sandwich bundle of {}",
+                B256::repeat_byte(1)
+            )
+        );
+        assert_eq!(lines[4], "  the transaction isn't part of any bundle");
+    }
+}
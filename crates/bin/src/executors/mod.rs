@@ -1,18 +1,20 @@
 pub mod discovery_only;
+mod explain;
 mod processors;
 mod range;
+mod reinspect;
 use std::ops::RangeInclusive;
 
 use brontes_database::libmdbx::StateToInitialize;
 use brontes_metrics::{
     pricing::DexPricingMetrics,
-    range::{FinishedRange, GlobalRangeMetrics},
+    range::{FinishedRange, GlobalRangeMetrics, TipMetrics},
 };
 use futures::{future::join_all, Stream};
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressState, ProgressStyle};
 pub use processors::*;
 mod shared;
-use brontes_database::{clickhouse::ClickhouseHandle, Tables};
+use brontes_database::{clickhouse::ClickhouseHandle, parquet::ActionParquetWriter, Tables};
 use futures::pin_mut;
 use shared::multi_block_window::MultiBlockWindow;
 mod tip;
@@ -28,23 +30,30 @@ use brontes_classifier::Classifier;
 use brontes_core::decoding::{Parser, TracingProvider};
 use brontes_database::libmdbx::LibmdbxInit;
 use brontes_inspect::Inspector;
-use brontes_pricing::{BrontesBatchPricer, GraphManager, LoadState};
+use brontes_pricing::{BrontesBatchPricer, GraphManager, GraphSnapshot, LoadState};
 use brontes_types::{
-    db::traits::LibmdbxReader, BrontesTaskExecutor, FastHashMap, UnboundedYapperReceiver,
+    db::traits::LibmdbxReader, BrontesTaskExecutor, ChainConfig, FastHashMap,
+    UnboundedYapperReceiver,
 };
+pub use explain::TxExplainer;
 use futures::{stream::FuturesUnordered, Future, StreamExt};
 use indicatif::MultiProgress;
 use itertools::Itertools;
+use malachite::Rational;
 pub use range::RangeExecutorWithPricing;
+pub use reinspect::Reinspector;
 use reth_tasks::shutdown::GracefulShutdown;
 pub use tip::TipInspector;
 use tokio::{sync::mpsc::unbounded_channel, task::JoinHandle};
 
+pub use self::shared::checkpoint::CheckpointStore;
 use self::shared::{
-    dex_pricing::WaitingForPricerFuture, metadata_loader::MetadataLoader,
+    checkpoint::{RangeCheckpoint, RangeCheckpointer, CHECKPOINT_INTERVAL},
+    dex_pricing::WaitingForPricerFuture,
+    metadata_loader::MetadataLoader,
     state_collector::StateCollector,
 };
-use crate::cli::static_object;
+use crate::{cli::static_object, sinks::ResultSinks};
 
 pub const PROMETHEUS_ENDPOINT_IP: [u8; 4] = [0u8, 0u8, 0u8, 0u8];
 
@@ -54,6 +63,7 @@ pub struct BrontesRunConfig<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseH
     pub max_tasks: u64,
     pub min_batch_size: u64,
     pub quote_asset: Address,
+    pub chain: &'static ChainConfig,
     pub force_dex_pricing: bool,
     pub force_no_dex_pricing: bool,
     pub inspectors: &'static [&'static dyn Inspector<Result = P::InspectType>],
@@ -65,6 +75,15 @@ pub struct BrontesRunConfig<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseH
     pub metrics: bool,
     pub is_snapshot: bool,
     pub cex_window: usize,
+    pub checkpoints: CheckpointStore,
+    pub resume: bool,
+    /// where the results of blocks processed at tip are streamed to
+    pub sinks: Option<&'static ResultSinks>,
+    /// the max relative price movement of a pair within a block, see
+    /// [`BrontesBatchPricer::with_max_block_movement`]
+    pub max_block_movement: Rational,
+    /// where the normalized actions of every processed block are written to
+    pub action_writer: Option<&'static ActionParquetWriter>,
     _p: PhantomData<P>,
 }
 
@@ -77,6 +96,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         max_tasks: u64,
         min_batch_size: u64,
         quote_asset: Address,
+        chain: &'static ChainConfig,
         force_dex_pricing: bool,
         force_no_dex_pricing: bool,
         inspectors: &'static [&'static dyn Inspector<Result = P::InspectType>],
@@ -88,6 +108,11 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         metrics: bool,
         is_snapshot: bool,
         cex_window: usize,
+        checkpoints: CheckpointStore,
+        resume: bool,
+        sinks: Option<&'static ResultSinks>,
+        max_block_movement: Rational,
+        action_writer: Option<&'static ActionParquetWriter>,
     ) -> Self {
         Self {
             clickhouse,
@@ -99,12 +124,18 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             libmdbx,
             inspectors,
             quote_asset,
+            chain,
             force_no_dex_pricing,
             cli_only,
             metrics,
             tip_db,
             is_snapshot,
             cex_window,
+            checkpoints,
+            resume,
+            sinks,
+            max_block_movement,
+            action_writer,
             _p: PhantomData,
         }
     }
@@ -159,6 +190,12 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             }
         }
 
+        let total_set_range = end_block
+            - self
+                .range_type
+                .get_start_block(self.libmdbx)
+                .unwrap_or(end_block);
+
         if !should_run_tip_inspector {
             self.build_range_executors(executor.clone(), end_block, pricing_metrics.clone())
                 .for_each(|block_range| {
@@ -186,11 +223,12 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
                     .await;
             }
             tracing::info!("starting tip inspector");
+            let back_from_tip = self.range_type.back_from_tip();
             let tip_inspector = self.build_tip_inspector(
                 usize::MAX,
                 executor.clone(),
                 end_block,
-                self.range_type.back_from_tip(),
+                back_from_tip,
                 pricing_metrics,
             );
 
@@ -202,13 +240,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
 
         let metrics = FinishedRange::default();
         metrics.running_ranges.increment(futures.len() as f64);
-        metrics.total_set_range.increment(
-            end_block
-                - self
-                    .range_type
-                    .get_start_block(self.libmdbx)
-                    .unwrap_or(end_block),
-        );
+        metrics.total_set_range.increment(total_set_range);
 
         Ok(Brontes { futures, metrics })
     }
@@ -241,6 +273,10 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
     /// This function uses `buffer_unordered(8)` to limit concurrent
     /// initialization of RangeExecutors. The actual number of concurrently
     /// running executors is not limited by this buffer.
+    ///
+    /// When resuming, the ranges of the previous run are reused so their
+    /// checkpoints line up. Finished ranges are skipped and the others continue
+    /// from their last checkpoint with the pricer state restored.
     fn build_range_executors(
         &'_ self,
         executor: BrontesTaskExecutor,
@@ -257,13 +293,27 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
                     start_block.unwrap()
                 };
 
-                self.calculate_chunks(start_block, end_block)
+                if self.resume {
+                    self.resume_chunks(start_block, end_block, |start, end| {
+                        self.calculate_chunks(start, end)
+                    })
+                } else {
+                    self.calculate_chunks(start_block, end_block)
+                }
             }
+            RangeType::MultipleRanges(ranges) if self.resume => ranges
+                .iter()
+                .flat_map(|(start, end)| {
+                    self.resume_chunks(*start, *end, |start, end| vec![(start, end)])
+                })
+                .collect_vec(),
             RangeType::MultipleRanges(ranges) => ranges.clone(),
         };
 
         let progress_bar = self.initialize_global_progress_bar();
 
+        let chunks = self.load_checkpoints(chunks, progress_bar.as_ref());
+
         let state_to_init = Arc::new(self.state_to_initialize(end_block));
 
         #[cfg(feature = "sorella-server")]
@@ -289,11 +339,16 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         }
 
         let range_metrics = self.metrics.then(|| {
-            GlobalRangeMetrics::new(chunks.iter().map(|(start, end)| end - start).collect_vec())
+            GlobalRangeMetrics::new(
+                chunks
+                    .iter()
+                    .map(|(start, end, _)| end - start)
+                    .collect_vec(),
+            )
         });
 
         futures::stream::iter(chunks.into_iter().enumerate().map(
-            move |(batch_id, (start_block, end_block))| {
+            move |(batch_id, (start_block, end_block, checkpoint))| {
                 let ranges =
                     state_to_init.get_state_for_ranges(start_block as usize, end_block as usize);
 
@@ -315,23 +370,52 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
                             .unwrap();
                     }
 
+                    let (resume_block, last_block, pricer_state) = match checkpoint {
+                        Some(checkpoint) => (
+                            checkpoint.resume_block().min(end_block),
+                            checkpoint.last_block,
+                            checkpoint.pricer_state,
+                        ),
+                        None => (start_block, None, None),
+                    };
+                    if resume_block != start_block {
+                        tracing::info!(
+                            "Resuming batch {batch_id} at block {resume_block}, restored pricer \
+                             state: {}",
+                            pricer_state.is_some()
+                        );
+                    }
+
+                    let mut checkpointer = RangeCheckpointer::new(
+                        self.checkpoints.clone(),
+                        start_block,
+                        end_block,
+                        last_block,
+                    );
+                    if last_block.is_none() {
+                        checkpointer.init();
+                    }
+
                     #[allow(clippy::async_yields_async)]
                     RangeExecutorWithPricing::new(
                         batch_id,
-                        start_block,
+                        resume_block,
                         end_block,
                         self.init_state_collector(
                             batch_id,
                             executor.clone(),
-                            start_block,
+                            resume_block,
                             end_block,
                             false,
                             pricing_metrics,
+                            pricer_state,
                         ),
                         self.libmdbx,
                         self.inspectors,
                         prgrs_bar,
                         metrics,
+                        checkpointer,
+                        self.action_writer,
                     )
                 }
             },
@@ -339,29 +423,45 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         .buffer_unordered(buffer_size)
     }
 
+    /// The tip inspector rebuilds its state collector from the first orphaned
+    /// block when it detects a reorg, so the config is moved into the builder.
     fn build_tip_inspector(
-        &self,
+        self,
         range_id: usize,
         executor: BrontesTaskExecutor,
         start_block: u64,
         back_from_tip: u64,
         pricing_metrics: Option<DexPricingMetrics>,
     ) -> TipInspector<T, DB, CH, P> {
-        let state_collector = self.init_state_collector(
-            range_id,
-            executor,
-            start_block,
-            start_block,
-            true,
-            pricing_metrics,
-        );
+        let parser = self.parser;
+        let tip_db = self.tip_db;
+        let inspectors = self.inspectors;
+        let metrics = self.metrics.then(TipMetrics::default);
+        let sinks = self.sinks;
+        let action_writer = self.action_writer;
+
+        let state_collector_builder = Box::new(move |start_block| {
+            self.init_state_collector(
+                range_id,
+                executor.clone(),
+                start_block,
+                start_block,
+                true,
+                pricing_metrics.clone(),
+                None,
+            )
+        });
+
         TipInspector::new(
             start_block,
             back_from_tip,
-            state_collector,
-            self.parser,
-            self.tip_db,
-            self.inspectors,
+            state_collector_builder,
+            parser,
+            tip_db,
+            inspectors,
+            metrics,
+            sinks,
+            action_writer,
         )
     }
 
@@ -379,6 +479,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
     /// * `end_block` - The last block in the range.
     /// * `tip` - Boolean flag indicating if this is for tip processing.
     /// * `pricing_metrics` - Optional metrics for DEX pricing.
+    /// * `pricer_state` - Optional pricer state restored from a checkpoint.
     ///
     /// # Returns
     ///
@@ -392,6 +493,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         end_block: u64,
         tip: bool,
         pricing_metrics: Option<DexPricingMetrics>,
+        pricer_state: Option<GraphSnapshot>,
     ) -> StateCollector<T, DB, CH> {
         let shutdown = Arc::new(AtomicBool::new(false));
         let (tx, rx) = unbounded_channel();
@@ -408,12 +510,15 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
                 pools
                     .into_iter()
                     .filter(|(_, p, _)| p.has_state_updater())
-                    .map(|(addr, protocol, pair)| (addr, (protocol, pair)))
+                    .map(|(addr, protocol, pairs)| (addr, (protocol, pairs)))
                     .collect::<Vec<_>>()
             })
             .collect::<FastHashMap<_, _>>();
 
-        let pair_graph = GraphManager::init_from_db_state(pairs, pricing_metrics.clone());
+        let mut pair_graph = GraphManager::init_from_db_state(pairs, pricing_metrics.clone());
+        if let Some(state) = pricer_state {
+            pair_graph.restore_state(state, pricing_metrics.clone());
+        }
 
         let data_req = Arc::new(AtomicBool::new(true));
 
@@ -421,6 +526,7 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             range_id,
             shutdown.clone(),
             self.quote_asset,
+            self.chain,
             pair_graph,
             UnboundedYapperReceiver::new(rx, 100_000, "batch pricer".into()),
             self.parser.get_tracer(),
@@ -429,9 +535,12 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
             data_req.clone(),
             pricing_metrics.clone(),
             executor.clone(),
-        );
+        )
+        .with_max_block_movement(self.max_block_movement.clone());
 
-        let pricing = WaitingForPricerFuture::new(pricer, executor);
+        // tip runs don't checkpoint, so there is no need to snapshot their pricer
+        let snapshot_interval = (!tip).then_some(CHECKPOINT_INTERVAL);
+        let pricing = WaitingForPricerFuture::new(pricer, executor, snapshot_interval);
         let fetcher = MetadataLoader::new(
             tip.then_some(self.clickhouse),
             pricing,
@@ -525,6 +634,81 @@ impl<T: TracingProvider, DB: LibmdbxInit, CH: ClickhouseHandle, P: Processor>
         Ok(())
     }
 
+    /// Splits the range into the ranges of the previous run that lie within
+    /// it, the parts of the range the previous run didn't cover are split
+    /// with `split`.
+    fn resume_chunks(
+        &self,
+        start_block: u64,
+        end_block: u64,
+        split: impl Fn(u64, u64) -> Vec<(u64, u64)>,
+    ) -> Vec<(u64, u64)> {
+        let mut chunks = vec![];
+        let mut next_block = start_block;
+
+        for (start, end) in self.checkpoints.ranges() {
+            // skip ranges outside of this one and overlapping ranges of older runs
+            if start < next_block || end > end_block {
+                continue
+            }
+            if start > next_block {
+                chunks.extend(split(next_block, start - 1));
+            }
+            chunks.push((start, end));
+            next_block = end + 1;
+        }
+
+        if next_block <= end_block {
+            chunks.extend(split(next_block, end_block));
+        }
+
+        chunks
+    }
+
+    /// Loads the checkpoint of every chunk when resuming, dropping the chunks
+    /// that already finished. Otherwise the checkpoints of a previous run that
+    /// overlap with the chunks are cleared.
+    fn load_checkpoints(
+        &self,
+        chunks: Vec<(u64, u64)>,
+        progress_bar: Option<&ProgressBar>,
+    ) -> Vec<(u64, u64, Option<RangeCheckpoint>)> {
+        if !self.resume {
+            for (start, end) in &chunks {
+                if let Err(e) = self.checkpoints.clear_overlapping(*start, *end) {
+                    tracing::warn!(err=%e, "failed to clear old checkpoints");
+                }
+            }
+
+            return chunks
+                .into_iter()
+                .map(|(start, end)| (start, end, None))
+                .collect_vec()
+        }
+
+        chunks
+            .into_iter()
+            .filter_map(|(start, end)| {
+                let checkpoint = self.checkpoints.load(start, end).ok();
+                if let Some(checkpoint) = checkpoint.as_ref() {
+                    let done = if checkpoint.finished {
+                        end - start
+                    } else {
+                        checkpoint.resume_block().min(end) - start
+                    };
+                    progress_bar.inspect(|pb| pb.inc(done));
+
+                    if checkpoint.finished {
+                        tracing::info!("Skipping finished block range {start}-{end}");
+                        return None
+                    }
+                }
+
+                Some((start, end, checkpoint))
+            })
+            .collect_vec()
+    }
+
     ///Calculate the block chunks using min batch size and max_tasks.
     /// Max tasks defaults to 50% of physical cores of the system if not set
     fn calculate_chunks(&self, start_block: u64, end_block: u64) -> Vec<(u64, u64)> {
@@ -1,28 +1,30 @@
-#[cfg(feature = "local-clickhouse")]
 use std::sync::Arc;
 
-use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
+use brontes_core::decoding::TracingProvider;
+use brontes_database::{
+    libmdbx::{DBWriter, LibmdbxReader},
+    parquet::ActionParquetWriter,
+};
 use brontes_inspect::{
     composer::{run_block_inspection, ComposerResults},
+    sandwich::simulate_victim_loss,
     Inspector,
 };
 #[cfg(feature = "local-clickhouse")]
 use brontes_types::frontend_prunes::{
     remove_burn_transfers, remove_collect_transfers, remove_mint_transfers, remove_swap_transfers,
 };
-#[cfg(feature = "local-clickhouse")]
-use brontes_types::normalized_actions::Action;
-#[cfg(feature = "local-clickhouse")]
-use brontes_types::tree::BlockTree;
 use brontes_types::{
-    db::block_analysis::BlockAnalysis,
+    db::{block_analysis::BlockAnalysis, metadata::Metadata},
     execute_on,
-    mev::{Bundle, MevBlock, MevType},
+    mev::{Bundle, BundleData, MevBlock, MevType},
+    normalized_actions::Action,
+    tree::BlockTree,
     BlockData, MultiBlockData,
 };
 use tracing::debug;
 
-use crate::Processor;
+use crate::{sinks::ResultSinks, Processor};
 
 #[derive(Debug, Clone, Copy)]
 pub struct MevProcessor;
@@ -30,10 +32,13 @@ pub struct MevProcessor;
 impl Processor for MevProcessor {
     type InspectType = Vec<Bundle>;
 
-    async fn process_results<DB: DBWriter + LibmdbxReader>(
+    async fn process_results<DB: DBWriter + LibmdbxReader, T: TracingProvider>(
         db: &'static DB,
+        tracer: Arc<T>,
         inspectors: &'static [&dyn Inspector<Result = Self::InspectType>],
         data: MultiBlockData,
+        sinks: Option<&'static ResultSinks>,
+        action_writer: Option<&'static ActionParquetWriter>,
     ) {
         let last = data.get_most_recent_block().clone();
         let BlockData { metadata, tree } = last;
@@ -50,17 +55,52 @@ impl Processor for MevProcessor {
             insert_tree(db, inner_tree, metadata.block_num).await;
         }
 
+        if let Some(action_writer) = action_writer {
+            if let Err(e) = action_writer.write_tree(&tree) {
+                tracing::error!(err=%e, block_num=metadata.block_num, "failed to write block actions to parquet");
+            }
+        }
+
         if tree.tx_roots.is_empty() {
             return
         }
 
-        let ComposerResults { block_details, mev_details, block_analysis, .. } =
+        let ComposerResults { block_details, mut mev_details, block_analysis, .. } =
             execute_on!(async_inspect, { run_block_inspection(inspectors, data, db) }).await;
 
+        simulate_sandwich_victims(&*tracer, inspectors, &metadata, &tree, &mut mev_details).await;
+
+        if let Some(sinks) = sinks {
+            sinks.publish(&block_details, &mev_details).await;
+        }
+
         insert_mev_results(db, block_details, mev_details, block_analysis).await;
     }
 }
 
+/// Measures the victim loss of the sandwiches. This needs the victims to be
+/// re-executed, which can't be done from within the inspectors
+pub async fn simulate_sandwich_victims<T: TracingProvider>(
+    tracer: &T,
+    inspectors: &[&dyn Inspector<Result = Vec<Bundle>>],
+    metadata: &Metadata,
+    tree: &BlockTree<Action>,
+    bundles: &mut [Bundle],
+) {
+    let Some(quote) = inspectors
+        .first()
+        .map(|inspector| inspector.get_quote_token())
+    else {
+        return
+    };
+
+    for bundle in bundles {
+        if let BundleData::Sandwich(sandwich) = &mut bundle.data {
+            simulate_victim_loss(tracer, quote, metadata, tree, sandwich).await;
+        }
+    }
+}
+
 #[cfg(feature = "local-clickhouse")]
 async fn insert_tree<DB: DBWriter + LibmdbxReader>(
     db: &DB,
@@ -91,6 +131,8 @@ async fn insert_mev_results<DB: DBWriter + LibmdbxReader>(
 
     let block_number = block_details.block_number;
     output_mev_and_update_searcher_info(database, &mev_details).await;
+    update_searcher_failed_attempts(database, &block_details).await;
+    update_builder_proposer_payment(database, &block_details).await;
 
     // Attempt to save the MEV block details
     if let Err(e) = database
@@ -111,6 +153,32 @@ async fn insert_mev_results<DB: DBWriter + LibmdbxReader>(
         );
     }
 }
+
+async fn update_searcher_failed_attempts<DB: DBWriter>(database: &DB, block_details: &MevBlock) {
+    let failed_attempts = &block_details.failed_attempts;
+    if failed_attempts.0.is_empty() {
+        return
+    }
+
+    if let Err(e) = database
+        .write_searcher_failed_attempts(block_details.block_number, failed_attempts.0.clone())
+        .await
+    {
+        tracing::error!("Failed to update searcher failed attempts in the database: {:?}", e);
+    }
+}
+
+async fn update_builder_proposer_payment<DB: DBWriter>(database: &DB, block_details: &MevBlock) {
+    let Some(payment_delta) = block_details.payment_delta else { return };
+
+    if let Err(e) = database
+        .write_builder_proposer_payment(block_details.builder_address, payment_delta)
+        .await
+    {
+        tracing::error!("Failed to update builder proposer payments in the database: {:?}", e);
+    }
+}
+
 async fn output_mev_and_update_searcher_info<DB: DBWriter + LibmdbxReader>(
     database: &DB,
     mev_details: &Vec<Bundle>,
@@ -1,17 +1,28 @@
 pub mod mev;
 
-use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
+use std::sync::Arc;
+
+use brontes_core::decoding::TracingProvider;
+use brontes_database::{
+    libmdbx::{DBWriter, LibmdbxReader},
+    parquet::ActionParquetWriter,
+};
 use brontes_inspect::Inspector;
 use brontes_types::MultiBlockData;
 use futures::Future;
 pub use mev::*;
 
+use crate::sinks::ResultSinks;
+
 pub trait Processor: Send + Sync + 'static + Unpin + Copy + Clone {
     type InspectType: Send + Sync + Unpin;
 
-    fn process_results<DB: DBWriter + LibmdbxReader>(
+    fn process_results<DB: DBWriter + LibmdbxReader, T: TracingProvider>(
         db: &'static DB,
+        tracer: Arc<T>,
         inspectors: &'static [&dyn Inspector<Result = Self::InspectType>],
         data: MultiBlockData,
+        sinks: Option<&'static ResultSinks>,
+        action_writer: Option<&'static ActionParquetWriter>,
     ) -> impl Future<Output = ()> + Send;
 }
@@ -8,6 +8,7 @@ use brontes_core::decoding::TracingProvider;
 use brontes_database::{
     clickhouse::ClickhouseHandle,
     libmdbx::{DBWriter, LibmdbxReader},
+    parquet::ActionParquetWriter,
 };
 use brontes_inspect::Inspector;
 use brontes_metrics::range::GlobalRangeMetrics;
@@ -16,10 +17,10 @@ use futures::{pin_mut, stream::FuturesUnordered, Future, StreamExt};
 use reth_tasks::shutdown::GracefulShutdown;
 use tracing::debug;
 
-use super::shared::state_collector::StateCollector;
+use super::shared::{checkpoint::RangeCheckpointer, state_collector::StateCollector};
 use crate::{executors::ProgressBar, Processor};
 
-type InsertFutures = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+type InsertFutures = Pin<Box<dyn Future<Output = u64> + Send + 'static>>;
 
 pub struct RangeExecutorWithPricing<
     T: TracingProvider,
@@ -36,6 +37,8 @@ pub struct RangeExecutorWithPricing<
     inspectors:     &'static [&'static dyn Inspector<Result = P::InspectType>],
     progress_bar:   Option<ProgressBar>,
     global_metrics: Option<GlobalRangeMetrics>,
+    checkpointer:   RangeCheckpointer,
+    action_writer:  Option<&'static ActionParquetWriter>,
     _p:             PhantomData<P>,
 }
 
@@ -51,6 +54,8 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
         inspectors: &'static [&'static dyn Inspector<Result = P::InspectType>],
         progress_bar: Option<ProgressBar>,
         global_metrics: Option<GlobalRangeMetrics>,
+        checkpointer: RangeCheckpointer,
+        action_writer: Option<&'static ActionParquetWriter>,
     ) -> Self {
         Self {
             id,
@@ -62,6 +67,8 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
             inspectors,
             progress_bar,
             global_metrics,
+            checkpointer,
+            action_writer,
             _p: PhantomData,
         }
     }
@@ -79,11 +86,12 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
             },
         }
 
-        while data_batching.insert_futures.next().await.is_some() {
+        while let Some(block) = data_batching.insert_futures.next().await {
             data_batching
                 .global_metrics
                 .as_ref()
                 .inspect(|m| m.finished_block(data_batching.id));
+            data_batching.checkpointer.on_block_processed(block);
         }
 
         drop(graceful_guard);
@@ -95,17 +103,32 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
             .as_ref()
             .inspect(|m| m.inc_inspector(self.id));
 
+        let block = data.get_most_recent_block().tree.header.number;
+        self.checkpointer.on_block_processing(block);
+
         let metrics = self.global_metrics.clone();
         let inspectors = self.inspectors;
         let libmdbx = self.libmdbx;
+        let tracer = self.collector.get_tracer();
+        let action_writer = self.action_writer;
         self.insert_futures.push(Box::pin(async move {
             if let Some(metrics) = metrics {
                 metrics
-                    .meter_processing(|| Box::pin(P::process_results(libmdbx, inspectors, data)))
+                    .meter_processing(|| {
+                        Box::pin(P::process_results(
+                            libmdbx,
+                            tracer,
+                            inspectors,
+                            data,
+                            None,
+                            action_writer,
+                        ))
+                    })
                     .await
             } else {
-                P::process_results(libmdbx, inspectors, data).await
+                P::process_results(libmdbx, tracer, inspectors, data, None, action_writer).await
             }
+            block
         }));
     }
 }
@@ -143,6 +166,7 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
                     self.on_price_finish(data);
                 }
                 None if self.insert_futures.is_empty() && self.current_block == self.end_block => {
+                    self.checkpointer.finish();
                     return Poll::Ready(())
                 }
                 None => {
@@ -152,11 +176,16 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle, P:
             }
         }
 
-        while let Poll::Ready(Some(_)) = self.insert_futures.poll_next_unpin(cx) {
+        if let Some((block, snapshot)) = self.collector.take_pricer_snapshot() {
+            self.checkpointer.on_pricer_snapshot(block, snapshot);
+        }
+
+        while let Poll::Ready(Some(block)) = self.insert_futures.poll_next_unpin(cx) {
             self.global_metrics.as_ref().inspect(|m| {
                 m.dec_inspector(self.id);
                 m.finished_block(self.id);
             });
+            self.checkpointer.on_block_processed(block);
         }
 
         // mark complete if we are done with the range
@@ -0,0 +1,295 @@
+use std::sync::Arc;
+
+use alloy_primitives::Address;
+use brontes_classifier::Classifier;
+use brontes_core::decoding::TracingProvider;
+use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
+use brontes_inspect::{
+    composer::{run_block_reinspection, ComposerResults},
+    Inspector, Inspectors,
+};
+use brontes_pricing::types::DexPriceMsg;
+use brontes_types::{
+    mev::{Bundle, MevType},
+    BlockData,
+};
+use itertools::Itertools;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+use super::{
+    shared::{multi_block_window::MultiBlockWindow, stored_metadata::StoredMetadataLoader},
+    simulate_sandwich_victims,
+};
+
+/// Re-runs a set of inspectors over blocks that were already processed. The
+/// block trees are rebuilt from the stored traces without pricing and the
+/// stored dex quotes and block info are used as the metadata, so nothing is
+/// re-traced or re-priced.
+///
+/// Only the bundles of the selected inspectors are replaced, the other stored
+/// bundles of the block are kept. Searcher info isn't updated as it would count
+/// the kept bundles twice.
+pub struct Reinspector<T: TracingProvider, DB: LibmdbxReader + DBWriter> {
+    start_block: u64,
+    end_block:   u64,
+    replaced:    Vec<MevType>,
+    inspectors:  &'static [&'static dyn Inspector<Result = Vec<Bundle>>],
+    classifier:  Classifier<'static, T, DB>,
+    pricing_rx:  UnboundedReceiver<DexPriceMsg>,
+    tracer:      Arc<T>,
+    libmdbx:     &'static DB,
+    metadata:    StoredMetadataLoader<DB>,
+}
+
+impl<T: TracingProvider, DB: LibmdbxReader + DBWriter> Reinspector<T, DB> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        start_block: u64,
+        end_block: u64,
+        quote_asset: Address,
+        selected: &[Inspectors],
+        inspectors: &'static [&'static dyn Inspector<Result = Vec<Bundle>>],
+        tracer: Arc<T>,
+        libmdbx: &'static DB,
+        cex_window_sec: usize,
+    ) -> Self {
+        // pricing is never run, the updates the classifier sends out are dropped
+        let (pricing_tx, pricing_rx) = unbounded_channel();
+        let classifier = Classifier::new(libmdbx, pricing_tx, tracer.clone());
+
+        Self {
+            start_block,
+            end_block,
+            replaced: replaced_mev_types(selected),
+            inspectors,
+            classifier,
+            pricing_rx,
+            tracer,
+            libmdbx,
+            metadata: StoredMetadataLoader::new(libmdbx, quote_asset, cex_window_sec),
+        }
+    }
+
+    pub async fn run(mut self) -> eyre::Result<()> {
+        let block_window_size = self
+            .inspectors
+            .iter()
+            .map(|inspector| inspector.block_window())
+            .max()
+            .expect("no inspectors loaded");
+        let mut window = MultiBlockWindow::new(block_window_size);
+
+        // multi block inspectors need the blocks before the start of the range
+        let first_block = self
+            .start_block
+            .saturating_sub(block_window_size as u64 - 1);
+
+        for block in first_block..=self.end_block {
+            let Some(data) = self.load_block(block).await else { continue };
+            let data = window.new_block_data(data);
+            if block < self.start_block || data.get_most_recent_block().tree.tx_roots.is_empty() {
+                continue
+            }
+
+            let stored = self
+                .libmdbx
+                .try_fetch_mev_block(block)?
+                .map(|mev_block| mev_block.mev)
+                .unwrap_or_default();
+
+            let BlockData { metadata, tree } = data.get_most_recent_block().clone();
+            let ComposerResults { block_details, mut mev_details, block_analysis, .. } =
+                run_block_reinspection(self.inspectors, data, self.libmdbx, stored, &self.replaced);
+
+            simulate_sandwich_victims(
+                &*self.tracer,
+                self.inspectors,
+                &metadata,
+                &tree,
+                &mut mev_details,
+            )
+            .await;
+
+            self.libmdbx
+                .save_mev_blocks(block, block_details, mev_details)
+                .await?;
+            self.libmdbx.write_block_analysis(block_analysis).await?;
+
+            tracing::debug!(target: "brontes::reinspect", %block, "reinspected block");
+        }
+
+        tracing::info!(
+            target: "brontes::reinspect",
+            start_block = self.start_block,
+            end_block = self.end_block,
+            "finished reinspection"
+        );
+
+        Ok(())
+    }
+
+    /// Rebuilds the block tree from the stored traces and loads the stored
+    /// metadata of the block
+    async fn load_block(&mut self, block: u64) -> Option<BlockData> {
+        let traces = self
+            .libmdbx
+            .load_trace(block)
+            .inspect_err(|e| tracing::warn!(%block, err=%e, "no stored traces for block"))
+            .ok()?;
+        let header = self
+            .tracer
+            .header_by_number(block)
+            .await
+            .ok()
+            .flatten()
+            .or_else(|| {
+                tracing::warn!(%block, "failed to load header");
+                None
+            })?;
+
+        let tree = self
+            .classifier
+            .build_block_tree(traces, header, false)
+            .await;
+        while self.pricing_rx.try_recv().is_ok() {}
+
+        let metadata = self
+            .metadata
+            .load(block, tree.header.beneficiary)
+            .inspect_err(|e| tracing::warn!(%block, err=%e, "no stored metadata for block"))
+            .ok()?;
+
+        Some(BlockData { metadata: metadata.into(), tree: tree.into() })
+    }
+}
+
+/// The mev types of the selected inspectors, the stored bundles of these types
+/// are replaced by the new results
+fn replaced_mev_types(selected: &[Inspectors]) -> Vec<MevType> {
+    selected
+        .iter()
+        .flat_map(|inspector| inspector.mev_types())
+        .copied()
+        .unique()
+        .collect_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::B256;
+    use brontes_classifier::test_utils::{call_trace, ClassifierTestUtils};
+    use brontes_types::{
+        constants::USDC_ADDRESS,
+        db::metadata::Metadata,
+        mev::{AtomicArb, BundleData, BundleHeader, Liquidation, SearcherTx},
+        MultiBlockData,
+    };
+
+    use super::*;
+
+    /// Returns the same bundles for every block
+    struct StaticInspector(Vec<Bundle>);
+
+    impl Inspector for StaticInspector {
+        type Result = Vec<Bundle>;
+
+        fn get_id(&self) -> &str {
+            "Static"
+        }
+
+        fn inspect_block(&self, _: MultiBlockData) -> Self::Result {
+            self.0.clone()
+        }
+
+        fn get_quote_token(&self) -> Address {
+            USDC_ADDRESS
+        }
+    }
+
+    fn bundle(mev_type: MevType, tx_index: u64, tx_hash: B256, profit_usd: f64) -> Bundle {
+        let data = match mev_type {
+            MevType::AtomicArb => {
+                BundleData::AtomicArb(AtomicArb { tx_hash, ..Default::default() })
+            }
+            MevType::Liquidation => BundleData::Liquidation(Liquidation {
+                liquidation_tx_hash: tx_hash,
+                ..Default::default()
+            }),
+            MevType::SearcherTx => {
+                BundleData::Unknown(SearcherTx { tx_hash, ..Default::default() })
+            }
+            _ => unreachable!(),
+        };
+
+        Bundle {
+            header: BundleHeader { tx_index, tx_hash, mev_type, profit_usd, ..Default::default() },
+            data,
+        }
+    }
+
+    #[test]
+    fn test_replaced_mev_types() {
+        assert_eq!(
+            replaced_mev_types(&[Inspectors::Sandwich, Inspectors::Jit]),
+            vec![MevType::Sandwich, MevType::JitSandwich, MevType::Jit]
+        );
+        assert_eq!(replaced_mev_types(&[Inspectors::AtomicArb]), vec![MevType::AtomicArb]);
+    }
+
+    #[brontes_macros::test]
+    async fn test_reinspection_replaces_selected_types() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        let txes = (1..=4u8)
+            .map(|i| {
+                vec![call_trace(
+                    vec![],
+                    Address::repeat_byte(i),
+                    Address::repeat_byte(0xf0 + i),
+                    vec![],
+                    vec![],
+                    vec![],
+                )]
+            })
+            .collect_vec();
+        let tree = classifier_utils
+            .build_block_tree_from_traces(19_000_000, txes)
+            .await;
+        let hashes = tree.tx_roots.iter().map(|root| root.tx_hash).collect_vec();
+
+        // the stored arb is replaced, the other stored bundles are kept
+        let stored = vec![
+            bundle(MevType::Liquidation, 0, hashes[0], 10.0),
+            bundle(MevType::AtomicArb, 1, hashes[1], 5.0),
+            bundle(MevType::SearcherTx, 2, hashes[2], 1.0),
+        ];
+        let inspector = StaticInspector(vec![bundle(MevType::AtomicArb, 3, hashes[3], 7.0)]);
+        let data = MultiBlockData {
+            per_block_data: vec![BlockData {
+                metadata: Metadata::default().into(),
+                tree:     tree.into(),
+            }],
+            blocks:         1,
+        };
+
+        let ComposerResults { mev_details, .. } = run_block_reinspection(
+            &[&inspector],
+            data,
+            classifier_utils.libmdbx,
+            stored,
+            &replaced_mev_types(&[Inspectors::AtomicArb]),
+        );
+
+        let results = mev_details
+            .iter()
+            .map(|bundle| (bundle.header.mev_type, bundle.header.tx_hash))
+            .collect_vec();
+        assert_eq!(
+            results,
+            vec![
+                (MevType::Liquidation, hashes[0]),
+                (MevType::SearcherTx, hashes[2]),
+                (MevType::AtomicArb, hashes[3]),
+            ]
+        );
+    }
+}
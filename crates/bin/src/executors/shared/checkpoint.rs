@@ -0,0 +1,373 @@
+use std::{
+    collections::BTreeSet,
+    fs::{self, File},
+    io::{BufReader, BufWriter, Write},
+    path::PathBuf,
+};
+
+use brontes_pricing::GraphSnapshot;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+/// Blocks between checkpoints of a range that isn't generating dex prices. When
+/// pricing, a checkpoint is written every time the pricer is snapshot.
+pub const CHECKPOINT_INTERVAL: u64 = 250;
+
+/// The progress of a range executor. The pricer state is the graph state of the
+/// pricer right after it priced `last_block`, so a resumed range can continue
+/// from the next block without re-warming its subgraphs.
+#[derive(Serialize, Deserialize)]
+pub struct RangeCheckpoint {
+    pub start_block:  u64,
+    pub end_block:    u64,
+    /// all blocks of the range up to and including this one have been processed
+    pub last_block:   Option<u64>,
+    pub finished:     bool,
+    pub pricer_state: Option<GraphSnapshot>,
+}
+
+impl RangeCheckpoint {
+    /// the block a resumed range should start at
+    pub fn resume_block(&self) -> u64 {
+        self.last_block.map(|b| b + 1).unwrap_or(self.start_block)
+    }
+}
+
+/// Stores a checkpoint file per range in the given directory
+#[derive(Debug, Clone)]
+pub struct CheckpointStore {
+    dir: PathBuf,
+}
+
+impl CheckpointStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn path(&self, start_block: u64, end_block: u64) -> PathBuf {
+        self.dir.join(format!("{start_block}-{end_block}.ckpt"))
+    }
+
+    /// the ranges that have a checkpoint, sorted by start block
+    pub fn ranges(&self) -> Vec<(u64, u64)> {
+        let Ok(entries) = fs::read_dir(&self.dir) else { return vec![] };
+
+        entries
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name().into_string().ok()?;
+                let (start, end) = name.strip_suffix(".ckpt")?.split_once('-')?;
+                Some((start.parse().ok()?, end.parse().ok()?))
+            })
+            .sorted()
+            .collect_vec()
+    }
+
+    pub fn load(&self, start_block: u64, end_block: u64) -> eyre::Result<RangeCheckpoint> {
+        let mut file = BufReader::new(File::open(self.path(start_block, end_block))?);
+        Ok(bincode::serde::decode_from_std_read(&mut file, bincode::config::standard())?)
+    }
+
+    pub fn save(&self, checkpoint: &RangeCheckpoint) -> eyre::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path(checkpoint.start_block, checkpoint.end_block);
+        // write to a temp file first so a crash mid write keeps the old checkpoint
+        let tmp = path.with_extension("tmp");
+
+        let mut file = BufWriter::new(File::create(&tmp)?);
+        bincode::serde::encode_into_std_write(checkpoint, &mut file, bincode::config::standard())?;
+        file.flush()?;
+        fs::rename(tmp, path)?;
+
+        Ok(())
+    }
+
+    /// removes the checkpoints of all ranges that overlap with the given range
+    pub fn clear_overlapping(&self, start_block: u64, end_block: u64) -> eyre::Result<()> {
+        for (start, end) in self.ranges() {
+            if start <= end_block && end >= start_block {
+                fs::remove_file(self.path(start, end))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Tracks which blocks of a range have been fully processed and writes the
+/// range's checkpoints.
+///
+/// Blocks are processed concurrently, so a checkpoint is only written once all
+/// blocks up to it have been processed. As the pricer works ahead of the
+/// processing, its snapshots are held until that point.
+pub struct RangeCheckpointer {
+    store:           CheckpointStore,
+    start_block:     u64,
+    end_block:       u64,
+    /// blocks handed to the processor that haven't finished yet
+    processing:      BTreeSet<u64>,
+    last_emitted:    Option<u64>,
+    last_checkpoint: Option<u64>,
+    pricer_snapshot: Option<(u64, GraphSnapshot)>,
+    pricing:         bool,
+}
+
+impl RangeCheckpointer {
+    pub fn new(
+        store: CheckpointStore,
+        start_block: u64,
+        end_block: u64,
+        last_checkpoint: Option<u64>,
+    ) -> Self {
+        Self {
+            store,
+            start_block,
+            end_block,
+            processing: BTreeSet::new(),
+            last_emitted: None,
+            last_checkpoint,
+            pricer_snapshot: None,
+            pricing: false,
+        }
+    }
+
+    pub fn on_block_processing(&mut self, block: u64) {
+        self.processing.insert(block);
+        self.last_emitted = self.last_emitted.max(Some(block));
+    }
+
+    pub fn on_block_processed(&mut self, block: u64) {
+        self.processing.remove(&block);
+        self.try_checkpoint();
+    }
+
+    pub fn on_pricer_snapshot(&mut self, block: u64, snapshot: GraphSnapshot) {
+        self.pricing = true;
+        self.pricer_snapshot = Some((block, snapshot));
+        self.try_checkpoint();
+    }
+
+    /// the highest block for which it and all blocks before it are processed
+    fn processed_up_to(&self) -> Option<u64> {
+        match self.processing.first() {
+            Some(block) => block.checked_sub(1).filter(|b| *b >= self.start_block),
+            None => self.last_emitted,
+        }
+    }
+
+    fn try_checkpoint(&mut self) {
+        let Some(processed) = self.processed_up_to() else { return };
+
+        if self.pricing {
+            if self
+                .pricer_snapshot
+                .as_ref()
+                .is_some_and(|(block, _)| *block <= processed)
+            {
+                let (block, snapshot) = self.pricer_snapshot.take().unwrap();
+                self.write(Some(block), false, Some(snapshot));
+            }
+            return
+        }
+
+        let next_checkpoint = self
+            .last_checkpoint
+            .map(|b| b + CHECKPOINT_INTERVAL)
+            .unwrap_or(self.start_block + CHECKPOINT_INTERVAL);
+
+        if processed >= next_checkpoint {
+            self.write(Some(processed), false, None);
+        }
+    }
+
+    /// records the range so a resumed run knows about it before it made any
+    /// progress
+    pub fn init(&mut self) {
+        self.write(self.last_checkpoint, false, None);
+    }
+
+    pub fn finish(&mut self) {
+        self.write(self.last_emitted.or(self.last_checkpoint), true, None);
+    }
+
+    fn write(
+        &mut self,
+        last_block: Option<u64>,
+        finished: bool,
+        pricer_state: Option<GraphSnapshot>,
+    ) {
+        let checkpoint = RangeCheckpoint {
+            start_block: self.start_block,
+            end_block: self.end_block,
+            last_block,
+            finished,
+            pricer_state,
+        };
+
+        if let Err(e) = self.store.save(&checkpoint) {
+            tracing::error!(
+                target: "brontes::checkpoint",
+                start_block = self.start_block,
+                end_block = self.end_block,
+                err = %e,
+                "failed to write range checkpoint"
+            );
+            return
+        }
+
+        tracing::debug!(
+            target: "brontes::checkpoint",
+            start_block = self.start_block,
+            end_block = self.end_block,
+            ?last_block,
+            finished,
+            "wrote range checkpoint"
+        );
+        self.last_checkpoint = last_block;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_pricing::{StateTracker, SubGraphRegistry, SubgraphVerifier};
+
+    use super::*;
+
+    fn snapshot() -> GraphSnapshot {
+        (SubGraphRegistry::new(None), SubgraphVerifier::new(), StateTracker::new(None))
+    }
+
+    fn checkpoint(start_block: u64, end_block: u64) -> RangeCheckpoint {
+        RangeCheckpoint {
+            start_block,
+            end_block,
+            last_block: None,
+            finished: false,
+            pricer_state: None,
+        }
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::new(dir.path().to_path_buf());
+        assert!(store.load(100, 200).is_err());
+
+        let mut saved = checkpoint(100, 200);
+        saved.last_block = Some(150);
+        saved.pricer_state = Some(snapshot());
+        store.save(&saved).unwrap();
+
+        let loaded = store.load(100, 200).unwrap();
+        assert_eq!(loaded.start_block, 100);
+        assert_eq!(loaded.end_block, 200);
+        assert_eq!(loaded.last_block, Some(150));
+        assert_eq!(loaded.resume_block(), 151);
+        assert!(!loaded.finished);
+        assert!(loaded.pricer_state.is_some());
+
+        // overwriting leaves no temp file behind
+        saved.finished = true;
+        store.save(&saved).unwrap();
+        assert!(store.load(100, 200).unwrap().finished);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        assert_eq!(checkpoint(100, 200).resume_block(), 100);
+    }
+
+    #[test]
+    fn test_ranges_skip_unrelated_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::new(dir.path().to_path_buf());
+        assert!(store.ranges().is_empty());
+
+        store.save(&checkpoint(300, 400)).unwrap();
+        store.save(&checkpoint(100, 200)).unwrap();
+        File::create(dir.path().join("notes.txt")).unwrap();
+        File::create(dir.path().join("a-b.ckpt")).unwrap();
+
+        assert_eq!(store.ranges(), vec![(100, 200), (300, 400)]);
+    }
+
+    #[test]
+    fn test_clear_overlapping() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::new(dir.path().to_path_buf());
+        for (start, end) in [(0, 99), (100, 199), (200, 299), (300, 399)] {
+            store.save(&checkpoint(start, end)).unwrap();
+        }
+
+        // the bounds are inclusive
+        store.clear_overlapping(199, 200).unwrap();
+        assert_eq!(store.ranges(), vec![(0, 99), (300, 399)]);
+
+        store.clear_overlapping(50, 50).unwrap();
+        assert_eq!(store.ranges(), vec![(300, 399)]);
+
+        store.clear_overlapping(400, 500).unwrap();
+        assert_eq!(store.ranges(), vec![(300, 399)]);
+    }
+
+    #[test]
+    fn test_checkpoint_waits_for_earlier_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::new(dir.path().to_path_buf());
+        let start = 1000;
+        let last = start + CHECKPOINT_INTERVAL + 10;
+        let mut checkpointer = RangeCheckpointer::new(store.clone(), start, 2000, None);
+
+        for block in start..=last {
+            checkpointer.on_block_processing(block);
+        }
+        // everything but the first two blocks finishes
+        for block in (start + 2..=last).rev() {
+            checkpointer.on_block_processed(block);
+        }
+        assert!(store.load(start, 2000).is_err());
+
+        // the first block alone isn't enough to reach the interval
+        checkpointer.on_block_processed(start);
+        assert!(store.load(start, 2000).is_err());
+
+        checkpointer.on_block_processed(start + 1);
+        assert_eq!(store.load(start, 2000).unwrap().last_block, Some(last));
+
+        checkpointer.finish();
+        let finished = store.load(start, 2000).unwrap();
+        assert!(finished.finished);
+        assert_eq!(finished.last_block, Some(last));
+    }
+
+    #[test]
+    fn test_pricer_snapshot_waits_for_processing() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::new(dir.path().to_path_buf());
+        let mut checkpointer = RangeCheckpointer::new(store.clone(), 1000, 2000, None);
+        checkpointer.init();
+        assert_eq!(store.load(1000, 2000).unwrap().last_block, None);
+
+        for block in 1000..=1010 {
+            checkpointer.on_block_processing(block);
+        }
+        for block in [1000, 1001, 1002, 1004, 1005] {
+            checkpointer.on_block_processed(block);
+        }
+
+        // the pricer is ahead of the processing, 1003 is still running
+        checkpointer.on_pricer_snapshot(1005, snapshot());
+        let pending = store.load(1000, 2000).unwrap();
+        assert_eq!(pending.last_block, None);
+        assert!(pending.pricer_state.is_none());
+
+        checkpointer.on_block_processed(1003);
+        let written = store.load(1000, 2000).unwrap();
+        assert_eq!(written.last_block, Some(1005));
+        assert_eq!(written.resume_block(), 1006);
+        assert!(written.pricer_state.is_some());
+
+        // while pricing, only snapshots are written
+        for block in 1006..=1010 {
+            checkpointer.on_block_processed(block);
+        }
+        assert_eq!(store.load(1000, 2000).unwrap().last_block, Some(1005));
+    }
+}
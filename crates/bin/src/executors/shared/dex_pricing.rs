@@ -6,7 +6,7 @@ use std::{
 };
 
 use brontes_core::decoding::TracingProvider;
-use brontes_pricing::BrontesBatchPricer;
+use brontes_pricing::{BrontesBatchPricer, GraphSnapshot};
 use brontes_types::{
     constants::START_OF_CHAINBOUND_MEMPOOL_DATA,
     db::{dex::DexQuotes, metadata::Metadata},
@@ -32,10 +32,20 @@ pub struct WaitingForPricerFuture<T: TracingProvider> {
     task_executor:            BrontesTaskExecutor,
     max_tree_block:           u64,
     pricing_resolved_cache:   VecDeque<(u64, DexQuotes)>,
+    /// if set, the graph state of the pricer is snapshot every n priced blocks
+    /// so that it can be checkpointed
+    snapshot_interval:        Option<u64>,
+    last_snapshot_block:      u64,
+    snapshot:                 Option<(u64, GraphSnapshot)>,
 }
 
 impl<T: TracingProvider> WaitingForPricerFuture<T> {
-    pub fn new(pricer: BrontesBatchPricer<T>, task_executor: BrontesTaskExecutor) -> Self {
+    pub fn new(
+        pricer: BrontesBatchPricer<T>,
+        task_executor: BrontesTaskExecutor,
+        snapshot_interval: Option<u64>,
+    ) -> Self {
+        let last_snapshot_block = pricer.current_block_processing();
         let (tx, rx) = channel(100);
         let tx_clone = tx.clone();
         let fut = Box::pin(Self::pricing_thread(pricer, tx_clone));
@@ -49,6 +59,9 @@ impl<T: TracingProvider> WaitingForPricerFuture<T> {
             tmp_trees: FastHashSet::default(),
             max_tree_block: 0,
             pricing_resolved_cache: VecDeque::new(),
+            snapshot_interval,
+            last_snapshot_block,
+            snapshot: None,
         }
     }
 
@@ -85,6 +98,22 @@ impl<T: TracingProvider> WaitingForPricerFuture<T> {
         self.task_executor.spawn_critical("dex pricer", fut);
     }
 
+    /// the latest snapshot of the pricer's graph state along with the block it
+    /// was taken after
+    pub fn take_snapshot(&mut self) -> Option<(u64, GraphSnapshot)> {
+        self.snapshot.take()
+    }
+
+    fn try_snapshot(&mut self, pricer: &BrontesBatchPricer<T>, block: u64) {
+        let Some(interval) = self.snapshot_interval else { return };
+        if block < self.last_snapshot_block + interval {
+            return
+        }
+
+        self.last_snapshot_block = block;
+        self.snapshot = Some((block, pricer.snapshot_graph_state()));
+    }
+
     pub fn add_failed_tree(&mut self, block: u64) {
         self.tmp_trees.insert(block);
     }
@@ -151,6 +180,9 @@ impl<T: TracingProvider> Stream for WaitingForPricerFuture<T> {
                 return Poll::Ready(None)
             };
 
+            if let Some((block, _)) = inner.as_ref() {
+                self.try_snapshot(&pricer, *block);
+            }
             self.reschedule(pricer);
             cx.waker().wake_by_ref();
 
@@ -11,6 +11,7 @@ use std::{
 
 use alloy_primitives::Address;
 use brontes_database::clickhouse::ClickhouseHandle;
+use brontes_pricing::GraphSnapshot;
 use brontes_types::{
     db::{
         cex::trades::{window_loader::CexWindow, CexTradeMap},
@@ -75,6 +76,10 @@ impl<T: TracingProvider, CH: ClickhouseHandle> MetadataLoader<T, CH> {
             && self.result_buf.len() < MAX_PENDING_TREES
     }
 
+    pub fn take_pricer_snapshot(&mut self) -> Option<(u64, GraphSnapshot)> {
+        self.dex_pricer_stream.take_snapshot()
+    }
+
     pub fn is_finished(&self) -> bool {
         self.result_buf.is_empty()
             && self.dex_pricer_stream.is_done()
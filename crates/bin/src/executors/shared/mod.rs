@@ -1,4 +1,6 @@
+pub mod checkpoint;
 pub mod dex_pricing;
 pub mod metadata_loader;
 pub mod multi_block_window;
 pub mod state_collector;
+pub mod stored_metadata;
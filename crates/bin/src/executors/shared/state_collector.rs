@@ -12,6 +12,7 @@ use brontes_classifier::Classifier;
 use brontes_core::decoding::Parser;
 use brontes_database::clickhouse::ClickhouseHandle;
 use brontes_metrics::range::GlobalRangeMetrics;
+use brontes_pricing::GraphSnapshot;
 use brontes_types::{
     db::traits::{DBWriter, LibmdbxReader},
     normalized_actions::Action,
@@ -67,6 +68,14 @@ impl<T: TracingProvider, DB: LibmdbxReader + DBWriter, CH: ClickhouseHandle>
         }
     }
 
+    pub fn get_tracer(&self) -> Arc<T> {
+        self.parser.get_tracer()
+    }
+
+    pub fn take_pricer_snapshot(&mut self) -> Option<(u64, GraphSnapshot)> {
+        self.metadata_fetcher.take_pricer_snapshot()
+    }
+
     pub fn get_shutdown(&self) -> Arc<AtomicBool> {
         self.mark_as_finished.clone()
     }
@@ -0,0 +1,61 @@
+use alloy_primitives::Address;
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_types::db::{
+    cex::trades::{window_loader::CexWindow, CexTradeMap},
+    metadata::Metadata,
+};
+use itertools::Itertools;
+
+/// Loads the metadata stored for blocks that were already processed, so they
+/// can be inspected again without re-fetching or re-pricing anything. Blocks
+/// have to be loaded in ascending order for the cex trade window to stay
+/// correct.
+pub struct StoredMetadataLoader<DB: LibmdbxReader> {
+    libmdbx:     &'static DB,
+    quote_asset: Address,
+    cex_window:  CexWindow,
+}
+
+impl<DB: LibmdbxReader> StoredMetadataLoader<DB> {
+    pub fn new(libmdbx: &'static DB, quote_asset: Address, cex_window_sec: usize) -> Self {
+        Self { libmdbx, quote_asset, cex_window: CexWindow::new(cex_window_sec) }
+    }
+
+    /// the stored metadata of the block, falling back to the metadata without
+    /// dex prices if none were stored
+    pub fn load(&mut self, block: u64, builder: Address) -> eyre::Result<Metadata> {
+        let mut metadata = self
+            .libmdbx
+            .get_metadata(block, self.quote_asset)
+            .or_else(|_| {
+                self.libmdbx
+                    .get_metadata_no_dex_price(block, self.quote_asset)
+            })?;
+        metadata.builder_info = self.libmdbx.try_fetch_builder_info(builder).ok().flatten();
+        metadata.cex_trades = Some(self.load_cex_trades(block));
+
+        Ok(metadata)
+    }
+
+    fn load_cex_trades(&mut self, block: u64) -> CexTradeMap {
+        if !self.cex_window.is_loaded() {
+            // given every download is -6 + 6 around the block
+            // we calculate the offset from the current block that we need
+            let offsets = (self.cex_window.get_window_lookahead() / 12) as u64;
+            let trades = (block - offsets..=block + offsets)
+                .filter_map(|block| self.libmdbx.get_cex_trades(block).ok())
+                .collect_vec();
+            self.cex_window.init(block + offsets, trades);
+
+            return self.cex_window.cex_trade_map()
+        }
+
+        let last_block = self.cex_window.get_last_end_block_loaded() + 1;
+        if let Ok(res) = self.libmdbx.get_cex_trades(last_block) {
+            self.cex_window.new_block(res);
+        }
+        self.cex_window.set_last_block(last_block);
+
+        self.cex_window.cex_trade_map()
+    }
+}
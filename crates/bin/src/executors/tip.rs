@@ -1,24 +1,37 @@
 use std::{
+    collections::VecDeque,
     marker::PhantomData,
     pin::Pin,
+    sync::atomic::Ordering::SeqCst,
     task::{Context, Poll},
     time::Duration,
 };
 
+use alloy_primitives::B256;
 use brontes_core::decoding::{Parser, TracingProvider};
 use brontes_database::{
     clickhouse::ClickhouseHandle,
     libmdbx::{DBWriter, LibmdbxReader},
+    parquet::ActionParquetWriter,
 };
 use brontes_inspect::Inspector;
+use brontes_metrics::range::TipMetrics;
 use brontes_types::MultiBlockData;
-use futures::{pin_mut, stream::FuturesUnordered, Future, StreamExt};
+use futures::{future::join_all, pin_mut, stream::FuturesUnordered, Future, FutureExt, StreamExt};
 use reth_tasks::shutdown::GracefulShutdown;
 use tokio::time::{interval, Interval};
-use tracing::debug;
+use tracing::{debug, warn};
 
 use super::shared::state_collector::StateCollector;
-use crate::Processor;
+use crate::{sinks::ResultSinks, Processor};
+
+/// The amount of processed blocks we keep the hash of. Reorgs deeper than
+/// this are only rolled back up to this depth.
+const MAX_REORG_DEPTH: usize = 64;
+
+pub type StateCollectorBuilder<T, DB, CH> = Box<dyn Fn(u64) -> StateCollector<T, DB, CH> + Send>;
+
+type ReorgCheckFuture = Pin<Box<dyn Future<Output = Option<u64>> + Send + 'static>>;
 
 pub struct TipInspector<
     T: TracingProvider,
@@ -30,10 +43,21 @@ pub struct TipInspector<
     back_from_tip:      u64,
     parser:             &'static Parser<T, DB>,
     state_collector:    StateCollector<T, DB, CH>,
+    collector_builder:  StateCollectorBuilder<T, DB, CH>,
+    /// hashes of the most recently processed blocks, used to detect reorgs
+    processed_blocks:   VecDeque<(u64, B256)>,
     database:           &'static DB,
     inspectors:         &'static [&'static dyn Inspector<Result = P::InspectType>],
     processing_futures: FuturesUnordered<Pin<Box<dyn Future<Output = ()> + Send + 'static>>>,
+    /// in flight check of the processed block hashes against the chain
+    reorg_check:        Option<ReorgCheckFuture>,
+    /// in flight rollback of the orphaned blocks, nothing new is processed
+    /// until it is done
+    rollback:           Option<Pin<Box<dyn Future<Output = ()> + Send + 'static>>>,
     poll_interval:      Interval,
+    metrics:            Option<TipMetrics>,
+    sinks:              Option<&'static ResultSinks>,
+    action_writer:      Option<&'static ActionParquetWriter>,
     _p:                 PhantomData<P>,
 }
 
@@ -43,20 +67,30 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
     pub fn new(
         current_block: u64,
         back_from_tip: u64,
-        state_collector: StateCollector<T, DB, CH>,
+        collector_builder: StateCollectorBuilder<T, DB, CH>,
         parser: &'static Parser<T, DB>,
         database: &'static DB,
         inspectors: &'static [&'static dyn Inspector<Result = P::InspectType>],
+        metrics: Option<TipMetrics>,
+        sinks: Option<&'static ResultSinks>,
+        action_writer: Option<&'static ActionParquetWriter>,
     ) -> Self {
         Self {
             back_from_tip,
-            state_collector,
+            state_collector: collector_builder(current_block),
+            collector_builder,
+            processed_blocks: VecDeque::with_capacity(MAX_REORG_DEPTH),
             inspectors,
             current_block,
             parser,
             processing_futures: FuturesUnordered::new(),
+            reorg_check: None,
+            rollback: None,
             database,
             poll_interval: interval(Duration::from_secs(3)),
+            metrics,
+            sinks,
+            action_writer,
             _p: PhantomData,
         }
     }
@@ -114,12 +148,93 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
         }
     }
 
+    /// Compares the hash of the processed blocks against the canonical chain.
+    /// Only the latest block is fetched unless it was reorged, in which case
+    /// all hashes are fetched at once to find the depth of the reorg.
+    fn check_for_reorg(&self) -> ReorgCheckFuture {
+        let parser = self.parser;
+        let processed = self.processed_blocks.clone();
+
+        Box::pin(async move {
+            let &(latest, latest_hash) = processed.back()?;
+            match parser.get_block_hash_for_number(latest).await {
+                Ok(Some(canonical)) if canonical == latest_hash => return None,
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::error!(block=%latest, "failed to fetch block hash: {:?}", e);
+                    return None
+                }
+            }
+
+            let canonical = join_all(
+                processed
+                    .iter()
+                    .map(|(block, _)| parser.get_block_hash_for_number(*block)),
+            )
+            .await
+            .into_iter()
+            .collect::<eyre::Result<Vec<_>>>()
+            .inspect_err(|e| tracing::error!("failed to fetch block hashes: {:?}", e))
+            .ok()?;
+
+            first_orphaned_block(&processed, &canonical)
+        })
+    }
+
+    /// Rolls back everything that was written from `first_orphaned` onwards
+    /// and restarts processing from there. The dex pricer and cex window only
+    /// move forward so the state collector is rebuilt from the orphaned block.
+    fn on_reorg(&mut self, first_orphaned: u64) {
+        let depth = self
+            .processed_blocks
+            .iter()
+            .filter(|(block, _)| *block >= first_orphaned)
+            .count();
+        warn!(
+            target:"brontes::tip_inspector",
+            %first_orphaned,
+            %depth,
+            "detected reorg, rolling back processed blocks"
+        );
+
+        if let Some(metrics) = &self.metrics {
+            metrics.reorgs.increment(1);
+            metrics.reorg_depth.record(depth as f64);
+        }
+
+        self.state_collector.get_shutdown().store(true, SeqCst);
+
+        let database = self.database;
+        let rollback = first_orphaned..=self.current_block.saturating_sub(1);
+        self.rollback = Some(Box::pin(async move {
+            if let Err(e) = database.rollback_blocks(rollback).await {
+                tracing::error!(%first_orphaned, "failed to roll back reorged blocks: {:?}", e);
+            }
+        }));
+
+        self.processed_blocks
+            .retain(|(block, _)| *block < first_orphaned);
+        self.current_block = first_orphaned;
+        self.state_collector = (self.collector_builder)(first_orphaned);
+    }
+
     fn on_price_finish(&mut self, data: MultiBlockData) {
         debug!(target:"brontes::tip_inspector","Completed DEX pricing");
+        if let Some(block) = data.per_block_data.last() {
+            if self.processed_blocks.len() == MAX_REORG_DEPTH {
+                self.processed_blocks.pop_front();
+            }
+            self.processed_blocks
+                .push_back((block.block_number(), block.tree.header.hash_slow()));
+        }
+
         self.processing_futures.push(Box::pin(P::process_results(
             self.database,
+            self.state_collector.get_tracer(),
             self.inspectors,
             data,
+            self.sinks,
+            self.action_writer,
         )));
     }
 }
@@ -130,11 +245,47 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
     type Output = ();
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(rollback) = self.rollback.as_mut() {
+            if rollback.poll_unpin(cx).is_pending() {
+                return Poll::Pending
+            }
+            self.rollback = None;
+        }
+
         // given we pull the next block sync, we use this to trigger looking
         // for the next block.
-        while self.poll_interval.poll_tick(cx).is_ready() {}
+        let mut ticked = false;
+        while self.poll_interval.poll_tick(cx).is_ready() {
+            ticked = true;
+        }
 
-        if self.start_block_inspector() && self.state_collector.should_process_next_block() {
+        // only check for reorgs once all in flight blocks are written, this way
+        // nothing for an orphaned block can be written after the rollback
+        if ticked
+            && self.reorg_check.is_none()
+            && self.processing_futures.is_empty()
+            && !self.state_collector.is_collecting_state()
+        {
+            self.reorg_check = Some(self.check_for_reorg());
+        }
+
+        if let Some(check) = self.reorg_check.as_mut() {
+            if let Poll::Ready(reorged) = check.poll_unpin(cx) {
+                self.reorg_check = None;
+                if let Some(first_orphaned) = reorged {
+                    self.on_reorg(first_orphaned);
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending
+                }
+            }
+        }
+
+        // no new blocks are started while checking for a reorg as they could
+        // otherwise be written after the rollback
+        if self.reorg_check.is_none()
+            && self.start_block_inspector()
+            && self.state_collector.should_process_next_block()
+        {
             let block = self.current_block;
             tracing::info!(%block,"starting new tip block");
             self.state_collector.fetch_state_for(block, 0, None);
@@ -153,3 +304,77 @@ impl<T: TracingProvider, DB: DBWriter + LibmdbxReader, CH: ClickhouseHandle, P:
         Poll::Pending
     }
 }
+
+/// Takes the processed blocks and their canonical hashes in the same order.
+/// Walks back from the latest block and returns the oldest block of the
+/// orphaned run, if any.
+fn first_orphaned_block(
+    processed: &VecDeque<(u64, B256)>,
+    canonical: &[Option<B256>],
+) -> Option<u64> {
+    processed
+        .iter()
+        .zip(canonical)
+        .rev()
+        .take_while(|((_, hash), canonical)| canonical.as_ref() != Some(hash))
+        .last()
+        .map(|((block, _), _)| *block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn processed(blocks: u64) -> VecDeque<(u64, B256)> {
+        (0..blocks)
+            .map(|block| (block, B256::with_last_byte(block as u8)))
+            .collect()
+    }
+
+    #[test]
+    fn test_no_reorg() {
+        let processed = processed(5);
+        let canonical = processed
+            .iter()
+            .map(|(_, hash)| Some(*hash))
+            .collect::<Vec<_>>();
+
+        assert_eq!(first_orphaned_block(&processed, &canonical), None);
+    }
+
+    #[test]
+    fn test_reorg_depth() {
+        let processed = processed(5);
+        let mut canonical = processed
+            .iter()
+            .map(|(_, hash)| Some(*hash))
+            .collect::<Vec<_>>();
+        canonical[3] = Some(B256::repeat_byte(0xff));
+        // the node doesn't know the latest block anymore
+        canonical[4] = None;
+
+        assert_eq!(first_orphaned_block(&processed, &canonical), Some(3));
+    }
+
+    #[test]
+    fn test_reorg_only_counts_from_latest() {
+        let processed = processed(5);
+        let mut canonical = processed
+            .iter()
+            .map(|(_, hash)| Some(*hash))
+            .collect::<Vec<_>>();
+        // a mismatch below a matching block isn't part of the reorg
+        canonical[1] = Some(B256::repeat_byte(0xff));
+        canonical[4] = Some(B256::repeat_byte(0xff));
+
+        assert_eq!(first_orphaned_block(&processed, &canonical), Some(4));
+    }
+
+    #[test]
+    fn test_whole_window_orphaned() {
+        let processed = processed(3);
+        let canonical = vec![None; 3];
+
+        assert_eq!(first_orphaned_block(&processed, &canonical), Some(0));
+    }
+}
@@ -14,6 +14,7 @@ use brontes::{
     cli::{Args, Commands},
     runner,
 };
+use brontes_types::ChainConfig;
 use clap::Parser;
 use eyre::eyre;
 use tracing::{error, info};
@@ -51,7 +52,21 @@ fn run() -> eyre::Result<()> {
         .brontes_db_path
         .unwrap_or(env::var("BRONTES_DB_PATH").expect("No BRONTES_DB_PATH in .env"));
 
-    init_tracing(opt.verbosity.directive());
+    init_tracing(opt.verbosity.directive(), opt.streams_to_stdout());
+
+    let chain_config = match opt.chain_config {
+        Some(path) => {
+            let config: ChainConfig = toml::from_str(&std::fs::read_to_string(path)?)?;
+            info!(target: "brontes", chain = %config.chain, "loaded chain config");
+            config
+        }
+        None => ChainConfig::default(),
+    };
+    // a bad genesis file should fail here, not once the tracer is started
+    reth_tracing_ext::chain_spec(&chain_config.chain)?;
+    chain_config
+        .init()
+        .map_err(|_| eyre!("chain config was already initialized"))?;
 
     let metrics_port = if opt.skip_prometheus { None } else { Some(opt.metrics_port) };
 
@@ -66,14 +81,33 @@ fn run() -> eyre::Result<()> {
                 command.execute(brontes_db_path, ctx)
             })
         }
+        Commands::Serve(command) => {
+            runner::run_command_until_exit(None, Duration::from_secs(5), |ctx| {
+                command.execute(brontes_db_path, ctx)
+            })
+        }
+        Commands::Reinspect(command) => {
+            runner::run_command_until_exit(None, Duration::from_secs(60), |ctx| {
+                command.execute(brontes_db_path, ctx)
+            })
+        }
+        Commands::Explain(command) => {
+            runner::run_command_until_exit(None, Duration::from_secs(60), |ctx| {
+                command.execute(brontes_db_path, ctx)
+            })
+        }
     }
 }
 
-fn init_tracing(verbosity: Directive) {
-    let layers = vec![
-        brontes_tracing::stdout(verbosity),
-        brontes_metrics::error_layer::BrontesErrorMetrics::default().boxed(),
-    ];
+fn init_tracing(verbosity: Directive, streams_to_stdout: bool) {
+    // keep stdout clean for the results when they are streamed there
+    let fmt_layer = if streams_to_stdout {
+        brontes_tracing::stderr(verbosity)
+    } else {
+        brontes_tracing::stdout(verbosity)
+    };
+    let layers =
+        vec![fmt_layer, brontes_metrics::error_layer::BrontesErrorMetrics::default().boxed()];
 
     brontes_tracing::init(layers);
 }
@@ -0,0 +1,65 @@
+use std::{sync::Arc, time::Duration};
+
+use rdkafka::{
+    producer::{FutureProducer, FutureRecord},
+    ClientConfig,
+};
+
+use super::ResultSink;
+
+/// Produces the results to a kafka topic, keyed by block number
+pub struct KafkaSink {
+    producer: FutureProducer,
+    topic:    String,
+}
+
+impl KafkaSink {
+    pub fn new(brokers: &str, topic: String) -> eyre::Result<Self> {
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("message.timeout.ms", "10000")
+            .create()?;
+        tracing::info!(target: "brontes::sinks", %brokers, %topic, "created kafka producer");
+
+        Ok(Self { producer, topic })
+    }
+}
+
+impl ResultSink for KafkaSink {
+    fn name(&self) -> &'static str {
+        "kafka"
+    }
+
+    async fn send(&mut self, block_number: u64, payload: Arc<str>) -> eyre::Result<()> {
+        let key = block_number.to_string();
+
+        self.producer
+            .send(record(&self.topic, &key, &payload), Duration::from_secs(0))
+            .await
+            .map_err(|(e, _)| e)?;
+
+        Ok(())
+    }
+}
+
+/// Records are keyed by the block number of the results
+fn record<'a>(topic: &'a str, key: &'a str, payload: &'a str) -> FutureRecord<'a, str, [u8]> {
+    FutureRecord::to(topic).key(key).payload(payload.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_is_keyed_by_block_number() {
+        let payload = r#"{"block":{"block_number":18000000},"bundles":[]}"#;
+        let key = 18_000_000_u64.to_string();
+        let record = record("brontes-results", &key, payload);
+
+        assert_eq!(record.topic, "brontes-results");
+        assert_eq!(record.key, Some("18000000"));
+        assert_eq!(record.payload, Some(payload.as_bytes()));
+        assert_eq!(record.partition, None);
+    }
+}
@@ -0,0 +1,234 @@
+//! Streams the results of each processed block to external consumers, so
+//! downstream services can act on them as soon as brontes has processed a
+//! block instead of polling the database.
+//!
+//! Every sink runs in its own task fed by a bounded channel. When a sink can't
+//! keep up, the [`BackPressure`] policy decides whether processing waits for
+//! it or the results are dropped for that sink.
+#[cfg(feature = "kafka-sink")]
+mod kafka;
+#[cfg(feature = "nats-sink")]
+mod nats;
+mod stdout;
+#[cfg(feature = "ws-sink")]
+mod websocket;
+
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use brontes_types::{
+    mev::{Bundle, MevBlock},
+    BrontesTaskExecutor,
+};
+use clap::ValueEnum;
+#[cfg(feature = "kafka-sink")]
+pub use kafka::KafkaSink;
+#[cfg(feature = "nats-sink")]
+pub use nats::NatsSink;
+use serde::Serialize;
+pub use stdout::StdoutSink;
+use tokio::sync::mpsc::{channel, error::TrySendError, Receiver, Sender};
+#[cfg(feature = "ws-sink")]
+pub use websocket::WebSocketSink;
+
+/// The payload sent to the sinks for every processed block
+#[derive(Debug, Serialize)]
+pub struct BlockResults<'a> {
+    pub block:   &'a MevBlock,
+    pub bundles: &'a [Bundle],
+}
+
+/// A destination the results of processed blocks are pushed to. The payload
+/// is the json encoded [`BlockResults`] of the block.
+pub trait ResultSink: Send + 'static {
+    fn name(&self) -> &'static str;
+
+    fn send(
+        &mut self,
+        block_number: u64,
+        payload: Arc<str>,
+    ) -> impl Future<Output = eyre::Result<()>> + Send;
+}
+
+/// What to do when the buffer of a sink is full
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BackPressure {
+    /// Drop the results for the sink that is behind
+    #[default]
+    Drop,
+    /// Wait for the sink to catch up, which stalls the processing of blocks
+    Block,
+}
+
+struct SinkHandle {
+    name:    &'static str,
+    tx:      Sender<(u64, Arc<str>)>,
+    dropped: AtomicU64,
+}
+
+/// Publishes the results of processed blocks to all registered sinks
+pub struct ResultSinks {
+    sinks:         Vec<SinkHandle>,
+    buffer:        usize,
+    back_pressure: BackPressure,
+}
+
+impl ResultSinks {
+    pub fn new(buffer: usize, back_pressure: BackPressure) -> Self {
+        Self { sinks: vec![], buffer, back_pressure }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    /// Spawns the task that feeds the sink
+    pub fn add_sink<S: ResultSink>(&mut self, sink: S, executor: &BrontesTaskExecutor) {
+        let name = sink.name();
+        let rx = self.register(name);
+        executor.spawn(run_sink(sink, rx));
+
+        tracing::info!(target: "brontes::sinks", sink = name, "added result sink");
+    }
+
+    fn register(&mut self, name: &'static str) -> Receiver<(u64, Arc<str>)> {
+        let (tx, rx) = channel(self.buffer);
+        self.sinks
+            .push(SinkHandle { name, tx, dropped: AtomicU64::new(0) });
+
+        rx
+    }
+
+    pub async fn publish(&self, block: &MevBlock, bundles: &[Bundle]) {
+        if self.sinks.is_empty() {
+            return
+        }
+
+        let block_number = block.block_number;
+        let payload: Arc<str> = match serde_json::to_string(&BlockResults { block, bundles }) {
+            Ok(payload) => payload.into(),
+            Err(e) => {
+                tracing::error!(target: "brontes::sinks", %block_number, err=%e, "failed to serialize block results");
+                return
+            }
+        };
+
+        for sink in &self.sinks {
+            let msg = (block_number, payload.clone());
+            match self.back_pressure {
+                BackPressure::Block => {
+                    if sink.tx.send(msg).await.is_err() {
+                        tracing::warn!(target: "brontes::sinks", sink = sink.name, "result sink closed");
+                    }
+                }
+                BackPressure::Drop => match sink.tx.try_send(msg) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(_)) => {
+                        let dropped = sink.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                        tracing::warn!(
+                            target: "brontes::sinks",
+                            sink = sink.name,
+                            %block_number,
+                            dropped,
+                            "result sink is behind, dropped block results"
+                        );
+                    }
+                    Err(TrySendError::Closed(_)) => {
+                        tracing::warn!(target: "brontes::sinks", sink = sink.name, "result sink closed");
+                    }
+                },
+            }
+        }
+    }
+}
+
+async fn run_sink<S: ResultSink>(mut sink: S, mut rx: Receiver<(u64, Arc<str>)>) {
+    while let Some((block_number, payload)) = rx.recv().await {
+        if let Err(e) = sink.send(block_number, payload).await {
+            tracing::warn!(
+                target: "brontes::sinks",
+                sink = sink.name(),
+                %block_number,
+                err = %e,
+                "failed to send block results"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn block(block_number: u64) -> MevBlock {
+        MevBlock { block_number, ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_drop_results_for_sink_that_is_behind() {
+        let mut sinks = ResultSinks::new(1, BackPressure::Drop);
+        let mut rx = sinks.register("test");
+
+        sinks.publish(&block(1), &[]).await;
+        sinks.publish(&block(2), &[]).await;
+
+        assert_eq!(rx.recv().await.unwrap().0, 1);
+        assert!(rx.try_recv().is_err());
+        assert_eq!(sinks.sinks[0].dropped.load(Ordering::Relaxed), 1);
+
+        // once the sink caught up the next results go through again
+        sinks.publish(&block(3), &[]).await;
+        assert_eq!(rx.recv().await.unwrap().0, 3);
+    }
+
+    #[tokio::test]
+    async fn test_block_until_sink_catches_up() {
+        let mut sinks = ResultSinks::new(1, BackPressure::Block);
+        let mut rx = sinks.register("test");
+
+        sinks.publish(&block(1), &[]).await;
+        let blocked =
+            tokio::time::timeout(Duration::from_millis(50), sinks.publish(&block(2), &[])).await;
+        assert!(blocked.is_err());
+
+        assert_eq!(rx.recv().await.unwrap().0, 1);
+        sinks.publish(&block(2), &[]).await;
+        assert_eq!(rx.recv().await.unwrap().0, 2);
+        assert_eq!(sinks.sinks[0].dropped.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn test_every_sink_gets_the_same_payload() {
+        let mut sinks = ResultSinks::new(4, BackPressure::Drop);
+        let mut first = sinks.register("first");
+        let mut second = sinks.register("second");
+
+        sinks.publish(&block(1), &[]).await;
+
+        let (_, first) = first.recv().await.unwrap();
+        let (_, second) = second.recv().await.unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[tokio::test]
+    async fn test_payload_shape() {
+        let mut sinks = ResultSinks::new(1, BackPressure::Drop);
+        let mut rx = sinks.register("test");
+
+        sinks.publish(&block(18_000_000), &[]).await;
+        let (block_number, payload) = rx.recv().await.unwrap();
+        let payload: serde_json::Value = serde_json::from_str(&payload).unwrap();
+
+        assert_eq!(block_number, 18_000_000);
+        assert_eq!(payload["block"]["block_number"], 18_000_000);
+        assert_eq!(payload["bundles"], serde_json::json!([]));
+        assert_eq!(payload.as_object().unwrap().len(), 2);
+    }
+}
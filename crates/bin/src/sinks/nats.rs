@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use super::ResultSink;
+
+/// Publishes the results to a nats subject
+pub struct NatsSink {
+    client:  async_nats::Client,
+    subject: String,
+}
+
+impl NatsSink {
+    pub async fn connect(url: &str, subject: String) -> eyre::Result<Self> {
+        let client = async_nats::connect(url).await?;
+        tracing::info!(target: "brontes::sinks", %url, %subject, "connected to nats");
+
+        Ok(Self { client, subject })
+    }
+}
+
+impl ResultSink for NatsSink {
+    fn name(&self) -> &'static str {
+        "nats"
+    }
+
+    async fn send(&mut self, _: u64, payload: Arc<str>) -> eyre::Result<()> {
+        self.client
+            .publish(self.subject.clone(), message(&payload).into())
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// The message body is the json payload as is
+fn message(payload: &str) -> Vec<u8> {
+    payload.as_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_is_the_json_payload() {
+        let payload = r#"{"block":{"block_number":1},"bundles":[]}"#;
+        let body: serde_json::Value = serde_json::from_slice(&message(payload)).unwrap();
+
+        assert_eq!(body["block"]["block_number"], 1);
+        assert_eq!(body["bundles"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use tokio::io::{stdout, AsyncWrite, AsyncWriteExt, Stdout};
+
+use super::ResultSink;
+
+/// Writes the results as newline delimited json to stdout
+pub struct StdoutSink<W = Stdout> {
+    out: W,
+}
+
+impl StdoutSink {
+    pub fn new() -> Self {
+        Self { out: stdout() }
+    }
+}
+
+impl Default for StdoutSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: AsyncWrite + Unpin + Send + 'static> ResultSink for StdoutSink<W> {
+    fn name(&self) -> &'static str {
+        "stdout"
+    }
+
+    async fn send(&mut self, _: u64, payload: Arc<str>) -> eyre::Result<()> {
+        let mut line = String::with_capacity(payload.len() + 1);
+        line.push_str(&payload);
+        line.push('\n');
+
+        self.out.write_all(line.as_bytes()).await?;
+        self.out.flush().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_writes_one_line_per_block() {
+        let mut sink = StdoutSink { out: Vec::new() };
+
+        sink.send(1, r#"{"block":1}"#.into()).await.unwrap();
+        sink.send(2, r#"{"block":2}"#.into()).await.unwrap();
+
+        assert_eq!(String::from_utf8(sink.out).unwrap(), "{\"block\":1}\n{\"block\":2}\n");
+    }
+}
@@ -0,0 +1,121 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use brontes_types::BrontesTaskExecutor;
+use futures::SinkExt;
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::broadcast::{self, error::RecvError},
+};
+use tokio_tungstenite::tungstenite::Message;
+
+use super::ResultSink;
+
+/// Results kept for clients that are behind before they start missing results
+const CLIENT_BUFFER: usize = 256;
+
+/// Serves the results to every client connected to a websocket server. Clients
+/// that fall behind skip the results they missed instead of holding up the
+/// others.
+pub struct WebSocketSink {
+    tx: broadcast::Sender<Arc<str>>,
+}
+
+impl WebSocketSink {
+    pub async fn bind(addr: SocketAddr, executor: &BrontesTaskExecutor) -> eyre::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let (tx, _) = broadcast::channel(CLIENT_BUFFER);
+
+        let clients = tx.clone();
+        let client_executor = executor.clone();
+        executor.spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, peer)) => {
+                        client_executor.spawn(serve_client(stream, peer, clients.subscribe()));
+                    }
+                    Err(e) => {
+                        tracing::warn!(target: "brontes::sinks", err=%e, "failed to accept websocket connection");
+                    }
+                }
+            }
+        });
+        tracing::info!(target: "brontes::sinks", %addr, "websocket sink listening");
+
+        Ok(Self { tx })
+    }
+}
+
+impl ResultSink for WebSocketSink {
+    fn name(&self) -> &'static str {
+        "websocket"
+    }
+
+    async fn send(&mut self, _: u64, payload: Arc<str>) -> eyre::Result<()> {
+        // errors only when no clients are connected
+        let _ = self.tx.send(payload);
+        Ok(())
+    }
+}
+
+async fn serve_client(
+    stream: TcpStream,
+    peer: SocketAddr,
+    mut results: broadcast::Receiver<Arc<str>>,
+) {
+    let mut ws = match tokio_tungstenite::accept_async(stream).await {
+        Ok(ws) => ws,
+        Err(e) => {
+            tracing::debug!(target: "brontes::sinks", %peer, err=%e, "websocket handshake failed");
+            return
+        }
+    };
+    tracing::debug!(target: "brontes::sinks", %peer, "websocket client connected");
+
+    loop {
+        let payload = match results.recv().await {
+            Ok(payload) => payload,
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::warn!(target: "brontes::sinks", %peer, skipped, "websocket client is behind, skipped block results");
+                continue
+            }
+            Err(RecvError::Closed) => break,
+        };
+
+        if ws.send(Message::Text(payload.to_string())).await.is_err() {
+            break
+        }
+    }
+
+    tracing::debug!(target: "brontes::sinks", %peer, "websocket client disconnected");
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::BrontesTaskManager;
+    use futures::StreamExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_clients_receive_the_payload_as_text() {
+        let manager = BrontesTaskManager::current();
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let mut sink = WebSocketSink::bind(addr, &manager.executor())
+            .await
+            .unwrap();
+
+        // the client is subscribed before the server side of the handshake completes
+        let (mut client, _) = tokio_tungstenite::connect_async(format!("ws://{addr}"))
+            .await
+            .unwrap();
+
+        let payload: Arc<str> = r#"{"block":{},"bundles":[]}"#.into();
+        sink.send(1, payload.clone()).await.unwrap();
+
+        let msg = client.next().await.unwrap().unwrap();
+        assert_eq!(msg, Message::Text(payload.to_string()));
+    }
+}
@@ -0,0 +1,3 @@
+mod ocr2_aggregator;
+
+pub use ocr2_aggregator::*;
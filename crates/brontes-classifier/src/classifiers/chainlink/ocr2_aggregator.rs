@@ -0,0 +1,137 @@
+use alloy_primitives::U256;
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::NormalizedOracleUpdate, structured_trace::CallInfo, Protocol,
+};
+
+// Aggregators are registered with the priced asset as token0. The answer is
+// kept in the decimals of the feed, which are 8 for usd feeds and 18 for eth
+// feeds.
+action_impl!(
+    Protocol::ChainlinkAggregator,
+    crate::ChainlinkOCR2Aggregator::transmitCall,
+    OracleUpdate,
+    [..AnswerUpdated],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: ChainlinkAggregatorTransmitCallLogs,
+    db_tx: &DB| {
+        let logs = log_data.answer_updated_field?;
+        let details = db_tx.get_protocol_details(info.target_address)?;
+        let asset = db_tx.try_fetch_token_info(details.token0)?;
+
+        Ok(NormalizedOracleUpdate {
+            protocol: Protocol::ChainlinkAggregator,
+            trace_index: info.trace_idx,
+            oracle: info.target_address,
+            from: info.msg_sender,
+            asset,
+            // price feeds never report a negative answer
+            answer: if logs.current.is_negative() { U256::ZERO } else { logs.current.into_raw() },
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, Bytes, Log, B256, I256};
+    use alloy_sol_types::{SolCall, SolEvent};
+    use brontes_types::{
+        db::token_info::TokenInfoWithAddress,
+        normalized_actions::{Action, NormalizedOracleUpdate},
+        structured_trace::TransactionTraceWithLogs,
+        Protocol, TreeSearchBuilder,
+    };
+
+    use super::*;
+    use crate::{
+        test_utils::{call_trace, ClassifierTestUtils},
+        ChainlinkOCR2Aggregator::{transmitCall, AnswerUpdated},
+    };
+
+    const ETH_USD_AGGREGATOR: Address =
+        Address::new(hex!("E62B71cf983019BFf55bC83B48601ce8419650CC"));
+
+    fn transmit(answer: I256) -> Vec<TransactionTraceWithLogs> {
+        let transmitter = Address::repeat_byte(1);
+
+        vec![call_trace(
+            vec![],
+            transmitter,
+            ETH_USD_AGGREGATOR,
+            transmitCall {
+                reportContext: [B256::repeat_byte(2); 3],
+                report:        Bytes::from(vec![3; 96]),
+                rs:            vec![B256::repeat_byte(4)],
+                ss:            vec![B256::repeat_byte(5)],
+                rawVs:         B256::ZERO,
+            }
+            .abi_encode(),
+            vec![],
+            vec![Log {
+                address: ETH_USD_AGGREGATOR,
+                data:    AnswerUpdated {
+                    current:   answer,
+                    roundId:   U256::from(1_000),
+                    updatedAt: U256::from(1_700_000_000),
+                }
+                .encode_log_data(),
+            }],
+        )]
+    }
+
+    async fn classify(answer: I256) -> Vec<Action> {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_token(TokenInfoWithAddress::weth());
+        classifier_utils.ensure_protocol(
+            Protocol::ChainlinkAggregator,
+            ETH_USD_AGGREGATOR,
+            TokenInfoWithAddress::weth().address,
+            Some(TokenInfoWithAddress::usdc().address),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(19_000_000, vec![transmit(answer)])
+            .await;
+
+        tree.tx_roots
+            .remove(0)
+            .collect(&TreeSearchBuilder::default().with_action(Action::is_oracle_update))
+    }
+
+    #[brontes_macros::test]
+    async fn test_chainlink_transmit() {
+        // $3000 in the 8 decimals of the feed
+        let answer = U256::from(300_000_000_000_u64);
+        let mut actions = classify(I256::from_raw(answer)).await;
+
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions.remove(0),
+            Action::OracleUpdate(NormalizedOracleUpdate {
+                protocol: Protocol::ChainlinkAggregator,
+                trace_index: 0,
+                oracle: ETH_USD_AGGREGATOR,
+                from: Address::repeat_byte(1),
+                asset: TokenInfoWithAddress::weth(),
+                answer,
+            })
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_chainlink_transmit_negative_answer() {
+        let mut actions = classify(I256::MINUS_ONE).await;
+
+        assert_eq!(actions.len(), 1);
+        let Action::OracleUpdate(update) = actions.remove(0) else {
+            panic!("not an oracle update")
+        };
+        assert_eq!(update.answer, U256::ZERO);
+    }
+}
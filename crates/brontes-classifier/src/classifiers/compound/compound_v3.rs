@@ -0,0 +1,168 @@
+use alloy_primitives::Address;
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    db::token_info::TokenInfoWithAddress, normalized_actions::NormalizedLiquidation,
+    structured_trace::CallInfo, utils::ToScaledRational,
+};
+use malachite::{num::basic::traits::Zero, Rational};
+
+// Comet liquidations happen in two steps. `absorb` moves the collateral of an
+// underwater account into the protocol reserves, then anyone can buy it at a
+// discount through `buyCollateral`. The buy is where the liquidator profits, so
+// that is what we classify. The debtor is linked from the `absorb` once the
+// whole block is classified. The buyer pays `baseAmount` of the base token of
+// the comet, which is token0 of the comet in the classifier config. If the base
+// token isn't known it is filled in from the transfer into the comet.
+action_impl!(
+    Protocol::CompoundV3,
+    crate::CompoundV3Comet::buyCollateralCall,
+    Liquidation,
+    [..BuyCollateral],
+    call_data: true,
+    logs: true,
+    |
+    info: CallInfo,
+    call_data: buyCollateralCall,
+    log_data: CompoundV3BuyCollateralCallLogs,
+    db_tx: &DB | {
+        let logs = log_data.buy_collateral_field?;
+        let collateral_info = db_tx.try_fetch_token_info(call_data.asset)?;
+        let liquidated_collateral =
+            logs.collateralAmount.to_scaled_rational(collateral_info.decimals);
+
+        let details = db_tx.get_protocol_details(info.target_address)?;
+        let (debt_asset, covered_debt) = if details.token0 == Address::ZERO {
+            (TokenInfoWithAddress::default(), Rational::ZERO)
+        } else {
+            let base_info = db_tx.try_fetch_token_info(details.token0)?;
+            let covered_debt = logs.baseAmount.to_scaled_rational(base_info.decimals);
+            (base_info, covered_debt)
+        };
+
+        return Ok(NormalizedLiquidation {
+            protocol: Protocol::CompoundV3,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            liquidator: info.msg_sender,
+            // linked from the absorb
+            debtor: Address::ZERO,
+            collateral_asset: collateral_info,
+            debt_asset,
+            covered_debt,
+            liquidated_collateral,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, Log, U256};
+    use alloy_sol_types::{SolCall, SolEvent};
+    use brontes_types::{
+        normalized_actions::{Action, NormalizedLiquidation},
+        Protocol, TreeSearchBuilder,
+    };
+    use malachite::Rational;
+
+    use crate::{
+        test_utils::{call_trace, ClassifierTestUtils},
+        CompoundV3Comet::{absorbCall, buyCollateralCall, AbsorbCollateral, BuyCollateral},
+    };
+
+    const COMET: Address = Address::new(hex!("c3d688B66703497DAA19211EEdff47f25384cdc3"));
+    const USDC: Address = Address::new(hex!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"));
+    const WETH: Address = Address::new(hex!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"));
+
+    #[brontes_macros::test]
+    async fn test_compound_v3_liquidation_absorbed_in_previous_block() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::CompoundV3,
+            COMET,
+            USDC,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let absorber = Address::repeat_byte(1);
+        let borrower = Address::repeat_byte(2);
+        let buyer = Address::repeat_byte(3);
+        let collateral = U256::from(2_000_000_000_000_000_000_u128);
+        let base_amount = U256::from(5_000_000_000_u64);
+
+        let absorb = call_trace(
+            vec![],
+            absorber,
+            COMET,
+            absorbCall { absorber, accounts: vec![borrower] }.abi_encode(),
+            vec![],
+            vec![Log {
+                address: COMET,
+                data:    AbsorbCollateral {
+                    absorber,
+                    borrower,
+                    asset: WETH,
+                    collateralAbsorbed: collateral,
+                    usdValue: U256::from(5_200_000_000_u64),
+                }
+                .encode_log_data(),
+            }],
+        );
+        let buy = call_trace(
+            vec![],
+            buyer,
+            COMET,
+            buyCollateralCall {
+                asset:      WETH,
+                minAmount:  collateral,
+                baseAmount: base_amount,
+                recipient:  buyer,
+            }
+            .abi_encode(),
+            vec![],
+            vec![Log {
+                address: COMET,
+                data:    BuyCollateral {
+                    buyer,
+                    asset: WETH,
+                    baseAmount: base_amount,
+                    collateralAmount: collateral,
+                }
+                .encode_log_data(),
+            }],
+        );
+
+        // the absorb and the buy are in different blocks
+        classifier_utils
+            .build_block_tree_from_traces(19_000_000, vec![vec![absorb]])
+            .await;
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(19_000_001, vec![vec![buy]])
+            .await;
+
+        let eq_action = Action::Liquidation(NormalizedLiquidation {
+            protocol:              Protocol::CompoundV3,
+            liquidated_collateral: Rational::from(2),
+            covered_debt:          Rational::from(5000),
+            debtor:                borrower,
+            debt_asset:            classifier_utils.get_token_info(USDC),
+            collateral_asset:      classifier_utils.get_token_info(WETH),
+            liquidator:            buyer,
+            pool:                  COMET,
+            trace_index:           0,
+            msg_value:             U256::ZERO,
+        });
+
+        let mut actions = tree
+            .tx_roots
+            .remove(0)
+            .collect(&TreeSearchBuilder::default().with_action(Action::is_liquidation));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions.remove(0), eq_action);
+    }
+}
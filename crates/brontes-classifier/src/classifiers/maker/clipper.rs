@@ -0,0 +1,255 @@
+use alloy_primitives::Address;
+use brontes_macros::action_impl;
+use brontes_types::{
+    db::token_info::TokenInfoWithAddress, normalized_actions::NormalizedLiquidation,
+    structured_trace::CallInfo, Protocol, ToScaledRational,
+};
+
+/// Vat internal amounts are in wad (18), ray (27) and rad (45) decimals
+const WAD: u8 = 18;
+const RAD: u8 = 45;
+
+// `Dog.bark` only kicks off the auction, the keeper is paid its incentive in
+// internal vat dai. The collateral is sold to the keeper in `Clipper.take`,
+// which is what we classify as the liquidation. Keepers can take the
+// collateral in a callback to `who`, in which case the swaps that fund the take
+// are nested under it.
+action_impl!(
+    Protocol::MakerClipper,
+    crate::MakerClipper::takeCall,
+    Liquidation,
+    [..Take],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: MakerClipperTakeCallLogs,
+    db_tx: &DB| {
+        let logs = log_data.take_field?;
+
+        // For the clipper, the token0 should always be set to DAI and token1 is the
+        // collateral gem. The gem is filled in later if it isn't known.
+        let details = db_tx.get_protocol_details(info.target_address)?;
+        let debt_asset = db_tx.try_fetch_token_info(details.token0)?;
+        let collateral_asset = if details.token1 == Address::ZERO {
+            TokenInfoWithAddress::default()
+        } else {
+            db_tx.try_fetch_token_info(details.token1)?
+        };
+
+        // owe is the dai paid in rad, price is dai per unit of collateral in ray.
+        // Vat gem balances are always in wad, independent of the gem decimals.
+        // The slice is truncated the same way the clipper does it.
+        let covered_debt = logs.owe.to_scaled_rational(RAD);
+        let liquidated_collateral = logs
+            .owe
+            .checked_div(logs.price)
+            .unwrap_or_default()
+            .to_scaled_rational(WAD);
+
+        Ok(NormalizedLiquidation {
+            protocol: Protocol::MakerClipper,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            liquidator: info.msg_sender,
+            debtor: logs.usr,
+            collateral_asset,
+            debt_asset,
+            covered_debt,
+            liquidated_collateral,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, Bytes, Log, U256};
+    use alloy_sol_types::{SolCall, SolEvent};
+    use brontes_types::{
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        normalized_actions::{Action, NormalizedLiquidation},
+        structured_trace::TransactionTraceWithLogs,
+        Protocol, TreeSearchBuilder,
+    };
+    use malachite::Rational;
+
+    use crate::{
+        erc20::transferCall,
+        test_utils::{call_trace, ClassifierTestUtils},
+        MakerClipper::{takeCall, Take},
+        UniswapV2::{swapCall, Swap},
+    };
+
+    const ETH_CLIPPER: Address = Address::new(hex!("c67963a140D9b4E9F2e16C93Ac7C8Ee4546dbD7C"));
+    const DAI: Address = Address::new(hex!("6B175474E89094C44Da98b954EedeAC495271d0F"));
+    const ETH_JOIN: Address = Address::new(hex!("2F0b23f53734252Bda2277357e97e1517d6B042A"));
+    const DAI_WETH_PAIR: Address = Address::new(hex!("A478c2975Ab1Ea89e8196811F51A7B7Ade33eB11"));
+    const KEEPER: Address = Address::repeat_byte(0x11);
+    const USR: Address = Address::repeat_byte(0x22);
+    const BLOCK: u64 = 19_000_000;
+
+    fn dai() -> TokenInfoWithAddress {
+        TokenInfoWithAddress {
+            address: DAI,
+            inner:   TokenInfo { decimals: 18, symbol: "DAI".to_string() },
+        }
+    }
+
+    async fn setup(gem: Option<Address>) -> ClassifierTestUtils {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_token(dai());
+        classifier_utils.ensure_token(TokenInfoWithAddress::weth());
+        classifier_utils.ensure_protocol(
+            Protocol::MakerClipper,
+            ETH_CLIPPER,
+            DAI,
+            gem,
+            None,
+            None,
+            None,
+            None,
+        );
+        classifier_utils
+    }
+
+    /// Takes 1 eth of collateral for 3000 dai, with the keeper callback if
+    /// `callback` is set.
+    fn take(callback: bool) -> TransactionTraceWithLogs {
+        call_trace(
+            vec![],
+            KEEPER,
+            ETH_CLIPPER,
+            takeCall {
+                id:   U256::from(1),
+                amt:  U256::from(10).pow(U256::from(18)),
+                max:  U256::from(3_000) * U256::from(10).pow(U256::from(27)),
+                who:  KEEPER,
+                data: if callback { Bytes::from(vec![1]) } else { Bytes::new() },
+            }
+            .abi_encode(),
+            vec![],
+            vec![Log {
+                address: ETH_CLIPPER,
+                data:    Take {
+                    id:    U256::from(1),
+                    max:   U256::from(3_000) * U256::from(10).pow(U256::from(27)),
+                    // dai per eth in ray
+                    price: U256::from(3_000) * U256::from(10).pow(U256::from(27)),
+                    // dai paid in rad
+                    owe:   U256::from(3_000) * U256::from(10).pow(U256::from(45)),
+                    tab:   U256::ZERO,
+                    lot:   U256::ZERO,
+                    usr:   USR,
+                }
+                .encode_log_data(),
+            }],
+        )
+    }
+
+    #[brontes_macros::test]
+    async fn test_clipper_take() {
+        let classifier_utils = setup(Some(TokenInfoWithAddress::weth().address)).await;
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(BLOCK, vec![vec![take(false)]])
+            .await;
+
+        let mut actions = tree
+            .tx_roots
+            .remove(0)
+            .collect(&TreeSearchBuilder::default().with_action(Action::is_liquidation));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions.remove(0),
+            Action::Liquidation(NormalizedLiquidation {
+                protocol:              Protocol::MakerClipper,
+                trace_index:           0,
+                pool:                  ETH_CLIPPER,
+                liquidator:            KEEPER,
+                debtor:                USR,
+                collateral_asset:      TokenInfoWithAddress::weth(),
+                debt_asset:            dai(),
+                covered_debt:          Rational::from(3_000),
+                liquidated_collateral: Rational::from(1),
+                msg_value:             U256::ZERO,
+            })
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_clipper_take_callback() {
+        let classifier_utils = setup(None).await;
+        classifier_utils.ensure_protocol(
+            Protocol::UniswapV2,
+            DAI_WETH_PAIR,
+            DAI,
+            Some(TokenInfoWithAddress::weth().address),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let one_eth = U256::from(10).pow(U256::from(18));
+        let dai_out = U256::from(3_010) * one_eth;
+        // the keeper exits the bought eth out of the join in the callback and
+        // sells it for the dai that pays for the take
+        let traces = vec![
+            take(true),
+            call_trace(vec![0], ETH_CLIPPER, KEEPER, vec![], vec![], vec![]),
+            call_trace(
+                vec![0, 0],
+                ETH_JOIN,
+                TokenInfoWithAddress::weth().address,
+                transferCall { _0: KEEPER, _1: one_eth }.abi_encode(),
+                vec![],
+                vec![],
+            ),
+            call_trace(
+                vec![0, 1],
+                KEEPER,
+                DAI_WETH_PAIR,
+                swapCall {
+                    amount0Out: dai_out,
+                    amount1Out: U256::ZERO,
+                    to:         KEEPER,
+                    data:       Bytes::new(),
+                }
+                .abi_encode(),
+                vec![],
+                vec![Log {
+                    address: DAI_WETH_PAIR,
+                    data:    Swap {
+                        sender:     KEEPER,
+                        amount0In:  U256::ZERO,
+                        amount1In:  one_eth,
+                        amount0Out: dai_out,
+                        amount1Out: U256::ZERO,
+                        to:         KEEPER,
+                    }
+                    .encode_log_data(),
+                }],
+            ),
+        ];
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(BLOCK, vec![traces])
+            .await;
+        let root = tree.tx_roots.remove(0);
+
+        let liquidations =
+            root.collect(&TreeSearchBuilder::default().with_action(Action::is_liquidation));
+        assert_eq!(liquidations.len(), 1);
+        let liquidation = liquidations[0].clone().try_liquidation().unwrap();
+        assert_eq!(liquidation.collateral_asset, TokenInfoWithAddress::weth());
+        assert_eq!(liquidation.liquidated_collateral, Rational::from(1));
+
+        // the callback swap stays in the tree for the liquidation inspector
+        let swaps = root.collect(&TreeSearchBuilder::default().with_action(Action::is_swap));
+        assert_eq!(swaps.len(), 1);
+        let swap = swaps[0].clone().try_swaps_merged().unwrap();
+        assert_eq!(swap.token_in, TokenInfoWithAddress::weth());
+        assert_eq!(swap.token_out, dai());
+        assert_eq!(swap.amount_out, Rational::from(3_010));
+    }
+}
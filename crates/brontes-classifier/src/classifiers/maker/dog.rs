@@ -0,0 +1,151 @@
+use alloy_primitives::Address;
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::NormalizedLiquidation, structured_trace::CallInfo, Protocol,
+};
+use malachite::{num::basic::traits::Zero, Rational};
+
+// `Dog.bark` moves the debt and collateral of an unsafe vault into a clipper
+// auction. Nothing leaves the vat until the auction is taken, the keeper is
+// only paid its incentive in internal vat dai, so the amounts are left at zero.
+// The debt asset is dai, the collateral is the gem of the clipper if it is
+// known.
+action_impl!(
+    Protocol::MakerDog,
+    crate::MakerDog::barkCall,
+    Liquidation,
+    [..Bark],
+    call_data: true,
+    logs: true,
+    |
+    info: CallInfo,
+    call_data: barkCall,
+    log_data: MakerDogBarkCallLogs,
+    db_tx: &DB| {
+        let logs = log_data.bark_field?;
+
+        let details = db_tx.get_protocol_details(info.target_address)?;
+        let debt_asset = db_tx.try_fetch_token_info(details.token0)?;
+        let collateral_asset = db_tx
+            .get_protocol_details(logs.clip)
+            .ok()
+            .filter(|clipper| clipper.token1 != Address::ZERO)
+            .and_then(|clipper| db_tx.try_fetch_token_info(clipper.token1).ok())
+            .unwrap_or_default();
+
+        Ok(NormalizedLiquidation {
+            protocol: Protocol::MakerDog,
+            trace_index: info.trace_idx,
+            pool: logs.clip,
+            liquidator: call_data.kpr,
+            debtor: call_data.urn,
+            collateral_asset,
+            debt_asset,
+            covered_debt: Rational::ZERO,
+            liquidated_collateral: Rational::ZERO,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, Log, B256, U256};
+    use alloy_sol_types::{SolCall, SolEvent};
+    use brontes_types::{
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        normalized_actions::{Action, NormalizedLiquidation},
+        Protocol, TreeSearchBuilder,
+    };
+    use malachite::{num::basic::traits::Zero, Rational};
+
+    use crate::{
+        test_utils::{call_trace, ClassifierTestUtils},
+        MakerDog::{barkCall, Bark},
+    };
+
+    const DOG: Address = Address::new(hex!("135954d155898D42C90D2a57824C690e0c7BEf1B"));
+    const ETH_CLIPPER: Address = Address::new(hex!("c67963a140D9b4E9F2e16C93Ac7C8Ee4546dbD7C"));
+    const DAI: Address = Address::new(hex!("6B175474E89094C44Da98b954EedeAC495271d0F"));
+
+    #[brontes_macros::test]
+    async fn test_dog_bark() {
+        let dai = TokenInfoWithAddress {
+            address: DAI,
+            inner:   TokenInfo { decimals: 18, symbol: "DAI".to_string() },
+        };
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_token(dai.clone());
+        classifier_utils.ensure_token(TokenInfoWithAddress::weth());
+        classifier_utils.ensure_protocol(
+            Protocol::MakerDog,
+            DOG,
+            DAI,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        classifier_utils.ensure_protocol(
+            Protocol::MakerClipper,
+            ETH_CLIPPER,
+            DAI,
+            Some(TokenInfoWithAddress::weth().address),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let keeper = Address::repeat_byte(0x11);
+        let urn = Address::repeat_byte(0x22);
+        let ilk = B256::right_padding_from(b"ETH-A");
+
+        let bark = call_trace(
+            vec![],
+            keeper,
+            DOG,
+            barkCall { ilk, urn, kpr: keeper }.abi_encode(),
+            vec![],
+            vec![Log {
+                address: DOG,
+                data:    Bark {
+                    ilk,
+                    urn,
+                    ink: U256::from(10).pow(U256::from(18)),
+                    art: U256::from(2_000) * U256::from(10).pow(U256::from(18)),
+                    due: U256::from(2_000) * U256::from(10).pow(U256::from(45)),
+                    clip: ETH_CLIPPER,
+                    id: U256::from(1),
+                }
+                .encode_log_data(),
+            }],
+        );
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(19_000_000, vec![vec![bark]])
+            .await;
+
+        let mut actions = tree
+            .tx_roots
+            .remove(0)
+            .collect(&TreeSearchBuilder::default().with_action(Action::is_liquidation));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions.remove(0),
+            Action::Liquidation(NormalizedLiquidation {
+                protocol:              Protocol::MakerDog,
+                trace_index:           0,
+                pool:                  ETH_CLIPPER,
+                liquidator:            keeper,
+                debtor:                urn,
+                collateral_asset:      TokenInfoWithAddress::weth(),
+                debt_asset:            dai,
+                covered_debt:          Rational::ZERO,
+                liquidated_collateral: Rational::ZERO,
+                msg_value:             U256::ZERO,
+            })
+        );
+    }
+}
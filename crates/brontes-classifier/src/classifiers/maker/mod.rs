@@ -6,3 +6,15 @@ pub use psm::*;
 mod dss_flash;
 
 pub use dss_flash::*;
+
+mod clipper;
+
+pub use clipper::*;
+
+mod dog;
+
+pub use dog::*;
+
+mod osm;
+
+pub use osm::*;
@@ -0,0 +1,105 @@
+use alloy_primitives::U256;
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::NormalizedOracleUpdate, structured_trace::CallInfo, Protocol,
+};
+
+// The OSM delays prices by an hour, `poke` makes the value queued on the
+// previous poke current and emits it. The spotter reads it on its next poke,
+// which is what makes vaults liquidatable. The value is a wad.
+action_impl!(
+    Protocol::MakerOSM,
+    crate::MakerOSM::pokeCall,
+    OracleUpdate,
+    [..LogValue],
+    logs: true,
+    |
+    info: CallInfo,
+    log_data: MakerOSMPokeCallLogs,
+    db_tx: &DB| {
+        let logs = log_data.log_value_field?;
+        let details = db_tx.get_protocol_details(info.target_address)?;
+        let asset = db_tx.try_fetch_token_info(details.token0)?;
+
+        Ok(NormalizedOracleUpdate {
+            protocol: Protocol::MakerOSM,
+            trace_index: info.trace_idx,
+            oracle: info.target_address,
+            from: info.msg_sender,
+            asset,
+            answer: U256::from_be_bytes(logs.val.0),
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, Log};
+    use alloy_sol_types::{SolCall, SolEvent};
+    use brontes_types::{
+        db::token_info::TokenInfoWithAddress,
+        normalized_actions::{Action, NormalizedOracleUpdate},
+        Protocol, TreeSearchBuilder,
+    };
+
+    use super::*;
+    use crate::{
+        test_utils::{call_trace, ClassifierTestUtils},
+        MakerOSM::{pokeCall, LogValue},
+    };
+
+    const ETH_OSM: Address = Address::new(hex!("81FE72B5A8d1A857d176C3E7d5Bd2679A9B85763"));
+
+    #[brontes_macros::test]
+    async fn test_osm_poke() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_token(TokenInfoWithAddress::weth());
+        classifier_utils.ensure_protocol(
+            Protocol::MakerOSM,
+            ETH_OSM,
+            TokenInfoWithAddress::weth().address,
+            Some(TokenInfoWithAddress::usdc().address),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let keeper = Address::repeat_byte(1);
+        // $3000 as a wad
+        let value = U256::from(3_000_000_000_000_000_000_000_u128);
+
+        let poke = call_trace(
+            vec![],
+            keeper,
+            ETH_OSM,
+            pokeCall {}.abi_encode(),
+            vec![],
+            vec![Log {
+                address: ETH_OSM,
+                data:    LogValue { val: value.into() }.encode_log_data(),
+            }],
+        );
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(19_000_000, vec![vec![poke]])
+            .await;
+
+        let mut actions = tree
+            .tx_roots
+            .remove(0)
+            .collect(&TreeSearchBuilder::default().with_action(Action::is_oracle_update));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions.remove(0),
+            Action::OracleUpdate(NormalizedOracleUpdate {
+                protocol:    Protocol::MakerOSM,
+                trace_index: 0,
+                oracle:      ETH_OSM,
+                from:        keeper,
+                asset:       TokenInfoWithAddress::weth(),
+                answer:      value,
+            })
+        );
+    }
+}
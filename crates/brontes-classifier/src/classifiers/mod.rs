@@ -38,9 +38,27 @@ pub use oneinch::*;
 pub mod clipper;
 pub use clipper::*;
 
+pub mod paraswap;
+pub use paraswap::*;
+
+pub mod kyberswap;
+pub use kyberswap::*;
+
+pub mod odos;
+pub use odos::*;
+
 pub mod dodo;
 pub use dodo::*;
 
+pub mod morpho;
+pub use morpho::*;
+
+pub mod spark;
+pub use spark::*;
+
+pub mod chainlink;
+pub use chainlink::*;
+
 discovery_dispatch!(
     DiscoveryClassifier,
     SushiSwapV2Discovery,
@@ -81,6 +99,9 @@ action_dispatch!(
     UniswapV3MintCall,
     UniswapV3BurnCall,
     UniswapV3CollectCall,
+    UniswapV4UnlockCall,
+    UniswapV4SwapCall,
+    UniswapV4InitializeCall,
     SushiSwapV3SwapCall,
     SushiSwapV3MintCall,
     SushiSwapV3BurnCall,
@@ -145,6 +166,9 @@ action_dispatch!(
     MakerPSMBuyGemCall,
     MakerPSMSellGemCall,
     MakerDssFlashFlashLoanCall,
+    MakerClipperTakeCall,
+    MakerDogBarkCall,
+    MakerOSMPokeCall,
     AaveV2LiquidationCallCall,
     AaveV3LiquidationCallCall,
     AaveV2FlashLoanCall,
@@ -162,6 +186,11 @@ action_dispatch!(
     CompoundV2LiquidateBorrowCall,
     CompoundV2Initialize_0Call,
     CompoundV2Initialize_1Call,
+    CompoundV3BuyCollateralCall,
+    MorphoBlueLiquidateCall,
+    MorphoBlueCreateMarketCall,
+    SparkLiquidationCallCall,
+    ChainlinkAggregatorTransmitCall,
     OneInchV5SwapCall,
     OneInchV5ClipperSwapCall,
     OneInchV5ClipperSwapToCall,
@@ -171,6 +200,17 @@ action_dispatch!(
     OneInchV5UniswapV3SwapToCall,
     OneInchV5UniswapV3SwapToWithPermitCall,
     OneInchFusionSettleOrdersCall,
+    ParaswapV5SimpleSwapCall,
+    ParaswapV5MultiSwapCall,
+    ParaswapV5MegaSwapCall,
+    ParaswapV5SwapOnUniswapV2ForkCall,
+    KyberSwapSwapCall,
+    KyberSwapSwapGenericCall,
+    KyberSwapSwapSimpleModeCall,
+    OdosV2SwapCall,
+    OdosV2SwapMultiCall,
+    OdosV2SwapCompactCall,
+    OdosV2SwapMultiCompactCall,
     ClipperExchangeSwapCall,
     ClipperExchangeSellEthForTokenCall,
     ClipperExchangeSellTokenForEthCall,
@@ -210,3 +250,54 @@ action_dispatch!(
     DodoSellQuoteCall,
     DodoFlashLoanCall
 );
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address};
+    use brontes_types::ChainConfig;
+
+    use super::*;
+
+    const UNISWAP_V3_FACTORY: Address =
+        Address::new(hex!("1F98431c8aD98523631AE4a59f267346ea31F984"));
+    const UNISWAP_V2_FACTORY: Address =
+        Address::new(hex!("5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f"));
+
+    #[test]
+    fn test_factory_remap() {
+        let v3_factory = Address::repeat_byte(1);
+        let v2_factory = Address::repeat_byte(2);
+        let chain_config = ChainConfig {
+            chain: "holesky".to_string(),
+            factories: [
+                ("UniswapV3Discovery".to_string(), v3_factory),
+                ("UniswapV2Discovery".to_string(), v2_factory),
+            ]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            DiscoveryClassifier::mainnet_factory(&chain_config, v3_factory),
+            UNISWAP_V3_FACTORY
+        );
+        assert_eq!(
+            DiscoveryClassifier::mainnet_factory(&chain_config, v2_factory),
+            UNISWAP_V2_FACTORY
+        );
+        // factories that aren't remapped are left as is
+        let other = Address::repeat_byte(3);
+        assert_eq!(DiscoveryClassifier::mainnet_factory(&chain_config, other), other);
+
+        // on mainnet nothing is remapped
+        assert_eq!(
+            DiscoveryClassifier::mainnet_factory(ChainConfig::mainnet(), v3_factory),
+            v3_factory
+        );
+        assert_eq!(
+            DiscoveryClassifier::mainnet_factory(ChainConfig::mainnet(), UNISWAP_V3_FACTORY),
+            UNISWAP_V3_FACTORY
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use alloy_primitives::{keccak256, Address};
+use alloy_sol_types::SolValue;
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::{NormalizedLiquidation, NormalizedPoolConfigUpdate},
+    structured_trace::CallInfo,
+    utils::ToScaledRational,
+    Protocol,
+};
+
+use crate::MorphoBlue::MarketParams;
+
+action_impl!(
+    Protocol::MorphoBlue,
+    crate::MorphoBlue::liquidateCall,
+    Liquidation,
+    [],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: liquidateCall,
+    return_data: liquidateReturn,
+    db_tx: &DB | {
+        let params = call_data.marketParams;
+        let debt_info = db_tx.try_fetch_token_info(params.loanToken)?;
+        let collateral_info = db_tx.try_fetch_token_info(params.collateralToken)?;
+
+        // the liquidator can either specify the seized collateral or the repaid
+        // shares, the returned amounts are always final
+        let liquidated_collateral = return_data._0.to_scaled_rational(collateral_info.decimals);
+        let covered_debt = return_data._1.to_scaled_rational(debt_info.decimals);
+
+        return Ok(NormalizedLiquidation {
+            protocol: Protocol::MorphoBlue,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            liquidator: info.msg_sender,
+            debtor: call_data.borrower,
+            collateral_asset: collateral_info,
+            debt_asset: debt_info,
+            covered_debt,
+            liquidated_collateral,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::MorphoBlue,
+    crate::MorphoBlue::createMarketCall,
+    PoolConfigUpdate,
+    [],
+    call_data: true,
+    |info: CallInfo, call_data: createMarketCall, _| {
+        let params = call_data.marketParams;
+
+        Ok(NormalizedPoolConfigUpdate {
+            trace_index: info.trace_idx,
+            protocol: Protocol::MorphoBlue,
+            pool_address: market_params_to_address(&params),
+            tokens: vec![params.loanToken, params.collateralToken],
+        })
+    }
+);
+
+/// Morpho markets live in the singleton and are identified by
+/// `keccak256(abi.encode(marketParams))`. Like uniswap v4 pools, we use the
+/// first 20 bytes of the id as the market address.
+pub fn market_params_to_address(params: &MarketParams) -> Address {
+    Address::from_slice(&keccak256(params.abi_encode())[0..20])
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, U256};
+    use alloy_sol_types::SolCall;
+    use brontes_types::{
+        normalized_actions::{Action, NormalizedLiquidation},
+        Protocol, TreeSearchBuilder,
+    };
+    use malachite::Rational;
+
+    use crate::{
+        test_utils::{call_trace, ClassifierTestUtils},
+        MorphoBlue::{liquidateCall, MarketParams},
+    };
+
+    const MORPHO: Address = Address::new(hex!("BBBBBbbBBb9cC5e90e3b3Af64bdAF62C37EEFFCb"));
+    const USDC: Address = Address::new(hex!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"));
+    const WETH: Address = Address::new(hex!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"));
+
+    #[brontes_macros::test]
+    async fn test_morpho_blue_liquidation() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::MorphoBlue,
+            MORPHO,
+            Address::ZERO,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let liquidator = Address::repeat_byte(1);
+        let borrower = Address::repeat_byte(2);
+        let market_params = MarketParams {
+            loanToken:       USDC,
+            collateralToken: WETH,
+            oracle:          Address::repeat_byte(3),
+            irm:             Address::repeat_byte(4),
+            lltv:            U256::from(860_000_000_000_000_000_u128),
+        };
+
+        // only the seized collateral is given, the repaid assets are returned
+        let liquidate = call_trace(
+            vec![],
+            liquidator,
+            MORPHO,
+            liquidateCall {
+                marketParams: market_params,
+                borrower,
+                seizedAssets: U256::from(1_500_000_000_000_000_000_u128),
+                repaidShares: U256::ZERO,
+                data: Default::default(),
+            }
+            .abi_encode(),
+            liquidateCall::abi_encode_returns(&(
+                U256::from(1_500_000_000_000_000_000_u128),
+                U256::from(4_250_500_000_u64),
+            )),
+            vec![],
+        );
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(19_000_000, vec![vec![liquidate]])
+            .await;
+
+        let eq_action = Action::Liquidation(NormalizedLiquidation {
+            protocol: Protocol::MorphoBlue,
+            liquidated_collateral: Rational::from_signeds(3, 2),
+            covered_debt: Rational::from_signeds(8501, 2),
+            debtor: borrower,
+            debt_asset: classifier_utils.get_token_info(USDC),
+            collateral_asset: classifier_utils.get_token_info(WETH),
+            liquidator,
+            pool: MORPHO,
+            trace_index: 0,
+            msg_value: U256::ZERO,
+        });
+
+        let mut actions = tree
+            .tx_roots
+            .remove(0)
+            .collect(&TreeSearchBuilder::default().with_action(Action::is_liquidation));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions.remove(0), eq_action);
+    }
+}
@@ -0,0 +1,196 @@
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{normalized_actions::NormalizedAggregator, structured_trace::CallInfo};
+
+action_impl!(
+    Protocol::OdosV2,
+    crate::OdosRouterV2::swapCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: swapCall,
+    _db_tx: &DB | {
+        let recipient = call_data.tokenInfo.outputReceiver;
+        return Ok(NormalizedAggregator {
+            protocol: Protocol::OdosV2,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient,
+            child_actions: vec![],
+            msg_value: info.msg_value
+        })
+    }
+);
+
+action_impl!(
+    Protocol::OdosV2,
+    crate::OdosRouterV2::swapMultiCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: swapMultiCall,
+    _db_tx: &DB | {
+        let recipient = call_data
+            .outputs
+            .first()
+            .map(|output| output.receiver)
+            .unwrap_or(info.msg_sender);
+        return Ok(NormalizedAggregator {
+            protocol: Protocol::OdosV2,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient,
+            child_actions: vec![],
+            msg_value: info.msg_value
+        })
+    }
+);
+
+// the compact entrypoints pack their arguments in a custom encoding, the
+// receivers can't be decoded from the abi so the caller is used
+action_impl!(
+    Protocol::OdosV2,
+    crate::OdosRouterV2::swapCompactCall,
+    Aggregator,
+    [],
+    |info: CallInfo, _db_tx: &DB| {
+        return Ok(NormalizedAggregator {
+            protocol:      Protocol::OdosV2,
+            trace_index:   info.trace_idx,
+            from:          info.from_address,
+            to:            info.target_address,
+            recipient:     info.msg_sender,
+            child_actions: vec![],
+            msg_value:     info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::OdosV2,
+    crate::OdosRouterV2::swapMultiCompactCall,
+    Aggregator,
+    [],
+    |info: CallInfo, _db_tx: &DB| {
+        return Ok(NormalizedAggregator {
+            protocol:      Protocol::OdosV2,
+            trace_index:   info.trace_idx,
+            from:          info.from_address,
+            to:            info.target_address,
+            recipient:     info.msg_sender,
+            child_actions: vec![],
+            msg_value:     info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, Bytes, U256};
+    use alloy_sol_types::{SolCall, SolValue};
+    use brontes_types::{
+        normalized_actions::{Action, NormalizedAggregator, NormalizedTransfer},
+        Protocol, TreeSearchBuilder,
+    };
+    use malachite::{num::basic::traits::Zero, Rational};
+
+    use crate::{
+        classifiers::erc20::transferCall,
+        test_utils::{call_trace, ClassifierTestUtils},
+        OdosRouterV2::{swapCall, swapCompactCall, swapMultiCall, swapMultiCompactCall},
+    };
+
+    const ROUTER: Address = Address::new(hex!("Cf5540fFFCdC3d510B18bFcA6d2b9987b0772559"));
+    const USDC: Address = Address::new(hex!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"));
+    const WETH: Address = Address::new(hex!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"));
+
+    #[brontes_macros::test]
+    async fn test_odos_v2_swap() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::OdosV2,
+            ROUTER,
+            Address::ZERO,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let user = Address::repeat_byte(1);
+        let recipient = Address::repeat_byte(2);
+        let amount_in = U256::from(3_000_000_000_u64);
+        let amount_out = U256::from(1_000_000_000_000_000_000_u128);
+
+        // (tokenInfo, pathDefinition, executor, referralCode), the output goes to the
+        // output receiver
+        let args = (
+            (USDC, amount_in, Address::repeat_byte(3), WETH, amount_out, amount_out, recipient),
+            Bytes::new(),
+            Address::repeat_byte(3),
+            0_u32,
+        );
+
+        let swap = call_trace(
+            vec![],
+            user,
+            ROUTER,
+            [swapCall::SELECTOR.as_slice(), &args.abi_encode_params()].concat(),
+            amount_out.abi_encode(),
+            vec![],
+        );
+        // the output is sent out of the router
+        let transfer_out = call_trace(
+            vec![0],
+            ROUTER,
+            WETH,
+            transferCall { _0: recipient, _1: amount_out }.abi_encode(),
+            vec![],
+            vec![],
+        );
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(19_000_000, vec![vec![swap, transfer_out]])
+            .await;
+
+        let eq_action = Action::Aggregator(NormalizedAggregator {
+            protocol: Protocol::OdosV2,
+            trace_index: 0,
+            from: user,
+            to: ROUTER,
+            recipient,
+            child_actions: vec![Action::Transfer(NormalizedTransfer {
+                trace_index: 1,
+                from:        ROUTER,
+                to:          recipient,
+                token:       classifier_utils.get_token_info(WETH),
+                amount:      Rational::from(1),
+                fee:         Rational::ZERO,
+                msg_value:   U256::ZERO,
+            })],
+            msg_value: U256::ZERO,
+        });
+
+        let mut actions = tree
+            .tx_roots
+            .remove(0)
+            .collect(&TreeSearchBuilder::default().with_action(Action::is_aggregator));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions.remove(0), eq_action);
+    }
+
+    #[test]
+    fn test_odos_v2_selectors() {
+        assert_eq!(swapCall::SELECTOR, hex!("3b635ce4"));
+        assert_eq!(swapCompactCall::SELECTOR, hex!("83bd37f9"));
+        assert_eq!(swapMultiCall::SELECTOR, hex!("7bf2d6d4"));
+        assert_eq!(swapMultiCompactCall::SELECTOR, hex!("84a7f3dd"));
+    }
+}
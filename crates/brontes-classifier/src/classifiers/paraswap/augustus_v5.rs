@@ -0,0 +1,217 @@
+use alloy_primitives::Address;
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{normalized_actions::NormalizedAggregator, structured_trace::CallInfo};
+
+/// Augustus sends the output to the caller when no beneficiary is set
+fn beneficiary_or_sender(beneficiary: Address, info: &CallInfo) -> Address {
+    if beneficiary == Address::ZERO {
+        info.msg_sender
+    } else {
+        beneficiary
+    }
+}
+
+action_impl!(
+    Protocol::ParaswapV5,
+    crate::ParaswapAugustusV5::simpleSwapCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: simpleSwapCall,
+    _db_tx: &DB | {
+        let recipient = beneficiary_or_sender(call_data.data.beneficiary, &info);
+        return Ok(NormalizedAggregator {
+            protocol: Protocol::ParaswapV5,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient,
+            child_actions: vec![],
+            msg_value: info.msg_value
+        })
+    }
+);
+
+action_impl!(
+    Protocol::ParaswapV5,
+    crate::ParaswapAugustusV5::multiSwapCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: multiSwapCall,
+    _db_tx: &DB | {
+        let recipient = beneficiary_or_sender(call_data.data.beneficiary, &info);
+        return Ok(NormalizedAggregator {
+            protocol: Protocol::ParaswapV5,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient,
+            child_actions: vec![],
+            msg_value: info.msg_value
+        })
+    }
+);
+
+action_impl!(
+    Protocol::ParaswapV5,
+    crate::ParaswapAugustusV5::megaSwapCall,
+    Aggregator,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: megaSwapCall,
+    _db_tx: &DB | {
+        let recipient = beneficiary_or_sender(call_data.data.beneficiary, &info);
+        return Ok(NormalizedAggregator {
+            protocol: Protocol::ParaswapV5,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            to: info.target_address,
+            recipient,
+            child_actions: vec![],
+            msg_value: info.msg_value
+        })
+    }
+);
+
+action_impl!(
+    Protocol::ParaswapV5,
+    crate::ParaswapAugustusV5::swapOnUniswapV2ForkCall,
+    Aggregator,
+    [],
+    |info: CallInfo, _db_tx: &DB| {
+        return Ok(NormalizedAggregator {
+            protocol:      Protocol::ParaswapV5,
+            trace_index:   info.trace_idx,
+            from:          info.from_address,
+            to:            info.target_address,
+            recipient:     info.msg_sender,
+            child_actions: vec![],
+            msg_value:     info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, Bytes, FixedBytes, U256};
+    use alloy_sol_types::{SolCall, SolValue};
+    use brontes_types::{
+        normalized_actions::{Action, NormalizedAggregator, NormalizedTransfer},
+        Protocol, TreeSearchBuilder,
+    };
+    use malachite::{num::basic::traits::Zero, Rational};
+
+    use crate::{
+        classifiers::erc20::transferCall,
+        test_utils::{call_trace, ClassifierTestUtils},
+        ParaswapAugustusV5::{
+            megaSwapCall, multiSwapCall, simpleSwapCall, swapOnUniswapV2ForkCall,
+        },
+    };
+
+    const AUGUSTUS: Address = Address::new(hex!("DEF171Fe48CF0115B1d80b88dc8eAB59176FEe57"));
+    const USDC: Address = Address::new(hex!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"));
+    const WETH: Address = Address::new(hex!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"));
+
+    #[brontes_macros::test]
+    async fn test_paraswap_v5_simple_swap() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::ParaswapV5,
+            AUGUSTUS,
+            Address::ZERO,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let user = Address::repeat_byte(1);
+        let recipient = Address::repeat_byte(2);
+        let amount_in = U256::from(3_000_000_000_u64);
+        let amount_out = U256::from(1_000_000_000_000_000_000_u128);
+
+        // (SimpleData,), the output goes to the beneficiary
+        let args = ((
+            USDC,
+            WETH,
+            amount_in,
+            amount_out,
+            amount_out,
+            vec![Address::repeat_byte(3)],
+            Bytes::new(),
+            vec![U256::ZERO, U256::ZERO],
+            vec![U256::ZERO],
+            recipient,
+            Address::ZERO,
+            U256::ZERO,
+            Bytes::new(),
+            U256::from(u64::MAX),
+            FixedBytes::<16>::ZERO,
+        ),);
+
+        let swap = call_trace(
+            vec![],
+            user,
+            AUGUSTUS,
+            [simpleSwapCall::SELECTOR.as_slice(), &args.abi_encode_params()].concat(),
+            amount_out.abi_encode(),
+            vec![],
+        );
+        // the output is sent out of the router
+        let transfer_out = call_trace(
+            vec![0],
+            AUGUSTUS,
+            WETH,
+            transferCall { _0: recipient, _1: amount_out }.abi_encode(),
+            vec![],
+            vec![],
+        );
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(19_000_000, vec![vec![swap, transfer_out]])
+            .await;
+
+        let eq_action = Action::Aggregator(NormalizedAggregator {
+            protocol: Protocol::ParaswapV5,
+            trace_index: 0,
+            from: user,
+            to: AUGUSTUS,
+            recipient,
+            child_actions: vec![Action::Transfer(NormalizedTransfer {
+                trace_index: 1,
+                from:        AUGUSTUS,
+                to:          recipient,
+                token:       classifier_utils.get_token_info(WETH),
+                amount:      Rational::from(1),
+                fee:         Rational::ZERO,
+                msg_value:   U256::ZERO,
+            })],
+            msg_value: U256::ZERO,
+        });
+
+        let mut actions = tree
+            .tx_roots
+            .remove(0)
+            .collect(&TreeSearchBuilder::default().with_action(Action::is_aggregator));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions.remove(0), eq_action);
+    }
+
+    #[test]
+    fn test_paraswap_v5_selectors() {
+        assert_eq!(simpleSwapCall::SELECTOR, hex!("54e3f31b"));
+        assert_eq!(multiSwapCall::SELECTOR, hex!("a94e78ef"));
+        assert_eq!(megaSwapCall::SELECTOR, hex!("46c67b6d"));
+        assert_eq!(swapOnUniswapV2ForkCall::SELECTOR, hex!("0b86a4c1"));
+    }
+}
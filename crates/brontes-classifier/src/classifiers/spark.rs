@@ -0,0 +1,132 @@
+use brontes_macros::action_impl;
+use brontes_types::{
+    normalized_actions::NormalizedLiquidation, structured_trace::CallInfo, utils::ToScaledRational,
+    Protocol,
+};
+use malachite::{num::basic::traits::Zero, Rational};
+
+// Spark is an Aave V3 fork with its own pool deployment. We register the pool
+// proxy instead of the implementation, so the pool is the target address here.
+action_impl!(
+    Protocol::Spark,
+    crate::AaveV3::liquidationCallCall,
+    Liquidation,
+    [],
+    call_data: true,
+    |
+    info: CallInfo,
+    call_data: liquidationCallCall,
+    db_tx: &DB | {
+        let debt_info = db_tx.try_fetch_token_info(call_data.debtAsset)?;
+        let collateral_info = db_tx.try_fetch_token_info(call_data.collateralAsset)?;
+
+        let covered_debt = call_data.debtToCover.to_scaled_rational(debt_info.decimals);
+
+        return Ok(NormalizedLiquidation {
+            protocol: Protocol::Spark,
+            trace_index: info.trace_idx,
+            pool: info.target_address,
+            liquidator: info.msg_sender,
+            debtor: call_data.user,
+            collateral_asset: collateral_info,
+            debt_asset: debt_info,
+            covered_debt,
+            // filled in later
+            liquidated_collateral: Rational::ZERO,
+            msg_value: info.msg_value,
+        })
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Address, U256};
+    use alloy_sol_types::SolCall;
+    use brontes_types::{
+        normalized_actions::{Action, NormalizedLiquidation},
+        Protocol, TreeSearchBuilder,
+    };
+    use malachite::Rational;
+
+    use crate::{
+        classifiers::erc20::transferCall,
+        test_utils::{call_trace, ClassifierTestUtils},
+        AaveV3::liquidationCallCall,
+    };
+
+    const SPARK_POOL: Address = Address::new(hex!("C13e21B648A5Ee794902342038FF3aDAB66BE987"));
+    const DAI: Address = Address::new(hex!("6B175474E89094C44Da98b954EedeAC495271d0F"));
+    const WETH: Address = Address::new(hex!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"));
+
+    #[brontes_macros::test]
+    async fn test_spark_liquidation() {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_protocol(
+            Protocol::Spark,
+            SPARK_POOL,
+            Address::ZERO,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let liquidator = Address::repeat_byte(1);
+        let borrower = Address::repeat_byte(2);
+        let sp_weth = Address::repeat_byte(3);
+        let seized = U256::from(1_050_000_000_000_000_000_u128);
+
+        let liquidation_call = call_trace(
+            vec![],
+            liquidator,
+            SPARK_POOL,
+            liquidationCallCall {
+                collateralAsset: WETH,
+                debtAsset:       DAI,
+                user:            borrower,
+                debtToCover:     U256::from(3_000_000_000_000_000_000_000_u128),
+                receiveAToken:   false,
+            }
+            .abi_encode(),
+            vec![],
+            vec![],
+        );
+        // the seized collateral is sent out of the spToken to the liquidator
+        let collateral_transfer = call_trace(
+            vec![0],
+            sp_weth,
+            WETH,
+            transferCall { _0: liquidator, _1: seized }.abi_encode(),
+            vec![],
+            vec![],
+        );
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(
+                19_000_000,
+                vec![vec![liquidation_call, collateral_transfer]],
+            )
+            .await;
+
+        let eq_action = Action::Liquidation(NormalizedLiquidation {
+            protocol: Protocol::Spark,
+            liquidated_collateral: Rational::from_signeds(21, 20),
+            covered_debt: Rational::from(3000),
+            debtor: borrower,
+            debt_asset: classifier_utils.get_token_info(DAI),
+            collateral_asset: classifier_utils.get_token_info(WETH),
+            liquidator,
+            pool: SPARK_POOL,
+            trace_index: 0,
+            msg_value: U256::ZERO,
+        });
+
+        let mut actions = tree
+            .tx_roots
+            .remove(0)
+            .collect(&TreeSearchBuilder::default().with_action(Action::is_liquidation));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions.remove(0), eq_action);
+    }
+}
@@ -0,0 +1,579 @@
+use alloy_primitives::{keccak256, Address, I256, U256};
+use alloy_sol_types::{SolCall, SolValue};
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_macros::action_impl;
+use brontes_pricing::Protocol;
+use brontes_types::{
+    db::token_info::TokenInfoWithAddress,
+    normalized_actions::{
+        Action, NormalizedAggregator, NormalizedBurn, NormalizedCollect, NormalizedMint,
+        NormalizedPoolConfigUpdate, NormalizedSwap,
+    },
+    structured_trace::{CallInfo, TraceActions, TransactionTraceWithLogs},
+    ChainConfig, ToScaledRational,
+};
+
+use crate::UniswapV4::{modifyLiquidityCall, PoolKey};
+
+action_impl!(
+    Protocol::UniswapV4,
+    crate::UniswapV4::unlockCall,
+    Aggregator,
+    [],
+    |info: CallInfo, _| {
+        Ok(NormalizedAggregator {
+            protocol:      Protocol::UniswapV4,
+            trace_index:   info.trace_idx,
+            from:          info.from_address,
+            to:            info.target_address,
+            recipient:     info.from_address,
+            child_actions: vec![],
+            msg_value:     info.msg_value,
+        })
+    }
+);
+
+action_impl!(
+    Protocol::UniswapV4,
+    crate::UniswapV4::swapCall,
+    Swap,
+    [Swap],
+    call_data: true,
+    return_data: true,
+    |
+    info: CallInfo,
+    call_data: swapCall,
+    return_data: swapReturn,
+    db_tx: &DB| {
+        let (token_0_delta, token_1_delta) = balance_delta_amounts(return_data.swapDelta);
+
+        let t0_info = currency_info(call_data.key.currency0, db_tx)?;
+        let t1_info = currency_info(call_data.key.currency1, db_tx)?;
+
+        // deltas are from the callers perspective, the negative side is what the
+        // caller owes the pool manager
+        let (amount_in, amount_out, token_in, token_out) = if token_0_delta.is_negative() {
+            (
+                token_0_delta.unsigned_abs().to_scaled_rational(t0_info.decimals),
+                token_1_delta.unsigned_abs().to_scaled_rational(t1_info.decimals),
+                t0_info,
+                t1_info,
+            )
+        } else {
+            (
+                token_1_delta.unsigned_abs().to_scaled_rational(t1_info.decimals),
+                token_0_delta.unsigned_abs().to_scaled_rational(t0_info.decimals),
+                t1_info,
+                t0_info,
+            )
+        };
+
+        // the recipient is only known once the locker takes its output, this is
+        // resolved in the multi frame classification of the unlock call
+        Ok(NormalizedSwap {
+            protocol: Protocol::UniswapV4,
+            trace_index: info.trace_idx,
+            from: info.from_address,
+            pool: pool_key_to_address(&call_data.key),
+            recipient: info.from_address,
+            token_in,
+            token_out,
+            amount_in,
+            amount_out,
+            msg_value: info.msg_value
+        })
+    }
+);
+
+action_impl!(
+    Protocol::UniswapV4,
+    crate::UniswapV4::initializeCall,
+    PoolConfigUpdate,
+    [Initialize],
+    call_data: true,
+    |info: CallInfo, call_data: initializeCall, _| {
+        Ok(NormalizedPoolConfigUpdate {
+            trace_index: info.trace_idx,
+            protocol: Protocol::UniswapV4,
+            pool_address: pool_key_to_address(&call_data.key),
+            tokens: vec![
+                currency_address(call_data.key.currency0),
+                currency_address(call_data.key.currency1),
+            ],
+        })
+    }
+);
+
+/// `modifyLiquidity` mints, burns and collects fees in one call, so it can't be
+/// classified to a single action through `action_impl`. The tree builder calls
+/// this for frames that the dispatch didn't classify.
+pub fn classify_modify_liquidity<DB: LibmdbxReader>(
+    trace: &TransactionTraceWithLogs,
+    db_tx: &DB,
+) -> Option<Vec<Action>> {
+    let calldata = trace.get_calldata();
+    if calldata.len() < 4 || calldata[0..4] != modifyLiquidityCall::SELECTOR {
+        return None
+    }
+    if db_tx.get_protocol(trace.get_to_address()).ok()? != Protocol::UniswapV4 {
+        return None
+    }
+
+    let call = modifyLiquidityCall::abi_decode(&calldata, false).ok()?;
+    let returns =
+        modifyLiquidityCall::abi_decode_returns(&trace.get_return_calldata(), false).ok()?;
+
+    let token = [
+        currency_info(call.key.currency0, db_tx).ok()?,
+        currency_info(call.key.currency1, db_tx).ok()?,
+    ];
+    let pool = pool_key_to_address(&call.key);
+    let from = trace.get_msg_sender();
+
+    // the caller delta includes the fees accrued by the position, split these
+    // out so that the mint or burn only holds the principal
+    let (caller_0, caller_1) = balance_delta_amounts(returns.callerDelta);
+    let (fees_0, fees_1) = balance_delta_amounts(returns.feesAccrued);
+    let principal = [caller_0 - fees_0, caller_1 - fees_1];
+
+    let amount = |delta: [i128; 2]| {
+        delta
+            .iter()
+            .zip(token.iter())
+            .map(|(delta, token)| delta.unsigned_abs().to_scaled_rational(token.decimals))
+            .collect::<Vec<_>>()
+    };
+
+    let mut actions = Vec::new();
+    if call.params.liquidityDelta.is_negative() {
+        actions.push(Action::Burn(NormalizedBurn {
+            protocol: Protocol::UniswapV4,
+            trace_index: trace.trace_idx,
+            from,
+            recipient: from,
+            pool,
+            token: token.to_vec(),
+            amount: amount(principal),
+        }));
+    } else if !call.params.liquidityDelta.is_zero() {
+        actions.push(Action::Mint(NormalizedMint {
+            protocol: Protocol::UniswapV4,
+            trace_index: trace.trace_idx,
+            from,
+            recipient: from,
+            pool,
+            token: token.to_vec(),
+            amount: amount(principal),
+        }));
+    }
+
+    if fees_0 != 0 || fees_1 != 0 {
+        actions.push(Action::Collect(NormalizedCollect {
+            protocol: Protocol::UniswapV4,
+            trace_index: trace.trace_idx,
+            from,
+            recipient: from,
+            pool,
+            token: token.to_vec(),
+            amount: amount([fees_0, fees_1]),
+        }));
+    }
+
+    Some(actions).filter(|actions| !actions.is_empty())
+}
+
+/// All v4 pools live in the pool manager and are identified by
+/// `keccak256(abi.encode(poolKey))`. Like balancer pool ids, we use the first
+/// 20 bytes of the id as the pool address throughout brontes.
+pub fn pool_key_to_address(key: &PoolKey) -> Address {
+    Address::from_slice(&keccak256(key.abi_encode())[0..20])
+}
+
+/// Native eth is `address(0)` in v4, we map it to weth like everywhere else.
+pub fn currency_address(currency: Address) -> Address {
+    if currency == Address::ZERO {
+        ChainConfig::get().wrapped_native
+    } else {
+        currency
+    }
+}
+
+pub fn currency_info<DB: LibmdbxReader>(
+    currency: Address,
+    db_tx: &DB,
+) -> eyre::Result<TokenInfoWithAddress> {
+    if currency == Address::ZERO {
+        return Ok(TokenInfoWithAddress::native_eth())
+    }
+
+    db_tx.try_fetch_token_info(currency)
+}
+
+/// A `BalanceDelta` packs the currency0 delta in the upper 128 bits and the
+/// currency1 delta in the lower 128 bits.
+pub fn balance_delta_amounts(delta: I256) -> (i128, i128) {
+    let raw = delta.into_raw();
+    let amount0 = (raw >> 128).to::<u128>() as i128;
+    let amount1 = (raw & U256::from(u128::MAX)).to::<u128>() as i128;
+
+    (amount0, amount1)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Bytes, Log, B256};
+    use alloy_sol_types::SolEvent;
+    use brontes_types::{normalized_actions::NormalizedTransfer, TreeSearchBuilder};
+    use malachite::{num::basic::traits::Zero, Rational};
+
+    use super::*;
+    use crate::{
+        classifiers::erc20::transferCall,
+        test_utils::{call_trace, ClassifierTestUtils},
+        UniswapV4::{initializeCall, swapCall, takeCall, unlockCall, Initialize, Swap},
+    };
+
+    const POOL_MANAGER: Address = Address::new(hex!("000000000004444c5dc75cB358380D2e3dE08A90"));
+
+    async fn v4_classifier_utils() -> ClassifierTestUtils {
+        let classifier_utils = ClassifierTestUtils::new().await;
+        classifier_utils.ensure_token(TokenInfoWithAddress::usdc());
+        classifier_utils.ensure_token(TokenInfoWithAddress::weth());
+        classifier_utils.ensure_protocol(
+            Protocol::UniswapV4,
+            POOL_MANAGER,
+            Address::ZERO,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        classifier_utils
+    }
+
+    /// usdc / weth, usdc sorts first
+    fn pool_key() -> PoolKey {
+        PoolKey {
+            currency0:   TokenInfoWithAddress::usdc().address,
+            currency1:   TokenInfoWithAddress::weth().address,
+            fee:         3000,
+            tickSpacing: 60,
+            hooks:       Address::ZERO,
+        }
+    }
+
+    /// the `PoolKey` tuple for encoding calldata
+    fn pool_key_params() -> (Address, Address, u32, i32, Address) {
+        let key = pool_key();
+        (key.currency0, key.currency1, key.fee, key.tickSpacing, key.hooks)
+    }
+
+    fn balance_delta(amount0: i128, amount1: i128) -> I256 {
+        I256::from_raw((U256::from(amount0 as u128) << 128) | U256::from(amount1 as u128))
+    }
+
+    /// a swap of 3000 usdc for 1 weth
+    fn swap_trace(trace_address: Vec<usize>, from: Address) -> TransactionTraceWithLogs {
+        let swap_log = Swap {
+            id:           keccak256(pool_key().abi_encode()),
+            sender:       from,
+            amount0:      -3_000_000_000,
+            amount1:      1_000_000_000_000_000_000,
+            sqrtPriceX96: U256::from(1) << 96,
+            liquidity:    1_000_000,
+            tick:         0,
+            fee:          3000,
+        };
+
+        call_trace(
+            trace_address,
+            from,
+            POOL_MANAGER,
+            // (key, (zeroForOne, amountSpecified, sqrtPriceLimitX96), hookData)
+            [
+                swapCall::SELECTOR.as_slice(),
+                &(pool_key_params(), (true, I256::ZERO, U256::ZERO), Bytes::new())
+                    .abi_encode_params(),
+            ]
+            .concat(),
+            swapCall::abi_encode_returns(&(balance_delta(
+                -3_000_000_000,
+                1_000_000_000_000_000_000,
+            ),)),
+            vec![Log { address: POOL_MANAGER, data: swap_log.encode_log_data() }],
+        )
+    }
+
+    fn modify_liquidity_trace(
+        from: Address,
+        liquidity_delta: i64,
+        caller_delta: I256,
+        fees_accrued: I256,
+    ) -> TransactionTraceWithLogs {
+        // (key, (tickLower, tickUpper, liquidityDelta, salt), hookData)
+        let params = (
+            pool_key_params(),
+            (-600_i32, 600_i32, I256::try_from(liquidity_delta).unwrap(), B256::ZERO),
+            Bytes::new(),
+        );
+
+        call_trace(
+            vec![],
+            from,
+            POOL_MANAGER,
+            [modifyLiquidityCall::SELECTOR.as_slice(), &params.abi_encode_params()].concat(),
+            modifyLiquidityCall::abi_encode_returns(&(caller_delta, fees_accrued)),
+            vec![],
+        )
+    }
+
+    #[brontes_macros::test]
+    async fn test_v4_swap() {
+        let classifier_utils = v4_classifier_utils().await;
+        let router = Address::repeat_byte(1);
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(21_000_000, vec![vec![swap_trace(vec![], router)]])
+            .await;
+
+        let mut actions = tree
+            .tx_roots
+            .remove(0)
+            .collect(&TreeSearchBuilder::default().with_action(Action::is_swap));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions.remove(0),
+            Action::Swap(NormalizedSwap {
+                protocol:    Protocol::UniswapV4,
+                trace_index: 0,
+                from:        router,
+                recipient:   router,
+                pool:        pool_key_to_address(&pool_key()),
+                token_in:    TokenInfoWithAddress::usdc(),
+                token_out:   TokenInfoWithAddress::weth(),
+                amount_in:   Rational::from(3_000),
+                amount_out:  Rational::from(1),
+                msg_value:   U256::ZERO,
+            })
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_v4_modify_liquidity_mint_with_fees() {
+        let classifier_utils = v4_classifier_utils().await;
+        let lp = Address::repeat_byte(1);
+
+        // 1000 usdc and 0.5 weth added, 10 usdc and 0.01 weth of fees collected
+        let modify = modify_liquidity_trace(
+            lp,
+            1_000_000,
+            balance_delta(-990_000_000, -490_000_000_000_000_000),
+            balance_delta(10_000_000, 10_000_000_000_000_000),
+        );
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(21_000_000, vec![vec![modify]])
+            .await;
+
+        let token = vec![TokenInfoWithAddress::usdc(), TokenInfoWithAddress::weth()];
+        let pool = pool_key_to_address(&pool_key());
+        let actions = tree.tx_roots.remove(0).collect(
+            &TreeSearchBuilder::default().with_actions([Action::is_mint, Action::is_collect]),
+        );
+        assert_eq!(
+            actions,
+            vec![
+                Action::Mint(NormalizedMint {
+                    protocol: Protocol::UniswapV4,
+                    trace_index: 0,
+                    from: lp,
+                    recipient: lp,
+                    pool,
+                    token: token.clone(),
+                    amount: vec![Rational::from(1_000), Rational::from_signeds(1, 2)],
+                }),
+                Action::Collect(NormalizedCollect {
+                    protocol: Protocol::UniswapV4,
+                    trace_index: 0,
+                    from: lp,
+                    recipient: lp,
+                    pool,
+                    token,
+                    amount: vec![Rational::from(10), Rational::from_signeds(1, 100)],
+                }),
+            ]
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_v4_modify_liquidity_burn() {
+        let classifier_utils = v4_classifier_utils().await;
+        let lp = Address::repeat_byte(1);
+
+        // 500 usdc and 0.25 weth removed, no fees
+        let modify = modify_liquidity_trace(
+            lp,
+            -1_000_000,
+            balance_delta(500_000_000, 250_000_000_000_000_000),
+            I256::ZERO,
+        );
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(21_000_000, vec![vec![modify]])
+            .await;
+
+        let actions = tree.tx_roots.remove(0).collect(
+            &TreeSearchBuilder::default().with_actions([Action::is_burn, Action::is_collect]),
+        );
+        assert_eq!(
+            actions,
+            vec![Action::Burn(NormalizedBurn {
+                protocol:    Protocol::UniswapV4,
+                trace_index: 0,
+                from:        lp,
+                recipient:   lp,
+                pool:        pool_key_to_address(&pool_key()),
+                token:       vec![TokenInfoWithAddress::usdc(), TokenInfoWithAddress::weth()],
+                amount:      vec![Rational::from(500), Rational::from_signeds(1, 4)],
+            })]
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_v4_unlock_take_recipient() {
+        let classifier_utils = v4_classifier_utils().await;
+        let router = Address::repeat_byte(1);
+        let recipient = Address::repeat_byte(2);
+        let weth = TokenInfoWithAddress::weth().address;
+        let amount_out = U256::from(1_000_000_000_000_000_000_u128);
+
+        let unlock = call_trace(
+            vec![],
+            router,
+            POOL_MANAGER,
+            unlockCall { data: Bytes::new() }.abi_encode(),
+            vec![],
+            vec![],
+        );
+        let callback = call_trace(vec![0], POOL_MANAGER, router, vec![], vec![], vec![]);
+        let swap = swap_trace(vec![0, 0], router);
+        let take = call_trace(
+            vec![0, 1],
+            router,
+            POOL_MANAGER,
+            takeCall { currency: weth, to: recipient, amount: amount_out }.abi_encode(),
+            vec![],
+            vec![],
+        );
+        let transfer_out = call_trace(
+            vec![0, 1, 0],
+            POOL_MANAGER,
+            weth,
+            transferCall { _0: recipient, _1: amount_out }.abi_encode(),
+            vec![],
+            vec![],
+        );
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(
+                21_000_000,
+                vec![vec![unlock, callback, swap, take, transfer_out]],
+            )
+            .await;
+
+        let mut actions = tree
+            .tx_roots
+            .remove(0)
+            .collect(&TreeSearchBuilder::default().with_action(Action::is_aggregator));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions.remove(0),
+            Action::Aggregator(NormalizedAggregator {
+                protocol: Protocol::UniswapV4,
+                trace_index: 0,
+                from: router,
+                to: POOL_MANAGER,
+                recipient,
+                child_actions: vec![
+                    Action::Swap(NormalizedSwap {
+                        protocol: Protocol::UniswapV4,
+                        trace_index: 2,
+                        from: router,
+                        recipient,
+                        pool: pool_key_to_address(&pool_key()),
+                        token_in: TokenInfoWithAddress::usdc(),
+                        token_out: TokenInfoWithAddress::weth(),
+                        amount_in: Rational::from(3_000),
+                        amount_out: Rational::from(1),
+                        msg_value: U256::ZERO,
+                    }),
+                    Action::Transfer(NormalizedTransfer {
+                        trace_index: 4,
+                        from:        POOL_MANAGER,
+                        to:          recipient,
+                        token:       TokenInfoWithAddress::weth(),
+                        amount:      Rational::from(1),
+                        fee:         Rational::ZERO,
+                        msg_value:   U256::ZERO,
+                    }),
+                ],
+                msg_value: U256::ZERO,
+            })
+        );
+    }
+
+    #[brontes_macros::test]
+    async fn test_v4_initialize() {
+        let classifier_utils = v4_classifier_utils().await;
+        let key = pool_key();
+        let initialize_log = Initialize {
+            id:           keccak256(key.abi_encode()),
+            currency0:    key.currency0,
+            currency1:    key.currency1,
+            fee:          key.fee,
+            tickSpacing:  key.tickSpacing,
+            hooks:        key.hooks,
+            sqrtPriceX96: U256::from(1) << 96,
+            tick:         0,
+        };
+
+        let initialize = call_trace(
+            vec![],
+            Address::repeat_byte(1),
+            POOL_MANAGER,
+            initializeCall { key: key.clone(), sqrtPriceX96: U256::from(1) << 96 }.abi_encode(),
+            initializeCall::abi_encode_returns(&(0,)),
+            vec![Log { address: POOL_MANAGER, data: initialize_log.encode_log_data() }],
+        );
+
+        let mut tree = classifier_utils
+            .build_block_tree_from_traces(21_000_000, vec![vec![initialize]])
+            .await;
+
+        let mut actions = tree
+            .tx_roots
+            .remove(0)
+            .collect(&TreeSearchBuilder::default().with_action(Action::is_pool_config_update));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions.remove(0),
+            Action::PoolConfigUpdate(NormalizedPoolConfigUpdate {
+                trace_index:  0,
+                protocol:     Protocol::UniswapV4,
+                pool_address: pool_key_to_address(&key),
+                tokens:       vec![key.currency0, key.currency1],
+            })
+        );
+    }
+
+    #[test]
+    fn test_balance_delta_amounts() {
+        let amount0: i128 = -1_000_000;
+        let amount1: i128 = 420_000_000_000_000_000;
+        let raw = (U256::from(amount0 as u128) << 128) | U256::from(amount1 as u128);
+
+        assert_eq!(balance_delta_amounts(I256::from_raw(raw)), (amount0, amount1));
+    }
+}
@@ -8,7 +8,7 @@ use brontes_types::{
     normalized_actions::{pool::NormalizedNewPool, Action, MultiFrameRequest},
     structured_trace::{TraceActions, TransactionTraceWithLogs, TxTrace},
     traits::TracingProvider,
-    tree::{root::NodeData, GasDetails, Node, Root},
+    tree::{root::NodeData, GasDetails, Node, Root, TxCall},
 };
 use futures::future::join_all;
 use reth_primitives::{Address, Header};
@@ -75,6 +75,7 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> DiscoveryOnlyClassif
                     .await;
 
                     let node = Node::new(trace_idx, address, vec![]);
+                    let call = TxCall::from_trace(&root_trace);
                     let action = vec![Action::Unclassified(root_trace)];
 
                     let mut tx_root = Root {
@@ -82,6 +83,7 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> DiscoveryOnlyClassif
                         head: node,
                         tx_hash: trace.tx_hash,
                         private: false,
+                        call,
                         total_msg_value_transfers: vec![],
                         gas_details: GasDetails {
                             coinbase_transfer:   None,
@@ -33,6 +33,7 @@ pub use classifiers::*;
 sol!(UniswapV2, "./classifier-abis/UniswapV2.json");
 sol!(SushiSwapV2, "./classifier-abis/SushiSwapV2.json");
 sol!(UniswapV3, "./classifier-abis/UniswapV3.json");
+sol!(UniswapV4, "./classifier-abis/UniswapV4PoolManager.json");
 sol!(SushiSwapV3, "./classifier-abis/SushiSwapV3.json");
 sol!(PancakeSwapV2, "./classifier-abis/PancakeSwapV2.json");
 sol!(PancakeSwapV3, "./classifier-abis/PancakeSwapV3.json");
@@ -52,10 +53,19 @@ sol!(AaveV3, "./classifier-abis/AaveV3Pool.json");
 sol!(UniswapX, "./classifier-abis/UniswapXExclusiveDutchOrderReactor.json");
 sol!(MakerPSM, "./classifier-abis/maker/MakerPSM.json");
 sol!(MakerDssFlash, "./classifier-abis/maker/MakerDssFlash.json");
+sol!(MakerClipper, "./classifier-abis/maker/MakerClipper.json");
+sol!(MakerOSM, "./classifier-abis/maker/MakerOSM.json");
+sol!(MakerDog, "./classifier-abis/maker/MakerDog.json");
 sol!(CompoundV2CToken, "./classifier-abis/CompoundV2CToken.json");
+sol!(CompoundV3Comet, "./classifier-abis/CompoundV3Comet.json");
+sol!(MorphoBlue, "./classifier-abis/MorphoBlue.json");
+sol!(ChainlinkOCR2Aggregator, "./classifier-abis/chainlink/ChainlinkOCR2Aggregator.json");
 sol!(OneInchAggregationRouterV5, "./classifier-abis/OneInchAggregationRouterV5.json");
 sol!(OneInchFusionSettlement, "./classifier-abis/OneInchFusionSettlement.json");
 sol!(ClipperExchange, "./classifier-abis/ClipperExchange.json");
+sol!(ParaswapAugustusV5, "./classifier-abis/ParaswapAugustusV5.json");
+sol!(KyberSwapMetaAggregationRouterV2, "./classifier-abis/KyberSwapMetaAggregationRouterV2.json");
+sol!(OdosRouterV2, "./classifier-abis/OdosRouterV2.json");
 sol!(CowswapGPv2Settlement, "./classifier-abis/cowswap/GPv2Settlement.json");
 sol!(ZeroXUniswapFeaure, "./classifier-abis/zero-x/ZeroXUniswapFeature.json");
 sol!(ZeroXUniswapV3Feature, "./classifier-abis/zero-x/ZeroXUniswapV3Feature.json");
@@ -0,0 +1,21 @@
+use brontes_types::{
+    normalized_actions::{
+        Action, MultiCallFrameClassification, MultiFrameAction, MultiFrameRequest,
+    },
+    Protocol,
+};
+
+use super::router_classifier;
+use crate::multi_frame_classification::MultiCallFrameClassifier;
+
+pub struct KyberSwapAgg;
+
+impl MultiCallFrameClassifier for KyberSwapAgg {
+    const KEY: [u8; 2] = [Protocol::KyberSwap as u8, MultiFrameAction::Aggregator as u8];
+
+    fn create_classifier(
+        request: MultiFrameRequest,
+    ) -> Option<MultiCallFrameClassification<Action>> {
+        Some(router_classifier(request))
+    }
+}
@@ -1,4 +1,53 @@
+use brontes_types::{
+    normalized_actions::{Action, MultiCallFrameClassification, MultiFrameRequest, NodeDataIndex},
+    TreeSearchBuilder,
+};
+
 pub mod one_inch;
 pub use one_inch::*;
 pub mod zero_x;
 pub use zero_x::*;
+pub mod uniswap_v4;
+pub use uniswap_v4::*;
+pub mod paraswap;
+pub use paraswap::*;
+pub mod kyberswap;
+pub use kyberswap::*;
+pub mod odos;
+pub use odos::*;
+
+/// For routers that only execute swaps and transfers on behalf of the caller,
+/// all of them become child actions of the aggregator action
+fn router_classifier(request: MultiFrameRequest) -> MultiCallFrameClassification<Action> {
+    MultiCallFrameClassification {
+        trace_index:         request.trace_idx,
+        tree_search_builder: TreeSearchBuilder::new().with_actions([
+            Action::is_swap,
+            Action::is_transfer,
+            Action::is_eth_transfer,
+        ]),
+        parse_fn:            Box::new(parse_router),
+    }
+}
+
+fn parse_router(
+    this_action: &mut Action,
+    child_nodes: Vec<(NodeDataIndex, Action)>,
+) -> Vec<NodeDataIndex> {
+    let this = this_action.try_aggregator_mut().unwrap();
+    let mut prune_nodes = Vec::new();
+
+    for (trace_index, action) in child_nodes {
+        match action {
+            Action::Swap(_)
+            | Action::SwapWithFee(_)
+            | Action::Transfer(_)
+            | Action::EthTransfer(_) => {
+                this.child_actions.push(action);
+                prune_nodes.push(trace_index);
+            }
+            _ => {}
+        }
+    }
+    prune_nodes
+}
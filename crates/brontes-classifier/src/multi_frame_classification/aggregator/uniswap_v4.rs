@@ -0,0 +1,94 @@
+use alloy_primitives::Address;
+use brontes_types::{
+    normalized_actions::{
+        Action, MultiCallFrameClassification, MultiFrameAction, MultiFrameRequest, NodeDataIndex,
+    },
+    ChainConfig, Protocol, TreeSearchBuilder,
+};
+
+use crate::multi_frame_classification::MultiCallFrameClassifier;
+
+pub struct UniswapV4Unlock;
+
+impl MultiCallFrameClassifier for UniswapV4Unlock {
+    const KEY: [u8; 2] = [Protocol::UniswapV4 as u8, MultiFrameAction::Aggregator as u8];
+
+    fn create_classifier(
+        request: MultiFrameRequest,
+    ) -> Option<MultiCallFrameClassification<Action>> {
+        Some(MultiCallFrameClassification {
+            trace_index:         request.trace_idx,
+            tree_search_builder: TreeSearchBuilder::new().with_actions([
+                Action::is_swap,
+                Action::is_mint,
+                Action::is_burn,
+                Action::is_collect,
+                Action::is_transfer,
+                Action::is_eth_transfer,
+            ]),
+            parse_fn:            Box::new(parse_unlock),
+        })
+    }
+}
+
+/// All pool interactions of a v4 unlock are settled against the pool manager
+/// at the end of the callback. Swaps and liquidity changes are classified on
+/// their own frame and collected here. Swap recipients are set from the `take`
+/// transfers out of the pool manager.
+fn parse_unlock(
+    this_action: &mut Action,
+    child_nodes: Vec<(NodeDataIndex, Action)>,
+) -> Vec<NodeDataIndex> {
+    let this = this_action.try_aggregator_mut().unwrap();
+    let pool_manager = this.to;
+    let mut prune_nodes = Vec::new();
+
+    for (trace_index, action) in child_nodes {
+        match action {
+            action @ (Action::Swap(_) | Action::Mint(_) | Action::Burn(_) | Action::Collect(_))
+                if action.get_protocol() == Protocol::UniswapV4 =>
+            {
+                this.child_actions.push(action);
+                prune_nodes.push(trace_index);
+            }
+            Action::Transfer(t) => {
+                if t.from == pool_manager {
+                    take_output(this.child_actions.as_mut_slice(), t.token.address, t.to);
+                    this.recipient = t.to;
+                }
+                this.child_actions.push(Action::Transfer(t));
+                prune_nodes.push(trace_index);
+            }
+            Action::EthTransfer(e) => {
+                if e.from == pool_manager {
+                    take_output(
+                        this.child_actions.as_mut_slice(),
+                        ChainConfig::get().wrapped_native,
+                        e.to,
+                    );
+                    this.recipient = e.to;
+                }
+                this.child_actions.push(Action::EthTransfer(e));
+                prune_nodes.push(trace_index);
+            }
+            _ => {}
+        }
+    }
+
+    prune_nodes
+}
+
+/// Sets the recipient of the last swap that output `token` and hasn't been
+/// taken yet.
+fn take_output(child_actions: &mut [Action], token: Address, to: Address) {
+    if let Some(swap) = child_actions
+        .iter_mut()
+        .rev()
+        .find_map(|action| match action {
+            Action::Swap(s) if s.token_out.address == token && s.recipient == s.from => Some(s),
+            _ => None,
+        })
+    {
+        swap.recipient = to;
+    }
+}
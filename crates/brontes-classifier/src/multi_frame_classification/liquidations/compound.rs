@@ -0,0 +1,46 @@
+use alloy_primitives::Address;
+use brontes_types::{
+    normalized_actions::{
+        Action, MultiCallFrameClassification, MultiFrameAction, MultiFrameRequest, NodeDataIndex,
+    },
+    Protocol, TreeSearchBuilder,
+};
+
+use crate::multi_frame_classification::MultiCallFrameClassifier;
+
+pub struct CompoundV3;
+
+impl MultiCallFrameClassifier for CompoundV3 {
+    const KEY: [u8; 2] = [Protocol::CompoundV3 as u8, MultiFrameAction::Liquidation as u8];
+
+    fn create_classifier(
+        request: MultiFrameRequest,
+    ) -> Option<MultiCallFrameClassification<Action>> {
+        Some(MultiCallFrameClassification {
+            trace_index:         request.trace_idx,
+            tree_search_builder: TreeSearchBuilder::new().with_action(Action::is_transfer),
+            parse_fn:            Box::new(parse_v3),
+        })
+    }
+}
+
+/// The buyer pays for the collateral in the base token of the comet. If the
+/// base token of the comet isn't in the classifier config, the transfer into
+/// the comet is the covered debt.
+fn parse_v3(this: &mut Action, child_nodes: Vec<(NodeDataIndex, Action)>) -> Vec<NodeDataIndex> {
+    let this = this.try_liquidation_mut().unwrap();
+    if this.debt_asset.address != Address::ZERO {
+        return vec![]
+    }
+
+    if let Some(transfer) = child_nodes.into_iter().find_map(|(_, action)| {
+        action
+            .try_transfer()
+            .filter(|transfer| transfer.to == this.pool)
+    }) {
+        this.debt_asset = transfer.token;
+        this.covered_debt = transfer.amount;
+    }
+
+    vec![]
+}
@@ -0,0 +1,64 @@
+use alloy_primitives::Address;
+use brontes_types::{
+    normalized_actions::{
+        Action, MultiCallFrameClassification, MultiFrameAction, MultiFrameRequest, NodeDataIndex,
+    },
+    Protocol, TreeSearchBuilder,
+};
+
+use crate::multi_frame_classification::MultiCallFrameClassifier;
+
+pub struct MakerClipper;
+
+impl MultiCallFrameClassifier for MakerClipper {
+    const KEY: [u8; 2] = [Protocol::MakerClipper as u8, MultiFrameAction::Liquidation as u8];
+
+    fn create_classifier(
+        request: MultiFrameRequest,
+    ) -> Option<MultiCallFrameClassification<Action>> {
+        Some(MultiCallFrameClassification {
+            trace_index:         request.trace_idx,
+            tree_search_builder: TreeSearchBuilder::new()
+                .with_actions([Action::is_transfer, Action::is_swap]),
+            parse_fn:            Box::new(parse_clipper),
+        })
+    }
+}
+
+/// The swaps in the take callback stay in the tree under the take, which is how
+/// the liquidation inspector finds the swaps that funded the liquidation. If
+/// the gem of the clipper isn't known, it is taken from the first non dai
+/// transfer in the callback, which is the exit of the bought collateral out of
+/// the gem join, or else from the first swap that sells it.
+fn parse_clipper(
+    this: &mut Action,
+    child_nodes: Vec<(NodeDataIndex, Action)>,
+) -> Vec<NodeDataIndex> {
+    let this = this.try_liquidation_mut().unwrap();
+    if this.collateral_asset.address != Address::ZERO {
+        return vec![]
+    }
+    let dai = this.debt_asset.address;
+
+    let (swaps, transfers): (Vec<_>, Vec<_>) = child_nodes
+        .into_iter()
+        .map(|(_, action)| action)
+        .partition(Action::is_swap);
+
+    if let Some(collateral) = transfers
+        .into_iter()
+        .filter_map(Action::try_transfer)
+        .map(|transfer| transfer.token)
+        .chain(
+            swaps
+                .into_iter()
+                .filter_map(Action::try_swaps_merged)
+                .map(|swap| swap.token_in),
+        )
+        .find(|token| token.address != dai)
+    {
+        this.collateral_asset = collateral;
+    }
+
+    vec![]
+}
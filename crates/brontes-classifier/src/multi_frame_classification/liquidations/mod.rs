@@ -1,2 +1,6 @@
 pub mod aave;
 pub use aave::*;
+pub mod compound;
+pub use compound::*;
+pub mod maker;
+pub use maker::*;
@@ -9,12 +9,15 @@ pub mod batch;
 pub mod flash_loan;
 pub mod liquidations;
 
-use aggregator::{OneInchAggregator, OneInchFusion, ZeroXAgg};
+use aggregator::{
+    KyberSwapAgg, OdosV2Agg, OneInchAggregator, OneInchFusion, ParaswapV5Agg, UniswapV4Unlock,
+    ZeroXAgg,
+};
 use batch::{Cowswap, UniswapX, ZeroXBatch};
 use brontes_types::normalized_actions::{Action, MultiCallFrameClassification, MultiFrameRequest};
 use flash_loan::{BalancerV2, MakerDss};
 use itertools::Itertools;
-use liquidations::{AaveV2, AaveV3};
+use liquidations::{AaveV2, AaveV3, CompoundV3, MakerClipper, Spark};
 use tracing::debug;
 
 use self::flash_loan::Dodo;
@@ -44,10 +47,17 @@ pub fn parse_multi_frame_requests(
             BalancerV2::KEY => BalancerV2::create_classifier(request),
             AaveV2::KEY => AaveV2::create_classifier(request),
             AaveV3::KEY => AaveV3::create_classifier(request),
+            Spark::KEY => Spark::create_classifier(request),
+            CompoundV3::KEY => CompoundV3::create_classifier(request),
+            MakerClipper::KEY => MakerClipper::create_classifier(request),
             ZeroXAgg::KEY => ZeroXAgg::create_classifier(request),
             ZeroXBatch::KEY => ZeroXBatch::create_classifier(request),
             MakerDss::KEY => MakerDss::create_classifier(request),
             Dodo::KEY => Dodo::create_classifier(request),
+            UniswapV4Unlock::KEY => UniswapV4Unlock::create_classifier(request),
+            ParaswapV5Agg::KEY => ParaswapV5Agg::create_classifier(request),
+            KyberSwapAgg::KEY => KyberSwapAgg::create_classifier(request),
+            OdosV2Agg::KEY => OdosV2Agg::create_classifier(request),
             _ => {
                 debug!(?request, "no multi frame classification impl for this request");
                 None
@@ -6,7 +6,7 @@ use std::{
     },
 };
 
-use alloy_primitives::{Address, TxHash};
+use alloy_primitives::{Address, Bytes, Log, TxHash, B256, U256, U64};
 use brontes_core::{
     decoding::TracingProvider, BlockTracesWithHeaderAnd, TraceLoader, TraceLoaderError,
     TxTracesWithHeaderAnd,
@@ -24,12 +24,18 @@ use brontes_types::{
         address_to_protocol_info::ProtocolInfo, dex::DexQuotes, token_info::TokenInfoWithAddress,
     },
     normalized_actions::{pool::NormalizedNewPool, NormalizedTransfer},
-    structured_trace::TraceActions,
+    structured_trace::{TraceActions, TransactionTraceWithLogs, TxTrace},
     tree::BlockTree,
-    BrontesTaskManager, FastHashMap, TreeCollector, TreeSearchBuilder, UnboundedYapperReceiver,
+    BrontesTaskManager, ChainConfig, FastHashMap, TreeCollector, TreeSearchBuilder,
+    UnboundedYapperReceiver,
 };
 use futures::{future::join_all, StreamExt};
 use reth_db::DatabaseError;
+use reth_primitives::Header;
+use reth_rpc_types::trace::parity::{
+    Action as TraceAction, CallAction, CallOutput, CallType, CreateAction, CreateOutput,
+    TraceOutput, TransactionTrace,
+};
 use serde_json::Value;
 use thiserror::Error;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
@@ -83,7 +89,7 @@ impl ClassifierTestUtils {
                 .flat_map(|(_, pools)| {
                     pools
                         .into_iter()
-                        .map(|(addr, protocol, pair)| (addr, (protocol, pair)))
+                        .map(|(addr, protocol, pairs)| (addr, (protocol, pairs)))
                         .collect::<Vec<_>>()
                 })
                 .collect::<FastHashMap<_, _>>()
@@ -99,6 +105,7 @@ impl ClassifierTestUtils {
                 0,
                 ctr.clone(),
                 quote_asset,
+                ChainConfig::mainnet(),
                 pair_graph,
                 UnboundedYapperReceiver::new(rx, 10000, "test".into()),
                 self.get_provider(),
@@ -409,6 +416,36 @@ impl ClassifierTestUtils {
         Ok(tree)
     }
 
+    /// Classifies a block made up of the given transactions, for actions that
+    /// we don't have a transaction for in the test db. The traces of a
+    /// transaction are in call order, see [`call_trace`].
+    pub async fn build_block_tree_from_traces(
+        &self,
+        block: u64,
+        txes: Vec<Vec<TransactionTraceWithLogs>>,
+    ) -> BlockTree<Action> {
+        self.build_block_tree_from_tx_traces(block, txes.into_iter().map(tx_trace).collect())
+            .await
+    }
+
+    /// Same as [`Self::build_block_tree_from_traces`] for transactions that
+    /// need their gas or status changed, see [`tx_trace`]. The block,
+    /// position and hash of the transactions are set here.
+    pub async fn build_block_tree_from_tx_traces(
+        &self,
+        block: u64,
+        mut txes: Vec<TxTrace>,
+    ) -> BlockTree<Action> {
+        for (tx_idx, tx) in txes.iter_mut().enumerate() {
+            tx.block_number = block;
+            tx.tx_index = tx_idx as u64;
+            tx.tx_hash = B256::from((U256::from(block) << 64) | U256::from(tx_idx));
+        }
+        let header = Header { number: block, ..Default::default() };
+
+        self.classifier.build_block_tree(txes, header, false).await
+    }
+
     pub async fn build_block_tree_with_pricing(
         &self,
         block: u64,
@@ -656,6 +693,89 @@ impl ClassifierTestUtils {
     }
 }
 
+/// A successful transaction with the given traces in call order, that pays no
+/// priority fee.
+pub fn tx_trace(mut trace: Vec<TransactionTraceWithLogs>) -> TxTrace {
+    for idx in 0..trace.len() {
+        let address = trace[idx].trace.trace_address.clone();
+        trace[idx].trace_idx = idx as u64;
+        trace[idx].trace.subtraces = trace
+            .iter()
+            .filter(|child| {
+                child.trace.trace_address.len() == address.len() + 1
+                    && child.trace.trace_address.starts_with(&address)
+            })
+            .count();
+    }
+
+    TxTrace::new(0, trace, B256::ZERO, 0, 100_000, 0, true)
+}
+
+/// A successful call for [`ClassifierTestUtils::build_block_tree_from_traces`].
+pub fn call_trace(
+    trace_address: Vec<usize>,
+    from: Address,
+    to: Address,
+    input: Vec<u8>,
+    output: Vec<u8>,
+    logs: Vec<Log>,
+) -> TransactionTraceWithLogs {
+    TransactionTraceWithLogs {
+        trace: TransactionTrace {
+            action: TraceAction::Call(CallAction {
+                from,
+                to,
+                value: U256::ZERO,
+                gas: U64::from(100_000),
+                input: Bytes::from(input),
+                call_type: CallType::Call,
+            }),
+            error: None,
+            result: Some(TraceOutput::Call(CallOutput {
+                gas_used: U64::from(50_000),
+                output:   Bytes::from(output),
+            })),
+            trace_address,
+            subtraces: 0,
+        },
+        logs,
+        msg_sender: from,
+        trace_idx: 0,
+        decoded_data: None,
+    }
+}
+
+/// A contract creation for
+/// [`ClassifierTestUtils::build_block_tree_from_traces`].
+pub fn create_trace(
+    trace_address: Vec<usize>,
+    from: Address,
+    created: Address,
+) -> TransactionTraceWithLogs {
+    TransactionTraceWithLogs {
+        trace:        TransactionTrace {
+            action: TraceAction::Create(CreateAction {
+                from,
+                value: U256::ZERO,
+                gas: U64::from(100_000),
+                init: Bytes::new(),
+            }),
+            error: None,
+            result: Some(TraceOutput::Create(CreateOutput {
+                gas_used: U64::from(50_000),
+                code:     Bytes::new(),
+                address:  created,
+            })),
+            trace_address,
+            subtraces: 0,
+        },
+        logs:         vec![],
+        msg_sender:   from,
+        trace_idx:    0,
+        decoded_data: None,
+    }
+}
+
 impl Deref for ClassifierTestUtils {
     type Target = TraceLoader;
 
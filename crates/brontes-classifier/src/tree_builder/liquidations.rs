@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use alloy_primitives::Address;
+use alloy_sol_types::SolEvent;
+use brontes_types::{normalized_actions::Action, tree::BlockTree, FastHashMap, Protocol};
+use parking_lot::Mutex;
+
+use crate::CompoundV3Comet::AbsorbCollateral;
+
+/// How many absorbs are kept per comet and collateral asset. Buys are matched
+/// against the latest absorb before them, the older ones are only kept around
+/// so blocks that are classified out of order still find their absorb.
+const MAX_ABSORBS_PER_ASSET: usize = 32;
+
+/// (block number, tx index, trace index)
+type Position = (u64, usize, u64);
+
+/// Compound V3 liquidations are split over `absorb` and `buyCollateral`, which
+/// can be in different transactions or blocks. Collateral absorbed by a comet
+/// is pooled in its reserves, so the debtor of a buy is taken from the latest
+/// absorb of the same asset on the same comet before it. The absorbs are kept
+/// across blocks for the lifetime of the classifier.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CompoundV3Absorbs {
+    // (comet, collateral asset) -> absorbs sorted by position
+    absorbed: Arc<Mutex<FastHashMap<(Address, Address), Vec<(Position, Address)>>>>,
+}
+
+impl CompoundV3Absorbs {
+    pub(crate) fn link_debtors(&self, tree: &mut BlockTree<Action>) {
+        let block = tree.header.number;
+        let mut absorbed = self.absorbed.lock();
+
+        for root in &tree.tx_roots {
+            for action in root.data_store.0.iter().flatten().flatten() {
+                let Action::Unclassified(trace) = action else { continue };
+                for log in &trace.logs {
+                    if log.topics().first() != Some(&AbsorbCollateral::SIGNATURE_HASH) {
+                        continue
+                    }
+                    let Ok(event) = AbsorbCollateral::decode_log_data(log, false) else { continue };
+
+                    let absorbs = absorbed.entry((log.address, event.asset)).or_default();
+                    let position = (block, root.position, trace.trace_idx);
+                    let idx = absorbs.partition_point(|(p, _)| *p < position);
+                    if absorbs.get(idx).map(|(p, _)| *p) != Some(position) {
+                        absorbs.insert(idx, (position, event.borrower));
+                    }
+                    if absorbs.len() > MAX_ABSORBS_PER_ASSET {
+                        absorbs.remove(0);
+                    }
+                }
+            }
+        }
+
+        for root in &mut tree.tx_roots {
+            let tx_idx = root.position;
+            for action in root.data_store.0.iter_mut().flatten().flatten() {
+                let Action::Liquidation(liquidation) = action else { continue };
+                if liquidation.protocol != Protocol::CompoundV3
+                    || liquidation.debtor != Address::ZERO
+                {
+                    continue
+                }
+
+                let position = (block, tx_idx, liquidation.trace_index);
+                if let Some((_, borrower)) = absorbed
+                    .get(&(liquidation.pool, liquidation.collateral_asset.address))
+                    .and_then(|absorbs| {
+                        absorbs[..absorbs.partition_point(|(p, _)| *p < position)].last()
+                    })
+                {
+                    liquidation.debtor = *borrower;
+                }
+            }
+        }
+    }
+}
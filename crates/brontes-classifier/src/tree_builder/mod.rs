@@ -9,10 +9,11 @@ use brontes_types::{
         NormalizedEthTransfer, NormalizedTransfer,
     },
     tree::root::NodeData,
-    ToScaledRational,
+    Protocol, ToScaledRational,
 };
 use malachite::{num::basic::traits::Zero, Rational};
 
+mod liquidations;
 mod tree_pruning;
 pub(crate) mod utils;
 use brontes_database::libmdbx::{DBWriter, LibmdbxReader};
@@ -21,10 +22,11 @@ use brontes_types::{
     normalized_actions::{Action, SelfdestructWithIndex},
     structured_trace::{TraceActions, TransactionTraceWithLogs, TxTrace},
     traits::TracingProvider,
-    tree::{BlockTree, GasDetails, Node, Root},
+    tree::{BlockTree, FailedTx, GasDetails, Node, Root, TxCall},
 };
 use futures::future::join_all;
 use itertools::Itertools;
+use liquidations::CompoundV3Absorbs;
 use malachite::num::arithmetic::traits::Abs;
 use reth_primitives::{Address, Header};
 use reth_rpc_types::trace::parity::{Action as TraceAction, CallType};
@@ -45,6 +47,7 @@ pub struct Classifier<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> {
     libmdbx:               &'db DB,
     provider:              Arc<T>,
     pricing_update_sender: UnboundedSender<DexPriceMsg>,
+    compound_v3_absorbs:   CompoundV3Absorbs,
 }
 
 impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, DB> {
@@ -53,7 +56,12 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
         pricing_update_sender: UnboundedSender<DexPriceMsg>,
         provider: Arc<T>,
     ) -> Self {
-        Self { libmdbx, pricing_update_sender, provider }
+        Self {
+            libmdbx,
+            pricing_update_sender,
+            provider,
+            compound_v3_absorbs: CompoundV3Absorbs::default(),
+        }
     }
 
     pub fn block_load_failure(&self, number: u64) {
@@ -75,8 +83,15 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
                 .unwrap();
         }
 
+        let base_fee = header.base_fee_per_gas.unwrap_or_default() as u128;
+        let failed_txs = traces
+            .iter()
+            .filter_map(|trace| FailedTx::from_trace(trace, base_fee))
+            .collect_vec();
+
         let tx_roots = self.build_tx_trees(traces, &header).await;
         let mut tree = BlockTree::new(header, tx_roots.len());
+        tree.failed_txs = failed_txs;
 
         // send out all updates
         let further_classification_requests =
@@ -86,6 +101,7 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
         remove_possible_transfer_double_counts(&mut tree);
 
         self.finish_classification(&mut tree, further_classification_requests);
+        self.compound_v3_absorbs.link_debtors(&mut tree);
         tree.finalize_tree();
 
         tree
@@ -152,6 +168,7 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
 
                     let address = root_trace.get_from_addr();
                     let trace_idx = root_trace.trace_idx;
+                    let call = TxCall::from_trace(&root_trace);
 
                     let classification = self
                         .process_classification(
@@ -179,6 +196,7 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
                         head: node,
                         tx_hash: trace.tx_hash,
                         private: false,
+                        call,
                         total_msg_value_transfers,
                         gas_details: GasDetails {
                             coinbase_transfer:   None,
@@ -402,9 +420,34 @@ impl<'db, T: TracingProvider, DB: LibmdbxReader + DBWriter> Classifier<'db, T, D
                 {
                     error!(pool=?p.pool_address,"failed to update pool config");
                 }
+
+                // the pricer needs the initialize log to register the full v4 pool id
+                if p.protocol == Protocol::UniswapV4 {
+                    let update = DexPriceMsg::Update(PoolUpdate {
+                        block,
+                        tx_idx,
+                        logs: trace.logs.clone(),
+                        action: results.1.clone(),
+                    });
+                    return (vec![results.0, update], vec![results.1])
+                }
             }
 
             (vec![results.0], vec![results.1])
+        } else if let Some(actions) = classify_modify_liquidity(&trace, self.libmdbx) {
+            let updates = actions
+                .iter()
+                .map(|action| {
+                    DexPriceMsg::Update(PoolUpdate {
+                        block,
+                        tx_idx,
+                        logs: trace.logs.clone(),
+                        action: action.clone(),
+                    })
+                })
+                .collect();
+
+            (updates, actions)
         } else if let Some(transfer) = self
             .classify_transfer(tx_idx, trace_index, &trace, block)
             .await
@@ -5,6 +5,8 @@ pub mod executor;
 #[cfg(not(feature = "local-reth"))]
 pub mod local_provider;
 pub mod missing_token_info;
+#[cfg(not(feature = "local-reth"))]
+pub mod rpc_provider;
 
 #[cfg(feature = "tests")]
 pub mod test_utils;
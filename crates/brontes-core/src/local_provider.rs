@@ -100,6 +100,15 @@ impl TracingProvider for LocalProvider {
         Ok((tx.block_number.ok_or_else(err)?, tx.transaction_index.ok_or_else(err)? as usize))
     }
 
+    async fn replay_transaction_without(
+        &self,
+        _: u64,
+        _: TxHash,
+        _: Vec<TxHash>,
+    ) -> eyre::Result<Vec<alloy_primitives::Log>> {
+        Err(eyre::eyre!("replaying transactions needs the local reth db"))
+    }
+
     async fn header_by_number(&self, number: BlockNumber) -> eyre::Result<Option<Header>> {
         let err = || eyre::eyre!("failed to unwrap option");
         let block = self
@@ -0,0 +1,1015 @@
+//! A [`TracingProvider`] that only needs the JSON-RPC api of an archive node.
+//!
+//! Traces are built from the `callTracer` of `debug_traceBlockByNumber`, with
+//! logs enabled, which every geth compatible client supports (reth, geth,
+//! erigon, anvil). Nodes that can't trace whole blocks fall back to batched
+//! `debug_traceTransaction` calls. Replaying a transaction without others
+//! builds the state before it out of `prestateTracer` diffs, which are applied
+//! as overrides to a `debug_traceCall`. Everything that doesn't need tracing
+//! goes through the [`LocalProvider`].
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use alloy_primitives::{LogData, U64};
+use alloy_rpc_types::AnyReceiptEnvelope;
+use brontes_types::{
+    structured_trace::{TransactionTraceWithLogs, TxTrace},
+    traits::TracingProvider,
+    FastHashMap,
+};
+use eyre::{eyre, WrapErr};
+use itertools::Itertools;
+use reth_primitives::{
+    Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Bytes, Header, StorageValue, TxHash,
+    B256, U256,
+};
+use reth_rpc_types::{
+    state::StateOverride,
+    trace::parity::{
+        Action, CallAction, CallOutput, CallType, CreateAction, CreateOutput, SelfdestructAction,
+        TraceOutput, TransactionTrace,
+    },
+    BlockOverrides, Log, TransactionReceipt, TransactionRequest,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::{debug, warn};
+
+use crate::local_provider::LocalProvider;
+
+/// Transactions traced per batch request when the node can't trace whole
+/// blocks
+pub const DEFAULT_TRACE_BATCH_SIZE: usize = 50;
+/// Delay before the first retry, doubled on every retry up to
+/// `2^MAX_BACKOFF_DOUBLINGS` times this delay
+const RETRY_BACKOFF: Duration = Duration::from_millis(200);
+const MAX_BACKOFF_DOUBLINGS: u8 = 5;
+/// JSON-RPC error code of unsupported methods
+const METHOD_NOT_FOUND: i64 = -32601;
+
+#[derive(Debug, Clone)]
+pub struct RpcTracingProvider {
+    client:     reqwest::Client,
+    url:        String,
+    provider:   LocalProvider,
+    retries:    u8,
+    batch_size: usize,
+    request_id: Arc<AtomicU64>,
+}
+
+impl RpcTracingProvider {
+    pub fn new(url: String, retries: u8) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            provider: LocalProvider::new(url.clone(), retries),
+            url,
+            retries,
+            batch_size: DEFAULT_TRACE_BATCH_SIZE,
+            request_id: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    async fn request<T: DeserializeOwned>(&self, method: &str, params: Value) -> eyre::Result<T> {
+        self.batch_request(vec![(method, params)])
+            .await?
+            .pop()
+            .ok_or_else(|| eyre!("empty response for {method}"))
+    }
+
+    /// Sends all calls in one JSON-RPC batch, the results are in the order of
+    /// the calls. The whole batch is retried on transport errors and timeouts,
+    /// errors returned by the node are returned right away
+    async fn batch_request<T: DeserializeOwned>(
+        &self,
+        calls: Vec<(&str, Value)>,
+    ) -> eyre::Result<Vec<T>> {
+        let first_id = self
+            .request_id
+            .fetch_add(calls.len() as u64, Ordering::Relaxed);
+        let body = calls
+            .iter()
+            .enumerate()
+            .map(|(i, (method, params))| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": first_id + i as u64,
+                    "method": method,
+                    "params": params,
+                })
+            })
+            .collect_vec();
+
+        let mut attempts = 0;
+        loop {
+            match self.send_batch(&body, first_id).await {
+                Ok(res) => return Ok(res),
+                Err(RpcError::Rpc { code, message }) if code == METHOD_NOT_FOUND => {
+                    return Err(eyre!("{} isn't supported by the node: {message}", calls[0].0))
+                }
+                Err(e) if !e.is_retryable() => return Err(eyre!("{} failed: {e}", calls[0].0)),
+                Err(e) if attempts >= self.retries => {
+                    return Err(eyre!("{} failed after {attempts} retries: {e}", calls[0].0))
+                }
+                Err(e) => {
+                    debug!(
+                        target: "brontes::rpc",
+                        method = calls[0].0,
+                        %e,
+                        attempts,
+                        "retrying request"
+                    );
+                    let backoff = 2u32.pow(attempts.min(MAX_BACKOFF_DOUBLINGS) as u32);
+                    tokio::time::sleep(RETRY_BACKOFF * backoff).await;
+                    attempts += 1;
+                }
+            }
+        }
+    }
+
+    async fn send_batch<T: DeserializeOwned>(
+        &self,
+        body: &[Value],
+        first_id: u64,
+    ) -> Result<Vec<T>, RpcError> {
+        let responses: Vec<RpcResponse> = self
+            .client
+            .post(&self.url)
+            .json(body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let mut results = responses
+            .into_iter()
+            .map(|res| match (res.result, res.error) {
+                (_, Some(error)) => {
+                    Err(RpcError::Rpc { code: error.code, message: error.message })
+                }
+                (Some(result), None) => Ok((res.id, result)),
+                (None, None) => Ok((res.id, Value::Null)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if results.len() != body.len() {
+            return Err(RpcError::Response(format!(
+                "expected {} responses, got {}",
+                body.len(),
+                results.len()
+            )))
+        }
+        // batch responses can be in any order
+        results.sort_by_key(|(id, _)| id.saturating_sub(first_id));
+
+        results
+            .into_iter()
+            .map(|(_, result)| {
+                serde_json::from_value(result).map_err(|e| RpcError::Response(e.to_string()))
+            })
+            .collect()
+    }
+
+    async fn block_by_number(&self, block_number: u64) -> eyre::Result<RpcBlock> {
+        self.block(BlockId::Number(BlockNumberOrTag::Number(block_number)))
+            .await?
+            .ok_or_else(|| eyre!("block {block_number} not found"))
+    }
+
+    async fn block(&self, block_id: BlockId) -> eyre::Result<Option<RpcBlock>> {
+        match block_id {
+            BlockId::Hash(hash) => {
+                self.request("eth_getBlockByHash", json!([hash.block_hash, false]))
+                    .await
+            }
+            BlockId::Number(number) => {
+                self.request("eth_getBlockByNumber", json!([number, false]))
+                    .await
+            }
+        }
+    }
+
+    /// The call frames of the transactions of the block in order
+    async fn trace_call_frames(&self, block: &RpcBlock) -> eyre::Result<Vec<CallFrame>> {
+        let block_trace = self
+            .request::<Vec<BlockTraceResult>>(
+                "debug_traceBlockByNumber",
+                json!([block.number, call_tracer_options()]),
+            )
+            .await;
+
+        match block_trace {
+            Ok(traces) => traces
+                .into_iter()
+                .map(|trace| {
+                    trace.result.ok_or_else(|| {
+                        eyre!("failed to trace tx: {}", trace.error.unwrap_or_default())
+                    })
+                })
+                .collect(),
+            Err(e) => {
+                warn!(
+                    target: "brontes::rpc",
+                    block = block.number.to::<u64>(),
+                    %e,
+                    "failed to trace block, tracing transactions instead"
+                );
+                self.trace_transactions(&block.transactions).await
+            }
+        }
+    }
+
+    async fn trace_transactions(&self, tx_hashes: &[B256]) -> eyre::Result<Vec<CallFrame>> {
+        let mut frames = Vec::with_capacity(tx_hashes.len());
+        for chunk in tx_hashes.chunks(self.batch_size) {
+            let calls = chunk
+                .iter()
+                .map(|hash| ("debug_traceTransaction", json!([hash, call_tracer_options()])))
+                .collect_vec();
+            frames.extend(self.batch_request::<CallFrame>(calls).await?);
+        }
+
+        Ok(frames)
+    }
+}
+
+#[async_trait::async_trait]
+impl TracingProvider for RpcTracingProvider {
+    async fn eth_call(
+        &self,
+        request: TransactionRequest,
+        block_number: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> eyre::Result<Bytes> {
+        let block = block_number.unwrap_or(BlockId::latest());
+        let params = match (state_overrides, block_overrides) {
+            (None, None) => json!([request, block]),
+            (state_overrides, None) => json!([request, block, state_overrides]),
+            (state_overrides, Some(block_overrides)) => {
+                json!([request, block, state_overrides, block_overrides])
+            }
+        };
+
+        self.request("eth_call", params).await
+    }
+
+    async fn block_hash_for_id(&self, block_num: u64) -> eyre::Result<Option<B256>> {
+        self.provider.block_hash_for_id(block_num).await
+    }
+
+    async fn best_block_number(&self) -> eyre::Result<u64> {
+        self.provider.best_block_number().await
+    }
+
+    async fn replay_block_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> eyre::Result<Option<Vec<TxTrace>>> {
+        let Some(block) = self.block(block_id).await? else { return Ok(None) };
+        let block_number = block.number.to::<u64>();
+
+        let frames = self
+            .trace_call_frames(&block)
+            .await
+            .wrap_err_with(|| format!("failed to trace block {block_number}"))?;
+        if frames.len() != block.transactions.len() {
+            return Err(eyre!(
+                "got {} traces for the {} transactions of block {block_number}",
+                frames.len(),
+                block.transactions.len()
+            ))
+        }
+
+        let traces = block
+            .transactions
+            .iter()
+            .zip(frames)
+            .enumerate()
+            .map(|(tx_index, (tx_hash, frame))| {
+                let is_success = frame.error.is_none();
+                // gas used and the effective price are filled in from the receipts
+                TxTrace::new(
+                    block_number,
+                    frame.into_traces(),
+                    *tx_hash,
+                    tx_index as u64,
+                    0,
+                    0,
+                    is_success,
+                )
+            })
+            .collect();
+
+        Ok(Some(traces))
+    }
+
+    async fn block_receipts(
+        &self,
+        number: BlockNumberOrTag,
+    ) -> eyre::Result<Option<Vec<TransactionReceipt<AnyReceiptEnvelope<Log>>>>> {
+        self.provider.block_receipts(number).await
+    }
+
+    async fn header_by_number(&self, number: BlockNumber) -> eyre::Result<Option<Header>> {
+        self.provider.header_by_number(number).await
+    }
+
+    async fn block_and_tx_index(&self, hash: TxHash) -> eyre::Result<(u64, usize)> {
+        self.provider.block_and_tx_index(hash).await
+    }
+
+    async fn replay_transaction_without(
+        &self,
+        block_number: u64,
+        tx_hash: TxHash,
+        skip: Vec<TxHash>,
+    ) -> eyre::Result<Vec<alloy_primitives::Log>> {
+        let block = self.block_by_number(block_number).await?;
+        let position = block
+            .transactions
+            .iter()
+            .position(|hash| *hash == tx_hash)
+            .ok_or_else(|| eyre!("{tx_hash:?} isn't in block {block_number}"))?;
+        let (first_skipped, reexecuted) = replay_plan(&block.transactions, position, &skip);
+
+        // the recorded state changes are only valid up to the first skipped tx, every
+        // change after it was made on state that includes the skipped txs
+        let mut overrides = StateOverrides::default();
+        if first_skipped > 0 {
+            let state_diffs: Vec<PrestateTraceResult> = self
+                .request("debug_traceBlockByNumber", json!([block.number, prestate_diff_options()]))
+                .await?;
+            for diff in state_diffs.into_iter().take(first_skipped) {
+                overrides.apply_diff(diff.result.unwrap_or_default());
+            }
+        }
+
+        let calls = reexecuted
+            .iter()
+            .chain([&tx_hash])
+            .map(|hash| ("eth_getTransactionByHash", json!([hash])))
+            .collect_vec();
+        let mut txs = self.batch_request::<Value>(calls).await?;
+        let target = txs
+            .pop()
+            .ok_or_else(|| eyre!("empty response for {tx_hash:?}"))?;
+
+        let parent = U64::from(block_number - 1);
+        let block_overrides = json!({
+            "number": block.number,
+            "time": block.timestamp,
+            "feeRecipient": block.miner,
+            "baseFeePerGas": block.base_fee_per_gas,
+        });
+
+        // the txs between the first skipped tx and this one are executed again on
+        // the state without the skipped txs
+        for tx in txs {
+            let mut options = prestate_diff_options();
+            options["stateOverrides"] = json!(overrides);
+            options["blockOverrides"] = block_overrides.clone();
+
+            let diff: PrestateDiff = self
+                .request("debug_traceCall", json!([call_request(&tx), parent, options]))
+                .await?;
+            overrides.apply_diff(diff);
+        }
+
+        let mut options = call_tracer_options();
+        options["stateOverrides"] = json!(overrides);
+        options["blockOverrides"] = block_overrides;
+
+        let frame: CallFrame = self
+            .request("debug_traceCall", json!([call_request(&target), parent, options]))
+            .await?;
+
+        let mut logs = Vec::new();
+        frame.collect_logs(&mut logs);
+
+        Ok(logs)
+    }
+
+    async fn get_storage(
+        &self,
+        block_number: Option<u64>,
+        address: Address,
+        storage_key: B256,
+    ) -> eyre::Result<Option<StorageValue>> {
+        self.provider
+            .get_storage(block_number, address, storage_key)
+            .await
+    }
+
+    async fn get_bytecode(
+        &self,
+        block_number: Option<u64>,
+        address: Address,
+    ) -> eyre::Result<Option<Bytecode>> {
+        self.provider.get_bytecode(block_number, address).await
+    }
+}
+
+fn call_tracer_options() -> Value {
+    json!({ "tracer": "callTracer", "tracerConfig": { "withLog": true } })
+}
+
+fn prestate_diff_options() -> Value {
+    json!({ "tracer": "prestateTracer", "tracerConfig": { "diffMode": true } })
+}
+
+/// The call of a transaction returned by `eth_getTransactionByHash`
+fn call_request(tx: &Value) -> Value {
+    json!({
+        "from": tx["from"],
+        "to": tx["to"],
+        "gas": tx["gas"],
+        "gasPrice": tx["gasPrice"],
+        "value": tx["value"],
+        "input": tx["input"],
+    })
+}
+
+/// For replaying the tx at `position` without the `skip` txs, returns the
+/// index of the first skipped tx, whose recorded state changes can be used
+/// before it, and the txs after it that have to be executed again.
+fn replay_plan(transactions: &[B256], position: usize, skip: &[B256]) -> (usize, Vec<B256>) {
+    let before = &transactions[..position];
+    let first_skipped = before
+        .iter()
+        .position(|hash| skip.contains(hash))
+        .unwrap_or(position);
+    let reexecuted = before[first_skipped..]
+        .iter()
+        .filter(|hash| !skip.contains(hash))
+        .copied()
+        .collect();
+
+    (first_skipped, reexecuted)
+}
+
+#[derive(Debug, thiserror::Error)]
+enum RpcError {
+    #[error(transparent)]
+    Transport(#[from] reqwest::Error),
+    #[error("rpc error {code}: {message}")]
+    Rpc { code: i64, message: String },
+    #[error("invalid response: {0}")]
+    Response(String),
+}
+
+impl RpcError {
+    /// Whether the request might succeed when sent again. Only errors on the
+    /// way to the node, timeouts and overloaded nodes are retried
+    fn is_retryable(&self) -> bool {
+        let Self::Transport(e) = self else { return false };
+        if let Some(status) = e.status() {
+            return status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        }
+
+        e.is_timeout() || e.is_connect() || e.is_request() || e.is_body()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    #[serde(default)]
+    id:     u64,
+    result: Option<Value>,
+    error:  Option<RpcErrorObject>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcErrorObject {
+    code:    i64,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcBlock {
+    number:           U64,
+    timestamp:        U64,
+    miner:            Address,
+    base_fee_per_gas: Option<U256>,
+    /// the transaction hashes, blocks are fetched without the full
+    /// transactions
+    transactions:     Vec<B256>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockTraceResult {
+    result: Option<CallFrame>,
+    error:  Option<String>,
+}
+
+/// A frame of the geth `callTracer`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CallFrame {
+    #[serde(rename = "type")]
+    typ:      String,
+    from:     Address,
+    to:       Option<Address>,
+    value:    Option<U256>,
+    gas:      U64,
+    gas_used: U64,
+    #[serde(default)]
+    input:    Bytes,
+    output:   Option<Bytes>,
+    error:    Option<String>,
+    #[serde(default)]
+    calls:    Vec<CallFrame>,
+    #[serde(default)]
+    logs:     Vec<CallLogFrame>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct CallLogFrame {
+    address:  Address,
+    #[serde(default)]
+    topics:   Vec<B256>,
+    #[serde(default)]
+    data:     Bytes,
+    /// the number of calls of the frame made before the log
+    position: Option<U64>,
+}
+
+impl CallLogFrame {
+    fn into_log(self) -> alloy_primitives::Log {
+        alloy_primitives::Log {
+            address: self.address,
+            data:    LogData::new_unchecked(self.topics, self.data),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PrestateTraceResult {
+    result: Option<PrestateDiff>,
+}
+
+/// The `prestateTracer` in diff mode. `pre` holds the touched accounts before
+/// the tx and `post` only the values that changed, cleared storage slots and
+/// destructed accounts are left out of `post`
+#[derive(Debug, Default, Deserialize)]
+struct PrestateDiff {
+    #[serde(default)]
+    pre:  FastHashMap<Address, PrestateAccount>,
+    #[serde(default)]
+    post: FastHashMap<Address, PrestateAccount>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PrestateAccount {
+    balance: Option<U256>,
+    nonce:   Option<u64>,
+    code:    Option<Bytes>,
+    #[serde(default)]
+    storage: FastHashMap<B256, B256>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountOverride {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    balance:    Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce:      Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code:       Option<Bytes>,
+    #[serde(skip_serializing_if = "FastHashMap::is_empty")]
+    state_diff: FastHashMap<B256, B256>,
+}
+
+impl AccountOverride {
+    fn apply(&mut self, account: PrestateAccount) {
+        self.balance = account.balance.or(self.balance);
+        self.nonce = account.nonce.map(U64::from).or(self.nonce);
+        self.code = account.code.or(self.code.take());
+        self.state_diff.extend(account.storage);
+    }
+
+    fn destruct(&mut self, pre: PrestateAccount) {
+        self.balance = Some(U256::ZERO);
+        self.nonce = Some(U64::ZERO);
+        self.code = Some(Bytes::new());
+        self.state_diff
+            .extend(pre.storage.into_keys().map(|slot| (slot, B256::ZERO)));
+    }
+}
+
+/// The state of the replayed block up to a tx, as overrides on the state of
+/// the parent block
+#[derive(Debug, Default, Serialize)]
+#[serde(transparent)]
+struct StateOverrides(FastHashMap<Address, AccountOverride>);
+
+impl StateOverrides {
+    fn apply_diff(&mut self, PrestateDiff { pre, mut post }: PrestateDiff) {
+        for (address, pre) in pre {
+            let account = self.0.entry(address).or_default();
+            match post.remove(&address) {
+                Some(post) => {
+                    let cleared = pre
+                        .storage
+                        .into_keys()
+                        .filter(|slot| !post.storage.contains_key(slot))
+                        .collect_vec();
+                    account
+                        .state_diff
+                        .extend(cleared.into_iter().map(|slot| (slot, B256::ZERO)));
+                    account.apply(post);
+                }
+                None => account.destruct(pre),
+            }
+        }
+
+        // accounts created by the tx
+        for (address, post) in post {
+            self.0.entry(address).or_default().apply(post);
+        }
+    }
+}
+
+impl CallFrame {
+    /// Flattens the frames into parity style traces in the same order and with
+    /// the same msg senders as the reth tracing inspector. Calls to precompiles
+    /// are left out
+    fn into_traces(self) -> Vec<TransactionTraceWithLogs> {
+        let mut traces = Vec::new();
+        let mut trace_idx = 0;
+        self.flatten(vec![], &mut trace_idx, &mut traces);
+
+        traces
+    }
+
+    fn flatten(
+        mut self,
+        trace_address: Vec<usize>,
+        trace_idx: &mut u64,
+        traces: &mut Vec<TransactionTraceWithLogs>,
+    ) {
+        let calls = std::mem::take(&mut self.calls)
+            .into_iter()
+            .filter(|call| !call.to.is_some_and(is_precompile))
+            .collect_vec();
+        let logs = std::mem::take(&mut self.logs)
+            .into_iter()
+            .map(CallLogFrame::into_log)
+            .collect_vec();
+
+        let trace = self.to_parity_trace(trace_address.clone(), calls.len());
+        let msg_sender = match &trace.action {
+            // a delegate call runs in the context of the last call that wasn't a delegate
+            // call, so it has the same msg sender
+            Action::Call(call) if call.call_type == CallType::DelegateCall => traces
+                .iter()
+                .rev()
+                .find(|prev| match &prev.trace.action {
+                    Action::Call(c) => c.call_type != CallType::DelegateCall,
+                    Action::Create(_) => true,
+                    _ => false,
+                })
+                .map(|prev| prev.msg_sender)
+                .unwrap_or(call.from),
+            Action::Call(call) => call.from,
+            Action::Create(create) => create.from,
+            Action::Reward(reward) => reward.author,
+            Action::Selfdestruct(selfdestruct) => selfdestruct.address,
+        };
+
+        traces.push(TransactionTraceWithLogs {
+            trace,
+            logs,
+            msg_sender,
+            trace_idx: *trace_idx,
+            decoded_data: None,
+        });
+        *trace_idx += 1;
+
+        for (i, call) in calls.into_iter().enumerate() {
+            let mut child_address = trace_address.clone();
+            child_address.push(i);
+            call.flatten(child_address, trace_idx, traces);
+        }
+    }
+
+    /// The logs of the frame and its calls in the order they were emitted
+    fn collect_logs(self, logs: &mut Vec<alloy_primitives::Log>) {
+        let mut own_logs = self.logs.into_iter().peekable();
+        for (i, call) in self.calls.into_iter().enumerate() {
+            while let Some(log) =
+                own_logs.next_if(|log| log.position.is_some_and(|pos| pos <= U64::from(i)))
+            {
+                logs.push(log.into_log());
+            }
+            call.collect_logs(logs);
+        }
+        logs.extend(own_logs.map(CallLogFrame::into_log));
+    }
+
+    fn to_parity_trace(&self, trace_address: Vec<usize>, subtraces: usize) -> TransactionTrace {
+        let value = self.value.unwrap_or_default();
+        let to = self.to.unwrap_or_default();
+        let output = self.output.clone().unwrap_or_default();
+
+        let (action, result) = match self.typ.as_str() {
+            "CREATE" | "CREATE2" => (
+                Action::Create(CreateAction {
+                    from: self.from,
+                    value,
+                    gas: self.gas,
+                    init: self.input.clone(),
+                }),
+                TraceOutput::Create(CreateOutput {
+                    gas_used: self.gas_used,
+                    code:     output,
+                    address:  to,
+                }),
+            ),
+            "SELFDESTRUCT" => {
+                let action = Action::Selfdestruct(SelfdestructAction {
+                    address:        self.from,
+                    refund_address: to,
+                    balance:        value,
+                });
+                return TransactionTrace {
+                    action,
+                    error: None,
+                    result: None,
+                    trace_address,
+                    subtraces,
+                }
+            }
+            typ => (
+                Action::Call(CallAction {
+                    from: self.from,
+                    to,
+                    value,
+                    gas: self.gas,
+                    input: self.input.clone(),
+                    call_type: match typ {
+                        "DELEGATECALL" => CallType::DelegateCall,
+                        "STATICCALL" => CallType::StaticCall,
+                        "CALLCODE" => CallType::CallCode,
+                        _ => CallType::Call,
+                    },
+                }),
+                TraceOutput::Call(CallOutput { gas_used: self.gas_used, output }),
+            ),
+        };
+
+        // the same as parity, calls that failed for any other reason than a revert
+        // have no result
+        let result = match &self.error {
+            Some(error) if error != "execution reverted" => None,
+            _ => Some(result),
+        };
+
+        TransactionTrace { action, error: self.error.clone(), result, trace_address, subtraces }
+    }
+}
+
+/// The precompiles up to cancun, 0x01 through 0x0a
+fn is_precompile(address: Address) -> bool {
+    address.0[..19].iter().all(|byte| *byte == 0) && (1..=10).contains(&address.0[19])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_frame_into_traces() {
+        let frame: CallFrame = serde_json::from_value(json!({
+            "type": "CALL",
+            "from": "0x00000000000000000000000000000000000000aa",
+            "to": "0x00000000000000000000000000000000000000bb",
+            "value": "0x1",
+            "gas": "0x10000",
+            "gasUsed": "0x5000",
+            "input": "0x12345678",
+            "output": "0x",
+            "calls": [
+                {
+                    "type": "DELEGATECALL",
+                    "from": "0x00000000000000000000000000000000000000bb",
+                    "to": "0x00000000000000000000000000000000000000cc",
+                    "gas": "0x8000",
+                    "gasUsed": "0x1000",
+                    "input": "0x",
+                    "logs": [{
+                        "address": "0x00000000000000000000000000000000000000bb",
+                        "topics": ["0x0000000000000000000000000000000000000000000000000000000000000001"],
+                        "data": "0x"
+                    }]
+                },
+                {
+                    "type": "STATICCALL",
+                    "from": "0x00000000000000000000000000000000000000bb",
+                    "to": "0x0000000000000000000000000000000000000001",
+                    "gas": "0x100",
+                    "gasUsed": "0x100",
+                    "input": "0x"
+                },
+                {
+                    "type": "CALL",
+                    "from": "0x00000000000000000000000000000000000000bb",
+                    "to": "0x00000000000000000000000000000000000000dd",
+                    "gas": "0x100",
+                    "gasUsed": "0x100",
+                    "input": "0x",
+                    "error": "out of gas"
+                }
+            ]
+        }))
+        .unwrap();
+
+        let traces = frame.into_traces();
+        let sender = Address::with_last_byte(0xaa);
+
+        assert_eq!(traces.len(), 3);
+        assert_eq!(traces[0].trace.subtraces, 2);
+        assert_eq!(traces[0].msg_sender, sender);
+        assert_eq!(traces[1].trace.trace_address, vec![0]);
+        assert_eq!(traces[1].msg_sender, sender);
+        assert_eq!(traces[1].logs.len(), 1);
+        assert_eq!(traces[1].logs[0].address, Address::with_last_byte(0xbb));
+        assert_eq!(traces[2].trace.trace_address, vec![1]);
+        assert_eq!(traces[2].trace_idx, 2);
+        assert!(traces[2].trace.result.is_none());
+    }
+
+    #[test]
+    fn test_replay_plan_two_victims() {
+        let [other, frontrun, victim_0, victim_1, backrun] =
+            [1, 2, 3, 4, 5].map(B256::with_last_byte);
+        let block = [other, frontrun, victim_0, victim_1, backrun];
+
+        // the second victim has to see the first victim executed without the frontrun
+        let (first_skipped, reexecuted) = replay_plan(&block, 3, &[frontrun]);
+        assert_eq!(first_skipped, 1);
+        assert_eq!(reexecuted, vec![victim_0]);
+
+        let (first_skipped, reexecuted) = replay_plan(&block, 2, &[frontrun]);
+        assert_eq!(first_skipped, 1);
+        assert!(reexecuted.is_empty());
+
+        // nothing skipped before the tx, all recorded changes can be used
+        let (first_skipped, reexecuted) = replay_plan(&block, 4, &[backrun]);
+        assert_eq!(first_skipped, 4);
+        assert!(reexecuted.is_empty());
+    }
+
+    #[test]
+    fn test_apply_prestate_diffs() {
+        let pool = Address::with_last_byte(0xaa);
+        let destructed = Address::with_last_byte(0xbb);
+        let [slot_0, slot_1] = [0, 1].map(B256::with_last_byte);
+
+        let account = |balance: Option<u64>, storage: &[(B256, u8)]| PrestateAccount {
+            balance: balance.map(U256::from),
+            storage: storage
+                .iter()
+                .map(|(slot, value)| (*slot, B256::with_last_byte(*value)))
+                .collect(),
+            ..Default::default()
+        };
+        let mut overrides = StateOverrides::default();
+
+        overrides.apply_diff(PrestateDiff {
+            pre:  [
+                (pool, account(Some(1), &[(slot_0, 1)])),
+                (destructed, account(Some(5), &[(slot_0, 7)])),
+            ]
+            .into_iter()
+            .collect(),
+            post: [(pool, account(Some(2), &[(slot_0, 2), (slot_1, 3)]))]
+                .into_iter()
+                .collect(),
+        });
+        // the second victim clears slot 1
+        overrides.apply_diff(PrestateDiff {
+            pre:  [(pool, account(None, &[(slot_0, 2), (slot_1, 3)]))]
+                .into_iter()
+                .collect(),
+            post: [(pool, account(None, &[(slot_0, 4)]))]
+                .into_iter()
+                .collect(),
+        });
+
+        let pool = &overrides.0[&pool];
+        assert_eq!(pool.balance, Some(U256::from(2)));
+        assert_eq!(pool.state_diff[&slot_0], B256::with_last_byte(4));
+        assert_eq!(pool.state_diff[&slot_1], B256::ZERO);
+
+        let destructed = &overrides.0[&destructed];
+        assert_eq!(destructed.balance, Some(U256::ZERO));
+        assert_eq!(destructed.code, Some(Bytes::new()));
+        assert_eq!(destructed.state_diff[&slot_0], B256::ZERO);
+    }
+
+    #[test]
+    fn test_is_precompile() {
+        assert!(is_precompile(Address::with_last_byte(1)));
+        assert!(is_precompile(Address::with_last_byte(10)));
+        assert!(!is_precompile(Address::with_last_byte(11)));
+        assert!(!is_precompile(Address::ZERO));
+    }
+
+    /// Answers each connection with the next of `responses` and counts the
+    /// requests that were answered
+    async fn serve(responses: Vec<(u16, Value)>) -> (String, Arc<AtomicU64>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let served = Arc::new(AtomicU64::new(0));
+        let counter = served.clone();
+
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+
+                // read the whole request before answering
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                loop {
+                    let read = stream.read(&mut buf).await.unwrap();
+                    if read == 0 {
+                        break
+                    }
+                    request.extend_from_slice(&buf[..read]);
+                    let text = String::from_utf8_lossy(&request);
+                    let Some(header_end) = text.find("\r\n\r\n") else { continue };
+                    let content_length = text[..header_end]
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or_default();
+                    if request.len() >= header_end + 4 + content_length {
+                        break
+                    }
+                }
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                let body = body.to_string();
+                let response = format!(
+                    "HTTP/1.1 {status} STATUS\r\ncontent-type: \
+                     application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        (url, served)
+    }
+
+    #[tokio::test]
+    async fn test_rpc_errors_are_not_retried() {
+        let error = json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "error": { "code": -32000, "message": "execution reverted" }
+        });
+        let (url, served) = serve(vec![(200, error.clone()), (200, error)]).await;
+        let provider = RpcTracingProvider::new(url, 3);
+
+        let res = provider.request::<Value>("eth_call", json!([])).await;
+        assert!(res.unwrap_err().to_string().contains("execution reverted"));
+        assert_eq!(served.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_transport_errors_are_retried() {
+        let (url, served) = serve(vec![
+            (503, json!("overloaded")),
+            (200, json!({ "jsonrpc": "2.0", "id": 0, "result": "0x1" })),
+        ])
+        .await;
+        let provider = RpcTracingProvider::new(url, 3);
+
+        let res = provider
+            .request::<Value>("eth_blockNumber", json!([]))
+            .await
+            .unwrap();
+        assert_eq!(res, json!("0x1"));
+        assert_eq!(served.load(Ordering::SeqCst), 2);
+    }
+}
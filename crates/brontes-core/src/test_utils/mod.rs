@@ -44,7 +44,7 @@ use tracing_subscriber::filter::Directive;
 
 use crate::decoding::parser::TraceParser;
 #[cfg(not(feature = "local-reth"))]
-use crate::local_provider::LocalProvider;
+use crate::rpc_provider::RpcTracingProvider;
 
 /// Functionality to load all state needed for any testing requirements
 pub struct TraceLoader {
@@ -566,6 +566,7 @@ pub async fn init_trace_parser(
         max_tasks as u64,
         executor.executor(),
         static_files,
+        reth_primitives::MAINNET.clone(),
     );
     handle.spawn(executor);
     let tracer = Box::new(client) as Box<dyn TracingProvider>;
@@ -583,7 +584,7 @@ pub async fn init_trace_parser(
     let db_endpoint = env::var("RETH_ENDPOINT").expect("No db Endpoint in .env");
     let db_port = env::var("RETH_PORT").expect("No DB port.env");
     let url = format!("{db_endpoint}:{db_port}");
-    let tracer = Box::new(LocalProvider::new(url, 15)) as Box<dyn TracingProvider>;
+    let tracer = Box::new(RpcTracingProvider::new(url, 15)) as Box<dyn TracingProvider>;
 
     TraceParser::new(libmdbx, Arc::new(tracer), Arc::new(metrics_tx)).await
 }
@@ -0,0 +1,485 @@
+use std::{
+    fs::File,
+    io::{Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use alloy_primitives::Address;
+use arrow::{
+    array::{Array, ArrayRef, AsArray, StringArray},
+    compute::{cast, cast_with_options, CastOptions},
+    csv::{reader::Format, ReaderBuilder},
+    datatypes::{DataType, Field, Float64Type, Schema, TimeUnit, UInt64Type},
+    record_batch::RecordBatch,
+};
+use brontes_types::{
+    db::cex::{
+        quotes::RawCexQuotes,
+        trades::{RawCexTrades, TradeType},
+        CexExchange, CexSymbols,
+    },
+    pair::Pair,
+};
+use eyre::{eyre, WrapErr};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+use super::CexDataSource;
+
+/// Cex data stored in local parquet or csv files. The format is picked from
+/// the file extension. Columns are matched by name, so any column order and
+/// any extra columns are fine.
+///
+/// - symbols: `exchange`, `symbol_pair`, `base_address`, `quote_address`
+/// - trades: `exchange`, `symbol`, `timestamp`, `side`, `price`, `amount` and
+///   an optional `trade_type` (`Maker` or `Taker`, defaults to `Taker`)
+/// - quotes: `exchange`, `symbol`, `timestamp`, `ask_amount`, `ask_price`,
+///   `bid_price`, `bid_amount`
+///
+/// Timestamps are unix microseconds. Csv values are parsed when the columns
+/// are mapped, so a column can mix integer and float values. The files are
+/// read a batch at a time and only the rows within the requested time range
+/// are kept.
+#[derive(Debug, Clone)]
+pub struct LocalCexFiles {
+    pub symbols: PathBuf,
+    pub trades:  Option<PathBuf>,
+    pub quotes:  Option<PathBuf>,
+}
+
+impl LocalCexFiles {
+    pub fn new(symbols: PathBuf, trades: Option<PathBuf>, quotes: Option<PathBuf>) -> Self {
+        Self { symbols, trades, quotes }
+    }
+}
+
+impl CexDataSource for LocalCexFiles {
+    fn symbols(&self) -> eyre::Result<Vec<CexSymbols>> {
+        let mut symbols = Vec::new();
+        for batch in read_batches(&self.symbols)? {
+            symbols.extend(batch_symbols(&batch?)?);
+        }
+
+        Ok(symbols)
+    }
+
+    fn trades(&self, start: u64, end: u64) -> eyre::Result<Vec<RawCexTrades>> {
+        let Some(path) = &self.trades else { return Ok(vec![]) };
+
+        let mut trades = Vec::new();
+        for batch in read_batches(path)? {
+            trades.extend(batch_trades(&batch?, start, end)?);
+        }
+        trades.sort_by_key(|trade| trade.timestamp);
+
+        Ok(trades)
+    }
+
+    fn quotes(&self, start: u64, end: u64) -> eyre::Result<Vec<RawCexQuotes>> {
+        let Some(path) = &self.quotes else { return Ok(vec![]) };
+
+        let mut quotes = Vec::new();
+        for batch in read_batches(path)? {
+            quotes.extend(batch_quotes(&batch?, start, end)?);
+        }
+        quotes.sort_by_key(|quote| quote.timestamp);
+
+        Ok(quotes)
+    }
+
+    fn has_trades(&self) -> bool {
+        self.trades.is_some()
+    }
+
+    fn has_quotes(&self) -> bool {
+        self.quotes.is_some()
+    }
+}
+
+fn batch_symbols(batch: &RecordBatch) -> eyre::Result<Vec<CexSymbols>> {
+    let exchange = string_column(batch, "exchange")?;
+    let symbol = string_column(batch, "symbol_pair")?;
+    let base = string_column(batch, "base_address")?;
+    let quote = string_column(batch, "quote_address")?;
+
+    (0..batch.num_rows())
+        .map(|i| {
+            Ok(CexSymbols {
+                exchange:     CexExchange::from(exchange.value(i)),
+                symbol_pair:  symbol.value(i).to_string(),
+                address_pair: Pair(
+                    parse_address(base.value(i), "base_address")?,
+                    parse_address(quote.value(i), "quote_address")?,
+                ),
+            })
+        })
+        .collect()
+}
+
+fn batch_trades(batch: &RecordBatch, start: u64, end: u64) -> eyre::Result<Vec<RawCexTrades>> {
+    let timestamp = timestamp_column(batch)?;
+    let timestamp = timestamp.as_primitive::<UInt64Type>();
+    let exchange = string_column(batch, "exchange")?;
+    let symbol = string_column(batch, "symbol")?;
+    let side = string_column(batch, "side")?;
+    let price = float_column(batch, "price")?;
+    let price = price.as_primitive::<Float64Type>();
+    let amount = float_column(batch, "amount")?;
+    let amount = amount.as_primitive::<Float64Type>();
+    let trade_type = batch
+        .column_by_name("trade_type")
+        .map(|col| cast(col, &DataType::Utf8))
+        .transpose()?;
+    let trade_type = trade_type.as_ref().map(|col| col.as_string::<i32>());
+
+    let mut trades = Vec::new();
+    for i in 0..batch.num_rows() {
+        let ts = timestamp.value(i);
+        if ts < start || ts >= end {
+            continue
+        }
+
+        trades.push(RawCexTrades {
+            exchange:   CexExchange::from(exchange.value(i)),
+            trade_type: trade_type
+                .filter(|col| col.is_valid(i))
+                .map(|col| parse_trade_type(col.value(i)))
+                .transpose()?
+                .unwrap_or_default(),
+            symbol:     symbol.value(i).to_string(),
+            timestamp:  ts,
+            side:       side.value(i).to_string(),
+            price:      price.value(i),
+            amount:     amount.value(i),
+        });
+    }
+
+    Ok(trades)
+}
+
+fn batch_quotes(batch: &RecordBatch, start: u64, end: u64) -> eyre::Result<Vec<RawCexQuotes>> {
+    let timestamp = timestamp_column(batch)?;
+    let timestamp = timestamp.as_primitive::<UInt64Type>();
+    let exchange = string_column(batch, "exchange")?;
+    let symbol = string_column(batch, "symbol")?;
+    let [ask_amount, ask_price, bid_price, bid_amount] =
+        ["ask_amount", "ask_price", "bid_price", "bid_amount"]
+            .map(|name| float_column(batch, name));
+    let (ask_amount, ask_price, bid_price, bid_amount) =
+        (ask_amount?, ask_price?, bid_price?, bid_amount?);
+
+    let mut quotes = Vec::new();
+    for i in 0..batch.num_rows() {
+        let ts = timestamp.value(i);
+        if ts < start || ts >= end {
+            continue
+        }
+
+        quotes.push(RawCexQuotes {
+            exchange:   CexExchange::from(exchange.value(i)),
+            symbol:     symbol.value(i).to_string(),
+            timestamp:  ts,
+            ask_amount: ask_amount.as_primitive::<Float64Type>().value(i),
+            ask_price:  ask_price.as_primitive::<Float64Type>().value(i),
+            bid_price:  bid_price.as_primitive::<Float64Type>().value(i),
+            bid_amount: bid_amount.as_primitive::<Float64Type>().value(i),
+        });
+    }
+
+    Ok(quotes)
+}
+
+type Batches = Box<dyn Iterator<Item = eyre::Result<RecordBatch>>>;
+
+/// Reads the file a batch at a time.
+fn read_batches(path: &Path) -> eyre::Result<Batches> {
+    let mut file =
+        File::open(path).wrap_err_with(|| format!("failed to open {}", path.display()))?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("parquet") => Ok(Box::new(
+            ParquetRecordBatchReaderBuilder::try_new(file)?
+                .build()?
+                .map(|batch| batch.map_err(eyre::Report::from)),
+        )),
+        Some("csv") => {
+            // only the header is read here, every column is read as a string and
+            // parsed when it's mapped
+            let (header, _) = Format::default()
+                .with_header(true)
+                .infer_schema(&mut file, Some(0))?;
+            let schema = Schema::new(
+                header
+                    .fields()
+                    .iter()
+                    .map(|field| Field::new(field.name(), DataType::Utf8, true))
+                    .collect::<Vec<_>>(),
+            );
+            file.seek(SeekFrom::Start(0))?;
+
+            Ok(Box::new(
+                ReaderBuilder::new(Arc::new(schema))
+                    .with_header(true)
+                    .build(file)?
+                    .map(|batch| batch.map_err(eyre::Report::from)),
+            ))
+        }
+        _ => Err(eyre!("unsupported cex data file {}, expected .parquet or .csv", path.display())),
+    }
+}
+
+fn column<'a>(batch: &'a RecordBatch, name: &str) -> eyre::Result<&'a ArrayRef> {
+    batch
+        .column_by_name(name)
+        .ok_or_else(|| eyre!("missing column `{name}`"))
+}
+
+fn string_column<'a>(batch: &'a RecordBatch, name: &str) -> eyre::Result<&'a StringArray> {
+    column(batch, name)?
+        .as_string_opt::<i32>()
+        .ok_or_else(|| eyre!("column `{name}` is not a string column"))
+}
+
+/// Casts that fail on values that can't be converted instead of nulling them,
+/// so a malformed csv value is an error rather than a zero.
+fn cast_column(col: &ArrayRef, data_type: &DataType, name: &str) -> eyre::Result<ArrayRef> {
+    cast_with_options(col, data_type, &CastOptions { safe: false, ..Default::default() })
+        .wrap_err_with(|| format!("invalid value in column `{name}`"))
+}
+
+/// Numeric columns are cast so that both integer and float encodings are
+/// accepted.
+fn float_column(batch: &RecordBatch, name: &str) -> eyre::Result<ArrayRef> {
+    cast_column(column(batch, name)?, &DataType::Float64, name)
+}
+
+/// Arrow timestamp columns are normalized to microseconds, plain integer
+/// columns are taken as microseconds as is.
+fn timestamp_column(batch: &RecordBatch) -> eyre::Result<ArrayRef> {
+    let col = column(batch, "timestamp")?;
+    let col = match col.data_type() {
+        DataType::Timestamp(..) => {
+            let micros = cast(col, &DataType::Timestamp(TimeUnit::Microsecond, None))?;
+            cast(&micros, &DataType::Int64)?
+        }
+        _ => col.clone(),
+    };
+
+    cast_column(&col, &DataType::UInt64, "timestamp")
+}
+
+fn parse_address(value: &str, name: &str) -> eyre::Result<Address> {
+    value
+        .parse()
+        .map_err(|_| eyre!("invalid address `{value}` in column `{name}`"))
+}
+
+fn parse_trade_type(value: &str) -> eyre::Result<TradeType> {
+    match value.to_lowercase().as_str() {
+        "maker" => Ok(TradeType::Maker),
+        "taker" => Ok(TradeType::Taker),
+        _ => Err(eyre!("invalid trade type `{value}`, expected maker or taker")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use arrow::array::{Float64Array, TimestampMillisecondArray, UInt64Array};
+    use parquet::arrow::ArrowWriter;
+
+    use super::*;
+
+    const WETH: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+    const USDT: &str = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+
+    fn write_csv(dir: &Path, name: &str, contents: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn write_parquet(dir: &Path, name: &str, columns: Vec<(&str, ArrayRef)>) -> PathBuf {
+        let path = dir.join(name);
+        let batch = RecordBatch::try_from_iter(columns).unwrap();
+        let mut writer =
+            ArrowWriter::try_new(File::create(&path).unwrap(), batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        path
+    }
+
+    fn strings(values: &[&str]) -> ArrayRef {
+        Arc::new(StringArray::from(values.to_vec()))
+    }
+
+    fn floats(values: &[f64]) -> ArrayRef {
+        Arc::new(Float64Array::from(values.to_vec()))
+    }
+
+    fn integers(values: &[u64]) -> ArrayRef {
+        Arc::new(UInt64Array::from(values.to_vec()))
+    }
+
+    fn symbols_csv(dir: &Path) -> PathBuf {
+        write_csv(
+            dir,
+            "symbols.csv",
+            &format!(
+                "quote_address,symbol_pair,listed,exchange,base_address\n{USDT},ETHUSDT,2017,\
+                 binance,{WETH}\n"
+            ),
+        )
+    }
+
+    #[test]
+    fn test_csv_symbols() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = LocalCexFiles::new(symbols_csv(dir.path()), None, None);
+
+        assert_eq!(
+            files.symbols().unwrap(),
+            vec![CexSymbols {
+                exchange:     CexExchange::Binance,
+                symbol_pair:  "ETHUSDT".to_string(),
+                address_pair: Pair(WETH.parse().unwrap(), USDT.parse().unwrap()),
+            }]
+        );
+        assert!(!files.has_trades());
+        assert!(files.trades(0, u64::MAX).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_csv_trades() {
+        let dir = tempfile::tempdir().unwrap();
+        // columns in any order with extra columns, integer and float values mixed
+        // in a column and trade types that are missing or in any case
+        let trades = write_csv(
+            dir.path(),
+            "trades.csv",
+            "id,amount,price,symbol,side,timestamp,exchange,trade_type\n1,0.5,3000.5,ETHUSDT,buy,\
+             300,binance,Maker\n2,2,3001,ETHUSDT,sell,100,okex,\n3,1,2999,ETHUSDT,buy,200,binance,\
+             taker\n4,1,2999,ETHUSDT,buy,400,binance,maker\n",
+        );
+        let files = LocalCexFiles::new(symbols_csv(dir.path()), Some(trades), None);
+
+        let trade = |exchange, trade_type, timestamp, side: &str, price, amount| RawCexTrades {
+            exchange,
+            trade_type,
+            symbol: "ETHUSDT".to_string(),
+            timestamp,
+            side: side.to_string(),
+            price,
+            amount,
+        };
+        // the end of the range is exclusive and the trades are sorted
+        assert_eq!(
+            files.trades(100, 400).unwrap(),
+            vec![
+                trade(CexExchange::Okex, TradeType::Taker, 100, "sell", 3001.0, 2.0),
+                trade(CexExchange::Binance, TradeType::Taker, 200, "buy", 2999.0, 1.0),
+                trade(CexExchange::Binance, TradeType::Maker, 300, "buy", 3000.5, 0.5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_invalid_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let symbols = symbols_csv(dir.path());
+        let trades = |name: &str, contents: &str| {
+            LocalCexFiles::new(symbols.clone(), Some(write_csv(dir.path(), name, contents)), None)
+                .trades(0, u64::MAX)
+        };
+
+        let header = "exchange,symbol,timestamp,side,price,amount,trade_type";
+        assert!(trades("price.csv", &format!("{header}\nbinance,ETHUSDT,1,buy,abc,1,\n")).is_err());
+        assert!(trades("ts.csv", &format!("{header}\nbinance,ETHUSDT,-1,buy,1,1,\n")).is_err());
+        assert!(trades("type.csv", &format!("{header}\nbinance,ETHUSDT,1,buy,1,1,x\n")).is_err());
+        assert!(trades(
+            "missing.csv",
+            "exchange,symbol,timestamp,side,price\nbinance,ETHUSDT,1,buy,1\n"
+        )
+        .is_err());
+
+        let address = write_csv(
+            dir.path(),
+            "address.csv",
+            "exchange,symbol_pair,base_address,quote_address\nbinance,ETHUSDT,weth,usdt\n",
+        );
+        assert!(LocalCexFiles::new(address, None, None).symbols().is_err());
+    }
+
+    #[test]
+    fn test_parquet_quotes() {
+        let dir = tempfile::tempdir().unwrap();
+        // arrow timestamps are converted to microseconds and integer columns are
+        // read as floats
+        let quotes = write_parquet(
+            dir.path(),
+            "quotes.parquet",
+            vec![
+                ("bid_amount", integers(&[3, 4, 5])),
+                ("timestamp", Arc::new(TimestampMillisecondArray::from(vec![3, 1, 2])) as ArrayRef),
+                ("symbol", strings(&["ETHUSDT", "ETHUSDT", "ETHUSDT"])),
+                ("ask_price", floats(&[3000.5, 3001.5, 3002.5])),
+                ("exchange", strings(&["binance", "okex", "coinbase"])),
+                ("bid_price", floats(&[2999.5, 3000.5, 3001.5])),
+                ("ask_amount", floats(&[0.5, 1.5, 2.5])),
+            ],
+        );
+        let files = LocalCexFiles::new(symbols_csv(dir.path()), None, Some(quotes));
+        assert!(files.has_quotes());
+
+        let quote =
+            |exchange, timestamp, ask_amount, ask_price, bid_price, bid_amount| RawCexQuotes {
+                exchange,
+                symbol: "ETHUSDT".to_string(),
+                timestamp,
+                ask_amount,
+                ask_price,
+                bid_price,
+                bid_amount,
+            };
+        assert_eq!(
+            files.quotes(0, 3_000).unwrap(),
+            vec![
+                quote(CexExchange::Okex, 1_000, 1.5, 3001.5, 3000.5, 4.0),
+                quote(CexExchange::Coinbase, 2_000, 2.5, 3002.5, 3001.5, 5.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parquet_trades_without_trade_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let trades = write_parquet(
+            dir.path(),
+            "trades.parquet",
+            vec![
+                ("exchange", strings(&["binance"])),
+                ("symbol", strings(&["ETHUSDT"])),
+                ("timestamp", integers(&[10])),
+                ("side", strings(&["sell"])),
+                ("price", floats(&[3000.0])),
+                ("amount", floats(&[1.5])),
+            ],
+        );
+        let files = LocalCexFiles::new(symbols_csv(dir.path()), Some(trades), None);
+
+        let trades = files.trades(0, u64::MAX).unwrap();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].trade_type, TradeType::Taker);
+        assert_eq!(trades[0].timestamp, 10);
+        assert_eq!(trades[0].amount, 1.5);
+    }
+
+    #[test]
+    fn test_unsupported_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let symbols = write_csv(dir.path(), "symbols.json", "[]");
+
+        assert!(LocalCexFiles::new(symbols, None, None).symbols().is_err());
+    }
+}
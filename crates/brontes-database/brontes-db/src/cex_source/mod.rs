@@ -0,0 +1,255 @@
+//! Offline ingestion of cex data.
+//!
+//! The [`CexDataSource`] trait abstracts over where raw cex trades and quotes
+//! come from. Data from a source is run through the same converters that are
+//! used for the clickhouse download, so the resulting `CexPrice` and
+//! `CexTrades` tables are indistinguishable from the ones built from the
+//! Sorella db.
+
+use std::ops::RangeInclusive;
+
+use brontes_types::db::{
+    block_times::BlockTimes,
+    cex::{
+        quotes::{CexQuotesConverter, RawCexQuotes},
+        trades::{CexTradesConverter, RawCexTrades},
+        BestCexPerPair, CexSymbols,
+    },
+    initialized_state::{CEX_QUOTES_FLAG, CEX_TRADES_FLAG},
+};
+use eyre::eyre;
+use tracing::{info, warn};
+
+use crate::{
+    libmdbx::LibmdbxReadWriter, BlockInfo, CexPrice, CexPriceData, CexTrades, CexTradesData,
+};
+
+mod local;
+pub use local::LocalCexFiles;
+
+const SECONDS_TO_US: u64 = 1_000_000;
+/// Quotes are matched up to 301 seconds around the block time, trades up to 6
+/// seconds. We pad the requested range with the larger of the two windows.
+const TIME_PADDING_US: u64 = 301 * SECONDS_TO_US;
+/// Blocks imported at once, about 3 hours of cex data
+const IMPORT_CHUNK_BLOCKS: u64 = 1_000;
+
+/// A source of raw cex data. All timestamps are in microseconds.
+pub trait CexDataSource {
+    /// The mapping of exchange symbols to token pairs. Any trade or quote
+    /// without a matching symbol is dropped.
+    fn symbols(&self) -> eyre::Result<Vec<CexSymbols>>;
+
+    /// All trades with `start <= timestamp < end`, sorted by timestamp.
+    fn trades(&self, start: u64, end: u64) -> eyre::Result<Vec<RawCexTrades>>;
+
+    /// All quotes with `start <= timestamp < end`, sorted by timestamp.
+    fn quotes(&self, start: u64, end: u64) -> eyre::Result<Vec<RawCexQuotes>>;
+
+    /// The most liquid exchanges for each symbol, used to rank venues in the
+    /// `CexPrice` table. Sources without this data can leave it empty.
+    fn best_cex_per_pair(&self) -> eyre::Result<Vec<BestCexPerPair>> {
+        Ok(vec![])
+    }
+
+    /// Whether the source holds trades, the `CexTrades` table is left
+    /// untouched otherwise.
+    fn has_trades(&self) -> bool;
+
+    /// Whether the source holds quotes, the `CexPrice` table is left
+    /// untouched otherwise.
+    fn has_quotes(&self) -> bool;
+}
+
+/// Converts the data of `source` for the given block range and writes it to
+/// the `CexPrice` and `CexTrades` tables. Block times are taken from the
+/// `BlockInfo` table, so it must be initialized for the range.
+///
+/// The range is imported `IMPORT_CHUNK_BLOCKS` blocks at a time, each chunk
+/// is written before the data of the next one is read so that only a chunk of
+/// raw data is held in memory.
+pub fn import_cex_data<S: CexDataSource>(
+    db: &LibmdbxReadWriter,
+    source: &S,
+    range: RangeInclusive<u64>,
+) -> eyre::Result<()> {
+    let symbols = source.symbols()?;
+    info!(target: "brontes_db::cex_import", symbols = symbols.len(), "loaded symbol mapping");
+    let best_cex_per_pair = if source.has_quotes() { source.best_cex_per_pair()? } else { vec![] };
+
+    let mut imported = false;
+    for chunk_start in range.clone().step_by(IMPORT_CHUNK_BLOCKS as usize) {
+        let chunk = chunk_start..=(chunk_start + IMPORT_CHUNK_BLOCKS - 1).min(*range.end());
+        imported |= import_chunk(db, source, &symbols, &best_cex_per_pair, chunk)?;
+    }
+
+    if !imported {
+        return Err(eyre!("no block info found for blocks {:?}", range))
+    }
+
+    Ok(())
+}
+
+/// Imports the cex data of a chunk of blocks, returns false if none of the
+/// blocks have block info.
+fn import_chunk<S: CexDataSource>(
+    db: &LibmdbxReadWriter,
+    source: &S,
+    symbols: &[CexSymbols],
+    best_cex_per_pair: &[BestCexPerPair],
+    range: RangeInclusive<u64>,
+) -> eyre::Result<bool> {
+    let block_times = block_times(db, range.clone())?;
+    let (Some(first), Some(last)) = (block_times.first(), block_times.last()) else {
+        warn!(target: "brontes_db::cex_import", ?range, "no block info found, skipping blocks");
+        return Ok(false)
+    };
+    let start = first.timestamp.saturating_sub(TIME_PADDING_US);
+    let end = last.timestamp + TIME_PADDING_US;
+
+    if source.has_quotes() {
+        let quotes = source.quotes(start, end)?;
+        info!(
+            target: "brontes_db::cex_import",
+            ?range,
+            quotes = quotes.len(),
+            "converting cex quotes"
+        );
+
+        let prices = CexQuotesConverter::new(
+            block_times.clone(),
+            symbols.to_vec(),
+            quotes,
+            best_cex_per_pair.to_vec(),
+        )
+        .convert_to_prices()
+        .into_iter()
+        .map(|(block_num, price_map)| CexPriceData::new(block_num, price_map))
+        .collect::<Vec<_>>();
+
+        db.db.write_table::<CexPrice, CexPriceData>(&prices)?;
+        db.inited_range(range.clone(), CEX_QUOTES_FLAG)?;
+        info!(target: "brontes_db::cex_import", blocks = prices.len(), "wrote cex quotes");
+    }
+
+    if source.has_trades() {
+        let trades = source.trades(start, end)?;
+        info!(
+            target: "brontes_db::cex_import",
+            ?range,
+            trades = trades.len(),
+            "converting cex trades"
+        );
+
+        let trades = CexTradesConverter::new(block_times, symbols.to_vec(), trades)
+            .convert_to_trades()
+            .into_iter()
+            .map(|(block_num, trade_map)| CexTradesData::new(block_num, trade_map))
+            .collect::<Vec<_>>();
+
+        db.db.write_table::<CexTrades, CexTradesData>(&trades)?;
+        db.inited_range(range, CEX_TRADES_FLAG)?;
+        info!(target: "brontes_db::cex_import", blocks = trades.len(), "wrote cex trades");
+    }
+
+    Ok(true)
+}
+
+fn block_times(
+    db: &LibmdbxReadWriter,
+    range: RangeInclusive<u64>,
+) -> eyre::Result<Vec<BlockTimes>> {
+    db.db.view_db(|tx| {
+        let mut block_times = Vec::new();
+        for block_number in range {
+            if let Some(info) = tx.get::<BlockInfo>(block_number)? {
+                block_times.push(BlockTimes {
+                    block_number,
+                    timestamp: info.block_timestamp * SECONDS_TO_US,
+                });
+            }
+        }
+
+        Ok(block_times)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use brontes_types::db::metadata::BlockMetadataInner;
+
+    use super::*;
+    use crate::BlockInfoData;
+
+    const GENESIS: u64 = 1_700_000_000;
+
+    /// Records the windows of trades that are requested
+    #[derive(Default)]
+    struct WindowSource {
+        windows: Mutex<Vec<(u64, u64)>>,
+    }
+
+    impl CexDataSource for WindowSource {
+        fn symbols(&self) -> eyre::Result<Vec<CexSymbols>> {
+            Ok(vec![])
+        }
+
+        fn trades(&self, start: u64, end: u64) -> eyre::Result<Vec<RawCexTrades>> {
+            self.windows.lock().unwrap().push((start, end));
+            Ok(vec![])
+        }
+
+        fn quotes(&self, _: u64, _: u64) -> eyre::Result<Vec<RawCexQuotes>> {
+            unreachable!("the source has no quotes")
+        }
+
+        fn has_trades(&self) -> bool {
+            true
+        }
+
+        fn has_quotes(&self) -> bool {
+            false
+        }
+    }
+
+    fn block_time_us(block: u64) -> u64 {
+        (GENESIS + block * 12) * SECONDS_TO_US
+    }
+
+    #[test]
+    fn test_import_in_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = LibmdbxReadWriter::init_db_tests(dir.path()).unwrap();
+
+        // the second chunk has no block info
+        let info = (0..2_500)
+            .filter(|block| !(1_000..2_000).contains(block))
+            .map(|block| {
+                BlockInfoData::new(
+                    block,
+                    BlockMetadataInner {
+                        block_timestamp: GENESIS + block * 12,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        db.db
+            .write_table::<BlockInfo, BlockInfoData>(&info)
+            .unwrap();
+
+        let source = WindowSource::default();
+        import_cex_data(&db, &source, 0..=2_499).unwrap();
+        assert_eq!(
+            *source.windows.lock().unwrap(),
+            vec![
+                (block_time_us(0) - TIME_PADDING_US, block_time_us(999) + TIME_PADDING_US),
+                (block_time_us(2_000) - TIME_PADDING_US, block_time_us(2_499) + TIME_PADDING_US),
+            ]
+        );
+
+        assert!(import_cex_data(&db, &source, 1_000..=1_999).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fmt::Debug, str::FromStr};
+use std::{fmt::Debug, ops::RangeInclusive, str::FromStr};
 
 use ::clickhouse::DbRow;
 use alloy_primitives::Address;
@@ -61,6 +61,25 @@ use crate::{
 const SECONDS_TO_US: f64 = 1_000_000.0;
 const MAX_MARKOUT_TIME: f64 = 300.0;
 
+/// Tables holding the per block results, these are rolled back on a reorg
+const BLOCK_RESULT_TABLES: [&str; 15] = [
+    "mev.mev_blocks",
+    "mev.bundle_header",
+    "mev.searcher_tx",
+    "mev.cex_dex_quotes",
+    "mev.cex_dex",
+    "mev.liquidations",
+    "mev.jit_sandwich",
+    "mev.jit",
+    "mev.sandwiches",
+    "mev.atomic_arbs",
+    "mev.long_tail",
+    "mev.frontrun",
+    "brontes.dex_price_mapping",
+    "brontes.tree",
+    "brontes.block_analysis",
+];
+
 #[derive(Clone)]
 pub struct Clickhouse {
     pub tip:                 bool,
@@ -183,6 +202,8 @@ impl Clickhouse {
                         tx.send(vec![(s, self.tip, self.run_id).into()])?
                     }
                     BundleData::Unknown(s) => tx.send(vec![(s, self.tip, self.run_id).into()])?,
+                    BundleData::LongTail(s) => tx.send(vec![(s, self.tip, self.run_id).into()])?,
+                    BundleData::Frontrun(s) => tx.send(vec![(s, self.tip, self.run_id).into()])?,
                 };
 
                 Ok(()) as eyre::Result<()>
@@ -192,6 +213,23 @@ impl Clickhouse {
         Ok(())
     }
 
+    /// Deletes the results of the given blocks. At tip the inserts skip the
+    /// buffer, so all rows of the blocks are written by the time a reorg is
+    /// detected
+    pub async fn rollback_blocks(&self, blocks: RangeInclusive<u64>) -> eyre::Result<()> {
+        for table in BLOCK_RESULT_TABLES {
+            let query = format!(
+                "ALTER TABLE {table} ON CLUSTER eth_cluster0 DELETE WHERE block_number >= {} AND \
+                 block_number <= {}",
+                blocks.start(),
+                blocks.end()
+            );
+            self.client.execute_remote(&query, &()).await?;
+        }
+
+        Ok(())
+    }
+
     pub async fn write_dex_quotes(
         &self,
         block_num: u64,
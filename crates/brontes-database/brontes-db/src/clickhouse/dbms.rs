@@ -24,6 +24,8 @@ clickhouse_dbms!(
         MevJit,
         MevSandwiches,
         MevAtomic_Arbs,
+        MevLong_Tail,
+        MevFrontrun,
         BrontesToken_Info,
         EthereumPools,
         BrontesTree,
@@ -125,6 +127,20 @@ remote_clickhouse_table!(
     "crates/brontes-database/brontes-db/src/clickhouse/tables/"
 );
 
+remote_clickhouse_table!(
+    BrontesClickhouseTables,
+    [Mev, Long_Tail],
+    DbDataWithRunId<LongTail>,
+    "crates/brontes-database/brontes-db/src/clickhouse/tables/"
+);
+
+remote_clickhouse_table!(
+    BrontesClickhouseTables,
+    [Mev, Frontrun],
+    DbDataWithRunId<Frontrun>,
+    "crates/brontes-database/brontes-db/src/clickhouse/tables/"
+);
+
 remote_clickhouse_table!(
     BrontesClickhouseTables,
     [Brontes, Token_Info],
@@ -240,6 +256,8 @@ db_types!(
     (JitLiquidity, MevJit, true),
     (Sandwich, MevSandwiches, true),
     (AtomicArb, MevAtomic_Arbs, true),
+    (LongTail, MevLong_Tail, true),
+    (Frontrun, MevFrontrun, true),
     (TokenInfoWithAddress, BrontesToken_Info, false),
     (ProtocolInfoClickhouse, EthereumPools, false),
     (TransactionRoot, BrontesTree, true),
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{ops::RangeInclusive, sync::Arc};
 
 use alloy_primitives::Address;
 use brontes_types::{
@@ -6,17 +6,18 @@ use brontes_types::{
         address_metadata::AddressMetadata,
         address_to_protocol_info::ProtocolInfo,
         block_analysis::BlockAnalysis,
-        builder::BuilderInfo,
+        builder::{BuilderInfo, ProposerPaymentStats},
+        cex::{quotes::CexPriceMap, trades::CexTradeMap},
         dex::DexQuotes,
-        metadata::Metadata,
+        metadata::{BlockMetadataInner, Metadata},
         mev_block::MevBlockWithClassified,
-        searcher::SearcherInfo,
+        relay_bids::BlockRelayBids,
+        searcher::{FailedAttemptStats, SearcherInfo},
         token_info::TokenInfoWithAddress,
-        traits::{DBWriter, LibmdbxReader, ProtocolCreatedRange},
+        traits::{DBWriter, LibmdbxReader, ProtocolCreatedBefore, ProtocolCreatedRange},
     },
-    mev::{Bundle, MevBlock},
+    mev::{Bundle, FailedAttempt, MevBlock},
     normalized_actions::Action,
-    pair::Pair,
     structured_trace::TxTrace,
     traits::TracingProvider,
     BlockTree, FastHashMap, Protocol,
@@ -166,6 +167,12 @@ impl<I: DBWriter + Send + Sync> DBWriter for ClickhouseMiddleware<I> {
 
         self.inner().save_traces(block, traces).await
     }
+
+    async fn rollback_blocks(&self, blocks: RangeInclusive<u64>) -> eyre::Result<()> {
+        self.client.rollback_blocks(blocks.clone()).await?;
+
+        self.inner().rollback_blocks(blocks).await
+    }
 }
 
 impl<I: LibmdbxInit> LibmdbxInit for ClickhouseMiddleware<I> {
@@ -317,6 +324,33 @@ impl<I: LibmdbxInit> LibmdbxReader for ClickhouseMiddleware<I> {
         self.inner.fetch_all_builder_info()
     }
 
+    fn try_fetch_searcher_failed_attempts(
+        &self,
+        searcher: Address,
+    ) -> eyre::Result<Option<FailedAttemptStats>> {
+        self.inner.try_fetch_searcher_failed_attempts(searcher)
+    }
+
+    fn fetch_all_searcher_failed_attempts(
+        &self,
+    ) -> eyre::Result<Vec<(Address, FailedAttemptStats)>> {
+        self.inner.fetch_all_searcher_failed_attempts()
+    }
+
+    fn try_fetch_builder_proposer_payments(
+        &self,
+        builder_coinbase_addr: Address,
+    ) -> eyre::Result<Option<ProposerPaymentStats>> {
+        self.inner
+            .try_fetch_builder_proposer_payments(builder_coinbase_addr)
+    }
+
+    fn fetch_all_builder_proposer_payments(
+        &self,
+    ) -> eyre::Result<Vec<(Address, ProposerPaymentStats)>> {
+        self.inner.fetch_all_builder_proposer_payments()
+    }
+
     //TODO: JOE
     fn try_fetch_mev_blocks(
         &self,
@@ -326,6 +360,18 @@ impl<I: LibmdbxInit> LibmdbxReader for ClickhouseMiddleware<I> {
         todo!("Joe");
     }
 
+    fn try_fetch_mev_block(&self, block_num: u64) -> eyre::Result<Option<MevBlockWithClassified>> {
+        self.inner.try_fetch_mev_block(block_num)
+    }
+
+    fn fetch_mev_block_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<MevBlockWithClassified>> {
+        self.inner.fetch_mev_block_range(start_block, end_block)
+    }
+
     fn fetch_all_mev_blocks(
         &self,
         _start_block: Option<u64>,
@@ -363,10 +409,7 @@ impl<I: LibmdbxInit> LibmdbxReader for ClickhouseMiddleware<I> {
         self.inner.try_fetch_token_info(address)
     }
 
-    fn protocols_created_before(
-        &self,
-        start_block: u64,
-    ) -> eyre::Result<FastHashMap<(Address, Protocol), Pair>> {
+    fn protocols_created_before(&self, start_block: u64) -> eyre::Result<ProtocolCreatedBefore> {
         self.inner.protocols_created_before(start_block)
     }
 
@@ -385,6 +428,26 @@ impl<I: LibmdbxInit> LibmdbxReader for ClickhouseMiddleware<I> {
     fn load_trace(&self, block_num: u64) -> eyre::Result<Vec<TxTrace>> {
         self.inner.load_trace(block_num)
     }
+
+    fn try_fetch_block_info(&self, block_num: u64) -> eyre::Result<Option<BlockMetadataInner>> {
+        self.inner.try_fetch_block_info(block_num)
+    }
+
+    fn try_fetch_cex_quotes(&self, block_num: u64) -> eyre::Result<Option<CexPriceMap>> {
+        self.inner.try_fetch_cex_quotes(block_num)
+    }
+
+    fn try_fetch_block_cex_trades(&self, block_num: u64) -> eyre::Result<Option<CexTradeMap>> {
+        self.inner.try_fetch_block_cex_trades(block_num)
+    }
+
+    fn try_fetch_traces(&self, block_num: u64) -> eyre::Result<Option<Vec<TxTrace>>> {
+        self.inner.try_fetch_traces(block_num)
+    }
+
+    fn try_fetch_relay_bids(&self, block_num: u64) -> eyre::Result<Option<BlockRelayBids>> {
+        self.inner.try_fetch_relay_bids(block_num)
+    }
 }
 
 pub struct ReadOnlyMiddleware<I: DBWriter> {
@@ -494,6 +557,30 @@ impl<I: DBWriter + Send + Sync> DBWriter for ReadOnlyMiddleware<I> {
     async fn save_traces(&self, block: u64, traces: Vec<TxTrace>) -> eyre::Result<()> {
         self.client.save_traces(block, traces.clone()).await
     }
+
+    /// nothing is written to libmdbx in read only mode
+    async fn write_searcher_failed_attempts(
+        &self,
+        _block_number: u64,
+        _attempts: Vec<FailedAttempt>,
+    ) -> eyre::Result<()> {
+        Ok(())
+    }
+
+    /// nothing is written to libmdbx in read only mode
+    async fn write_builder_proposer_payment(
+        &self,
+        _builder_address: Address,
+        _payment_delta: i128,
+    ) -> eyre::Result<()> {
+        Ok(())
+    }
+
+    /// only the clickhouse rows are rolled back as nothing is written to
+    /// libmdbx in read only mode
+    async fn rollback_blocks(&self, blocks: RangeInclusive<u64>) -> eyre::Result<()> {
+        self.client.rollback_blocks(blocks).await
+    }
 }
 
 impl<I: LibmdbxInit> LibmdbxInit for ReadOnlyMiddleware<I> {
@@ -639,6 +726,33 @@ impl<I: LibmdbxInit> LibmdbxReader for ReadOnlyMiddleware<I> {
         self.inner.fetch_all_builder_info()
     }
 
+    fn try_fetch_searcher_failed_attempts(
+        &self,
+        searcher: Address,
+    ) -> eyre::Result<Option<FailedAttemptStats>> {
+        self.inner.try_fetch_searcher_failed_attempts(searcher)
+    }
+
+    fn fetch_all_searcher_failed_attempts(
+        &self,
+    ) -> eyre::Result<Vec<(Address, FailedAttemptStats)>> {
+        self.inner.fetch_all_searcher_failed_attempts()
+    }
+
+    fn try_fetch_builder_proposer_payments(
+        &self,
+        builder_coinbase_addr: Address,
+    ) -> eyre::Result<Option<ProposerPaymentStats>> {
+        self.inner
+            .try_fetch_builder_proposer_payments(builder_coinbase_addr)
+    }
+
+    fn fetch_all_builder_proposer_payments(
+        &self,
+    ) -> eyre::Result<Vec<(Address, ProposerPaymentStats)>> {
+        self.inner.fetch_all_builder_proposer_payments()
+    }
+
     //TODO: JOE
     fn try_fetch_mev_blocks(
         &self,
@@ -648,6 +762,18 @@ impl<I: LibmdbxInit> LibmdbxReader for ReadOnlyMiddleware<I> {
         todo!("Joe");
     }
 
+    fn try_fetch_mev_block(&self, block_num: u64) -> eyre::Result<Option<MevBlockWithClassified>> {
+        self.inner.try_fetch_mev_block(block_num)
+    }
+
+    fn fetch_mev_block_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<MevBlockWithClassified>> {
+        self.inner.fetch_mev_block_range(start_block, end_block)
+    }
+
     fn fetch_all_mev_blocks(
         &self,
         _start_block: Option<u64>,
@@ -685,10 +811,7 @@ impl<I: LibmdbxInit> LibmdbxReader for ReadOnlyMiddleware<I> {
         self.inner.try_fetch_token_info(address)
     }
 
-    fn protocols_created_before(
-        &self,
-        start_block: u64,
-    ) -> eyre::Result<FastHashMap<(Address, Protocol), Pair>> {
+    fn protocols_created_before(&self, start_block: u64) -> eyre::Result<ProtocolCreatedBefore> {
         self.inner.protocols_created_before(start_block)
     }
 
@@ -707,4 +830,24 @@ impl<I: LibmdbxInit> LibmdbxReader for ReadOnlyMiddleware<I> {
     fn load_trace(&self, block_num: u64) -> eyre::Result<Vec<TxTrace>> {
         self.inner.load_trace(block_num)
     }
+
+    fn try_fetch_block_info(&self, block_num: u64) -> eyre::Result<Option<BlockMetadataInner>> {
+        self.inner.try_fetch_block_info(block_num)
+    }
+
+    fn try_fetch_cex_quotes(&self, block_num: u64) -> eyre::Result<Option<CexPriceMap>> {
+        self.inner.try_fetch_cex_quotes(block_num)
+    }
+
+    fn try_fetch_block_cex_trades(&self, block_num: u64) -> eyre::Result<Option<CexTradeMap>> {
+        self.inner.try_fetch_block_cex_trades(block_num)
+    }
+
+    fn try_fetch_traces(&self, block_num: u64) -> eyre::Result<Option<Vec<TxTrace>>> {
+        self.inner.try_fetch_traces(block_num)
+    }
+
+    fn try_fetch_relay_bids(&self, block_num: u64) -> eyre::Result<Option<BlockRelayBids>> {
+        self.inner.try_fetch_relay_bids(block_num)
+    }
 }
@@ -115,6 +115,8 @@ impl ClickhouseBuffered {
             (MevSandwiches, Sandwich),
             (MevAtomic_Arbs, AtomicArb),
             (MevLiquidations, Liquidation),
+            (MevLong_Tail, LongTail),
+            (MevFrontrun, Frontrun),
             (BrontesDex_Price_Mapping, DexQuotesWithBlockNumber),
             (BrontesToken_Info, TokenInfoWithAddress),
             (EthereumPools, ProtocolInfoClickhouse),
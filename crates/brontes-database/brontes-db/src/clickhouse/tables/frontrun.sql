@@ -0,0 +1,35 @@
+CREATE TABLE mev.frontrun ON CLUSTER eth_cluster0
+(
+    `block_number` UInt64,
+    `frontrun_tx_hash` String,
+    `frontrun_swaps` Nested(
+        `trace_idx` UInt64,
+        `from` String,
+        `recipient` String,
+        `pool` String,
+        `token_in` Tuple(String, String),
+        `token_out` Tuple(String, String),
+        `amount_in` Tuple(UInt256, UInt256),
+        `amount_out` Tuple(UInt256, UInt256)
+    ),
+    `frontrun_gas_details` Tuple(
+        `coinbase_transfer` Nullable(UInt128),
+        `priority_fee` UInt128,
+        `gas_used` UInt128,
+        `effective_gas_price` UInt128
+    ),
+    `victim_tx_hashes` Array(String),
+    `victim_reverted` Array(Bool),
+    `victim_gas_details` Nested(
+        `tx_hash` String,
+        `coinbase_transfer` Nullable(UInt128),
+        `priority_fee` UInt128,
+        `gas_used` UInt128,
+        `effective_gas_price` UInt128
+    ),
+    `priority_fee_outbids` Array(Int128),
+    `run_id` UInt64
+)
+ENGINE = ReplicatedReplacingMergeTree('/clickhouse/eth_cluster0/tables/all/mev/frontrun', '{replica}', `run_id`)
+PRIMARY KEY (`block_number`, `frontrun_tx_hash`)
+ORDER BY (`block_number`, `frontrun_tx_hash`)
@@ -0,0 +1,46 @@
+CREATE TABLE mev.long_tail ON CLUSTER eth_cluster0
+(
+    `block_number` UInt64,
+    `pool` String,
+    `token` String,
+    `launch_block` UInt64,
+    `entry_swaps` Nested(
+        `tx_hash` String,
+        `trace_idx` UInt64,
+        `from` String,
+        `recipient` String,
+        `pool` String,
+        `token_in` Tuple(String, String),
+        `token_out` Tuple(String, String),
+        `amount_in` Tuple(UInt256, UInt256),
+        `amount_out` Tuple(UInt256, UInt256)
+    ),
+    `entry_gas_details` Nested(
+        `tx_hash` String,
+        `coinbase_transfer` Nullable(UInt128),
+        `priority_fee` UInt128,
+        `gas_used` UInt128,
+        `effective_gas_price` UInt128
+    ),
+    `exit_tx_hash` String,
+    `exit_swaps` Nested(
+        `trace_idx` UInt64,
+        `from` String,
+        `recipient` String,
+        `pool` String,
+        `token_in` Tuple(String, String),
+        `token_out` Tuple(String, String),
+        `amount_in` Tuple(UInt256, UInt256),
+        `amount_out` Tuple(UInt256, UInt256)
+    ),
+    `exit_gas_details` Tuple(
+        `coinbase_transfer` Nullable(UInt128),
+        `priority_fee` UInt128,
+        `gas_used` UInt128,
+        `effective_gas_price` UInt128
+    ),
+    `run_id` UInt64
+)
+ENGINE = ReplicatedReplacingMergeTree('/clickhouse/eth_cluster0/tables/all/mev/long_tail', '{replica}', `run_id`)
+PRIMARY KEY (`block_number`, `exit_tx_hash`)
+ORDER BY (`block_number`, `exit_tx_hash`)
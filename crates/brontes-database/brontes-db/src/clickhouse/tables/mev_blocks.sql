@@ -31,6 +31,8 @@ CREATE TABLE mev.mev_blocks ON CLUSTER eth_cluster0
     `proposer_fee_recipient` Nullable(String),
     `proposer_mev_reward` Nullable(UInt128),
     `proposer_profit_usd` Nullable(Float64),
+    `payment_verified` Nullable(Bool),
+    `payment_delta` Nullable(Int128),
     `total_mev_profit_usd` Float64,
     `possible_mev` Nested (
         `tx_hash` String,
@@ -43,6 +45,31 @@ CREATE TABLE mev.mev_blocks ON CLUSTER eth_cluster0
         `triggers.coinbase_transfer` Bool,
         `triggers.high_priority_fee` Bool
     ),
+    `failed_attempts` Nested (
+        `tx_hash` String,
+        `tx_idx` UInt64,
+        `eoa` String,
+        `mev_contract` String,
+        `reverted` Bool,
+        `gas_details.priority_fee` UInt128,
+        `gas_details.gas_used` UInt128,
+        `gas_details.effective_gas_price` UInt128,
+        `gas_paid_usd` Float64
+    ),
+    `bid_analysis` Nested (
+        `slot` UInt64,
+        `bid_count` UInt64,
+        `builder_count` UInt64,
+        `winning_builder_pubkey` String,
+        `winning_bid` UInt128,
+        `winning_bid_time_ms` Int64,
+        `runner_up_builder_pubkey` Nullable(String),
+        `runner_up_bid` Nullable(UInt128),
+        `winning_margin` Nullable(Int128),
+        `bid_shading` Nullable(Float64),
+        `proposer_delay_ms` Int64,
+        `bid_curve` Array(Tuple(Int64, UInt128))
+    ),
     `run_id` UInt64
 ) 
 ENGINE = ReplicatedReplacingMergeTree('/clickhouse/eth_cluster0/tables/all/mev/mev_blocks', '{replica}', `run_id`)
@@ -29,7 +29,9 @@ CREATE TABLE mev.sandwiches ON CLUSTER eth_cluster0
         `token_in` Tuple(String, String),
         `token_out` Tuple(String, String),
         `amount_in` Tuple(UInt256, UInt256),
-        `amount_out` Tuple(UInt256, UInt256)
+        `amount_out` Tuple(UInt256, UInt256),
+        `counterfactual_amount_out` Float64,
+        `loss_usd` Float64
     ),
     `victim_gas_details` Nested(
         `tx_hash` String,
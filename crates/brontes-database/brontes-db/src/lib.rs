@@ -3,9 +3,11 @@
 #![feature(const_trait_impl)]
 #![feature(noop_waker)]
 
+pub mod cex_source;
 pub mod clickhouse;
 pub mod libmdbx;
 pub mod parquet;
+pub mod relay_bids;
 pub use libmdbx::{
     tables::*,
     types::{CompressedTable, IntoTableKey},
@@ -33,7 +33,10 @@ pub fn merge_libmdbx_dbs(
             Builder,
             AddressToProtocolInfo,
             TokenDecimals,
-            DexPrice
+            DexPrice,
+            RelayBids,
+            BuilderProposerPayments,
+            SearcherFailedAttempts
             );
 
             eyre::Ok(())
@@ -107,7 +107,8 @@ impl LibmdbxPartitioner {
                     MevBlocks,
                     InitializedState,
                     PoolCreationBlocks,
-                    TxTraces
+                    TxTraces,
+                    RelayBids
                 );
                 // manually dex pricing
                 self.parent_db
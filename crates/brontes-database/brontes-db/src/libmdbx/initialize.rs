@@ -11,7 +11,7 @@ use brontes_types::{
     },
     traits::TracingProvider,
     unordered_buffer_map::BrontesStreamExt,
-    FastHashMap, Protocol,
+    ChainConfig, FastHashMap, Protocol,
 };
 use futures::{join, stream::iter, Future, StreamExt};
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressState, ProgressStyle};
@@ -343,11 +343,14 @@ impl<TP: TracingProvider, CH: ClickhouseHandle> LibmdbxInitializer<TP, CH> {
         Some(progress_bar)
     }
 
-    /// loads up the `classifier_config.toml` and ensures the values are in the
-    /// database
+    /// loads up the `classifier_config.toml`, or the classifier config of the
+    /// chain config if set, and ensures the values are in the database
     async fn load_classifier_config_data(&self) {
         let mut workspace_dir = workspace_dir();
-        workspace_dir.push(CLASSIFIER_CONFIG_FILE);
+        match &ChainConfig::get().classifier_config {
+            Some(config) => workspace_dir.push(config),
+            None => workspace_dir.push(CLASSIFIER_CONFIG_FILE),
+        }
 
         let Ok(config) = toml::from_str::<tomlTable>(&{
             let Ok(path) = std::fs::read_to_string(workspace_dir) else {
@@ -379,10 +382,10 @@ impl<TP: TracingProvider, CH: ClickhouseHandle> LibmdbxInitializer<TP, CH> {
                         .unwrap();
                 }
 
-                let token_addrs = if table.len() < 2 {
-                    [Address::default(), Address::default()]
-                } else {
-                    [table[0].address, table[1].address]
+                let token_addrs = match table.as_slice() {
+                    [] => [Address::default(), Address::default()],
+                    [token0] => [token0.address, Address::default()],
+                    [token0, token1, ..] => [token0.address, token1.address],
                 };
 
                 self.libmdbx
@@ -4,11 +4,11 @@ use alloy_primitives::Address;
 use brontes_metrics::db_reads::LibmdbxMetrics;
 use brontes_pricing::Protocol;
 use brontes_types::{
-    constants::{ETH_ADDRESS, WETH_ADDRESS},
+    constants::ETH_ADDRESS,
     db::{
         address_metadata::AddressMetadata,
         address_to_protocol_info::ProtocolInfo,
-        builder::BuilderInfo,
+        builder::{BuilderInfo, ProposerPaymentStats},
         cex::{quotes::CexPriceMap, trades::CexTradeMap},
         dex::{make_filter_key_range, DexPrices, DexQuotes},
         initialized_state::{
@@ -17,16 +17,17 @@ use brontes_types::{
         },
         metadata::{BlockMetadata, BlockMetadataInner, Metadata},
         mev_block::MevBlockWithClassified,
-        searcher::SearcherInfo,
+        relay_bids::BlockRelayBids,
+        searcher::{FailedAttemptStats, SearcherInfo},
         token_info::{TokenInfo, TokenInfoWithAddress},
-        traits::{DBWriter, LibmdbxReader},
+        traits::{DBWriter, LibmdbxReader, ProtocolCreatedBefore, ProtocolCreatedRange},
     },
-    mev::{Bundle, MevBlock},
+    mev::{Bundle, FailedAttempt, MevBlock},
     normalized_actions::Action,
     pair::Pair,
     structured_trace::TxTrace,
     traits::TracingProvider,
-    BlockTree, BrontesTaskExecutor, FastHashMap, UnboundedYapperReceiver,
+    BlockTree, BrontesTaskExecutor, ChainConfig, FastHashMap, FastHashSet, UnboundedYapperReceiver,
 };
 use eyre::{eyre, ErrReport};
 use futures::Future;
@@ -35,7 +36,10 @@ use itertools::Itertools;
 use malachite::Rational;
 use reth_db::table::{Compress, Encode};
 use reth_interfaces::db::LogLevel;
-use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedSender},
+    Notify,
+};
 use tracing::{info, instrument};
 
 use super::{
@@ -94,11 +98,82 @@ pub trait LibmdbxInit: LibmdbxReader + DBWriter {
 
 #[derive(Clone)]
 pub struct LibmdbxReadWriter {
-    pub db:  Arc<Libmdbx>,
-    pub tx:  UnboundedSender<StampedWriterMessage>,
-    metrics: Option<LibmdbxMetrics>,
+    pub db:          Arc<Libmdbx>,
+    pub tx:          UnboundedSender<StampedWriterMessage>,
+    metrics:         Option<LibmdbxMetrics>,
     // 100 shards for now, might change in future
-    cache:   ReadWriteCache,
+    cache:           ReadWriteCache,
+    searcher_labels: Arc<SearcherLabels>,
+}
+
+/// Searcher labels that are set for a run, e.g from the inspector config. They
+/// are applied on top of the stored searcher info when it is read and are never
+/// written to the db.
+#[derive(Debug, Clone, Default)]
+pub struct SearcherLabels {
+    pub eoas:      FastHashMap<Address, SearcherInfo>,
+    pub contracts: FastHashMap<Address, SearcherInfo>,
+}
+
+impl SearcherLabels {
+    fn apply(
+        labels: &FastHashMap<Address, SearcherInfo>,
+        address: Address,
+        stored: Option<SearcherInfo>,
+    ) -> Option<SearcherInfo> {
+        let Some(label) = labels.get(&address) else { return stored };
+        let mut info = stored.unwrap_or_default();
+        info.merge(label.clone());
+
+        Some(info)
+    }
+
+    fn apply_all(
+        labels: &FastHashMap<Address, SearcherInfo>,
+        stored: Vec<(Address, SearcherInfo)>,
+    ) -> Vec<(Address, SearcherInfo)> {
+        let mut seen = FastHashSet::default();
+        let mut all = stored
+            .into_iter()
+            .filter_map(|(address, info)| {
+                seen.insert(address);
+                Some((address, Self::apply(labels, address, Some(info))?))
+            })
+            .collect_vec();
+
+        all.extend(
+            labels
+                .iter()
+                .filter(|(address, _)| !seen.contains(*address))
+                .map(|(address, label)| (*address, label.clone())),
+        );
+
+        all
+    }
+
+    /// Puts the stored values back for the fields a label sets, so that
+    /// labelled searcher info that was read and updated is written without
+    /// the label.
+    fn strip(
+        labels: &FastHashMap<Address, SearcherInfo>,
+        address: Address,
+        info: SearcherInfo,
+        stored: impl FnOnce() -> eyre::Result<Option<SearcherInfo>>,
+    ) -> eyre::Result<SearcherInfo> {
+        if !labels.contains_key(&address) {
+            return Ok(info)
+        }
+        let stored = stored()?.unwrap_or_default();
+
+        Ok(SearcherInfo {
+            name: stored.name,
+            fund: stored.fund,
+            builder: stored.builder,
+            config_labels: stored.config_labels,
+            sibling_searchers: stored.sibling_searchers,
+            ..info
+        })
+    }
 }
 
 impl LibmdbxReadWriter {
@@ -124,9 +199,16 @@ impl LibmdbxReadWriter {
             tx,
             metrics: metrics.then(LibmdbxMetrics::default),
             cache: ReadWriteCache::new(memory_per_table_mb, metrics),
+            searcher_labels: Arc::default(),
         })
     }
 
+    /// Applies the searcher labels to all searcher info that is read
+    pub fn with_searcher_labels(mut self, searcher_labels: SearcherLabels) -> Self {
+        self.searcher_labels = Arc::new(searcher_labels);
+        self
+    }
+
     pub fn init_db_tests<P: AsRef<Path>>(path: P) -> eyre::Result<Self> {
         // 5 gb total
         let memory_per_table_mb = 1_000;
@@ -138,7 +220,13 @@ impl LibmdbxReadWriter {
         let writer = LibmdbxWriter::new(db.clone(), yapper, false);
         writer.run_no_shutdown();
 
-        Ok(Self { db, tx, metrics: None, cache: ReadWriteCache::new(memory_per_table_mb, false) })
+        Ok(Self {
+            db,
+            tx,
+            metrics: None,
+            cache: ReadWriteCache::new(memory_per_table_mb, false),
+            searcher_labels: Arc::default(),
+        })
     }
 }
 
@@ -437,6 +525,27 @@ impl LibmdbxReader for LibmdbxReadWriter {
         })
     }
 
+    fn try_fetch_block_info(&self, block_num: u64) -> eyre::Result<Option<BlockMetadataInner>> {
+        self.db.view_db(|tx| Ok(tx.get::<BlockInfo>(block_num)?))
+    }
+
+    fn try_fetch_cex_quotes(&self, block_num: u64) -> eyre::Result<Option<CexPriceMap>> {
+        self.db.view_db(|tx| Ok(tx.get::<CexPrice>(block_num)?))
+    }
+
+    fn try_fetch_block_cex_trades(&self, block_num: u64) -> eyre::Result<Option<CexTradeMap>> {
+        self.db.view_db(|tx| Ok(tx.get::<CexTrades>(block_num)?))
+    }
+
+    fn try_fetch_traces(&self, block_num: u64) -> eyre::Result<Option<Vec<TxTrace>>> {
+        self.db
+            .view_db(|tx| Ok(tx.get::<TxTraces>(block_num)?.and_then(|i| i.traces)))
+    }
+
+    fn try_fetch_relay_bids(&self, block_num: u64) -> eyre::Result<Option<BlockRelayBids>> {
+        self.db.view_db(|tx| Ok(tx.get::<RelayBids>(block_num)?))
+    }
+
     #[brontes_macros::metrics_call(ptr=metrics,scope,db_read,"protocol_info")]
     fn get_protocol_details(&self, address: Address) -> eyre::Result<ProtocolInfo> {
         self.db.view_db(|tx| {
@@ -515,7 +624,8 @@ impl LibmdbxReader for LibmdbxReadWriter {
 
     #[brontes_macros::metrics_call(ptr=metrics,scope, db_read, "try_fetch_token_info")]
     fn try_fetch_token_info(&self, og_address: Address) -> eyre::Result<TokenInfoWithAddress> {
-        let address = if og_address == ETH_ADDRESS { WETH_ADDRESS } else { og_address };
+        let address =
+            if og_address == ETH_ADDRESS { ChainConfig::get().wrapped_native } else { og_address };
 
         self.db
             .view_db(|tx| match self.cache.token_info(true, |lock| lock.get(&address)) {
@@ -556,24 +666,21 @@ impl LibmdbxReader for LibmdbxReadWriter {
         self.db.view_db(|tx| {
             let mut res = FastHashMap::default();
             for eoa in searcher_eoa {
-                match self.cache.searcher_eoa(true, |h| h.get(&eoa)) {
-                    Some(Some(val)) => {
-                        res.insert(eoa, val.clone());
-                    }
-                    Some(None) => continue,
-                    None => {
-                        if let Ok(Some(r)) = tx
-                            .get::<SearcherEOAs>(eoa)
-                            .map_err(ErrReport::from)
-                            .inspect(|data| {
-                                self.cache.searcher_eoa(false, |f| {
-                                    f.get_with(eoa, || data.clone());
-                                })
+                let stored = match self.cache.searcher_eoa(true, |h| h.get(&eoa)) {
+                    Some(stored) => stored,
+                    None => tx
+                        .get::<SearcherEOAs>(eoa)
+                        .map_err(ErrReport::from)
+                        .inspect(|data| {
+                            self.cache.searcher_eoa(false, |f| {
+                                f.get_with(eoa, || data.clone());
                             })
-                        {
-                            res.insert(eoa, r);
-                        }
-                    }
+                        })
+                        .ok()
+                        .flatten(),
+                };
+                if let Some(info) = SearcherLabels::apply(&self.searcher_labels.eoas, eoa, stored) {
+                    res.insert(eoa, info);
                 }
             }
             Ok(res)
@@ -585,21 +692,8 @@ impl LibmdbxReader for LibmdbxReadWriter {
         &self,
         searcher_eoa: Address,
     ) -> eyre::Result<Option<SearcherInfo>> {
-        match self.cache.searcher_eoa(true, |f| f.get(&searcher_eoa)) {
-            Some(Some(e)) => return Ok(Some(e.clone())),
-            Some(None) => return Ok(None),
-            None => self
-                .db
-                .view_db(|tx| {
-                    tx.get::<SearcherEOAs>(searcher_eoa)
-                        .map_err(ErrReport::from)
-                })
-                .inspect(|data| {
-                    self.cache.searcher_eoa(false, |f| {
-                        f.get_with(searcher_eoa, || data.clone());
-                    });
-                }),
-        }
+        let stored = self.stored_searcher_eoa_info(searcher_eoa)?;
+        Ok(SearcherLabels::apply(&self.searcher_labels.eoas, searcher_eoa, stored))
     }
 
     #[brontes_macros::metrics_call(ptr=metrics,scope,db_read,"try_fetch_searcher_contract_infos")]
@@ -610,24 +704,23 @@ impl LibmdbxReader for LibmdbxReadWriter {
         self.db.view_db(|tx| {
             let mut res = FastHashMap::default();
             for contract in searcher {
-                match self.cache.searcher_contract(true, |h| h.get(&contract)) {
-                    Some(Some(val)) => {
-                        res.insert(contract, val.clone());
-                    }
-                    Some(None) => continue,
-                    None => {
-                        if let Ok(Some(r)) = tx
-                            .get::<SearcherContracts>(contract)
-                            .map_err(ErrReport::from)
-                            .inspect(|data| {
-                                self.cache.searcher_contract(false, |f| {
-                                    f.get_with(contract, || data.clone());
-                                })
+                let stored = match self.cache.searcher_contract(true, |h| h.get(&contract)) {
+                    Some(stored) => stored,
+                    None => tx
+                        .get::<SearcherContracts>(contract)
+                        .map_err(ErrReport::from)
+                        .inspect(|data| {
+                            self.cache.searcher_contract(false, |f| {
+                                f.get_with(contract, || data.clone());
                             })
-                        {
-                            res.insert(contract, r);
-                        }
-                    }
+                        })
+                        .ok()
+                        .flatten(),
+                };
+                if let Some(info) =
+                    SearcherLabels::apply(&self.searcher_labels.contracts, contract, stored)
+                {
+                    res.insert(contract, info);
                 }
             }
             Ok(res)
@@ -639,29 +732,13 @@ impl LibmdbxReader for LibmdbxReadWriter {
         &self,
         searcher_contract: Address,
     ) -> eyre::Result<Option<SearcherInfo>> {
-        match self
-            .cache
-            .searcher_contract(true, |f| f.get(&searcher_contract))
-        {
-            Some(Some(e)) => return Ok(Some(e.clone())),
-            Some(None) => return Ok(None),
-            None => self
-                .db
-                .view_db(|tx| {
-                    tx.get::<SearcherContracts>(searcher_contract)
-                        .map_err(ErrReport::from)
-                })
-                .inspect(|data| {
-                    self.cache.searcher_contract(false, |f| {
-                        f.get_with(searcher_contract, || data.clone());
-                    });
-                }),
-        }
+        let stored = self.stored_searcher_contract_info(searcher_contract)?;
+        Ok(SearcherLabels::apply(&self.searcher_labels.contracts, searcher_contract, stored))
     }
 
     #[instrument(level = "error", skip_all)]
     fn fetch_all_searcher_eoa_info(&self) -> eyre::Result<Vec<(Address, SearcherInfo)>> {
-        self.db.export_db(
+        let stored = self.db.export_db(
             None,
             |start_key, tx| {
                 let mut cur = tx.cursor_read::<SearcherEOAs>()?;
@@ -675,12 +752,14 @@ impl LibmdbxReader for LibmdbxReadWriter {
                 Ok(cur)
             },
             |cursor| Ok(cursor.next().map(|inner| inner.map(|i| (i.0, i.1)))?),
-        )
+        )?;
+
+        Ok(SearcherLabels::apply_all(&self.searcher_labels.eoas, stored))
     }
 
     #[instrument(level = "error", skip_all)]
     fn fetch_all_searcher_contract_info(&self) -> eyre::Result<Vec<(Address, SearcherInfo)>> {
-        self.db.export_db(
+        let stored = self.db.export_db(
             None,
             |start_key, tx| {
                 let mut cur = tx.cursor_read::<SearcherContracts>()?;
@@ -694,13 +773,12 @@ impl LibmdbxReader for LibmdbxReadWriter {
                 Ok(cur)
             },
             |cursor| Ok(cursor.next().map(|inner| inner.map(|i| (i.0, i.1)))?),
-        )
+        )?;
+
+        Ok(SearcherLabels::apply_all(&self.searcher_labels.contracts, stored))
     }
 
-    fn protocols_created_before(
-        &self,
-        block_num: u64,
-    ) -> eyre::Result<FastHashMap<(Address, Protocol), Pair>> {
+    fn protocols_created_before(&self, block_num: u64) -> eyre::Result<ProtocolCreatedBefore> {
         self.db.view_db(|tx| {
         let mut cursor = tx.cursor_read::<PoolCreationBlocks>()?;
         let mut map = FastHashMap::default();
@@ -712,10 +790,7 @@ impl LibmdbxReader for LibmdbxReadWriter {
                     continue;
                 };
 
-                map.insert(
-                    (addr, protocol_info.protocol),
-                    Pair(protocol_info.token0, protocol_info.token1),
-                );
+                map.insert((addr, protocol_info.protocol), protocol_info.get_pairs());
             }
         }
 
@@ -729,7 +804,7 @@ impl LibmdbxReader for LibmdbxReadWriter {
         &self,
         start_block: u64,
         end_block: u64,
-    ) -> eyre::Result<FastHashMap<u64, Vec<(Address, Protocol, Pair)>>> {
+    ) -> eyre::Result<ProtocolCreatedRange> {
         self.db.view_db(|tx| {
         let mut cursor = tx.cursor_read::<PoolCreationBlocks>()?;
         let mut map = FastHashMap::default();
@@ -744,7 +819,7 @@ impl LibmdbxReader for LibmdbxReadWriter {
                 map.entry(block).or_insert(vec![]).push((
                     addr,
                     protocol_info.protocol,
-                    Pair(protocol_info.token0, protocol_info.token1),
+                    protocol_info.get_pairs(),
                 ));
             }
         }
@@ -835,6 +910,42 @@ impl LibmdbxReader for LibmdbxReadWriter {
         )
     }
 
+    fn try_fetch_searcher_failed_attempts(
+        &self,
+        searcher: Address,
+    ) -> eyre::Result<Option<FailedAttemptStats>> {
+        self.db
+            .view_db(|tx| Ok(tx.get::<SearcherFailedAttempts>(searcher)?))
+    }
+
+    #[instrument(level = "error", skip_all)]
+    fn fetch_all_searcher_failed_attempts(
+        &self,
+    ) -> eyre::Result<Vec<(Address, FailedAttemptStats)>> {
+        self.db.view_db(|tx| {
+            let mut cursor = tx.cursor_read::<SearcherFailedAttempts>()?;
+            Ok(cursor.walk(None)?.collect::<Result<Vec<_>, _>>()?)
+        })
+    }
+
+    fn try_fetch_builder_proposer_payments(
+        &self,
+        builder_coinbase_addr: Address,
+    ) -> eyre::Result<Option<ProposerPaymentStats>> {
+        self.db
+            .view_db(|tx| Ok(tx.get::<BuilderProposerPayments>(builder_coinbase_addr)?))
+    }
+
+    #[instrument(level = "error", skip_all)]
+    fn fetch_all_builder_proposer_payments(
+        &self,
+    ) -> eyre::Result<Vec<(Address, ProposerPaymentStats)>> {
+        self.db.view_db(|tx| {
+            let mut cursor = tx.cursor_read::<BuilderProposerPayments>()?;
+            Ok(cursor.walk(None)?.collect::<Result<Vec<_>, _>>()?)
+        })
+    }
+
     #[instrument(level = "error", skip_all)]
     fn try_fetch_mev_blocks(
         &self,
@@ -862,6 +973,24 @@ impl LibmdbxReader for LibmdbxReadWriter {
         )
     }
 
+    fn try_fetch_mev_block(&self, block_num: u64) -> eyre::Result<Option<MevBlockWithClassified>> {
+        self.db.view_db(|tx| Ok(tx.get::<MevBlocks>(block_num)?))
+    }
+
+    fn fetch_mev_block_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<MevBlockWithClassified>> {
+        self.db.view_db(|tx| {
+            let mut cursor = tx.cursor_read::<MevBlocks>()?;
+            Ok(cursor
+                .walk_range(start_block..=end_block)?
+                .map(|res| res.map(|(_, block)| block))
+                .collect::<Result<Vec<_>, _>>()?)
+        })
+    }
+
     #[instrument(level = "error", skip_all)]
     fn fetch_all_mev_blocks(
         &self,
@@ -918,6 +1047,19 @@ impl DBWriter for LibmdbxReadWriter {
         eoa_info: SearcherInfo,
         contract_info: Option<SearcherInfo>,
     ) -> eyre::Result<()> {
+        let eoa_info =
+            SearcherLabels::strip(&self.searcher_labels.eoas, eoa_address, eoa_info, || {
+                self.stored_searcher_eoa_info(eoa_address)
+            })?;
+        let contract_info = match (contract_address, contract_info) {
+            (Some(addr), Some(info)) => {
+                Some(SearcherLabels::strip(&self.searcher_labels.contracts, addr, info, || {
+                    self.stored_searcher_contract_info(addr)
+                })?)
+            }
+            (_, info) => info,
+        };
+
         self.cache.searcher_eoa(false, |handle| {
             handle.insert(eoa_address, Some(eoa_info.clone()));
         });
@@ -944,6 +1086,11 @@ impl DBWriter for LibmdbxReadWriter {
         searcher_eoa: Address,
         searcher_info: SearcherInfo,
     ) -> eyre::Result<()> {
+        let searcher_info =
+            SearcherLabels::strip(&self.searcher_labels.eoas, searcher_eoa, searcher_info, || {
+                self.stored_searcher_eoa_info(searcher_eoa)
+            })?;
+
         self.cache.searcher_eoa(false, |handle| {
             handle.insert(searcher_eoa, Some(searcher_info.clone()));
         });
@@ -959,6 +1106,13 @@ impl DBWriter for LibmdbxReadWriter {
         searcher_contract: Address,
         searcher_info: SearcherInfo,
     ) -> eyre::Result<()> {
+        let searcher_info = SearcherLabels::strip(
+            &self.searcher_labels.contracts,
+            searcher_contract,
+            searcher_info,
+            || self.stored_searcher_contract_info(searcher_contract),
+        )?;
+
         self.cache.searcher_contract(false, |handle| {
             handle.insert(searcher_contract, Some(searcher_info.clone()));
         });
@@ -1065,6 +1219,22 @@ impl DBWriter for LibmdbxReadWriter {
             .send(WriterMessage::Traces { block, traces }.stamp())?)
     }
 
+    /// Waits for the writer to finish the rollback as the searcher info it
+    /// reverts is cached here
+    async fn rollback_blocks(&self, blocks: RangeInclusive<u64>) -> eyre::Result<()> {
+        let done = Arc::new(Notify::new());
+        self.tx
+            .send(WriterMessage::Rollback { blocks, done: done.clone() }.stamp())?;
+        done.notified().await;
+
+        self.cache
+            .searcher_eoa(false, |handle| handle.invalidate_all());
+        self.cache
+            .searcher_contract(false, |handle| handle.invalidate_all());
+
+        Ok(())
+    }
+
     async fn write_builder_info(
         &self,
         builder_address: Address,
@@ -1076,6 +1246,26 @@ impl DBWriter for LibmdbxReadWriter {
         )?)
     }
 
+    async fn write_searcher_failed_attempts(
+        &self,
+        block_number: u64,
+        attempts: Vec<FailedAttempt>,
+    ) -> eyre::Result<()> {
+        Ok(self
+            .tx
+            .send(WriterMessage::SearcherFailedAttempts { block_number, attempts }.stamp())?)
+    }
+
+    async fn write_builder_proposer_payment(
+        &self,
+        builder_address: Address,
+        payment_delta: i128,
+    ) -> eyre::Result<()> {
+        Ok(self.tx.send(
+            WriterMessage::BuilderProposerPayment { builder_address, payment_delta }.stamp(),
+        )?)
+    }
+
     /// only for internal functionality (i.e. clickhouse)
     async fn insert_tree(&self, _tree: BlockTree<Action>) -> eyre::Result<()> {
         Ok(())
@@ -1091,6 +1281,51 @@ impl DBWriter for LibmdbxReadWriter {
 }
 
 impl LibmdbxReadWriter {
+    /// Searcher eoa info as stored, without the config labels applied.
+    fn stored_searcher_eoa_info(
+        &self,
+        searcher_eoa: Address,
+    ) -> eyre::Result<Option<SearcherInfo>> {
+        match self.cache.searcher_eoa(true, |f| f.get(&searcher_eoa)) {
+            Some(stored) => Ok(stored),
+            None => self
+                .db
+                .view_db(|tx| {
+                    tx.get::<SearcherEOAs>(searcher_eoa)
+                        .map_err(ErrReport::from)
+                })
+                .inspect(|data| {
+                    self.cache.searcher_eoa(false, |f| {
+                        f.get_with(searcher_eoa, || data.clone());
+                    });
+                }),
+        }
+    }
+
+    /// Searcher contract info as stored, without the config labels applied.
+    fn stored_searcher_contract_info(
+        &self,
+        searcher_contract: Address,
+    ) -> eyre::Result<Option<SearcherInfo>> {
+        match self
+            .cache
+            .searcher_contract(true, |f| f.get(&searcher_contract))
+        {
+            Some(stored) => Ok(stored),
+            None => self
+                .db
+                .view_db(|tx| {
+                    tx.get::<SearcherContracts>(searcher_contract)
+                        .map_err(ErrReport::from)
+                })
+                .inspect(|data| {
+                    self.cache.searcher_contract(false, |f| {
+                        f.get_with(searcher_contract, || data.clone());
+                    });
+                }),
+        }
+    }
+
     #[instrument(target = "libmdbx_read_write::insert_batched_data", skip_all, level = "warn")]
     fn insert_batched_data<T: CompressedTable>(
         &self,
@@ -1317,7 +1552,7 @@ pub fn determine_eth_prices(
     Some(
         cex_quotes
             .get_quote_from_most_liquid_exchange(
-                &Pair(quote_asset, WETH_ADDRESS),
+                &Pair(quote_asset, ChainConfig::get().wrapped_native),
                 block_timestamp,
                 None,
             )?
@@ -1338,3 +1573,80 @@ pub fn tables_to_initialize(data: InitializedStateMeta) -> Vec<(Tables, bool)> {
         (Tables::CexTrades, data.is_initialized(CEX_TRADES_FLAG)),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::{db::searcher::Fund, mev::MevType};
+
+    use super::*;
+
+    fn labels() -> FastHashMap<Address, SearcherInfo> {
+        let label = SearcherInfo {
+            name: Some("searcher".to_string()),
+            config_labels: vec![MevType::AtomicArb],
+            ..Default::default()
+        };
+
+        [(Address::repeat_byte(1), label)].into_iter().collect()
+    }
+
+    #[test]
+    fn test_apply_searcher_labels() {
+        let labels = labels();
+        let stored = SearcherInfo {
+            fund: Fund::Wintermute,
+            config_labels: vec![MevType::CexDexTrades],
+            ..Default::default()
+        };
+
+        let info =
+            SearcherLabels::apply(&labels, Address::repeat_byte(1), Some(stored.clone())).unwrap();
+        assert_eq!(info.name.as_deref(), Some("searcher"));
+        assert_eq!(info.config_labels, vec![MevType::CexDexTrades, MevType::AtomicArb]);
+
+        // unlabelled searchers are read as stored
+        assert_eq!(
+            SearcherLabels::apply(&labels, Address::repeat_byte(2), Some(stored.clone())),
+            Some(stored.clone())
+        );
+        assert_eq!(SearcherLabels::apply(&labels, Address::repeat_byte(2), None), None);
+
+        let all = SearcherLabels::apply_all(&labels, vec![(Address::repeat_byte(2), stored)]);
+        assert_eq!(all.len(), 2);
+        assert!(all
+            .iter()
+            .any(|(address, info)| *address == Address::repeat_byte(1)
+                && info.name.as_deref() == Some("searcher")));
+    }
+
+    #[test]
+    fn test_strip_searcher_labels() {
+        let labels = labels();
+        let stored = SearcherInfo {
+            fund: Fund::Wintermute,
+            config_labels: vec![MevType::CexDexTrades],
+            ..Default::default()
+        };
+
+        let mut read =
+            SearcherLabels::apply(&labels, Address::repeat_byte(1), Some(stored.clone())).unwrap();
+        read.mev_count.bundle_count = 3;
+
+        let written =
+            SearcherLabels::strip(&labels, Address::repeat_byte(1), read, || Ok(Some(stored)))
+                .unwrap();
+        assert_eq!(written.name, None);
+        assert_eq!(written.fund, Fund::Wintermute);
+        assert_eq!(written.config_labels, vec![MevType::CexDexTrades]);
+        // updates made by the run are kept
+        assert_eq!(written.mev_count.bundle_count, 3);
+
+        // unlabelled searchers are written as is without reading the stored info
+        let info = SearcherInfo { name: Some("other".to_string()), ..Default::default() };
+        let written = SearcherLabels::strip(&labels, Address::repeat_byte(2), info, || {
+            panic!("stored info read for an unlabelled searcher")
+        })
+        .unwrap();
+        assert_eq!(written.name.as_deref(), Some("other"));
+    }
+}
@@ -1,26 +1,28 @@
 use std::{
-    ops::Deref,
+    collections::BTreeMap,
+    ops::{Deref, RangeInclusive},
     sync::Arc,
     task::Poll,
     time::{Duration, Instant},
 };
 
 use alloy_primitives::Address;
+use brontes_libmdbx::RW;
 use brontes_metrics::db_writer::WriterMetrics;
 use brontes_types::{
     db::{
         address_metadata::AddressMetadata,
         address_to_protocol_info::ProtocolInfo,
-        builder::BuilderInfo,
-        dex::{make_key, DexQuoteWithIndex, DexQuotes},
-        initialized_state::{DATA_PRESENT, DEX_PRICE_FLAG, TRACE_FLAG},
+        builder::{BuilderInfo, ProposerPaymentStats},
+        dex::{make_filter_key_range, make_key, DexQuoteWithIndex, DexQuotes},
+        initialized_state::{DATA_NOT_PRESENT_UNKNOWN, DATA_PRESENT, DEX_PRICE_FLAG, TRACE_FLAG},
         mev_block::MevBlockWithClassified,
         pool_creation_block::PoolsToAddresses,
-        searcher::SearcherInfo,
+        searcher::{FailedAttemptStats, SearcherInfo},
         token_info::TokenInfo,
         traces::TxTracesInner,
     },
-    mev::{Bundle, MevBlock},
+    mev::{Bundle, FailedAttempt, MevBlock, MevType},
     structured_trace::TxTrace,
     FastHashMap, Protocol, UnboundedYapperReceiver,
 };
@@ -36,6 +38,7 @@ use tracing::instrument;
 
 use crate::{
     libmdbx::{
+        implementation::compressed_wrappers::tx::CompressedLibmdbxTx,
         tables::*,
         types::{LibmdbxData, ReturnKV},
         Libmdbx,
@@ -45,6 +48,9 @@ use crate::{
 
 // how often we will append data
 const CLEAR_AM: usize = 1000;
+// how many blocks of failed attempts we keep around to be able to revert them
+// on a reorg, the tip inspector never rolls back deeper than this
+const FAILED_ATTEMPT_BLOCKS: usize = 64;
 
 //TODO: Mark instant here
 type InsetQueue = FastHashMap<Tables, Vec<(Vec<u8>, Vec<u8>)>>;
@@ -78,10 +84,18 @@ pub enum WriterMessage {
         searcher_contract: Address,
         searcher_info:     Box<SearcherInfo>,
     },
+    SearcherFailedAttempts {
+        block_number: u64,
+        attempts:     Vec<FailedAttempt>,
+    },
     BuilderInfo {
         builder_address: Address,
         builder_info:    Box<BuilderInfo>,
     },
+    BuilderProposerPayment {
+        builder_address: Address,
+        payment_delta:   i128,
+    },
     AddressMeta {
         address:  Address,
         metadata: Box<AddressMetadata>,
@@ -97,6 +111,10 @@ pub enum WriterMessage {
         block:  u64,
         traces: Vec<TxTrace>,
     },
+    Rollback {
+        blocks: RangeInclusive<u64>,
+        done:   Arc<Notify>,
+    },
     Init(InitTables, Arc<Notify>),
 }
 
@@ -214,16 +232,22 @@ init!(
     MevBlocks,
     SearcherEOAs,
     SearcherContracts,
-    InitializedState
+    InitializedState,
+    RelayBids,
+    BuilderProposerPayments,
+    SearcherFailedAttempts
 );
 
 /// due to libmdbx's 1 write tx limit. it makes sense
 /// to split db and ensure we never breach this
 pub struct LibmdbxWriter {
-    db:           Arc<Libmdbx>,
-    insert_queue: InsetQueue,
-    rx:           UnboundedYapperReceiver<StampedWriterMessage>,
-    metrics:      WriterMetrics,
+    db:              Arc<Libmdbx>,
+    insert_queue:    InsetQueue,
+    rx:              UnboundedYapperReceiver<StampedWriterMessage>,
+    metrics:         WriterMetrics,
+    /// failed attempt stats of the most recent blocks, these aren't stored per
+    /// block so we keep them to revert them on a reorg
+    failed_attempts: BTreeMap<u64, FastHashMap<Address, FailedAttemptStats>>,
 }
 
 impl LibmdbxWriter {
@@ -232,7 +256,13 @@ impl LibmdbxWriter {
         rx: UnboundedYapperReceiver<StampedWriterMessage>,
         metrics: bool,
     ) -> Self {
-        Self { rx, db, insert_queue: FastHashMap::default(), metrics: WriterMetrics::new(metrics) }
+        Self {
+            rx,
+            db,
+            insert_queue: FastHashMap::default(),
+            metrics: WriterMetrics::new(metrics),
+            failed_attempts: BTreeMap::default(),
+        }
     }
 
     fn handle_msg(&mut self, stamped_msg: StampedWriterMessage) -> eyre::Result<()> {
@@ -262,6 +292,10 @@ impl LibmdbxWriter {
                 self.write_builder_info(builder_address, *builder_info)?;
                 "builderinfo"
             }
+            WriterMessage::BuilderProposerPayment { builder_address, payment_delta } => {
+                self.write_builder_proposer_payment(builder_address, payment_delta)?;
+                "builderproposerpayment"
+            }
             WriterMessage::AddressMeta { address, metadata } => {
                 self.write_address_meta(address, *metadata)?;
                 "addressmeta"
@@ -283,6 +317,16 @@ impl LibmdbxWriter {
                 self.write_searcher_contract_info(searcher_contract, *searcher_info)?;
                 "searchercontractinfo"
             }
+            WriterMessage::SearcherFailedAttempts { block_number, attempts } => {
+                self.write_searcher_failed_attempts(block_number, attempts)?;
+                "searcherfailedattempts"
+            }
+            WriterMessage::Rollback { blocks, done } => {
+                let res = self.rollback_blocks(blocks);
+                done.notify_one();
+                res?;
+                "rollback"
+            }
             WriterMessage::Init(init, not) => {
                 init.write_data(self.db.clone())?;
                 not.notify_one();
@@ -390,6 +434,51 @@ impl LibmdbxWriter {
         Ok(())
     }
 
+    /// Merges the attempts into the stored stats of their eoas and contracts
+    #[instrument(
+        target = "libmdbx_read_write::write_searcher_failed_attempts",
+        skip_all,
+        level = "warn"
+    )]
+    fn write_searcher_failed_attempts(
+        &mut self,
+        block_number: u64,
+        attempts: Vec<FailedAttempt>,
+    ) -> eyre::Result<()> {
+        let mut block_stats: FastHashMap<Address, FailedAttemptStats> = FastHashMap::default();
+        for attempt in &attempts {
+            for address in [attempt.eoa, attempt.mev_contract] {
+                block_stats
+                    .entry(address)
+                    .or_default()
+                    .merge(FailedAttemptStats::from_attempt(attempt));
+            }
+        }
+
+        self.failed_attempts
+            .insert(block_number, block_stats.clone());
+        while self.failed_attempts.len() > FAILED_ATTEMPT_BLOCKS {
+            self.failed_attempts.pop_first();
+        }
+
+        let data = self.db.view_db(|tx| {
+            block_stats
+                .into_iter()
+                .map(|(address, stats)| {
+                    let mut stored = tx
+                        .get::<SearcherFailedAttempts>(address)?
+                        .unwrap_or_default();
+                    stored.merge(stats);
+                    Ok(SearcherFailedAttemptsData::new(address, stored))
+                })
+                .collect::<eyre::Result<Vec<_>>>()
+        })?;
+
+        self.instrumented_write::<SearcherFailedAttempts, SearcherFailedAttemptsData>(&data)
+            .expect("libmdbx write failure");
+        Ok(())
+    }
+
     #[instrument(target = "libmdbx_read_write::write_address_meta", skip_all, level = "warn")]
     fn write_address_meta(&self, address: Address, metadata: AddressMetadata) -> eyre::Result<()> {
         let data = AddressMetaData::new(address, metadata);
@@ -530,6 +619,86 @@ impl LibmdbxWriter {
         self.init_state_updating(block, TRACE_FLAG)
     }
 
+    /// Removes the mev, dex pricing and traces of the given blocks and marks
+    /// them as uninitialized. What the mev of the blocks added to the searcher
+    /// and builder stats is reverted before the mev is removed. Anything still
+    /// queued is flushed first as it can hold data of the blocks being removed.
+    #[instrument(target = "libmdbx_read_write::rollback_blocks", skip_all, level = "warn")]
+    fn rollback_blocks(&mut self, blocks: RangeInclusive<u64>) -> eyre::Result<()> {
+        self.insert_remaining();
+
+        let tx = self.db.rw_tx()?;
+        for block in blocks {
+            if let Some(mev_block) = tx.get::<MevBlocks>(block)? {
+                Self::revert_mev_block(&tx, mev_block)?;
+            }
+            for (address, stats) in self.failed_attempts.remove(&block).unwrap_or_default() {
+                if let Some(mut stored) = tx.get::<SearcherFailedAttempts>(address)? {
+                    stored.remove(stats);
+                    tx.put::<SearcherFailedAttempts>(address, stored)?;
+                }
+            }
+
+            tx.delete::<MevBlocks>(block, None)?;
+            tx.delete::<TxTraces>(block, None)?;
+
+            let mut cursor = tx.cursor_write::<DexPrice>()?;
+            let (start_key, end_key) = make_filter_key_range(block);
+            let mut walker = cursor.walk_range(start_key..=end_key)?;
+            while walker.next().transpose()?.is_some() {
+                walker.delete_current()?;
+            }
+
+            if let Some(mut state) = tx.get::<InitializedState>(block)? {
+                state.set(DEX_PRICE_FLAG, DATA_NOT_PRESENT_UNKNOWN);
+                state.set(TRACE_FLAG, DATA_NOT_PRESENT_UNKNOWN);
+                tx.put::<InitializedState>(block, state)?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Undoes the searcher info and builder proposer payment updates of a
+    /// processed block, see `insert_mev_results` in the bin crate. The builder
+    /// mev aggregates of the block only live in its `MevBlock`, which is
+    /// removed with it. A builder left without any payments is removed so that
+    /// a rolled back block leaves no trace.
+    fn revert_mev_block(
+        tx: &CompressedLibmdbxTx<RW>,
+        MevBlockWithClassified { block, mev }: MevBlockWithClassified,
+    ) -> eyre::Result<()> {
+        for header in mev.iter().map(|bundle| &bundle.header) {
+            if header.mev_type == MevType::Unknown || header.mev_type == MevType::SearcherTx {
+                continue
+            }
+
+            if let Some(mut eoa_info) = tx.get::<SearcherEOAs>(header.eoa)? {
+                eoa_info.revert_bundle(header);
+                tx.put::<SearcherEOAs>(header.eoa, eoa_info)?;
+            }
+            let Some(contract) = header.mev_contract else { continue };
+            if let Some(mut contract_info) = tx.get::<SearcherContracts>(contract)? {
+                contract_info.revert_bundle(header);
+                tx.put::<SearcherContracts>(contract, contract_info)?;
+            }
+        }
+
+        if let Some(payment_delta) = block.payment_delta {
+            if let Some(mut stats) = tx.get::<BuilderProposerPayments>(block.builder_address)? {
+                stats.remove(ProposerPaymentStats::from_payment_delta(payment_delta));
+                if stats.blocks == 0 {
+                    tx.delete::<BuilderProposerPayments>(block.builder_address, None)?;
+                } else {
+                    tx.put::<BuilderProposerPayments>(block.builder_address, stats)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     #[instrument(target = "libmdbx_read_write::write_builder_info", skip_all, level = "warn")]
     fn write_builder_info(
         &self,
@@ -542,6 +711,30 @@ impl LibmdbxWriter {
         Ok(())
     }
 
+    /// Merges the payment into the stored stats here so that consecutive
+    /// blocks of the same builder don't race on the read
+    #[instrument(
+        target = "libmdbx_read_write::write_builder_proposer_payment",
+        skip_all,
+        level = "warn"
+    )]
+    fn write_builder_proposer_payment(
+        &self,
+        builder_address: Address,
+        payment_delta: i128,
+    ) -> eyre::Result<()> {
+        let mut stats = self
+            .db
+            .view_db(|tx| Ok(tx.get::<BuilderProposerPayments>(builder_address)?))?
+            .unwrap_or_default();
+        stats.merge(ProposerPaymentStats::from_payment_delta(payment_delta));
+
+        let data = BuilderProposerPaymentsData::new(builder_address, stats);
+        self.instrumented_write::<BuilderProposerPayments, BuilderProposerPaymentsData>(&[data])
+            .expect("libmdbx write failure");
+        Ok(())
+    }
+
     #[instrument(target = "libmdbx_read_write::init_state_updating", skip_all, level = "warn")]
     fn init_state_updating(&mut self, block: u64, flag: u16) -> eyre::Result<()> {
         let tx = self.db.ro_tx()?;
@@ -685,3 +878,113 @@ impl Future for LibmdbxWriter {
         Poll::Pending
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::{
+        db::searcher::SearcherInfo,
+        mev::{BundleData, BundleHeader, Liquidation},
+    };
+
+    use super::*;
+
+    const BUILDER: Address = Address::repeat_byte(1);
+    const EOA: Address = Address::repeat_byte(2);
+    const CONTRACT: Address = Address::repeat_byte(3);
+
+    fn writer(db: Arc<Libmdbx>) -> LibmdbxWriter {
+        let (_, rx) = tokio::sync::mpsc::unbounded_channel();
+        LibmdbxWriter::new(db, UnboundedYapperReceiver::new(rx, 1024, "test".to_string()), false)
+    }
+
+    /// Writes a block the way `insert_mev_results` in the bin crate does
+    fn process_block(writer: &mut LibmdbxWriter, block_number: u64, payment_delta: i128) {
+        let header = BundleHeader {
+            block_number,
+            eoa: EOA,
+            mev_contract: Some(CONTRACT),
+            profit_usd: 10.0,
+            bribe_usd: 2.0,
+            mev_type: MevType::Liquidation,
+            ..Default::default()
+        };
+
+        let (eoa_info, contract_info) = writer
+            .db
+            .view_db(|tx| {
+                Ok((tx.get::<SearcherEOAs>(EOA)?, tx.get::<SearcherContracts>(CONTRACT)?))
+            })
+            .unwrap();
+        let mut eoa_info = eoa_info.unwrap_or_default();
+        let mut contract_info = contract_info.unwrap_or_default();
+        eoa_info.update_with_bundle(&header);
+        contract_info.update_with_bundle(&header);
+        writer
+            .write_searcher_info(EOA, Some(CONTRACT), eoa_info, Some(contract_info))
+            .unwrap();
+        writer
+            .write_builder_proposer_payment(BUILDER, payment_delta)
+            .unwrap();
+
+        let block = MevBlock {
+            block_number,
+            builder_address: BUILDER,
+            payment_delta: Some(payment_delta),
+            ..Default::default()
+        };
+        let bundle = Bundle { header, data: BundleData::Liquidation(Liquidation::default()) };
+        writer
+            .save_mev_blocks(block_number, block, vec![bundle])
+            .unwrap();
+    }
+
+    fn stored(
+        db: &Libmdbx,
+        block_number: u64,
+    ) -> (bool, Option<SearcherInfo>, Option<SearcherInfo>, Option<ProposerPaymentStats>) {
+        db.view_db(|tx| {
+            Ok((
+                tx.get::<MevBlocks>(block_number)?.is_some(),
+                tx.get::<SearcherEOAs>(EOA)?,
+                tx.get::<SearcherContracts>(CONTRACT)?,
+                tx.get::<BuilderProposerPayments>(BUILDER)?,
+            ))
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rollback_saved_mev_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Libmdbx::init_db(dir.path(), None).unwrap());
+        let mut writer = writer(db.clone());
+
+        process_block(&mut writer, 1, 100);
+        process_block(&mut writer, 2, -40);
+        // the mev blocks are still queued, the rollback has to flush them first
+        writer.rollback_blocks(2..=2).unwrap();
+
+        let (block_2, ..) = stored(&db, 2);
+        assert!(!block_2);
+        let (block_1, eoa, contract, payments) = stored(&db, 1);
+        assert!(block_1);
+        for info in [eoa.unwrap(), contract.unwrap()] {
+            assert_eq!(info.mev_count.bundle_count, 1);
+            assert_eq!(info.mev_count.liquidation_count, Some(1));
+            assert_eq!(info.pnl.liquidation, Some(10.0));
+            assert_eq!(info.gas_bids.liquidation, Some(2.0));
+        }
+        assert_eq!(payments, Some(ProposerPaymentStats::from_payment_delta(100)));
+
+        writer.rollback_blocks(1..=1).unwrap();
+
+        let (block_1, eoa, contract, payments) = stored(&db, 1);
+        assert!(!block_1);
+        for info in [eoa.unwrap(), contract.unwrap()] {
+            assert_eq!(info.mev_count.bundle_count, 0);
+            assert_eq!(info.pnl.liquidation.unwrap_or_default(), 0.0);
+            assert_eq!(info.gas_bids.liquidation.unwrap_or_default(), 0.0);
+        }
+        assert_eq!(payments, None);
+    }
+}
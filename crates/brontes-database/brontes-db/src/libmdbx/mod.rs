@@ -24,7 +24,7 @@ use eyre::Context;
 use implementation::compressed_wrappers::tx::CompressedLibmdbxTx;
 use initialize::LibmdbxInitializer;
 pub use libmdbx_read_write::{
-    determine_eth_prices, LibmdbxInit, LibmdbxReadWriter, StateToInitialize,
+    determine_eth_prices, LibmdbxInit, LibmdbxReadWriter, SearcherLabels, StateToInitialize,
 };
 use reth_db::{
     is_database_empty,
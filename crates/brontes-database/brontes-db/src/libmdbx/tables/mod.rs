@@ -10,7 +10,7 @@ use brontes_types::{
     db::{
         address_metadata::{AddressMetadata, AddressMetadataRedefined},
         address_to_protocol_info::{ProtocolInfo, ProtocolInfoRedefined},
-        builder::{BuilderInfo, BuilderInfoRedefined},
+        builder::{BuilderInfo, BuilderInfoRedefined, ProposerPaymentStats},
         cex::{
             quotes::{CexPriceMap, CexPriceMapRedefined},
             trades::{CexTradeMap, CexTradeMapRedefined},
@@ -24,7 +24,8 @@ use brontes_types::{
         metadata::{BlockMetadataInner, BlockMetadataInnerRedefined},
         mev_block::{MevBlockWithClassified, MevBlockWithClassifiedRedefined},
         pool_creation_block::{PoolsToAddresses, PoolsToAddressesRedefined},
-        searcher::{SearcherInfo, SearcherInfoRedefined},
+        relay_bids::{BlockRelayBids, BlockRelayBidsRedefined},
+        searcher::{FailedAttemptStats, SearcherInfo, SearcherInfoRedefined},
         token_info::TokenInfo,
         traces::{TxTracesInner, TxTracesInnerRedefined},
         traits::LibmdbxReader,
@@ -58,7 +59,7 @@ use super::{
     CompressedTable,
 };
 
-pub const NUM_TABLES: usize = 14;
+pub const NUM_TABLES: usize = 17;
 
 macro_rules! tables {
     ($($table:ident),*) => {
@@ -276,7 +277,12 @@ impl Tables {
                     )
                     .await
             }
-            Tables::SearcherEOAs | Tables::SearcherContracts | Tables::InitializedState => Ok(()),
+            Tables::SearcherEOAs
+            | Tables::SearcherContracts
+            | Tables::InitializedState
+            | Tables::RelayBids
+            | Tables::BuilderProposerPayments
+            | Tables::SearcherFailedAttempts => Ok(()),
             _ => unimplemented!("'initialize_table' not implemented for {:?}", self),
         }
     }
@@ -373,6 +379,12 @@ impl Tables {
             Self::MevBlocks => exporter.export_mev_blocks().await,
             Self::SearcherContracts | Self::SearcherEOAs => exporter.export_searcher_info().await,
             Self::Builder => exporter.export_builder_info().await,
+            Self::DexPrice => exporter.export_dex_prices().await,
+            Self::CexPrice => exporter.export_cex_prices().await,
+            Self::CexTrades => exporter.export_cex_trades().await,
+            Self::BlockInfo => exporter.export_block_info().await,
+            Self::TxTraces => exporter.export_tx_traces().await,
+            Self::RelayBids => exporter.export_relay_bids().await,
             _ => unreachable!("Parquet export not yet supported for this table"),
         }
     }
@@ -537,7 +549,10 @@ tables!(
     SearcherEOAs,
     SearcherContracts,
     InitializedState,
-    CexTrades
+    CexTrades,
+    RelayBids,
+    BuilderProposerPayments,
+    SearcherFailedAttempts
 );
 
 /// Must be in this order when defining
@@ -1031,3 +1046,61 @@ compressed_table!(
         }
     }
 );
+
+compressed_table!(
+    Table RelayBids {
+        Data {
+            key: u64,
+            value: BlockRelayBids,
+            compressed_value: BlockRelayBidsRedefined
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table BuilderProposerPayments {
+        #[serde_as]
+        Data {
+            #[serde(with = "address_string")]
+            key: Address,
+            value: ProposerPaymentStats,
+            compressed_value: ProposerPaymentStats
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
+
+compressed_table!(
+    Table SearcherFailedAttempts {
+        #[serde_as]
+        Data {
+            #[serde(with = "address_string")]
+            key: Address,
+            value: FailedAttemptStats,
+            compressed_value: FailedAttemptStats
+        },
+        Init {
+            init_size: None,
+            init_method: Other,
+            http_endpoint: None
+        },
+        CLI {
+            can_insert: False
+        }
+    }
+);
@@ -0,0 +1,317 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use arrow::record_batch::RecordBatch;
+use brontes_types::{
+    frontend_prunes::{
+        remove_burn_transfers, remove_collect_transfers, remove_mint_transfers,
+        remove_swap_transfers,
+    },
+    normalized_actions::Action,
+    tree::BlockTree,
+};
+use eyre::{eyre, Result, WrapErr};
+use parquet::arrow::ArrowWriter;
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    oneshot,
+};
+use tracing::{error, info};
+
+use super::{actions::block_actions_to_record_batch, create_parquet_writer};
+
+/// Blocks that are written to one parquet file by default
+pub const DEFAULT_ACTION_BLOCKS_PER_FILE: u64 = 10_000;
+
+enum WriterMessage {
+    Block(u64, RecordBatch),
+    Close(oneshot::Sender<()>),
+}
+
+/// Writes the normalized actions of the processed blocks to rolling parquet
+/// files, one file per `blocks_per_file` aligned block range, e.g.
+/// "19000000-19009999.parquet".
+///
+/// Blocks are processed concurrently and out of order, so every range has its
+/// own open file that is finished once all of its blocks were written. Files
+/// are written as `.parquet.tmp` and only renamed once they are finished, the
+/// ranges that aren't complete on shutdown are named after the first and last
+/// block written.
+pub struct ActionParquetWriter {
+    tx: UnboundedSender<WriterMessage>,
+}
+
+impl ActionParquetWriter {
+    pub fn spawn(dir: PathBuf, blocks_per_file: u64) -> Result<Self> {
+        std::fs::create_dir_all(&dir)
+            .wrap_err_with(|| format!("Failed to create directory: {}", dir.display()))?;
+
+        let (tx, rx) = unbounded_channel();
+        let files =
+            RollingFiles { dir, blocks_per_file: blocks_per_file.max(1), open: BTreeMap::new() };
+        std::thread::Builder::new()
+            .name("action-parquet-writer".to_string())
+            .spawn(move || files.run(rx))?;
+
+        Ok(Self { tx })
+    }
+
+    /// Writes the actions of the block. The transfers of swaps, mints, burns
+    /// and collects are removed, the same as for the trees stored in
+    /// clickhouse
+    pub fn write_tree(&self, tree: &BlockTree<Action>) -> Result<()> {
+        let mut tree = tree.clone();
+        remove_swap_transfers(&mut tree);
+        remove_mint_transfers(&mut tree);
+        remove_burn_transfers(&mut tree);
+        remove_collect_transfers(&mut tree);
+
+        let record_batch = block_actions_to_record_batch(&tree)
+            .wrap_err("Failed to convert block actions to record batch")?;
+
+        self.tx
+            .send(WriterMessage::Block(tree.header.number, record_batch))
+            .map_err(|_| eyre!("action parquet writer has shut down"))
+    }
+
+    /// Finishes all open files, blocks written after this start new files
+    pub async fn close(&self) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.tx
+            .send(WriterMessage::Close(tx))
+            .map_err(|_| eyre!("action parquet writer has shut down"))?;
+
+        rx.await
+            .map_err(|_| eyre!("action parquet writer has shut down"))
+    }
+}
+
+struct RollingFiles {
+    dir:             PathBuf,
+    blocks_per_file: u64,
+    /// the open files by the first block of their range
+    open:            BTreeMap<u64, RollingFile>,
+}
+
+impl RollingFiles {
+    fn run(mut self, mut rx: UnboundedReceiver<WriterMessage>) {
+        while let Some(message) = rx.blocking_recv() {
+            match message {
+                WriterMessage::Block(block, record_batch) => self.write_block(block, record_batch),
+                WriterMessage::Close(ack) => {
+                    self.finish_all();
+                    let _ = ack.send(());
+                }
+            }
+        }
+
+        self.finish_all();
+    }
+
+    fn write_block(&mut self, block: u64, record_batch: RecordBatch) {
+        let range_start = block - block % self.blocks_per_file;
+        let range_end = range_start + self.blocks_per_file - 1;
+        let tmp_path = self
+            .dir
+            .join(format!("{range_start}-{range_end}.parquet.tmp"));
+
+        let file = self
+            .open
+            .entry(range_start)
+            .or_insert_with(|| RollingFile::new(tmp_path));
+
+        if let Err(e) = file.write(block, &record_batch) {
+            error!(err=%e, %block, "failed to write block actions to parquet");
+        }
+
+        if file.blocks >= self.blocks_per_file {
+            let file = self.open.remove(&range_start).unwrap();
+            self.finish(file);
+        }
+    }
+
+    fn finish_all(&mut self) {
+        for (_, file) in std::mem::take(&mut self.open) {
+            self.finish(file);
+        }
+    }
+
+    fn finish(&self, file: RollingFile) {
+        match file.finish(&self.dir) {
+            Ok(Some(path)) => info!(path=%path.display(), "wrote block actions"),
+            Ok(None) => (),
+            Err(e) => error!(err=%e, "failed to finish block actions parquet file"),
+        }
+    }
+}
+
+struct RollingFile {
+    tmp_path:    PathBuf,
+    /// created with the first block that has actions
+    writer:      Option<ArrowWriter<File>>,
+    blocks:      u64,
+    first_block: u64,
+    last_block:  u64,
+}
+
+impl RollingFile {
+    fn new(tmp_path: PathBuf) -> Self {
+        Self { tmp_path, writer: None, blocks: 0, first_block: u64::MAX, last_block: 0 }
+    }
+
+    fn write(&mut self, block: u64, record_batch: &RecordBatch) -> Result<()> {
+        self.blocks += 1;
+        self.first_block = self.first_block.min(block);
+        self.last_block = self.last_block.max(block);
+
+        if record_batch.num_rows() == 0 {
+            return Ok(())
+        }
+        if self.writer.is_none() {
+            self.writer = Some(create_parquet_writer(record_batch, self.tmp_path.clone())?);
+        }
+
+        self.writer
+            .as_mut()
+            .unwrap()
+            .write(record_batch)
+            .wrap_err("Failed to write record batch to Parquet file")
+    }
+
+    /// The path of the finished file, none if no block had actions
+    fn finish(self, dir: &Path) -> Result<Option<PathBuf>> {
+        let Some(writer) = self.writer else { return Ok(None) };
+        writer.close().wrap_err("Failed to close Parquet writer")?;
+
+        let path = dir.join(format!("{}-{}.parquet", self.first_block, self.last_block));
+        std::fs::rename(&self.tmp_path, &path)
+            .wrap_err_with(|| format!("Failed to move parquet file to: {}", path.display()))?;
+
+        Ok(Some(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::UInt64Array,
+        datatypes::{DataType, Field, Schema},
+    };
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use super::*;
+
+    /// A record batch with a row per action of the block
+    fn block_batch(block: u64, actions: usize) -> RecordBatch {
+        let schema = Schema::new(vec![Field::new("block_number", DataType::UInt64, false)]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(UInt64Array::from(vec![block; actions]))],
+        )
+        .unwrap()
+    }
+
+    fn files(dir: &Path) -> Vec<String> {
+        let mut files = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    fn read_blocks(path: PathBuf) -> Vec<u64> {
+        ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap()
+            .flat_map(|batch| {
+                batch
+                    .unwrap()
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<UInt64Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rotate_at_blocks_per_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files_writer = RollingFiles {
+            dir:             dir.path().to_path_buf(),
+            blocks_per_file: 3,
+            open:            BTreeMap::new(),
+        };
+
+        // out of order and spanning two ranges
+        files_writer.write_block(11, block_batch(11, 2));
+        files_writer.write_block(9, block_batch(9, 1));
+        files_writer.write_block(12, block_batch(12, 1));
+        assert_eq!(files(dir.path()), vec!["12-14.parquet.tmp", "9-11.parquet.tmp"]);
+
+        // the last block of the range finishes its file, even without actions
+        files_writer.write_block(10, block_batch(10, 0));
+        assert_eq!(files(dir.path()), vec!["12-14.parquet.tmp", "9-11.parquet"]);
+        assert_eq!(read_blocks(dir.path().join("9-11.parquet")), vec![11, 11, 9]);
+
+        files_writer.write_block(14, block_batch(14, 1));
+        files_writer.write_block(13, block_batch(13, 1));
+        files_writer.write_block(15, block_batch(15, 1));
+        assert_eq!(files(dir.path()), vec!["12-14.parquet", "15-17.parquet.tmp", "9-11.parquet"]);
+        assert_eq!(read_blocks(dir.path().join("12-14.parquet")), vec![12, 14, 13]);
+        assert_eq!(files_writer.open.keys().copied().collect::<Vec<_>>(), vec![15]);
+    }
+
+    #[test]
+    fn test_range_without_actions_writes_no_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files_writer = RollingFiles {
+            dir:             dir.path().to_path_buf(),
+            blocks_per_file: 2,
+            open:            BTreeMap::new(),
+        };
+
+        files_writer.write_block(4, block_batch(4, 0));
+        files_writer.write_block(5, block_batch(5, 0));
+        assert!(files_writer.open.is_empty());
+        assert!(files(dir.path()).is_empty());
+    }
+
+    #[brontes_macros::test]
+    async fn test_close_flushes_open_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let writer = ActionParquetWriter::spawn(dir.path().to_path_buf(), 10).unwrap();
+
+        for block in [20, 21, 35] {
+            writer
+                .tx
+                .send(WriterMessage::Block(block, block_batch(block, 1)))
+                .unwrap();
+        }
+        writer.close().await.unwrap();
+
+        // partial ranges are named after the blocks that were written
+        assert_eq!(files(dir.path()), vec!["20-21.parquet", "35-35.parquet"]);
+        assert_eq!(read_blocks(dir.path().join("20-21.parquet")), vec![20, 21]);
+        assert_eq!(read_blocks(dir.path().join("35-35.parquet")), vec![35]);
+
+        // blocks after the close start a new file of the range
+        writer
+            .tx
+            .send(WriterMessage::Block(22, block_batch(22, 2)))
+            .unwrap();
+        writer.close().await.unwrap();
+
+        assert_eq!(files(dir.path()), vec!["20-21.parquet", "22-22.parquet", "35-35.parquet"]);
+        assert_eq!(read_blocks(dir.path().join("22-22.parquet")), vec![22, 22]);
+    }
+}
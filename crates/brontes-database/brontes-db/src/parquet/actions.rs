@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::Array,
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::{
+    normalized_actions::{
+        Action, NormalizedBurn, NormalizedLiquidation, NormalizedMint, NormalizedSwap,
+        NormalizedTransfer,
+    },
+    tree::{BlockTree, Root},
+    TreeSearchBuilder,
+};
+use itertools::Itertools;
+
+use super::{
+    normalized_actions::{
+        burns::get_normalized_burn_list_array, liquidations::get_normalized_liquidation_list_array,
+        mints::get_normalized_mint_list_array, swaps::get_normalized_swap_list_array,
+        transfers::get_normalized_transfer_list_array,
+    },
+    utils::{build_string_array, build_uint64_array},
+};
+
+/// The normalized actions of a transaction, the actions nested in
+/// aggregators, flash loans and batches are flattened
+#[derive(Default)]
+struct TxActions {
+    tx_hash:      String,
+    tx_index:     u64,
+    swaps:        Vec<NormalizedSwap>,
+    mints:        Vec<NormalizedMint>,
+    burns:        Vec<NormalizedBurn>,
+    transfers:    Vec<NormalizedTransfer>,
+    liquidations: Vec<NormalizedLiquidation>,
+}
+
+impl TxActions {
+    fn from_root(root: &Root<Action>) -> Self {
+        let mut actions = Self {
+            tx_hash: root.tx_hash.to_string(),
+            tx_index: root.position as u64,
+            ..Default::default()
+        };
+
+        let search_args = TreeSearchBuilder::default().with_actions([
+            Action::is_swap,
+            Action::is_mint,
+            Action::is_burn,
+            Action::is_transfer,
+            Action::is_liquidation,
+            Action::is_nested_action,
+        ]);
+        for action in root
+            .collect(&search_args)
+            .into_iter()
+            .flat_map(flatten_nested)
+        {
+            match action {
+                Action::Swap(swap) => actions.swaps.push(swap),
+                Action::SwapWithFee(swap) => actions.swaps.push(swap.swap),
+                Action::Mint(mint) => actions.mints.push(mint),
+                Action::Burn(burn) => actions.burns.push(burn),
+                Action::Transfer(transfer) => actions.transfers.push(transfer),
+                Action::Liquidation(liquidation) => actions.liquidations.push(liquidation),
+                _ => (),
+            }
+        }
+
+        actions
+    }
+
+    fn is_empty(&self) -> bool {
+        self.swaps.is_empty()
+            && self.mints.is_empty()
+            && self.burns.is_empty()
+            && self.transfers.is_empty()
+            && self.liquidations.is_empty()
+    }
+}
+
+fn flatten_nested(action: Action) -> Vec<Action> {
+    match action {
+        Action::Aggregator(aggregator) => aggregator.child_actions,
+        Action::FlashLoan(flash_loan) => flash_loan.fetch_underlying_actions().collect(),
+        Action::Batch(batch) => batch.fetch_underlying_actions().collect(),
+        action => vec![action],
+    }
+}
+
+/// One row per transaction of the block that has at least one swap, mint,
+/// burn, transfer or liquidation
+pub fn block_actions_to_record_batch(tree: &BlockTree<Action>) -> Result<RecordBatch, ArrowError> {
+    let block_number = tree.header.number;
+    let txes = tree
+        .tx_roots
+        .iter()
+        .map(TxActions::from_root)
+        .filter(|tx| !tx.is_empty())
+        .collect_vec();
+
+    let block_number_array = build_uint64_array(vec![block_number; txes.len()]);
+    let tx_hash_array = build_string_array(txes.iter().map(|tx| tx.tx_hash.clone()).collect());
+    let tx_index_array = build_uint64_array(txes.iter().map(|tx| tx.tx_index).collect());
+    let swaps_array =
+        get_normalized_swap_list_array(txes.iter().map(|tx| tx.swaps.iter().collect()).collect());
+    let mints_array =
+        get_normalized_mint_list_array(txes.iter().map(|tx| tx.mints.iter().collect()).collect());
+    let burns_array = get_normalized_burn_list_array(txes.iter().map(|tx| &tx.burns).collect());
+    let transfers_array =
+        get_normalized_transfer_list_array(txes.iter().map(|tx| &tx.transfers).collect());
+    let liquidations_array = get_normalized_liquidation_list_array(
+        txes.iter()
+            .map(|tx| tx.liquidations.iter().collect())
+            .collect(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("tx_hash", DataType::Utf8, false),
+        Field::new("tx_index", DataType::UInt64, false),
+        Field::new("swaps", swaps_array.data_type().clone(), false),
+        Field::new("mints", mints_array.data_type().clone(), false),
+        Field::new("burns", burns_array.data_type().clone(), false),
+        Field::new("transfers", transfers_array.data_type().clone(), false),
+        Field::new("liquidations", liquidations_array.data_type().clone(), false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(tx_hash_array),
+            Arc::new(tx_index_array),
+            Arc::new(swaps_array),
+            Arc::new(mints_array),
+            Arc::new(burns_array),
+            Arc::new(transfers_array),
+            Arc::new(liquidations_array),
+        ],
+    )
+}
@@ -0,0 +1,178 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::UInt64Array,
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::metadata::BlockMetadataInner;
+use itertools::Itertools;
+
+use super::utils::{
+    build_string_array, build_uint64_array, get_list_string_array_from_owned,
+    get_string_array_from_owned, optional_u128_to_binary_array,
+};
+
+pub fn block_info_to_record_batch(
+    block_info: Vec<(u64, BlockMetadataInner)>,
+) -> Result<RecordBatch, ArrowError> {
+    let block_number_array = build_uint64_array(block_info.iter().map(|info| info.0).collect());
+    let block_hash_array = build_string_array(
+        block_info
+            .iter()
+            .map(|info| format!("{:#066x}", info.1.block_hash))
+            .collect(),
+    );
+    let block_timestamp_array = build_uint64_array(
+        block_info
+            .iter()
+            .map(|info| info.1.block_timestamp)
+            .collect(),
+    );
+    let relay_timestamp_array = UInt64Array::from(
+        block_info
+            .iter()
+            .map(|info| info.1.relay_timestamp)
+            .collect_vec(),
+    );
+    let p2p_timestamp_array = UInt64Array::from(
+        block_info
+            .iter()
+            .map(|info| info.1.p2p_timestamp)
+            .collect_vec(),
+    );
+    let proposer_fee_recipient_array = get_string_array_from_owned(
+        block_info
+            .iter()
+            .map(|info| {
+                info.1
+                    .proposer_fee_recipient
+                    .map(|recipient| recipient.to_string())
+            })
+            .collect_vec(),
+    );
+    let proposer_mev_reward_array = optional_u128_to_binary_array(
+        block_info
+            .iter()
+            .map(|info| info.1.proposer_mev_reward)
+            .collect(),
+    );
+    let private_flow_array = get_list_string_array_from_owned(
+        block_info
+            .iter()
+            .map(|info| {
+                info.1
+                    .private_flow
+                    .iter()
+                    .map(|tx| tx.to_string())
+                    .collect_vec()
+            })
+            .collect_vec(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("block_hash", DataType::Utf8, false),
+        Field::new("block_timestamp", DataType::UInt64, false),
+        Field::new("relay_timestamp", DataType::UInt64, true),
+        Field::new("p2p_timestamp", DataType::UInt64, true),
+        Field::new("proposer_fee_recipient", DataType::Utf8, true),
+        Field::new("proposer_mev_reward", DataType::Binary, true),
+        Field::new(
+            "private_flow",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            true,
+        ),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(block_hash_array),
+            Arc::new(block_timestamp_array),
+            Arc::new(relay_timestamp_array),
+            Arc::new(p2p_timestamp_array),
+            Arc::new(proposer_fee_recipient_array),
+            Arc::new(proposer_mev_reward_array),
+            Arc::new(private_flow_array),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Address, B256, U256};
+    use arrow::array::{Array, BinaryArray, ListArray, StringArray};
+
+    use super::*;
+    use crate::parquet::round_trip;
+
+    #[test]
+    fn test_block_info_round_trip() {
+        let fee_recipient = Address::repeat_byte(7);
+        let private_tx = B256::repeat_byte(9);
+        let block_info = vec![
+            (
+                1,
+                BlockMetadataInner {
+                    block_hash:             U256::from(11),
+                    block_timestamp:        1_700_000_000,
+                    relay_timestamp:        Some(1_700_000_001_000),
+                    p2p_timestamp:          None,
+                    proposer_fee_recipient: Some(fee_recipient),
+                    proposer_mev_reward:    Some(5 * 10u128.pow(17)),
+                    private_flow:           vec![private_tx],
+                },
+            ),
+            (
+                2,
+                BlockMetadataInner {
+                    block_hash: U256::from(12),
+                    block_timestamp: 1_700_000_012,
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        let record_batch = block_info_to_record_batch(block_info).unwrap();
+        let read = round_trip(record_batch.clone());
+        assert_eq!(read, record_batch);
+        assert_eq!(read.num_rows(), 2);
+
+        let column = |name: &str| read.column_by_name(name).unwrap().clone();
+        let block_hashes = column("block_hash");
+        let block_hashes = block_hashes.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(block_hashes.value(0), format!("{:#066x}", U256::from(11)));
+        let relay_timestamps = column("relay_timestamp");
+        let relay_timestamps = relay_timestamps
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(relay_timestamps.value(0), 1_700_000_001_000);
+        assert!(relay_timestamps.is_null(1));
+        let p2p_timestamps = column("p2p_timestamp");
+        assert_eq!(p2p_timestamps.null_count(), 2);
+        let fee_recipients = column("proposer_fee_recipient");
+        let fee_recipients = fee_recipients
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(fee_recipients.value(0), fee_recipient.to_string());
+        assert!(fee_recipients.is_null(1));
+        let mev_rewards = column("proposer_mev_reward");
+        let mev_rewards = mev_rewards.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(mev_rewards.value(0), (5 * 10u128.pow(17)).to_be_bytes());
+        assert!(mev_rewards.is_null(1));
+        let private_flow = column("private_flow");
+        let private_flow = private_flow.as_any().downcast_ref::<ListArray>().unwrap();
+        let first_block_flow = private_flow.value(0);
+        let first_block_flow = first_block_flow
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(first_block_flow.value(0), private_tx.to_string());
+        assert!(private_flow.is_null(1));
+    }
+}
@@ -6,16 +6,26 @@ use arrow::{
     error::ArrowError,
     record_batch::RecordBatch,
 };
-use brontes_types::db::builder::BuilderInfo;
+use brontes_types::{
+    db::builder::{BuilderInfo, ProposerPaymentStats},
+    FastHashMap,
+};
 use itertools::Itertools;
 
 use super::utils::{
-    build_string_array, get_list_string_array_from_owned, get_string_array_from_owned,
+    build_string_array, build_uint64_array, get_list_string_array_from_owned,
+    get_string_array_from_owned, optional_i128_to_binary_array,
 };
 
 pub fn builder_info_to_record_batch(
     builder_info: Vec<(Address, BuilderInfo)>,
+    proposer_payments: FastHashMap<Address, ProposerPaymentStats>,
 ) -> Result<RecordBatch, ArrowError> {
+    let payments = builder_info
+        .iter()
+        .map(|info| proposer_payments.get(&info.0).copied().unwrap_or_default())
+        .collect_vec();
+
     let address_array = build_string_array(
         builder_info
             .iter()
@@ -71,6 +81,21 @@ pub fn builder_info_to_record_batch(
             .collect_vec(),
     );
 
+    let payment_blocks_array =
+        build_uint64_array(payments.iter().map(|stats| stats.blocks).collect_vec());
+    let underpaid_blocks_array = build_uint64_array(
+        payments
+            .iter()
+            .map(|stats| stats.underpaid_blocks)
+            .collect_vec(),
+    );
+    let total_payment_delta_array = optional_i128_to_binary_array(
+        payments
+            .iter()
+            .map(|stats| Some(stats.total_payment_delta))
+            .collect_vec(),
+    );
+
     let schema = Schema::new(vec![
         Field::new("address", DataType::Utf8, false),
         Field::new("fund", DataType::Utf8, true),
@@ -90,6 +115,9 @@ pub fn builder_info_to_record_batch(
             true,
         ),
         Field::new("collateral_addr", DataType::Utf8, true),
+        Field::new("proposer_payment_blocks", DataType::UInt64, false),
+        Field::new("underpaid_blocks", DataType::UInt64, false),
+        Field::new("total_payment_delta", DataType::Binary, false),
     ]);
 
     RecordBatch::try_new(
@@ -101,6 +129,9 @@ pub fn builder_info_to_record_batch(
             Arc::new(searchers_eoa_array),
             Arc::new(searchers_contract_array),
             Arc::new(ultrasound_relay_address_array),
+            Arc::new(payment_blocks_array),
+            Arc::new(underpaid_blocks_array),
+            Arc::new(total_payment_delta_array),
         ],
     )
 }
@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use arrow::{
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::{db::cex::quotes::CexPriceMap, ToFloatNearest};
+
+use super::utils::{build_float64_array, build_string_array, build_uint64_array};
+
+/// One row per quote snapshot of each exchange and pair
+pub fn cex_quotes_to_record_batch(
+    cex_quotes: Vec<(u64, CexPriceMap)>,
+) -> Result<RecordBatch, ArrowError> {
+    let rows = cex_quotes
+        .iter()
+        .flat_map(|(block_number, price_map)| {
+            price_map.quotes.iter().flat_map(move |(exchange, pairs)| {
+                pairs.iter().flat_map(move |(pair, quotes)| {
+                    quotes
+                        .iter()
+                        .map(move |quote| (*block_number, exchange, pair, quote))
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let block_number_array = build_uint64_array(rows.iter().map(|row| row.0).collect());
+    let exchange_array = build_string_array(rows.iter().map(|row| row.1.to_string()).collect());
+    let token0_array = build_string_array(rows.iter().map(|row| row.2 .0.to_string()).collect());
+    let token1_array = build_string_array(rows.iter().map(|row| row.2 .1.to_string()).collect());
+    let timestamp_array = build_uint64_array(rows.iter().map(|row| row.3.timestamp).collect());
+    let bid_price_array = build_float64_array(
+        rows.iter()
+            .map(|row| row.3.price.0.clone().to_float())
+            .collect(),
+    );
+    let ask_price_array = build_float64_array(
+        rows.iter()
+            .map(|row| row.3.price.1.clone().to_float())
+            .collect(),
+    );
+    let bid_amount_array = build_float64_array(
+        rows.iter()
+            .map(|row| row.3.amount.0.clone().to_float())
+            .collect(),
+    );
+    let ask_amount_array = build_float64_array(
+        rows.iter()
+            .map(|row| row.3.amount.1.clone().to_float())
+            .collect(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("exchange", DataType::Utf8, false),
+        Field::new("token0", DataType::Utf8, false),
+        Field::new("token1", DataType::Utf8, false),
+        Field::new("timestamp", DataType::UInt64, false),
+        Field::new("bid_price", DataType::Float64, false),
+        Field::new("ask_price", DataType::Float64, false),
+        Field::new("bid_amount", DataType::Float64, false),
+        Field::new("ask_amount", DataType::Float64, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(exchange_array),
+            Arc::new(token0_array),
+            Arc::new(token1_array),
+            Arc::new(timestamp_array),
+            Arc::new(bid_price_array),
+            Arc::new(ask_price_array),
+            Arc::new(bid_amount_array),
+            Arc::new(ask_amount_array),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::Address;
+    use arrow::array::{Float64Array, StringArray, UInt64Array};
+    use brontes_types::{
+        db::cex::{quotes::CexQuote, CexExchange},
+        pair::Pair,
+    };
+    use malachite::Rational;
+
+    use super::*;
+    use crate::parquet::round_trip;
+
+    fn quote(exchange: CexExchange, timestamp: u64, bid: u64, ask: u64) -> CexQuote {
+        CexQuote {
+            exchange,
+            timestamp,
+            price: (Rational::from(bid), Rational::from(ask)),
+            amount: (Rational::from(10), Rational::from_unsigneds(1u64, 2u64)),
+        }
+    }
+
+    fn price_map(exchange: CexExchange, pair: Pair, quotes: Vec<CexQuote>) -> CexPriceMap {
+        let mut price_map = CexPriceMap::new();
+        price_map
+            .quotes
+            .entry(exchange)
+            .or_default()
+            .insert(pair, quotes);
+        price_map
+    }
+
+    #[test]
+    fn test_cex_quotes_round_trip() {
+        let pair = Pair(Address::repeat_byte(1), Address::repeat_byte(2));
+        let quotes = vec![
+            (
+                1,
+                price_map(
+                    CexExchange::Binance,
+                    pair,
+                    vec![
+                        quote(CexExchange::Binance, 100, 1_999, 2_001),
+                        quote(CexExchange::Binance, 200, 2_000, 2_002),
+                    ],
+                ),
+            ),
+            (
+                2,
+                price_map(
+                    CexExchange::Coinbase,
+                    pair,
+                    vec![quote(CexExchange::Coinbase, 300, 2_010, 2_011)],
+                ),
+            ),
+        ];
+
+        let record_batch = cex_quotes_to_record_batch(quotes).unwrap();
+        let read = round_trip(record_batch.clone());
+        assert_eq!(read, record_batch);
+        assert_eq!(read.num_rows(), 3);
+
+        let column = |name: &str| read.column_by_name(name).unwrap().clone();
+        let block_numbers = column("block_number");
+        let block_numbers = block_numbers
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(block_numbers.values(), &[1, 1, 2]);
+        let exchanges = column("exchange");
+        let exchanges = exchanges.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(exchanges.value(0), "Binance");
+        assert_eq!(exchanges.value(2), "Coinbase");
+        let timestamps = column("timestamp");
+        let timestamps = timestamps.as_any().downcast_ref::<UInt64Array>().unwrap();
+        assert_eq!(timestamps.values(), &[100, 200, 300]);
+        let bid_prices = column("bid_price");
+        let bid_prices = bid_prices.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(bid_prices.values(), &[1_999.0, 2_000.0, 2_010.0]);
+        let ask_amounts = column("ask_amount");
+        let ask_amounts = ask_amounts.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(ask_amounts.values(), &[0.5, 0.5, 0.5]);
+    }
+}
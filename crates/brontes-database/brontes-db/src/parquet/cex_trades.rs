@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use arrow::{
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::{db::cex::trades::CexTradeMap, ToFloatNearest};
+
+use super::utils::{build_float64_array, build_string_array, build_uint64_array};
+
+/// One row per trade of each exchange and pair
+pub fn cex_trades_to_record_batch(
+    cex_trades: Vec<(u64, CexTradeMap)>,
+) -> Result<RecordBatch, ArrowError> {
+    let rows = cex_trades
+        .iter()
+        .flat_map(|(block_number, trade_map)| {
+            trade_map.0.iter().flat_map(move |(exchange, pairs)| {
+                pairs.iter().flat_map(move |(pair, trades)| {
+                    trades
+                        .iter()
+                        .map(move |trade| (*block_number, exchange, pair, trade))
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let block_number_array = build_uint64_array(rows.iter().map(|row| row.0).collect());
+    let exchange_array = build_string_array(rows.iter().map(|row| row.1.to_string()).collect());
+    let token0_array = build_string_array(rows.iter().map(|row| row.2 .0.to_string()).collect());
+    let token1_array = build_string_array(rows.iter().map(|row| row.2 .1.to_string()).collect());
+    let timestamp_array = build_uint64_array(rows.iter().map(|row| row.3.timestamp).collect());
+    let price_array = build_float64_array(
+        rows.iter()
+            .map(|row| row.3.price.clone().to_float())
+            .collect(),
+    );
+    let amount_array = build_float64_array(
+        rows.iter()
+            .map(|row| row.3.amount.clone().to_float())
+            .collect(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("exchange", DataType::Utf8, false),
+        Field::new("token0", DataType::Utf8, false),
+        Field::new("token1", DataType::Utf8, false),
+        Field::new("timestamp", DataType::UInt64, false),
+        Field::new("price", DataType::Float64, false),
+        Field::new("amount", DataType::Float64, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(exchange_array),
+            Arc::new(token0_array),
+            Arc::new(token1_array),
+            Arc::new(timestamp_array),
+            Arc::new(price_array),
+            Arc::new(amount_array),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::Address;
+    use arrow::array::{Float64Array, StringArray, UInt64Array};
+    use brontes_types::{
+        db::cex::{trades::CexTrades, CexExchange},
+        pair::Pair,
+        FastHashMap,
+    };
+    use malachite::Rational;
+
+    use super::*;
+    use crate::parquet::round_trip;
+
+    fn trade(exchange: CexExchange, timestamp: u64, price: u64, amount: Rational) -> CexTrades {
+        CexTrades { exchange, timestamp, price: Rational::from(price), amount }
+    }
+
+    fn trade_map(exchange: CexExchange, pair: Pair, trades: Vec<CexTrades>) -> CexTradeMap {
+        let mut pairs = FastHashMap::default();
+        pairs.insert(pair, trades);
+        let mut trade_map = CexTradeMap::default();
+        trade_map.0.insert(exchange, pairs);
+        trade_map
+    }
+
+    #[test]
+    fn test_cex_trades_round_trip() {
+        let pair = Pair(Address::repeat_byte(1), Address::repeat_byte(2));
+        let trades = vec![
+            (
+                1,
+                trade_map(
+                    CexExchange::Binance,
+                    pair,
+                    vec![
+                        trade(CexExchange::Binance, 100, 2_000, Rational::from(3)),
+                        trade(
+                            CexExchange::Binance,
+                            150,
+                            2_001,
+                            Rational::from_unsigneds(1u64, 4u64),
+                        ),
+                    ],
+                ),
+            ),
+            (
+                2,
+                trade_map(
+                    CexExchange::Okex,
+                    pair,
+                    vec![trade(CexExchange::Okex, 300, 1_990, Rational::from(1))],
+                ),
+            ),
+        ];
+
+        let record_batch = cex_trades_to_record_batch(trades).unwrap();
+        let read = round_trip(record_batch.clone());
+        assert_eq!(read, record_batch);
+        assert_eq!(read.num_rows(), 3);
+
+        let column = |name: &str| read.column_by_name(name).unwrap().clone();
+        let block_numbers = column("block_number");
+        let block_numbers = block_numbers
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(block_numbers.values(), &[1, 1, 2]);
+        let exchanges = column("exchange");
+        let exchanges = exchanges.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(exchanges.value(1), "Binance");
+        assert_eq!(exchanges.value(2), "Okex");
+        let token1 = column("token1");
+        let token1 = token1.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(token1.value(0), pair.1.to_string());
+        let prices = column("price");
+        let prices = prices.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(prices.values(), &[2_000.0, 2_001.0, 1_990.0]);
+        let amounts = column("amount");
+        let amounts = amounts.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(amounts.values(), &[3.0, 0.25, 1.0]);
+    }
+}
@@ -0,0 +1,152 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::BooleanArray,
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::{db::dex::DexQuotes, ToFloatNearest};
+
+use super::utils::{build_float64_array, build_string_array, build_uint64_array};
+
+/// One row per priced pair of each transaction
+pub fn dex_quotes_to_record_batch(
+    dex_quotes: Vec<(u64, DexQuotes)>,
+) -> Result<RecordBatch, ArrowError> {
+    let rows = dex_quotes
+        .iter()
+        .flat_map(|(block_number, quotes)| {
+            quotes
+                .0
+                .iter()
+                .enumerate()
+                .filter_map(|(tx_idx, quote)| Some((tx_idx, quote.as_ref()?)))
+                .flat_map(move |(tx_idx, quote)| {
+                    quote
+                        .iter()
+                        .map(move |(pair, price)| (*block_number, tx_idx as u64, pair, price))
+                })
+        })
+        .collect::<Vec<_>>();
+
+    let block_number_array = build_uint64_array(rows.iter().map(|row| row.0).collect());
+    let tx_idx_array = build_uint64_array(rows.iter().map(|row| row.1).collect());
+    let token0_array = build_string_array(rows.iter().map(|row| row.2 .0.to_string()).collect());
+    let token1_array = build_string_array(rows.iter().map(|row| row.2 .1.to_string()).collect());
+    let goes_through0_array = build_string_array(
+        rows.iter()
+            .map(|row| row.3.goes_through.0.to_string())
+            .collect(),
+    );
+    let goes_through1_array = build_string_array(
+        rows.iter()
+            .map(|row| row.3.goes_through.1.to_string())
+            .collect(),
+    );
+    let pre_state_array = build_float64_array(
+        rows.iter()
+            .map(|row| row.3.pre_state.clone().to_float())
+            .collect(),
+    );
+    let post_state_array = build_float64_array(
+        rows.iter()
+            .map(|row| row.3.post_state.clone().to_float())
+            .collect(),
+    );
+    let is_transfer_array =
+        BooleanArray::from(rows.iter().map(|row| row.3.is_transfer).collect::<Vec<_>>());
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("tx_idx", DataType::UInt64, false),
+        Field::new("token0", DataType::Utf8, false),
+        Field::new("token1", DataType::Utf8, false),
+        Field::new("goes_through_token0", DataType::Utf8, false),
+        Field::new("goes_through_token1", DataType::Utf8, false),
+        Field::new("pre_state", DataType::Float64, false),
+        Field::new("post_state", DataType::Float64, false),
+        Field::new("is_transfer", DataType::Boolean, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(tx_idx_array),
+            Arc::new(token0_array),
+            Arc::new(token1_array),
+            Arc::new(goes_through0_array),
+            Arc::new(goes_through1_array),
+            Arc::new(pre_state_array),
+            Arc::new(post_state_array),
+            Arc::new(is_transfer_array),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::Address;
+    use arrow::array::{Float64Array, StringArray, UInt64Array};
+    use brontes_types::{
+        db::dex::{DexPrices, DexQuotes},
+        pair::Pair,
+        FastHashMap,
+    };
+    use malachite::Rational;
+
+    use super::*;
+    use crate::parquet::round_trip;
+
+    fn quote(
+        pair: Pair,
+        pre_state: Rational,
+        post_state: Rational,
+    ) -> FastHashMap<Pair, DexPrices> {
+        let mut quote = FastHashMap::default();
+        quote.insert(
+            pair,
+            DexPrices { pre_state, post_state, goes_through: pair, is_transfer: false },
+        );
+        quote
+    }
+
+    #[test]
+    fn test_dex_quotes_round_trip() {
+        let pair = Pair(Address::repeat_byte(1), Address::repeat_byte(2));
+        let quotes = vec![
+            (
+                1,
+                DexQuotes(vec![
+                    None,
+                    Some(quote(pair, Rational::from(2), Rational::from_unsigneds(5u64, 2u64))),
+                ]),
+            ),
+            (2, DexQuotes(vec![Some(quote(pair.flip(), Rational::from(3), Rational::from(4)))])),
+        ];
+
+        let record_batch = dex_quotes_to_record_batch(quotes).unwrap();
+        let read = round_trip(record_batch.clone());
+        assert_eq!(read, record_batch);
+        assert_eq!(read.num_rows(), 2);
+
+        let column = |name: &str| read.column_by_name(name).unwrap().clone();
+        let block_numbers = column("block_number");
+        let block_numbers = block_numbers
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(block_numbers.values(), &[1, 2]);
+        let tx_idx = column("tx_idx");
+        let tx_idx = tx_idx.as_any().downcast_ref::<UInt64Array>().unwrap();
+        assert_eq!(tx_idx.values(), &[1, 0]);
+        let token0 = column("token0");
+        let token0 = token0.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(token0.value(0), pair.0.to_string());
+        assert_eq!(token0.value(1), pair.1.to_string());
+        let post_state = column("post_state");
+        let post_state = post_state.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(post_state.values(), &[2.5, 4.0]);
+    }
+}
@@ -2,8 +2,8 @@ use std::sync::Arc;
 
 use arrow::{
     array::{
-        Array, ArrayRef, Float64Array, Float64Builder, StringArray, StringBuilder, StructArray,
-        UInt64Builder,
+        Array, ArrayRef, BooleanArray, Float64Array, Float64Builder, Int64Array, StringArray,
+        StringBuilder, StructArray, UInt64Array, UInt64Builder,
     },
     datatypes::{DataType, Field, Schema},
     error::ArrowError,
@@ -13,7 +13,7 @@ use brontes_types::mev::MevBlock;
 
 use super::utils::{
     build_float64_array, build_record_batch, build_string_array, build_uint64_array,
-    u128_to_binary_array,
+    optional_i128_to_binary_array, optional_u128_to_binary_array, u128_to_binary_array,
 };
 
 pub fn mev_block_to_record_batch(mev_blocks: Vec<MevBlock>) -> Result<RecordBatch, ArrowError> {
@@ -70,6 +70,65 @@ pub fn mev_block_to_record_batch(mev_blocks: Vec<MevBlock>) -> Result<RecordBatc
             .collect(),
     );
 
+    let payment_verified_array = BooleanArray::from(
+        mev_blocks
+            .iter()
+            .map(|mb| mb.payment_verified)
+            .collect::<Vec<_>>(),
+    );
+    let payment_delta_array =
+        optional_i128_to_binary_array(mev_blocks.iter().map(|mb| mb.payment_delta).collect());
+
+    let failed_attempt_count_array = build_uint64_array(
+        mev_blocks
+            .iter()
+            .map(|mb| mb.failed_attempts.0.len() as u64)
+            .collect(),
+    );
+    let failed_attempt_gas_usd_array = build_float64_array(
+        mev_blocks
+            .iter()
+            .map(|mb| mb.failed_attempts.total_gas_paid_usd())
+            .collect(),
+    );
+
+    let bid_count_array = UInt64Array::from(
+        mev_blocks
+            .iter()
+            .map(|mb| mb.bid_analysis.as_ref().map(|a| a.bid_count))
+            .collect::<Vec<_>>(),
+    );
+    let winning_bid_array = optional_u128_to_binary_array(
+        mev_blocks
+            .iter()
+            .map(|mb| mb.bid_analysis.as_ref().map(|a| a.winning_bid))
+            .collect(),
+    );
+    let winning_bid_time_array = Int64Array::from(
+        mev_blocks
+            .iter()
+            .map(|mb| mb.bid_analysis.as_ref().map(|a| a.winning_bid_time_ms))
+            .collect::<Vec<_>>(),
+    );
+    let runner_up_bid_array = optional_u128_to_binary_array(
+        mev_blocks
+            .iter()
+            .map(|mb| mb.bid_analysis.as_ref().and_then(|a| a.runner_up_bid))
+            .collect(),
+    );
+    let bid_shading_array = Float64Array::from(
+        mev_blocks
+            .iter()
+            .map(|mb| mb.bid_analysis.as_ref().and_then(|a| a.bid_shading))
+            .collect::<Vec<_>>(),
+    );
+    let proposer_delay_array = Int64Array::from(
+        mev_blocks
+            .iter()
+            .map(|mb| mb.bid_analysis.as_ref().map(|a| a.proposer_delay_ms))
+            .collect::<Vec<_>>(),
+    );
+
     let mev_count_array = get_mev_count_array(&mev_blocks);
     let (proposer_fee_recipient_array, proposer_profit_usd_array) =
         get_proposer_arrays(&mev_blocks);
@@ -94,7 +153,17 @@ pub fn mev_block_to_record_batch(mev_blocks: Vec<MevBlock>) -> Result<RecordBatc
             Arc::new(proposer_fee_recipient_array),
             Arc::new(proposer_mev_reward_array),
             Arc::new(proposer_profit_usd_array),
+            Arc::new(payment_verified_array),
+            Arc::new(payment_delta_array),
             Arc::new(total_mev_profit_usds_array),
+            Arc::new(failed_attempt_count_array),
+            Arc::new(failed_attempt_gas_usd_array),
+            Arc::new(bid_count_array),
+            Arc::new(winning_bid_array),
+            Arc::new(winning_bid_time_array),
+            Arc::new(runner_up_bid_array),
+            Arc::new(bid_shading_array),
+            Arc::new(proposer_delay_array),
         ],
     )
 }
@@ -116,7 +185,17 @@ fn build_schema(mev_count_array: &StructArray) -> Schema {
         Field::new("proposer_fee_recipient", DataType::Utf8, true),
         Field::new("proposer_mev_reward", DataType::Binary, true),
         Field::new("proposer_profit_usd", DataType::Float64, true),
+        Field::new("payment_verified", DataType::Boolean, true),
+        Field::new("payment_delta", DataType::Binary, true),
         Field::new("total_mev_profit_usd", DataType::Float64, false),
+        Field::new("failed_attempt_count", DataType::UInt64, false),
+        Field::new("failed_attempt_gas_usd", DataType::Float64, false),
+        Field::new("bid_count", DataType::UInt64, true),
+        Field::new("winning_bid", DataType::Binary, true),
+        Field::new("winning_bid_time_ms", DataType::Int64, true),
+        Field::new("runner_up_bid", DataType::Binary, true),
+        Field::new("bid_shading", DataType::Float64, true),
+        Field::new("proposer_delay_ms", DataType::Int64, true),
     ])
 }
 
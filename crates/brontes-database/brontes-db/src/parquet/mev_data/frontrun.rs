@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::Array,
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::mev::Frontrun;
+use itertools::Itertools;
+
+use crate::parquet::{
+    normalized_actions::{
+        gas_details::{get_gas_details_array, get_gas_details_list_array},
+        swaps::get_normalized_swap_list_array,
+    },
+    utils::{
+        build_uint64_array, get_list_bool_array_from_owned, get_list_string_array_from_owned,
+        get_string_array_from_owned,
+    },
+};
+
+pub fn frontrun_to_record_batch(frontruns: Vec<Frontrun>) -> Result<RecordBatch, ArrowError> {
+    let block_number_array =
+        build_uint64_array(frontruns.iter().map(|fr| fr.block_number).collect());
+
+    let frontrun_tx_hash_array = get_string_array_from_owned(
+        frontruns
+            .iter()
+            .map(|fr| Some(fr.frontrun_tx_hash.to_string()))
+            .collect_vec(),
+    );
+
+    let frontrun_swaps_array = get_normalized_swap_list_array(
+        frontruns
+            .iter()
+            .map(|fr| fr.frontrun_swaps.iter().collect_vec())
+            .collect_vec(),
+    );
+
+    let frontrun_gas_details_array =
+        get_gas_details_array(frontruns.iter().map(|fr| fr.frontrun_gas_details).collect());
+
+    let victim_tx_hashes_array = get_list_string_array_from_owned(
+        frontruns
+            .iter()
+            .map(|fr| {
+                fr.victim_tx_hashes
+                    .iter()
+                    .map(|hash| hash.to_string())
+                    .collect_vec()
+            })
+            .collect_vec(),
+    );
+
+    let victim_reverted_array = get_list_bool_array_from_owned(
+        frontruns
+            .iter()
+            .map(|fr| fr.victim_reverted.clone())
+            .collect_vec(),
+    );
+
+    let victim_gas_details_array = get_gas_details_list_array(
+        frontruns
+            .iter()
+            .map(|fr| &fr.victim_gas_details)
+            .collect_vec(),
+    );
+
+    let priority_fee_outbids_array = get_list_string_array_from_owned(
+        frontruns
+            .iter()
+            .map(|fr| {
+                fr.priority_fee_outbids()
+                    .into_iter()
+                    .map(|outbid| outbid.to_string())
+                    .collect_vec()
+            })
+            .collect_vec(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("frontrun_tx_hash", DataType::Utf8, false),
+        Field::new("frontrun_swaps", frontrun_swaps_array.data_type().clone(), false),
+        Field::new("frontrun_gas_details", frontrun_gas_details_array.data_type().clone(), false),
+        Field::new("victim_tx_hashes", victim_tx_hashes_array.data_type().clone(), false),
+        Field::new("victim_reverted", victim_reverted_array.data_type().clone(), false),
+        Field::new("victim_gas_details", victim_gas_details_array.data_type().clone(), false),
+        Field::new("priority_fee_outbids", priority_fee_outbids_array.data_type().clone(), false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(frontrun_tx_hash_array),
+            Arc::new(frontrun_swaps_array),
+            Arc::new(frontrun_gas_details_array),
+            Arc::new(victim_tx_hashes_array),
+            Arc::new(victim_reverted_array),
+            Arc::new(victim_gas_details_array),
+            Arc::new(priority_fee_outbids_array),
+        ],
+    )
+}
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::Array,
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::mev::LongTail;
+use itertools::Itertools;
+
+use crate::parquet::{
+    normalized_actions::{
+        gas_details::{get_gas_details_array, get_gas_details_list_array},
+        swaps::get_normalized_swap_list_array,
+    },
+    utils::{build_uint64_array, get_list_string_array_from_owned, get_string_array_from_owned},
+};
+
+pub fn long_tail_to_record_batch(long_tails: Vec<LongTail>) -> Result<RecordBatch, ArrowError> {
+    let block_number_array =
+        build_uint64_array(long_tails.iter().map(|lt| lt.block_number).collect());
+
+    let pool_array = get_string_array_from_owned(
+        long_tails
+            .iter()
+            .map(|lt| Some(lt.pool.to_string()))
+            .collect(),
+    );
+
+    let token_array = get_string_array_from_owned(
+        long_tails
+            .iter()
+            .map(|lt| Some(lt.token.to_string()))
+            .collect(),
+    );
+
+    let launch_block_array =
+        build_uint64_array(long_tails.iter().map(|lt| lt.launch_block).collect());
+
+    let entry_tx_hashes_array = get_list_string_array_from_owned(
+        long_tails
+            .iter()
+            .map(|lt| {
+                lt.entry_tx_hashes
+                    .iter()
+                    .map(|hash| hash.to_string())
+                    .collect_vec()
+            })
+            .collect_vec(),
+    );
+
+    let entry_swaps_array = get_normalized_swap_list_array(
+        long_tails
+            .iter()
+            .map(|lt| lt.entry_swaps.iter().flatten().collect_vec())
+            .collect_vec(),
+    );
+
+    let entry_gas_details_array = get_gas_details_list_array(
+        long_tails
+            .iter()
+            .map(|lt| &lt.entry_gas_details)
+            .collect_vec(),
+    );
+
+    let exit_tx_hash_array = get_string_array_from_owned(
+        long_tails
+            .iter()
+            .map(|lt| Some(lt.exit_tx_hash.to_string()))
+            .collect_vec(),
+    );
+
+    let exit_swaps_array = get_normalized_swap_list_array(
+        long_tails
+            .iter()
+            .map(|lt| lt.exit_swaps.iter().collect_vec())
+            .collect_vec(),
+    );
+
+    let exit_gas_details_array =
+        get_gas_details_array(long_tails.iter().map(|lt| lt.exit_gas_details).collect());
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("pool", DataType::Utf8, false),
+        Field::new("token", DataType::Utf8, false),
+        Field::new("launch_block", DataType::UInt64, false),
+        Field::new("entry_tx_hashes", entry_tx_hashes_array.data_type().clone(), false),
+        Field::new("entry_swaps", entry_swaps_array.data_type().clone(), false),
+        Field::new("entry_gas_details", entry_gas_details_array.data_type().clone(), false),
+        Field::new("exit_tx_hash", DataType::Utf8, false),
+        Field::new("exit_swaps", exit_swaps_array.data_type().clone(), false),
+        Field::new("exit_gas_details", exit_gas_details_array.data_type().clone(), false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(pool_array),
+            Arc::new(token_array),
+            Arc::new(launch_block_array),
+            Arc::new(entry_tx_hashes_array),
+            Arc::new(entry_swaps_array),
+            Arc::new(entry_gas_details_array),
+            Arc::new(exit_tx_hash_array),
+            Arc::new(exit_swaps_array),
+            Arc::new(exit_gas_details_array),
+        ],
+    )
+}
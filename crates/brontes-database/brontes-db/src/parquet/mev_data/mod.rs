@@ -1,16 +1,20 @@
 mod atomic_arb;
 
 mod cex_dex;
+mod frontrun;
 mod jit;
 mod jit_sandwich;
 mod liquidation;
+mod long_tail;
 mod sandwich;
 mod searcher_tx;
 
 pub use atomic_arb::*;
+pub use frontrun::*;
 //pub use cex_dex::*;
 pub use jit::*;
 pub use jit_sandwich::*;
 pub use liquidation::*;
+pub use long_tail::*;
 pub use sandwich::*;
 pub use searcher_tx::*;
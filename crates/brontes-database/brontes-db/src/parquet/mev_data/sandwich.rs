@@ -14,7 +14,10 @@ use crate::parquet::{
         gas_details::{get_gas_details_array, get_gas_details_list_array},
         swaps::get_normalized_swap_list_array,
     },
-    utils::{get_list_string_array_from_owned, get_string_array_from_owned},
+    utils::{
+        get_list_float_array_from_owned, get_list_string_array_from_owned,
+        get_string_array_from_owned,
+    },
 };
 
 pub fn sandwich_to_record_batch(sandwiches: Vec<Sandwich>) -> Result<RecordBatch, ArrowError> {
@@ -63,6 +66,14 @@ pub fn sandwich_to_record_batch(sandwiches: Vec<Sandwich>) -> Result<RecordBatch
             .collect_vec(),
     );
 
+    let (victim_counterfactual_out, victim_loss_usd): (Vec<_>, Vec<_>) = sandwiches
+        .iter()
+        .map(|s| s.victim_swap_losses().unzip::<_, _, Vec<_>, Vec<_>>())
+        .unzip();
+    let victim_counterfactual_out_array =
+        get_list_float_array_from_owned(victim_counterfactual_out);
+    let victim_loss_usd_array = get_list_float_array_from_owned(victim_loss_usd);
+
     let victim_swaps_gas_details_array = get_gas_details_list_array(
         sandwiches
             .iter()
@@ -97,6 +108,12 @@ pub fn sandwich_to_record_batch(sandwiches: Vec<Sandwich>) -> Result<RecordBatch
             false,
         ),
         Field::new("victim_swaps", victim_swaps_array.data_type().clone(), false),
+        Field::new(
+            "victim_counterfactual_out",
+            victim_counterfactual_out_array.data_type().clone(),
+            false,
+        ),
+        Field::new("victim_loss_usd", victim_loss_usd_array.data_type().clone(), false),
         Field::new(
             "victim_swaps_gas_details",
             victim_swaps_gas_details_array.data_type().clone(),
@@ -115,6 +132,8 @@ pub fn sandwich_to_record_batch(sandwiches: Vec<Sandwich>) -> Result<RecordBatch
             Arc::new(frontrun_gas_details_array),
             Arc::new(victim_swaps_tx_hashes_array),
             Arc::new(victim_swaps_array),
+            Arc::new(victim_counterfactual_out_array),
+            Arc::new(victim_loss_usd_array),
             Arc::new(victim_swaps_gas_details_array),
             Arc::new(backrun_tx_hash_array),
             Arc::new(backrun_swaps_array),
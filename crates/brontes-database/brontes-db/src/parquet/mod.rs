@@ -1,5 +1,6 @@
 use std::{
     fs::File,
+    ops::RangeInclusive,
     path::{Path, PathBuf},
 };
 
@@ -7,6 +8,7 @@ use arrow::record_batch::RecordBatch;
 use brontes_types::{
     db::traits::LibmdbxReader,
     mev::{BundleData, MevType},
+    structured_trace::TxTrace,
 };
 use chrono::Local;
 use eyre::{Error, Ok, Result, WrapErr};
@@ -20,28 +22,51 @@ use tracing::error;
 
 use crate::Tables;
 
+mod action_writer;
+mod actions;
 #[allow(dead_code)]
 mod address_meta;
+mod block_info;
 mod builder;
 mod bundle_header;
+mod cex_price;
+mod cex_trades;
+mod dex_price;
 mod mev_block;
 mod mev_data;
 mod normalized_actions;
+mod relay_bids;
 mod searcher;
+mod tx_traces;
 pub mod utils;
 
+pub use action_writer::{ActionParquetWriter, DEFAULT_ACTION_BLOCKS_PER_FILE};
 use address_meta::address_metadata_to_record_batch;
+use block_info::block_info_to_record_batch;
 use builder::builder_info_to_record_batch;
 use bundle_header::bundle_headers_to_record_batch;
+use cex_price::cex_quotes_to_record_batch;
+use cex_trades::cex_trades_to_record_batch;
+use dex_price::dex_quotes_to_record_batch;
 use mev_block::mev_block_to_record_batch;
 use mev_data::*;
+use relay_bids::relay_bids_to_record_batch;
 use searcher::searcher_info_to_record_batch;
+use tx_traces::tx_traces_to_record_batch;
+
+/// Blocks that are exported as one parquet file by default for the block
+/// range partitioned tables
+pub const DEFAULT_PARTITION_SIZE: u64 = 100_000;
+/// Blocks that are loaded and written as one record batch. Only the rows of
+/// these blocks are held in memory while exporting a partition
+const BLOCKS_PER_RECORD_BATCH: u64 = 100;
 
 pub struct ParquetExporter<DB: LibmdbxReader> {
-    pub start_block:   Option<u64>,
-    pub end_block:     Option<u64>,
-    pub base_dir_path: Option<String>,
-    pub db:            &'static DB,
+    pub start_block:    Option<u64>,
+    pub end_block:      Option<u64>,
+    pub base_dir_path:  Option<String>,
+    pub partition_size: u64,
+    pub db:             &'static DB,
 }
 
 impl<DB> ParquetExporter<DB>
@@ -54,7 +79,12 @@ where
         base_dir_path: Option<String>,
         db: &'static DB,
     ) -> Self {
-        Self { start_block, end_block, base_dir_path, db }
+        Self { start_block, end_block, base_dir_path, partition_size: DEFAULT_PARTITION_SIZE, db }
+    }
+
+    pub fn with_partition_size(mut self, partition_size: u64) -> Self {
+        self.partition_size = partition_size.max(1);
+        self
     }
 
     pub async fn export_mev_blocks(&self) -> Result<(), Error> {
@@ -84,6 +114,8 @@ where
             jit_sandwich,
             searcher_tx,
             liquidation,
+            long_tail,
+            frontrun,
         ) = {
             let mut blocks = Vec::new();
             let mut bundle_headers = Vec::new();
@@ -94,6 +126,8 @@ where
             let mut jit_sandwich = Vec::new();
             let mut searcher_tx = Vec::new();
             let mut liquidation = Vec::new();
+            let mut long_tail = Vec::new();
+            let mut frontrun = Vec::new();
 
             for mb in mev_blocks_iter {
                 blocks.push(mb.block);
@@ -111,6 +145,8 @@ where
                         BundleData::Liquidation(liquidation_data) => {
                             liquidation.push(liquidation_data)
                         }
+                        BundleData::LongTail(long_tail_data) => long_tail.push(long_tail_data),
+                        BundleData::Frontrun(frontrun_data) => frontrun.push(frontrun_data),
                         _ => continue,
                     }
                 }
@@ -126,6 +162,8 @@ where
                 jit_sandwich,
                 searcher_tx,
                 liquidation,
+                long_tail,
+                frontrun,
             )
         };
 
@@ -245,6 +283,34 @@ where
             }));
         }
 
+        if !long_tail.is_empty() {
+            bundle_futures.push(tokio::task::spawn_blocking({
+                let base_dir_path = base_dir_path.clone();
+                move || {
+                    let long_tail_batch = long_tail_to_record_batch(long_tail)
+                        .wrap_err("Failed to convert Long Tail data to record batch")?;
+                    sync_write_parquet(
+                        long_tail_batch,
+                        get_path(base_dir_path, Tables::MevBlocks, Some(MevType::LongTail))?,
+                    )
+                }
+            }));
+        }
+
+        if !frontrun.is_empty() {
+            bundle_futures.push(tokio::task::spawn_blocking({
+                let base_dir_path = base_dir_path.clone();
+                move || {
+                    let frontrun_batch = frontrun_to_record_batch(frontrun)
+                        .wrap_err("Failed to convert Frontrun data to record batch")?;
+                    sync_write_parquet(
+                        frontrun_batch,
+                        get_path(base_dir_path, Tables::MevBlocks, Some(MevType::Frontrun))?,
+                    )
+                }
+            }));
+        }
+
         if !bundle_headers.is_empty() {
             bundle_futures.push(tokio::task::spawn_blocking({
                 let base_dir_path = base_dir_path.clone();
@@ -298,8 +364,16 @@ where
             return Err(Error::msg("No indexed searcher"))
         }
 
-        let searcher_info_batch = searcher_info_to_record_batch(eoa_info, contract_info)
-            .expect("Failed to convert Searcher Info to record batch");
+        let failed_attempts = self
+            .db
+            .fetch_all_searcher_failed_attempts()
+            .expect("Failed to query searcher failed attempts table")
+            .into_iter()
+            .collect();
+
+        let searcher_info_batch =
+            searcher_info_to_record_batch(eoa_info, contract_info, failed_attempts)
+                .expect("Failed to convert Searcher Info to record batch");
 
         write_parquet(
             searcher_info_batch,
@@ -322,7 +396,14 @@ where
             return Err(Error::msg("No builder info"))
         }
 
-        let builder_info_batch = builder_info_to_record_batch(builder_info)
+        let proposer_payments = self
+            .db
+            .fetch_all_builder_proposer_payments()
+            .expect("Failed to query builder proposer payments table")
+            .into_iter()
+            .collect();
+
+        let builder_info_batch = builder_info_to_record_batch(builder_info, proposer_payments)
             .expect("Failed to convert Searcher Info to record batch");
 
         write_parquet(
@@ -334,6 +415,186 @@ where
 
         Ok(())
     }
+
+    pub async fn export_dex_prices(&self) -> Result<(), Error> {
+        self.export_block_range(Tables::DexPrice, |db, blocks| {
+            let mut quotes = Vec::new();
+            for block in blocks {
+                let block_quotes = db.get_dex_quotes(block)?;
+                if !block_quotes.0.is_empty() {
+                    quotes.push((block, block_quotes));
+                }
+            }
+            if quotes.is_empty() {
+                return Ok(None)
+            }
+
+            Ok(Some(
+                dex_quotes_to_record_batch(quotes)
+                    .wrap_err("Failed to convert dex prices to record batch")?,
+            ))
+        })
+        .await
+    }
+
+    pub async fn export_cex_prices(&self) -> Result<(), Error> {
+        self.export_block_range(Tables::CexPrice, |db, blocks| {
+            let quotes = fetch_blocks(blocks, |block| db.try_fetch_cex_quotes(block))?;
+            if quotes.is_empty() {
+                return Ok(None)
+            }
+
+            Ok(Some(
+                cex_quotes_to_record_batch(quotes)
+                    .wrap_err("Failed to convert cex prices to record batch")?,
+            ))
+        })
+        .await
+    }
+
+    pub async fn export_cex_trades(&self) -> Result<(), Error> {
+        self.export_block_range(Tables::CexTrades, |db, blocks| {
+            let trades = fetch_blocks(blocks, |block| db.try_fetch_block_cex_trades(block))?;
+            if trades.is_empty() {
+                return Ok(None)
+            }
+
+            Ok(Some(
+                cex_trades_to_record_batch(trades)
+                    .wrap_err("Failed to convert cex trades to record batch")?,
+            ))
+        })
+        .await
+    }
+
+    pub async fn export_block_info(&self) -> Result<(), Error> {
+        self.export_block_range(Tables::BlockInfo, |db, blocks| {
+            let block_info = fetch_blocks(blocks, |block| db.try_fetch_block_info(block))?;
+            if block_info.is_empty() {
+                return Ok(None)
+            }
+
+            Ok(Some(
+                block_info_to_record_batch(block_info)
+                    .wrap_err("Failed to convert block info to record batch")?,
+            ))
+        })
+        .await
+    }
+
+    pub async fn export_tx_traces(&self) -> Result<(), Error> {
+        self.export_block_range(Tables::TxTraces, |db, blocks| {
+            let traces: Vec<TxTrace> = fetch_blocks(blocks, |block| db.try_fetch_traces(block))?
+                .into_iter()
+                .flat_map(|(_, traces)| traces)
+                .collect();
+            if traces.is_empty() {
+                return Ok(None)
+            }
+
+            Ok(Some(
+                tx_traces_to_record_batch(traces)
+                    .wrap_err("Failed to convert tx traces to record batch")?,
+            ))
+        })
+        .await
+    }
+
+    pub async fn export_relay_bids(&self) -> Result<(), Error> {
+        self.export_block_range(Tables::RelayBids, |db, blocks| {
+            let relay_bids = fetch_blocks(blocks, |block| db.try_fetch_relay_bids(block))?;
+            if relay_bids.is_empty() {
+                return Ok(None)
+            }
+
+            Ok(Some(
+                relay_bids_to_record_batch(relay_bids)
+                    .wrap_err("Failed to convert relay bids to record batch")?,
+            ))
+        })
+        .await
+    }
+
+    /// Exports the block range of the exporter into one parquet file per
+    /// partition of `partition_size` blocks. The blocks are read and written
+    /// [`BLOCKS_PER_RECORD_BATCH`] blocks at a time so large ranges are
+    /// streamed instead of being loaded at once.
+    async fn export_block_range<F>(&self, table: Tables, to_record_batch: F) -> Result<(), Error>
+    where
+        F: Fn(&DB, RangeInclusive<u64>) -> Result<Option<RecordBatch>> + Send + 'static,
+    {
+        let start_block = self.start_block.unwrap_or_default();
+        let end_block = match self.end_block {
+            Some(end_block) => end_block,
+            None => self
+                .db
+                .get_most_recent_block()
+                .wrap_err("Failed to fetch the most recent block")?,
+        };
+        if start_block > end_block {
+            return Err(eyre::eyre!("start block {start_block} is after end block {end_block}"))
+        }
+
+        let db = self.db;
+        let partition_size = self.partition_size;
+        let base_dir_path = self.base_dir_path.clone();
+
+        let written = tokio::task::spawn_blocking(move || {
+            let mut written = 0;
+            for partition_start in (start_block..=end_block).step_by(partition_size as usize) {
+                let partition_end = (partition_start + partition_size - 1).min(end_block);
+                let mut writer: Option<ArrowWriter<File>> = None;
+
+                for batch_start in
+                    (partition_start..=partition_end).step_by(BLOCKS_PER_RECORD_BATCH as usize)
+                {
+                    let batch_end = (batch_start + BLOCKS_PER_RECORD_BATCH - 1).min(partition_end);
+                    let Some(record_batch) = to_record_batch(db, batch_start..=batch_end)? else {
+                        continue
+                    };
+
+                    if writer.is_none() {
+                        let file_path = get_partition_path(
+                            base_dir_path.clone(),
+                            table,
+                            partition_start..=partition_end,
+                        )?;
+                        writer = Some(create_parquet_writer(&record_batch, file_path)?);
+                    }
+                    writer
+                        .as_mut()
+                        .unwrap()
+                        .write(&record_batch)
+                        .wrap_err("Failed to write record batch to Parquet file")?;
+                }
+
+                if let Some(writer) = writer {
+                    writer.close().wrap_err("Failed to close Parquet writer")?;
+                    written += 1;
+                }
+            }
+
+            Ok(written)
+        })
+        .await??;
+
+        if written == 0 {
+            error!(?table, "No data stored for the given range.");
+            return Err(eyre::eyre!("No {table:?} data stored for the given range."))
+        }
+
+        Ok(())
+    }
+}
+
+/// The stored values of the blocks, skipping blocks without a value
+fn fetch_blocks<T>(
+    blocks: RangeInclusive<u64>,
+    fetch: impl Fn(u64) -> Result<Option<T>>,
+) -> Result<Vec<(u64, T)>> {
+    blocks
+        .filter_map(|block| fetch(block).map(|value| Some((block, value?))).transpose())
+        .collect()
 }
 
 async fn write_parquet(record_batch: RecordBatch, file_path: PathBuf) -> Result<()> {
@@ -361,6 +622,21 @@ async fn write_parquet(record_batch: RecordBatch, file_path: PathBuf) -> Result<
     Ok(())
 }
 
+fn create_parquet_writer(
+    record_batch: &RecordBatch,
+    file_path: PathBuf,
+) -> Result<ArrowWriter<File>> {
+    let file = File::create(file_path.clone())
+        .wrap_err_with(|| format!("Failed to create file at path: {}", file_path.display()))?;
+
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+
+    ArrowWriter::try_new(file, record_batch.schema(), Some(props))
+        .wrap_err("Failed to initialize Parquet writer")
+}
+
 fn sync_write_parquet(record_batch: RecordBatch, file_path: PathBuf) -> Result<()> {
     let file = File::create(file_path.clone())
         .wrap_err_with(|| format!("Failed to create file at path: {}", file_path.display()))?;
@@ -381,6 +657,25 @@ fn sync_write_parquet(record_batch: RecordBatch, file_path: PathBuf) -> Result<(
     Ok(())
 }
 
+/// Writes the record batch to a parquet file and reads the file back
+#[cfg(test)]
+fn round_trip(record_batch: RecordBatch) -> RecordBatch {
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let dir = tempfile::tempdir().unwrap();
+    let file_path = dir.path().join("round_trip.parquet");
+    sync_write_parquet(record_batch, file_path.clone()).unwrap();
+
+    let mut batches = ParquetRecordBatchReaderBuilder::try_new(File::open(file_path).unwrap())
+        .unwrap()
+        .build()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(batches.len(), 1);
+    batches.remove(0)
+}
+
 pub fn get_path(
     custom_path: Option<String>,
     batch_type: Tables,
@@ -402,6 +697,24 @@ pub fn get_path(
     create_file_path(path)
 }
 
+/// The path of a block range partition, e.g.
+/// "data_exports/dex_prices/19000000-19099999.parquet". Exporting the same
+/// range again replaces the partition
+pub fn get_partition_path(
+    custom_path: Option<String>,
+    batch_type: Tables,
+    blocks: RangeInclusive<u64>,
+) -> Result<PathBuf> {
+    let base_path = custom_path
+        .as_deref()
+        .unwrap_or("../brontes-notebook/data/brontes-exports");
+
+    let dir_path = PathBuf::from(base_path).join(batch_type.get_default_path());
+    std::fs::create_dir_all(&dir_path)?;
+
+    Ok(dir_path.join(format!("{}-{}.parquet", blocks.start(), blocks.end())))
+}
+
 pub fn create_file_path<P: AsRef<Path>>(base_dir: P) -> Result<PathBuf> {
     let now = Local::now();
     let date_str = now.format("%m-%d").to_string();
@@ -424,6 +737,12 @@ impl Tables {
             Tables::SearcherEOAs => DEFAULT_SEARCHER_INFO_DIR,
             Tables::SearcherContracts => DEFAULT_SEARCHER_INFO_DIR,
             Tables::Builder => DEFAULT_BUILDER_INFO_DIR,
+            Tables::DexPrice => DEFAULT_DEX_PRICE_DIR,
+            Tables::CexPrice => DEFAULT_CEX_PRICE_DIR,
+            Tables::CexTrades => DEFAULT_CEX_TRADES_DIR,
+            Tables::BlockInfo => DEFAULT_BLOCK_INFO_DIR,
+            Tables::TxTraces => DEFAULT_TX_TRACES_DIR,
+            Tables::RelayBids => DEFAULT_RELAY_BIDS_DIR,
             _ => panic!("Unsupported table type"),
         }
     }
@@ -433,3 +752,9 @@ pub const DEFAULT_BLOCK_DIR: &str = "mev";
 pub const DEFAULT_METADATA_DIR: &str = "address_metadata";
 pub const DEFAULT_SEARCHER_INFO_DIR: &str = "searcher_info";
 pub const DEFAULT_BUILDER_INFO_DIR: &str = "builder-info";
+pub const DEFAULT_DEX_PRICE_DIR: &str = "dex_prices";
+pub const DEFAULT_CEX_PRICE_DIR: &str = "cex_prices";
+pub const DEFAULT_CEX_TRADES_DIR: &str = "cex_trades";
+pub const DEFAULT_BLOCK_INFO_DIR: &str = "block_info";
+pub const DEFAULT_TX_TRACES_DIR: &str = "tx_traces";
+pub const DEFAULT_RELAY_BIDS_DIR: &str = "relay_bids";
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use arrow::{
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::db::relay_bids::BlockRelayBids;
+
+use super::utils::{build_string_array, build_uint64_array, u128_to_binary_array};
+
+pub fn relay_bids_to_record_batch(
+    relay_bids: Vec<(u64, BlockRelayBids)>,
+) -> Result<RecordBatch, ArrowError> {
+    let bids = relay_bids
+        .iter()
+        .flat_map(|(block, bids)| bids.0.iter().map(move |bid| (*block, bid)))
+        .collect::<Vec<_>>();
+
+    let block_number_array = build_uint64_array(bids.iter().map(|(block, _)| *block).collect());
+    let relay_array =
+        build_string_array(bids.iter().map(|(_, bid)| bid.relay.to_string()).collect());
+    let slot_array = build_uint64_array(bids.iter().map(|(_, bid)| bid.slot).collect());
+    let parent_hash_array = build_string_array(
+        bids.iter()
+            .map(|(_, bid)| bid.parent_hash.clone())
+            .collect(),
+    );
+    let block_hash_array =
+        build_string_array(bids.iter().map(|(_, bid)| bid.block_hash.clone()).collect());
+    let builder_pubkey_array = build_string_array(
+        bids.iter()
+            .map(|(_, bid)| bid.builder_pubkey.clone())
+            .collect(),
+    );
+    let proposer_fee_recipient_array = build_string_array(
+        bids.iter()
+            .map(|(_, bid)| bid.proposer_fee_recipient.clone())
+            .collect(),
+    );
+    let gas_used_array = build_uint64_array(bids.iter().map(|(_, bid)| bid.gas_used).collect());
+    let value_array = u128_to_binary_array(bids.iter().map(|(_, bid)| bid.value).collect());
+    let num_tx_array = build_uint64_array(bids.iter().map(|(_, bid)| bid.num_tx).collect());
+    let timestamp_ms_array =
+        build_uint64_array(bids.iter().map(|(_, bid)| bid.timestamp_ms).collect());
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("relay", DataType::Utf8, false),
+        Field::new("slot", DataType::UInt64, false),
+        Field::new("parent_hash", DataType::Utf8, false),
+        Field::new("block_hash", DataType::Utf8, false),
+        Field::new("builder_pubkey", DataType::Utf8, false),
+        Field::new("proposer_fee_recipient", DataType::Utf8, false),
+        Field::new("gas_used", DataType::UInt64, false),
+        Field::new("value", DataType::Binary, false),
+        Field::new("num_tx", DataType::UInt64, false),
+        Field::new("timestamp_ms", DataType::UInt64, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(relay_array),
+            Arc::new(slot_array),
+            Arc::new(parent_hash_array),
+            Arc::new(block_hash_array),
+            Arc::new(builder_pubkey_array),
+            Arc::new(proposer_fee_recipient_array),
+            Arc::new(gas_used_array),
+            Arc::new(value_array),
+            Arc::new(num_tx_array),
+            Arc::new(timestamp_ms_array),
+        ],
+    )
+}
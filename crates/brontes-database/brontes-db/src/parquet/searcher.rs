@@ -7,7 +7,10 @@ use arrow::{
     error::ArrowError,
     record_batch::RecordBatch,
 };
-use brontes_types::db::searcher::{Fund, SearcherInfo};
+use brontes_types::{
+    db::searcher::{FailedAttemptStats, Fund, SearcherInfo},
+    FastHashMap,
+};
 use itertools::Itertools;
 
 use super::utils::{build_string_array, get_list_string_array, get_string_array_from_owned};
@@ -15,6 +18,7 @@ use super::utils::{build_string_array, get_list_string_array, get_string_array_f
 pub fn searcher_info_to_record_batch(
     eoa_info: Vec<(Address, SearcherInfo)>,
     contract_info: Vec<(Address, SearcherInfo)>,
+    failed_attempts: FastHashMap<Address, FailedAttemptStats>,
 ) -> Result<RecordBatch, ArrowError> {
     let address_array = build_string_array(
         eoa_info
@@ -72,6 +76,10 @@ pub fn searcher_info_to_record_batch(
         UInt64Builder::with_capacity(eoa_info.len() + contract_info.len());
     let mut searcher_tx_count_builder =
         UInt64Builder::with_capacity(eoa_info.len() + contract_info.len());
+    let mut failed_attempt_count_builder =
+        UInt64Builder::with_capacity(eoa_info.len() + contract_info.len());
+    let mut failed_attempts_gas_paid_usd_builder =
+        Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
 
     // Flatten TollByType fields for pnl and gas_bids
     let mut pnl_total_builder = Float64Builder::with_capacity(eoa_info.len() + contract_info.len());
@@ -117,6 +125,10 @@ pub fn searcher_info_to_record_batch(
         liquidation_count_builder.append_option(mev_count.liquidation_count);
         searcher_tx_count_builder.append_option(mev_count.searcher_tx_count);
 
+        let failed = failed_attempts.get(&info.0);
+        failed_attempt_count_builder.append_option(failed.map(|stats| stats.count));
+        failed_attempts_gas_paid_usd_builder.append_option(failed.map(|stats| stats.gas_paid_usd));
+
         let pnl = &info.1.pnl;
         pnl_total_builder.append_value(pnl.total);
         pnl_sandwich_builder.append_option(pnl.sandwich);
@@ -156,6 +168,8 @@ pub fn searcher_info_to_record_batch(
         Field::new("atomic_backrun_count", DataType::UInt64, true),
         Field::new("liquidation_count", DataType::UInt64, true),
         Field::new("searcher_tx_count", DataType::UInt64, true),
+        Field::new("failed_attempt_count", DataType::UInt64, true),
+        Field::new("failed_attempts_gas_paid_usd", DataType::Float64, true),
         Field::new("pnl_total", DataType::Float64, false),
         Field::new("pnl_sandwich", DataType::Float64, true),
         Field::new("pnl_cex_dex", DataType::Float64, true),
@@ -190,6 +204,8 @@ pub fn searcher_info_to_record_batch(
             Arc::new(atomic_backrun_count_builder.finish()),
             Arc::new(liquidation_count_builder.finish()),
             Arc::new(searcher_tx_count_builder.finish()),
+            Arc::new(failed_attempt_count_builder.finish()),
+            Arc::new(failed_attempts_gas_paid_usd_builder.finish()),
             Arc::new(pnl_total_builder.finish()),
             Arc::new(pnl_sandwich_builder.finish()),
             Arc::new(pnl_cex_dex_builder.finish()),
@@ -0,0 +1,247 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{BinaryArray, BooleanArray, UInt64Array},
+    datatypes::{DataType, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use brontes_types::structured_trace::{TraceActions, TransactionTraceWithLogs, TxTrace};
+use itertools::Itertools;
+use reth_rpc_types::trace::parity::{Action, TraceOutput};
+
+use super::utils::{
+    build_string_array, build_uint64_array, get_list_uint64_array_from_owned,
+    get_string_array_from_owned, u128_to_binary_array,
+};
+
+/// One row per call frame, the transaction fields are repeated on each of its
+/// frames
+pub fn tx_traces_to_record_batch(traces: Vec<TxTrace>) -> Result<RecordBatch, ArrowError> {
+    let rows = traces
+        .iter()
+        .flat_map(|tx| tx.trace.iter().map(move |trace| (tx, trace)))
+        .collect_vec();
+
+    let block_number_array =
+        build_uint64_array(rows.iter().map(|(tx, _)| tx.block_number).collect());
+    let tx_hash_array =
+        build_string_array(rows.iter().map(|(tx, _)| tx.tx_hash.to_string()).collect());
+    let tx_index_array = build_uint64_array(rows.iter().map(|(tx, _)| tx.tx_index).collect());
+    let tx_gas_used_array = u128_to_binary_array(rows.iter().map(|(tx, _)| tx.gas_used).collect());
+    let effective_price_array =
+        u128_to_binary_array(rows.iter().map(|(tx, _)| tx.effective_price).collect());
+    let is_success_array =
+        BooleanArray::from(rows.iter().map(|(tx, _)| tx.is_success).collect_vec());
+
+    let trace_idx_array =
+        build_uint64_array(rows.iter().map(|(_, trace)| trace.trace_idx).collect());
+    let trace_address_array = get_list_uint64_array_from_owned(
+        rows.iter()
+            .map(|(_, trace)| {
+                trace
+                    .get_trace_address()
+                    .into_iter()
+                    .map(|i| i as u64)
+                    .collect_vec()
+            })
+            .collect_vec(),
+    );
+    let action_type_array =
+        build_string_array(rows.iter().map(|(_, trace)| action_type(trace)).collect());
+    let from_array = build_string_array(
+        rows.iter()
+            .map(|(_, trace)| trace.get_from_addr().to_string())
+            .collect(),
+    );
+    let to_array = build_string_array(
+        rows.iter()
+            .map(|(_, trace)| trace.get_to_address().to_string())
+            .collect(),
+    );
+    let msg_sender_array = build_string_array(
+        rows.iter()
+            .map(|(_, trace)| trace.msg_sender.to_string())
+            .collect(),
+    );
+    let value_array = build_string_array(
+        rows.iter()
+            .map(|(_, trace)| trace.get_msg_value().to_string())
+            .collect(),
+    );
+    let input_array =
+        BinaryArray::from_iter_values(rows.iter().map(|(_, trace)| trace.get_calldata().to_vec()));
+    let output_array = BinaryArray::from_iter_values(
+        rows.iter()
+            .map(|(_, trace)| trace.get_return_calldata().to_vec()),
+    );
+    let gas_used_array = UInt64Array::from(
+        rows.iter()
+            .map(|(_, trace)| {
+                trace.trace.result.as_ref().map(|res| match res {
+                    TraceOutput::Call(call) => call.gas_used.to::<u64>(),
+                    TraceOutput::Create(create) => create.gas_used.to::<u64>(),
+                })
+            })
+            .collect_vec(),
+    );
+    let error_array = get_string_array_from_owned(
+        rows.iter()
+            .map(|(_, trace)| trace.trace.error.clone())
+            .collect_vec(),
+    );
+    let log_count_array = build_uint64_array(
+        rows.iter()
+            .map(|(_, trace)| trace.logs.len() as u64)
+            .collect(),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("tx_hash", DataType::Utf8, false),
+        Field::new("tx_index", DataType::UInt64, false),
+        Field::new("tx_gas_used", DataType::Binary, false),
+        Field::new("effective_price", DataType::Binary, false),
+        Field::new("is_success", DataType::Boolean, false),
+        Field::new("trace_idx", DataType::UInt64, false),
+        Field::new(
+            "trace_address",
+            DataType::List(Arc::new(Field::new("item", DataType::UInt64, true))),
+            false,
+        ),
+        Field::new("action_type", DataType::Utf8, false),
+        Field::new("from", DataType::Utf8, false),
+        Field::new("to", DataType::Utf8, false),
+        Field::new("msg_sender", DataType::Utf8, false),
+        Field::new("value", DataType::Utf8, false),
+        Field::new("input", DataType::Binary, false),
+        Field::new("output", DataType::Binary, false),
+        Field::new("gas_used", DataType::UInt64, true),
+        Field::new("error", DataType::Utf8, true),
+        Field::new("log_count", DataType::UInt64, false),
+    ]);
+
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(block_number_array),
+            Arc::new(tx_hash_array),
+            Arc::new(tx_index_array),
+            Arc::new(tx_gas_used_array),
+            Arc::new(effective_price_array),
+            Arc::new(is_success_array),
+            Arc::new(trace_idx_array),
+            Arc::new(trace_address_array),
+            Arc::new(action_type_array),
+            Arc::new(from_array),
+            Arc::new(to_array),
+            Arc::new(msg_sender_array),
+            Arc::new(value_array),
+            Arc::new(input_array),
+            Arc::new(output_array),
+            Arc::new(gas_used_array),
+            Arc::new(error_array),
+            Arc::new(log_count_array),
+        ],
+    )
+}
+
+fn action_type(trace: &TransactionTraceWithLogs) -> String {
+    match &trace.trace.action {
+        Action::Call(call) => format!("{:?}", call.call_type),
+        Action::Create(_) => "Create".to_string(),
+        Action::Selfdestruct(_) => "Selfdestruct".to_string(),
+        Action::Reward(_) => "Reward".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Address, B256};
+    use arrow::array::{Array, ListArray, StringArray};
+    use brontes_classifier::test_utils::call_trace;
+
+    use super::*;
+    use crate::parquet::round_trip;
+
+    #[test]
+    fn test_tx_traces_round_trip() {
+        let eoa = Address::repeat_byte(1);
+        let router = Address::repeat_byte(2);
+        let pool = Address::repeat_byte(3);
+
+        let root = call_trace(vec![], eoa, router, vec![1, 2, 3], vec![], vec![]);
+        let mut inner = call_trace(vec![0], router, pool, vec![4], vec![5, 6], vec![]);
+        inner.trace_idx = 1;
+        inner.trace.error = Some("Reverted".to_string());
+        let traces = vec![
+            TxTrace {
+                block_number:    1,
+                trace:           vec![root, inner],
+                tx_hash:         B256::repeat_byte(0xaa),
+                gas_used:        21_000,
+                effective_price: 10u128.pow(9),
+                tx_index:        0,
+                is_success:      true,
+            },
+            TxTrace {
+                block_number:    1,
+                trace:           vec![call_trace(vec![], eoa, pool, vec![], vec![], vec![])],
+                tx_hash:         B256::repeat_byte(0xbb),
+                gas_used:        50_000,
+                effective_price: 2 * 10u128.pow(9),
+                tx_index:        1,
+                is_success:      false,
+            },
+        ];
+
+        let record_batch = tx_traces_to_record_batch(traces).unwrap();
+        let read = round_trip(record_batch.clone());
+        assert_eq!(read, record_batch);
+        assert_eq!(read.num_rows(), 3);
+
+        let column = |name: &str| read.column_by_name(name).unwrap().clone();
+        let tx_hashes = column("tx_hash");
+        let tx_hashes = tx_hashes.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(tx_hashes.value(0), tx_hashes.value(1));
+        assert_eq!(tx_hashes.value(2), B256::repeat_byte(0xbb).to_string());
+        let tx_gas_used = column("tx_gas_used");
+        let tx_gas_used = tx_gas_used.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(tx_gas_used.value(2), 50_000u128.to_be_bytes());
+        let is_success = column("is_success");
+        let is_success = is_success.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert!(is_success.value(1));
+        assert!(!is_success.value(2));
+        let trace_addresses = column("trace_address");
+        let trace_addresses = trace_addresses
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .unwrap();
+        assert_eq!(trace_addresses.value(0).len(), 0);
+        let inner_address = trace_addresses.value(1);
+        let inner_address = inner_address
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(inner_address.values(), &[0]);
+        let action_types = column("action_type");
+        let action_types = action_types.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(action_types.value(0), "Call");
+        let to = column("to");
+        let to = to.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(to.value(1), pool.to_string());
+        let input = column("input");
+        let input = input.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(input.value(0), &[1, 2, 3]);
+        let output = column("output");
+        let output = output.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(output.value(1), &[5, 6]);
+        let gas_used = column("gas_used");
+        let gas_used = gas_used.as_any().downcast_ref::<UInt64Array>().unwrap();
+        assert_eq!(gas_used.value(0), 50_000);
+        let errors = column("error");
+        let errors = errors.as_any().downcast_ref::<StringArray>().unwrap();
+        assert!(errors.is_null(0));
+        assert_eq!(errors.value(1), "Reverted");
+    }
+}
@@ -2,8 +2,8 @@ use std::{fmt::Display, sync::Arc};
 
 use arrow::{
     array::{
-        Array, BinaryArray, BinaryBuilder, Float64Array, Float64Builder, ListArray, ListBuilder,
-        StringArray, StringBuilder, UInt64Array,
+        Array, BinaryArray, BinaryBuilder, BooleanBuilder, Float64Array, Float64Builder, ListArray,
+        ListBuilder, StringArray, StringBuilder, UInt64Array, UInt64Builder,
     },
     datatypes::Schema,
     error::ArrowError,
@@ -20,6 +20,30 @@ pub fn u128_to_binary_array(values: Vec<u128>) -> BinaryArray {
     builder.finish()
 }
 
+pub fn optional_u128_to_binary_array(values: Vec<Option<u128>>) -> BinaryArray {
+    let data_capacity = values.len() * 16;
+    let mut builder = BinaryBuilder::with_capacity(values.len(), data_capacity);
+    for value in values {
+        match value {
+            Some(value) => builder.append_value(value.to_be_bytes()),
+            None => builder.append_null(),
+        }
+    }
+    builder.finish()
+}
+
+pub fn optional_i128_to_binary_array(values: Vec<Option<i128>>) -> BinaryArray {
+    let data_capacity = values.len() * 16;
+    let mut builder = BinaryBuilder::with_capacity(values.len(), data_capacity);
+    for value in values {
+        match value {
+            Some(value) => builder.append_value(value.to_be_bytes()),
+            None => builder.append_null(),
+        }
+    }
+    builder.finish()
+}
+
 pub fn build_string_array(values: Vec<String>) -> StringArray {
     StringArray::from_iter_values(values)
 }
@@ -103,6 +127,39 @@ pub fn get_list_float_array_from_owned(values: Vec<Vec<f64>>) -> ListArray {
     builder.finish()
 }
 
+pub fn get_list_bool_array_from_owned(values: Vec<Vec<bool>>) -> ListArray {
+    let mut builder = ListBuilder::new(BooleanBuilder::new());
+
+    for v in values {
+        let bool_builder = builder.values();
+        if v.is_empty() {
+            builder.append_null();
+            continue;
+        } else {
+            for value in v {
+                bool_builder.append_value(value);
+            }
+            builder.append(true)
+        }
+    }
+
+    builder.finish()
+}
+
+pub fn get_list_uint64_array_from_owned(values: Vec<Vec<u64>>) -> ListArray {
+    let mut builder = ListBuilder::new(UInt64Builder::new());
+
+    for v in values {
+        let uint_builder = builder.values();
+        for value in v {
+            uint_builder.append_value(value);
+        }
+        builder.append(true)
+    }
+
+    builder.finish()
+}
+
 pub fn build_uint64_array(values: Vec<u64>) -> UInt64Array {
     UInt64Array::from(values)
 }
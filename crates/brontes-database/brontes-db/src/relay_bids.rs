@@ -0,0 +1,209 @@
+//! Ingestion of the bids the relays received.
+//!
+//! Bids are either fetched from the data api of every relay or read from a
+//! local dump, and stored per block in the `RelayBids` table. They are the
+//! input of the bid analysis attached to each `MevBlock`.
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+use brontes_types::{
+    block_metadata::{RelayBid, Relays},
+    db::relay_bids::BlockRelayBids,
+    FastHashMap,
+};
+use eyre::WrapErr;
+use futures::{stream, StreamExt};
+use itertools::Itertools;
+use tracing::{info, warn};
+
+use crate::{libmdbx::LibmdbxReadWriter, RelayBids, RelayBidsData};
+
+/// Blocks fetched from the relays before they are written
+const BLOCKS_PER_WRITE: usize = 500;
+/// Blocks fetched from the relays at once, each block queries every relay
+const CONCURRENT_BLOCKS: usize = 4;
+
+pub enum RelayBidSource {
+    /// The data api of every relay
+    Relays,
+    /// A newline delimited json file of bids in the format of the relay data
+    /// api, all received by `relay`
+    Dump { path: PathBuf, relay: Relays },
+}
+
+/// Writes the bids of `source` for the given block range to the `RelayBids`
+/// table. Blocks without bids are skipped.
+pub async fn import_relay_bids(
+    db: &LibmdbxReadWriter,
+    source: &RelayBidSource,
+    range: RangeInclusive<u64>,
+) -> eyre::Result<()> {
+    match source {
+        RelayBidSource::Relays => import_from_relays(db, range).await,
+        RelayBidSource::Dump { path, relay } => import_from_dump(db, path, *relay, range),
+    }
+}
+
+async fn import_from_relays(
+    db: &LibmdbxReadWriter,
+    range: RangeInclusive<u64>,
+) -> eyre::Result<()> {
+    for blocks in range.collect_vec().chunks(BLOCKS_PER_WRITE) {
+        let bids = stream::iter(blocks.iter().copied())
+            .map(|block| async move { (block, Relays::get_block_bids(block).await) })
+            .buffer_unordered(CONCURRENT_BLOCKS)
+            .collect::<Vec<_>>()
+            .await;
+
+        write_bids(db, bids)?;
+    }
+
+    Ok(())
+}
+
+fn import_from_dump(
+    db: &LibmdbxReadWriter,
+    path: &Path,
+    relay: Relays,
+    range: RangeInclusive<u64>,
+) -> eyre::Result<()> {
+    let file =
+        File::open(path).wrap_err_with(|| format!("Failed to open file: {}", path.display()))?;
+
+    let mut bids: FastHashMap<u64, Vec<RelayBid>> = FastHashMap::default();
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue
+        }
+
+        match RelayBid::from_json(&line, relay) {
+            Ok(bid) if range.contains(&bid.block_number) => {
+                bids.entry(bid.block_number).or_default().push(bid)
+            }
+            Ok(_) => (),
+            Err(e) => {
+                warn!(target: "brontes_db::relay_bids", line = i + 1, err=%e, "skipping invalid bid")
+            }
+        }
+    }
+
+    write_bids(db, bids.into_iter().collect())
+}
+
+fn write_bids(db: &LibmdbxReadWriter, bids: Vec<(u64, Vec<RelayBid>)>) -> eyre::Result<()> {
+    let data = bids
+        .into_iter()
+        .filter(|(_, bids)| !bids.is_empty())
+        .map(|(block, mut bids)| {
+            bids.sort();
+            RelayBidsData::new(block, BlockRelayBids(bids))
+        })
+        .collect::<Vec<_>>();
+
+    db.db.write_table::<RelayBids, RelayBidsData>(&data)?;
+    info!(target: "brontes_db::relay_bids", blocks = data.len(), "wrote relay bids");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use brontes_types::db::traits::LibmdbxReader;
+
+    use super::*;
+
+    /// A bid in the json format of the relay data api
+    fn bid_json(block_number: u64, value: u128, timestamp_ms: u64) -> String {
+        serde_json::json!({
+            "slot": (block_number + 1_000).to_string(),
+            "parent_hash": format!("0x{:064x}", block_number - 1),
+            "block_hash": format!("0x{:064x}", value),
+            "builder_pubkey": "0xaa",
+            "proposer_pubkey": "0xbb",
+            "proposer_fee_recipient": "0xcc",
+            "gas_limit": "30000000",
+            "gas_used": "15000000",
+            "value": value.to_string(),
+            "block_number": block_number.to_string(),
+            "num_tx": "100",
+            "timestamp": (timestamp_ms / 1000).to_string(),
+            "timestamp_ms": timestamp_ms.to_string(),
+        })
+        .to_string()
+    }
+
+    fn stored_values(db: &LibmdbxReadWriter, block: u64) -> Option<Vec<u128>> {
+        db.try_fetch_relay_bids(block)
+            .unwrap()
+            .map(|bids| bids.0.into_iter().map(|bid| bid.value).collect())
+    }
+
+    #[brontes_macros::test]
+    async fn test_import_bids_from_dump() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = LibmdbxReadWriter::init_db_tests(dir.path().join("db")).unwrap();
+
+        let dump = dir.path().join("bids.ndjson");
+        let mut file = File::create(&dump).unwrap();
+        for line in [
+            bid_json(100, 2, 1_200),
+            bid_json(100, 1, 1_100),
+            String::new(),
+            bid_json(101, 3, 2_000),
+            // missing fields
+            r#"{"slot": "1101", "block_number": "101"}"#.to_string(),
+            "not json".to_string(),
+            // outside of the imported range
+            bid_json(200, 4, 3_000),
+        ] {
+            writeln!(file, "{line}").unwrap();
+        }
+
+        let source = RelayBidSource::Dump { path: dump, relay: Relays::UltraSound };
+        import_relay_bids(&db, &source, 100..=150).await.unwrap();
+
+        // sorted by the time they were received
+        assert_eq!(stored_values(&db, 100), Some(vec![1, 2]));
+        assert_eq!(stored_values(&db, 101), Some(vec![3]));
+        assert_eq!(stored_values(&db, 102), None);
+        assert_eq!(stored_values(&db, 200), None);
+
+        let bids = db.try_fetch_relay_bids(101).unwrap().unwrap().0;
+        assert_eq!(bids[0].relay, Relays::UltraSound);
+        assert_eq!(bids[0].slot, 1_101);
+        assert_eq!(bids[0].parent_hash, format!("0x{:064x}", 100));
+        assert_eq!(bids[0].timestamp_ms, 2_000);
+    }
+
+    #[brontes_macros::test]
+    async fn test_import_missing_dump() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = LibmdbxReadWriter::init_db_tests(dir.path().join("db")).unwrap();
+
+        let source = RelayBidSource::Dump {
+            path:  dir.path().join("missing.ndjson"),
+            relay: Relays::UltraSound,
+        };
+        assert!(import_relay_bids(&db, &source, 0..=10).await.is_err());
+    }
+
+    #[test]
+    fn test_write_bids_skips_blocks_without_bids() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = LibmdbxReadWriter::init_db_tests(dir.path()).unwrap();
+
+        let bid = RelayBid::from_json(&bid_json(6, 5, 6_000), Relays::Titan).unwrap();
+        write_bids(&db, vec![(5, vec![]), (6, vec![bid])]).unwrap();
+
+        assert_eq!(stored_values(&db, 5), None);
+        assert_eq!(stored_values(&db, 6), Some(vec![5]));
+    }
+}
@@ -29,11 +29,11 @@
 //! ```
 use std::sync::Arc;
 
-use alloy_primitives::Address;
+use alloy_primitives::{Address, B256};
 use brontes_types::{
     db::{block_analysis::BlockAnalysis, traits::LibmdbxReader},
-    mev::Mev,
-    BlockData, FastHashMap, MultiBlockData,
+    mev::{Mev, PossibleMev},
+    BlockData, FastHashMap, FastHashSet, MultiBlockData,
 };
 use itertools::Itertools;
 use tracing::{span, Level};
@@ -90,10 +90,221 @@ pub fn run_block_inspection<DB: LibmdbxReader>(
     ComposerResults { block_details, mev_details, possible_mev_txes: possible_arbs, block_analysis }
 }
 
+/// Runs the given inspectors over a block that was inspected before, the
+/// stored bundles of the `replaced` types are dropped in favour of the new
+/// results while all other stored bundles are kept. The merged bundles go
+/// through composition and deduplication again.
+pub fn run_block_reinspection<DB: LibmdbxReader>(
+    orchestra: &[&dyn Inspector<Result = Vec<Bundle>>],
+    data: MultiBlockData,
+    db: &'static DB,
+    stored: Vec<Bundle>,
+    replaced: &[MevType],
+) -> ComposerResults {
+    let this_data = data.get_most_recent_block().clone();
+    let BlockData { metadata, tree } = this_data;
+
+    let (mut possible_mev_txes, mut classified_mev) = run_inspectors(orchestra, data);
+    let kept = stored
+        .into_iter()
+        .filter(|bundle| !replaced.contains(&bundle.header.mev_type))
+        .collect_vec();
+
+    let kept_hashes = kept
+        .iter()
+        .flat_map(|bundle| bundle.data.mev_transaction_hashes())
+        .collect::<FastHashSet<_>>();
+    possible_mev_txes
+        .0
+        .retain(|possible| !kept_hashes.contains(&possible.tx_hash));
+    classified_mev.extend(kept);
+
+    let possible_arbs = possible_mev_txes.clone();
+
+    let quote_token = orchestra[0].get_quote_token();
+
+    let (block_details, mev_details) =
+        on_orchestra_resolution(tree, possible_mev_txes, metadata, classified_mev, quote_token, db);
+
+    let block_analysis = BlockAnalysis::new(&block_details, &mev_details);
+
+    ComposerResults { block_details, mev_details, possible_mev_txes: possible_arbs, block_analysis }
+}
+
+/// What happened to a bundle found by an inspector during composition and
+/// deduplication
+#[derive(Debug, Clone, PartialEq)]
+pub enum BundleOutcome {
+    /// The bundle made it into the results unchanged
+    Kept,
+    /// The bundle was composed with other bundles into a bundle of this type
+    Composed(MevType),
+    /// The bundle was removed in favour of an overlapping bundle with a higher
+    /// precedence
+    Deduplicated { by: MevType, tx_hash: B256 },
+    /// The bundle was filtered out as it wasn't profitable
+    Unprofitable,
+}
+
+/// A bundle that includes the explained transaction along with the inspector
+/// that found it
+#[derive(Debug)]
+pub struct FoundBundle {
+    pub inspector: String,
+    pub bundle:    Bundle,
+    pub outcome:   BundleOutcome,
+}
+
+#[derive(Debug)]
+pub struct TxInspectionExplanation {
+    pub found:        Vec<FoundBundle>,
+    /// The final bundles that include the transaction
+    pub results:      Vec<Bundle>,
+    /// Set if discovery flagged the transaction as possible mev that no
+    /// inspector classified
+    pub possible_mev: Option<PossibleMev>,
+}
+
+/// Runs the inspectors over the block the same way as
+/// [`run_block_inspection`] and explains what happened to every bundle that
+/// includes the given transaction during composition and deduplication.
+pub fn explain_tx_inspection<DB: LibmdbxReader>(
+    orchestra: &[&dyn Inspector<Result = Vec<Bundle>>],
+    data: MultiBlockData,
+    db: &'static DB,
+    tx_hash: B256,
+) -> TxInspectionExplanation {
+    let this_data = data.get_most_recent_block().clone();
+    let BlockData { metadata, tree } = this_data;
+
+    let (possible_mev_txes, results) = run_inspectors_by_inspector(orchestra, data);
+    let possible_mev = possible_mev_txes
+        .0
+        .iter()
+        .find(|possible| possible.tx_hash == tx_hash)
+        .cloned();
+
+    let found = orchestra
+        .iter()
+        .zip(results)
+        .flat_map(|(inspector, bundles)| {
+            bundles
+                .into_iter()
+                .map(|bundle| (inspector.get_id().to_string(), bundle))
+        })
+        .collect_vec();
+    let all_bundles = found.iter().map(|(_, bundle)| bundle.clone()).collect_vec();
+
+    let quote_token = orchestra[0].get_quote_token();
+    let (_, final_bundles) = on_orchestra_resolution(
+        tree.clone(),
+        possible_mev_txes,
+        metadata,
+        all_bundles.clone(),
+        quote_token,
+        db,
+    );
+
+    let includes_tx = |bundle: &Bundle| bundle.data.mev_transaction_hashes().contains(&tx_hash);
+    let found = found
+        .into_iter()
+        .filter(|(_, bundle)| includes_tx(bundle))
+        .map(|(inspector, bundle)| {
+            let outcome = bundle_outcome(tree.clone(), db, &bundle, &all_bundles, &final_bundles);
+            FoundBundle { inspector, bundle, outcome }
+        })
+        .collect();
+    let results = final_bundles.into_iter().filter(includes_tx).collect();
+
+    TxInspectionExplanation { found, results, possible_mev }
+}
+
+/// Works out the outcome of a bundle by checking the composition and
+/// deduplication rules against the bundles found in the block
+fn bundle_outcome<DB: LibmdbxReader>(
+    tree: Arc<BlockTree<Action>>,
+    db: &'static DB,
+    bundle: &Bundle,
+    found: &[Bundle],
+    results: &[Bundle],
+) -> BundleOutcome {
+    let mev_type = bundle.header.mev_type;
+    if results
+        .iter()
+        .any(|res| res.header.mev_type == mev_type && res.header.tx_hash == bundle.header.tx_hash)
+    {
+        return BundleOutcome::Kept
+    }
+
+    let hashes = bundle.data.mev_transaction_hashes();
+    let composed = MEV_COMPOSABILITY_FILTER
+        .iter()
+        .filter(|(_, _, child_mev_types)| child_mev_types.contains(&mev_type))
+        .find_map(|(parent_mev_type, ..)| {
+            results
+                .iter()
+                .filter(|res| res.header.mev_type == *parent_mev_type)
+                .any(|res| {
+                    res.data
+                        .mev_transaction_hashes()
+                        .iter()
+                        .any(|hash| hashes.contains(hash))
+                })
+                .then_some(*parent_mev_type)
+        });
+    if let Some(parent_mev_type) = composed {
+        return BundleOutcome::Composed(parent_mev_type)
+    }
+
+    for (dominant_mev_type, extra_filter_fn, subordinate_mev_types) in
+        MEV_DEDUPLICATION_FILTER.iter()
+    {
+        if !subordinate_mev_types.contains(&mev_type) {
+            continue
+        }
+
+        let dominant = results
+            .iter()
+            .chain(found)
+            .filter(|other| other.header.mev_type == *dominant_mev_type)
+            .find(|other| {
+                try_deduping_mev(
+                    tree.clone(),
+                    Box::new(db),
+                    other,
+                    std::slice::from_ref(bundle),
+                    extra_filter_fn,
+                    &other.data.mev_transaction_hashes(),
+                )
+                .next()
+                .is_some()
+            });
+
+        if let Some(dominant) = dominant {
+            return BundleOutcome::Deduplicated {
+                by:      *dominant_mev_type,
+                tx_hash: dominant.header.tx_hash,
+            }
+        }
+    }
+
+    BundleOutcome::Unprofitable
+}
+
 fn run_inspectors(
     orchestra: &[&dyn Inspector<Result = Vec<Bundle>>],
     data: MultiBlockData,
 ) -> (PossibleMevCollection, Vec<Bundle>) {
+    let (possible_mev_txes, results) = run_inspectors_by_inspector(orchestra, data);
+    (possible_mev_txes, results.into_iter().flatten().collect())
+}
+
+/// Runs the inspectors and returns the bundles of each inspector, in the order
+/// of the orchestra
+fn run_inspectors_by_inspector(
+    orchestra: &[&dyn Inspector<Result = Vec<Bundle>>],
+    data: MultiBlockData,
+) -> (PossibleMevCollection, Vec<Vec<Bundle>>) {
     let this_data = data.get_most_recent_block().clone();
     let BlockData { metadata, tree } = this_data;
     let mut possible_mev_txes =
@@ -101,7 +312,7 @@ fn run_inspectors(
 
     let results = orchestra
         .par_iter()
-        .flat_map(|inspector| {
+        .map(|inspector| {
             let window = inspector.block_window();
             // not sufficient size yet
             if data.blocks < window {
@@ -115,7 +326,7 @@ fn run_inspectors(
         })
         .collect::<Vec<_>>();
 
-    results.iter().for_each(|bundle| {
+    results.iter().flatten().for_each(|bundle| {
         bundle
             .data
             .mev_transaction_hashes()
@@ -333,4 +544,44 @@ pub mod tests {
 
         inspector_util.run_composer(config, None).await.unwrap();
     }
+
+    #[brontes_macros::test]
+    pub async fn test_reinspection_keeps_other_bundles() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 0.2).await;
+        let (stored, reinspected) = inspector_util
+            .run_reinspection(
+                18674873,
+                vec![
+                    Inspectors::Sandwich,
+                    Inspectors::Jit,
+                    Inspectors::AtomicArb,
+                    Inspectors::SearcherActivity,
+                ],
+                vec![Inspectors::Sandwich],
+            )
+            .await
+            .unwrap();
+
+        let kept = stored
+            .iter()
+            .filter(|bundle| {
+                !Inspectors::Sandwich
+                    .mev_types()
+                    .contains(&bundle.header.mev_type)
+            })
+            .collect::<Vec<_>>();
+        assert!(!kept.is_empty(), "block should have bundles of the other inspectors");
+
+        for bundle in kept {
+            assert!(
+                reinspected
+                    .iter()
+                    .any(|r| r.header.tx_hash == bundle.header.tx_hash
+                        && r.header.mev_type == bundle.header.mev_type),
+                "reinspection dropped {} bundle {:?}",
+                bundle.header.mev_type,
+                bundle.header.tx_hash
+            );
+        }
+    }
 }
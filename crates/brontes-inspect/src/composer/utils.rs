@@ -1,14 +1,19 @@
 use std::sync::Arc;
 
-use alloy_primitives::{Address, FixedBytes};
+use alloy_primitives::{Address, FixedBytes, B256};
 use brontes_types::{
     db::{builder::BuilderInfo, metadata::Metadata, traits::LibmdbxReader},
-    mev::{Bundle, Mev, MevBlock, MevCount, MevType, PossibleMevCollection},
+    mev::{
+        BidAnalysis, Bundle, FailedAttempt, FailedAttemptCollection, Mev, MevBlock, MevCount,
+        MevType, PossibleMevCollection,
+    },
     normalized_actions::Action,
     tree::BlockTree,
-    FastHashMap, GasDetails, ToFloatNearest, ToScaledRational, TreeSearchBuilder,
+    FastHashMap, FastHashSet, GasDetails, ToFloatNearest, ToScaledRational, TreeSearchBuilder,
 };
+use itertools::Itertools;
 use malachite::{num::conversion::traits::RoundingFrom, rounding_modes::RoundingMode};
+use tracing::warn;
 
 use crate::composer::FilterFn;
 
@@ -29,8 +34,26 @@ pub(crate) fn build_mev_header<DB: LibmdbxReader>(
 
     let eth_price = metadata.get_eth_price(quote_token);
 
+    let failed_attempts = find_failed_attempts(&tree, metadata, orchestra_data, quote_token, db);
+
     let pre_processing = pre_process(tree.clone());
 
+    let relay_bids = db
+        .try_fetch_relay_bids(metadata.block_num)
+        .unwrap_or_default()
+        .map(|bids| bids.0)
+        .unwrap_or_default();
+    let bid_analysis = BidAnalysis::new(
+        &relay_bids,
+        metadata.block_hash.into(),
+        tree.header.parent_hash,
+        metadata.block_timestamp,
+        metadata.p2p_timestamp,
+        pre_processing.total_priority_fee + pre_processing.total_bribe,
+    );
+
+    let payment_delta = verify_proposer_payment(&tree, metadata, &pre_processing);
+
     let block_pnl = calculate_builder_profit(tree, metadata, orchestra_data, &pre_processing);
 
     let builder_searcher_bribes_usd = f64::rounding_from(
@@ -51,6 +74,24 @@ pub(crate) fn build_mev_header<DB: LibmdbxReader>(
         .unwrap()
         .and_then(|b| b.name);
 
+    if let Some(delta) = payment_delta.filter(|delta| *delta < 0) {
+        let block_hash = format!("{:?}", B256::from(metadata.block_hash));
+        let relays = relay_bids
+            .iter()
+            .filter(|bid| bid.block_hash.to_lowercase() == block_hash)
+            .map(|bid| bid.relay.to_string())
+            .unique()
+            .join(", ");
+        warn!(
+            block = metadata.block_num,
+            builder = ?pre_processing.builder_address,
+            builder_name = builder_name.as_deref().unwrap_or("unknown"),
+            %relays,
+            underpaid_wei = delta.unsigned_abs(),
+            "proposer received less than the relay bid"
+        );
+    }
+
     MevBlock {
         block_hash: metadata.block_hash.into(),
         block_number: metadata.block_num,
@@ -77,9 +118,80 @@ pub(crate) fn build_mev_header<DB: LibmdbxReader>(
         proposer_fee_recipient,
         proposer_mev_reward,
         proposer_profit_usd,
+        payment_verified: payment_delta.map(|delta| delta >= 0),
+        payment_delta,
         total_mev_profit_usd,
         possible_mev,
+        failed_attempts,
+        bid_analysis,
+    }
+}
+
+/// Reverted and no-op transactions sent to known searcher contracts, the gas
+/// of these is burnt on mev attempts that didn't land.
+fn find_failed_attempts<DB: LibmdbxReader>(
+    tree: &BlockTree<Action>,
+    metadata: &Metadata,
+    orchestra_data: &[Bundle],
+    quote_token: Address,
+    db: &'static DB,
+) -> FailedAttemptCollection {
+    let mev_txes: FastHashSet<_> = orchestra_data
+        .iter()
+        .flat_map(|bundle| bundle.data.mev_transaction_hashes())
+        .collect();
+
+    let reverted = tree
+        .failed_txs
+        .iter()
+        .map(|tx| (tx.tx_hash, tx.position, tx.eoa, tx.call.to, tx.gas_details, true));
+    let no_ops = tree
+        .tx_roots
+        .iter()
+        .filter(|root| root.is_no_op() && !mev_txes.contains(&root.tx_hash))
+        .map(|root| {
+            (root.tx_hash, root.position, root.head.address, root.call.to, root.gas_details, false)
+        });
+    let candidates = reverted.chain(no_ops).collect_vec();
+    if candidates.is_empty() {
+        return FailedAttemptCollection::default()
     }
+
+    let contracts = candidates
+        .iter()
+        .map(|(.., to, ..)| *to)
+        .unique()
+        .collect_vec();
+    let searcher_contracts = db
+        .try_fetch_searcher_contract_infos(contracts.clone())
+        .unwrap_or_default();
+    let address_metadata = db
+        .try_fetch_address_metadatas(contracts)
+        .unwrap_or_default();
+
+    let mut attempts = candidates
+        .into_iter()
+        .filter(|(.., to, ..)| {
+            searcher_contracts.contains_key(to)
+                || address_metadata
+                    .get(to)
+                    .is_some_and(|meta| meta.get_contract_type().is_mev_contract())
+        })
+        .map(|(tx_hash, position, eoa, mev_contract, gas_details, reverted)| FailedAttempt {
+            tx_hash,
+            tx_idx: position as u64,
+            eoa,
+            mev_contract,
+            reverted,
+            gas_details,
+            gas_paid_usd: metadata
+                .get_gas_price_usd(gas_details.gas_paid(), quote_token)
+                .to_float(),
+        })
+        .collect_vec();
+    attempts.sort_by_key(|attempt| attempt.tx_idx);
+
+    FailedAttemptCollection(attempts)
 }
 
 /// Sorts the given MEV data by type.
@@ -191,6 +303,8 @@ fn update_mev_count(mev_count: &mut MevCount, mev_type: MevType, count: u64) {
         MevType::AtomicArb => mev_count.atomic_backrun_count = Some(count),
         MevType::Liquidation => mev_count.liquidation_count = Some(count),
         MevType::SearcherTx => mev_count.searcher_tx_count = Some(count),
+        MevType::LongTail => mev_count.long_tail_count = Some(count),
+        MevType::Frontrun => mev_count.frontrun_count = Some(count),
         MevType::Unknown => (),
     }
 }
@@ -330,6 +444,42 @@ fn proposer_payment(
     })
 }
 
+/// The amount the proposer fee recipient received on chain minus the bid the
+/// relay reported as delivered, None if it isn't an mev boost block.
+///
+/// If the fee recipient is the coinbase it is paid through the coinbase balance
+/// change, otherwise by the builder's transfer in the last transaction of the
+/// block.
+fn verify_proposer_payment(
+    tree: &Arc<BlockTree<Action>>,
+    metadata: &Metadata,
+    pre_processing: &BlockPreprocessing,
+) -> Option<i128> {
+    let promised = metadata.proposer_mev_reward?;
+    let fee_recipient = metadata.proposer_fee_recipient?;
+
+    let paid = if pre_processing.builder_address == fee_recipient {
+        pre_processing.total_priority_fee + pre_processing.total_bribe
+    } else {
+        let collateral_address = metadata
+            .builder_info
+            .as_ref()
+            .and_then(|info| info.ultrasound_relay_collateral_address);
+
+        proposer_payment(
+            tree,
+            pre_processing.builder_address,
+            collateral_address,
+            Some(fee_recipient),
+        )
+        .filter(|(_, to, _)| *to == Some(fee_recipient))
+        .map(|(value, ..)| value as u128)
+        .unwrap_or_default()
+    };
+
+    Some(paid as i128 - promised as i128)
+}
+
 /// Accounts for the profit made by the builders vertically integrated searchers
 fn calculate_mev_searching_profit(bundles: &[Bundle], builder_info: &BuilderInfo) -> (f64, u128) {
     if builder_info.searchers_eoas.is_empty() && builder_info.searchers_contracts.is_empty() {
@@ -471,3 +621,153 @@ fn calculate_block_mev_stats(orchestra_data: &[Bundle], base_fee: u128) -> (u128
         },
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::{
+        db::metadata::BlockMetadata,
+        mev::{BundleData, SearcherTx},
+    };
+
+    use super::*;
+    use crate::test_utils::{InspectorTestUtils, USDC_ADDRESS};
+
+    fn payment_metadata(fee_recipient: Address, mev_reward: Option<u128>) -> Metadata {
+        Metadata {
+            block_metadata: BlockMetadata {
+                proposer_fee_recipient: Some(fee_recipient),
+                proposer_mev_reward: mev_reward,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[brontes_macros::test]
+    async fn test_verify_proposer_payment_to_coinbase() {
+        let test_utils = InspectorTestUtils::new(USDC_ADDRESS, 0.0).await;
+        let tree = Arc::new(
+            test_utils
+                .classifier_inspector
+                .build_block_tree(18674873)
+                .await
+                .unwrap(),
+        );
+        let pre_processing = pre_process(tree.clone());
+        let paid = pre_processing.total_priority_fee + pre_processing.total_bribe;
+        let coinbase = pre_processing.builder_address;
+
+        // the fee recipient is paid through the priority fees and coinbase transfers
+        let metadata = payment_metadata(coinbase, Some(paid - 10));
+        assert_eq!(verify_proposer_payment(&tree, &metadata, &pre_processing), Some(10));
+
+        let metadata = payment_metadata(coinbase, Some(paid + 10));
+        assert_eq!(verify_proposer_payment(&tree, &metadata, &pre_processing), Some(-10));
+    }
+
+    #[brontes_macros::test]
+    async fn test_verify_proposer_payment_by_builder_transfer() {
+        let test_utils = InspectorTestUtils::new(USDC_ADDRESS, 0.0).await;
+        let tree = Arc::new(
+            test_utils
+                .classifier_inspector
+                .build_block_tree(18674873)
+                .await
+                .unwrap(),
+        );
+        let pre_processing = pre_process(tree.clone());
+
+        let Action::EthTransfer(payment) = tree.tx_roots.last().unwrap().get_root_action() else {
+            panic!("the last transaction of the block isn't the proposer payment")
+        };
+        let paid: u128 = payment.value.to();
+
+        let metadata = payment_metadata(payment.to, Some(paid));
+        assert_eq!(verify_proposer_payment(&tree, &metadata, &pre_processing), Some(0));
+
+        let metadata = payment_metadata(payment.to, Some(paid + 1_000));
+        assert_eq!(verify_proposer_payment(&tree, &metadata, &pre_processing), Some(-1_000));
+
+        // a transfer to another address doesn't pay the fee recipient
+        let metadata = payment_metadata(Address::repeat_byte(1), Some(paid));
+        assert_eq!(
+            verify_proposer_payment(&tree, &metadata, &pre_processing),
+            Some(-(paid as i128))
+        );
+
+        // not an mev boost block
+        let metadata = payment_metadata(payment.to, None);
+        assert_eq!(verify_proposer_payment(&tree, &metadata, &pre_processing), None);
+    }
+
+    #[brontes_macros::test]
+    async fn test_find_failed_attempts() {
+        let test_utils = InspectorTestUtils::new(USDC_ADDRESS, 0.0).await;
+        let tree = test_utils
+            .classifier_inspector
+            .build_block_tree(18674873)
+            .await
+            .unwrap();
+        let db = test_utils.classifier_inspector.trace_loader.libmdbx;
+        let metadata = Metadata::default();
+
+        let attempts = find_failed_attempts(&tree, &metadata, &[], USDC_ADDRESS, db).0;
+        assert!(attempts.windows(2).all(|w| w[0].tx_idx <= w[1].tx_idx));
+
+        let contracts = attempts
+            .iter()
+            .map(|attempt| attempt.mev_contract)
+            .collect_vec();
+        let searcher_contracts = db
+            .try_fetch_searcher_contract_infos(contracts.clone())
+            .unwrap();
+        let address_metadata = db.try_fetch_address_metadatas(contracts).unwrap();
+
+        for attempt in &attempts {
+            if attempt.reverted {
+                assert!(tree
+                    .failed_txs
+                    .iter()
+                    .any(|tx| tx.tx_hash == attempt.tx_hash && tx.call.to == attempt.mev_contract));
+            } else {
+                let root = tree
+                    .tx_roots
+                    .iter()
+                    .find(|root| root.tx_hash == attempt.tx_hash)
+                    .unwrap();
+                assert!(root.is_no_op());
+                assert_eq!(root.call.to, attempt.mev_contract);
+            }
+
+            assert!(
+                searcher_contracts.contains_key(&attempt.mev_contract)
+                    || address_metadata
+                        .get(&attempt.mev_contract)
+                        .is_some_and(|meta| meta.get_contract_type().is_mev_contract()),
+                "{:?} isn't a searcher contract",
+                attempt.mev_contract
+            );
+        }
+
+        // no-ops that are part of a bundle aren't failed attempts
+        let bundles = attempts
+            .iter()
+            .filter(|attempt| !attempt.reverted)
+            .map(|attempt| Bundle {
+                header: Default::default(),
+                data:   BundleData::Unknown(SearcherTx {
+                    tx_hash: attempt.tx_hash,
+                    ..Default::default()
+                }),
+            })
+            .collect_vec();
+        let without_bundled = find_failed_attempts(&tree, &metadata, &bundles, USDC_ADDRESS, db).0;
+        assert_eq!(
+            without_bundled,
+            attempts
+                .into_iter()
+                .filter(|attempt| attempt.reverted)
+                .collect_vec()
+        );
+    }
+}
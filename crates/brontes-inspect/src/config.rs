@@ -0,0 +1,433 @@
+//! Detection thresholds of the inspectors, loaded from the toml file passed
+//! with `--config`. Every value defaults to the value brontes uses without a
+//! config, so a config only has to contain the values that are tuned.
+//!
+//! ```toml
+//! [atomic_arb]
+//! triangle_searcher_threshold = 20
+//! cross_pair_searcher_threshold = 10
+//! long_tail_searcher_threshold = 10
+//! unpriced_threshold_multiplier = 2
+//!
+//! [cex_dex]
+//! searcher_threshold = 20
+//!
+//! [cex_dex_markout]
+//! searcher_threshold = 20
+//! max_vwap_pre_us = 5000000
+//!
+//! [frontrun]
+//! calldata_similarity = 0.75
+//!
+//! [pricing]
+//! max_block_movement = 0.9
+//!
+//! [profit_floors]
+//! AtomicArb = 1.0
+//!
+//! [stable_pairs]
+//! usd = ["USDX"]
+//!
+//! [searcher_eoas."0x..."]
+//! name = "searcher"
+//! mev_types = ["AtomicArb"]
+//! ```
+use alloy_primitives::Address;
+use brontes_types::{
+    constants::{get_stable_type, is_euro_stable, is_gold_stable, is_usd_stable, StableType},
+    db::{cex::trades::CexDexTradeConfig, searcher::SearcherInfo},
+    mev::Bundle,
+    FastHashMap, MultiBlockData,
+};
+use serde::Deserialize;
+
+use crate::{Inspector, Inspectors};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InspectorConfig {
+    pub atomic_arb:         AtomicArbConfig,
+    pub cex_dex:            CexDexConfig,
+    pub cex_dex_markout:    CexDexMarkoutConfig,
+    pub frontrun:           FrontrunConfig,
+    pub pricing:            PricingConfig,
+    /// The minimum usd profit of the bundles of an inspector. Bundles without
+    /// a price are always kept
+    pub profit_floors:      FastHashMap<Inspectors, f64>,
+    pub stable_pairs:       StablePairs,
+    /// Searcher labels in the format of `searcher_config.toml`, they override
+    /// the stored labels of the searchers
+    pub searcher_eoas:      FastHashMap<String, SearcherInfo>,
+    pub searcher_contracts: FastHashMap<String, SearcherInfo>,
+}
+
+impl InspectorConfig {
+    pub fn validate(&self) -> eyre::Result<()> {
+        if self.atomic_arb.unpriced_threshold_multiplier == 0 {
+            return Err(eyre::eyre!("atomic_arb.unpriced_threshold_multiplier must be at least 1"))
+        }
+        if !(self.frontrun.calldata_similarity > 0.0 && self.frontrun.calldata_similarity <= 1.0) {
+            return Err(eyre::eyre!("frontrun.calldata_similarity must be within (0, 1]"))
+        }
+        if !(self.pricing.max_block_movement > 0.0 && self.pricing.max_block_movement <= 1.0) {
+            return Err(eyre::eyre!("pricing.max_block_movement must be within (0, 1]"))
+        }
+        if let Some((inspector, _)) = self
+            .profit_floors
+            .iter()
+            .find(|(_, floor)| !floor.is_finite())
+        {
+            return Err(eyre::eyre!("the profit floor of {inspector} must be a finite number"))
+        }
+        self.cex_dex_markout.validate()?;
+        self.searcher_labels()?;
+
+        Ok(())
+    }
+
+    /// The eoa and contract searcher labels of the config
+    #[allow(clippy::type_complexity)]
+    pub fn searcher_labels(
+        &self,
+    ) -> eyre::Result<(Vec<(Address, SearcherInfo)>, Vec<(Address, SearcherInfo)>)> {
+        let parse = |labels: &FastHashMap<String, SearcherInfo>| {
+            labels
+                .iter()
+                .map(|(address, info)| {
+                    let address = address
+                        .parse()
+                        .map_err(|_| eyre::eyre!("invalid searcher address '{address}'"))?;
+                    Ok((address, info.clone()))
+                })
+                .collect::<eyre::Result<Vec<_>>>()
+        };
+
+        Ok((parse(&self.searcher_eoas)?, parse(&self.searcher_contracts)?))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AtomicArbConfig {
+    /// Atomic arbs a searcher needs for its unprofitable triangle arbs to count
+    pub triangle_searcher_threshold:   u64,
+    /// Atomic arbs a searcher needs for its unprofitable cross pair and stable
+    /// arbs to count
+    pub cross_pair_searcher_threshold: u64,
+    /// Atomic arbs a searcher needs for its long tail arbs to count
+    pub long_tail_searcher_threshold:  u64,
+    /// The searcher thresholds are multiplied by this for arbs that couldn't
+    /// be priced
+    pub unpriced_threshold_multiplier: u64,
+}
+
+impl Default for AtomicArbConfig {
+    fn default() -> Self {
+        Self {
+            triangle_searcher_threshold:   20,
+            cross_pair_searcher_threshold: 10,
+            long_tail_searcher_threshold:  10,
+            unpriced_threshold_multiplier: 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CexDexConfig {
+    /// Cex dex bundles an address needs to count as a cex dex searcher
+    pub searcher_threshold: u64,
+}
+
+impl Default for CexDexConfig {
+    fn default() -> Self {
+        Self { searcher_threshold: 20 }
+    }
+}
+
+/// Overrides the time windows passed on the command line when set
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CexDexMarkoutConfig {
+    /// Cex dex trades a private, unclassified tx's searcher needs for the tx to
+    /// count, searchers with twice as many always count
+    pub searcher_threshold:         u64,
+    pub initial_vwap_pre_us:        Option<u64>,
+    pub initial_vwap_post_us:       Option<u64>,
+    pub max_vwap_pre_us:            Option<u64>,
+    pub max_vwap_post_us:           Option<u64>,
+    pub initial_optimistic_pre_us:  Option<u64>,
+    pub initial_optimistic_post_us: Option<u64>,
+    pub max_optimistic_pre_us:      Option<u64>,
+    pub max_optimistic_post_us:     Option<u64>,
+}
+
+impl Default for CexDexMarkoutConfig {
+    fn default() -> Self {
+        Self {
+            searcher_threshold:         20,
+            initial_vwap_pre_us:        None,
+            initial_vwap_post_us:       None,
+            max_vwap_pre_us:            None,
+            max_vwap_post_us:           None,
+            initial_optimistic_pre_us:  None,
+            initial_optimistic_post_us: None,
+            max_optimistic_pre_us:      None,
+            max_optimistic_post_us:     None,
+        }
+    }
+}
+
+impl CexDexMarkoutConfig {
+    /// The trade config with the windows of the config applied
+    pub fn apply(&self, mut trade_config: CexDexTradeConfig) -> CexDexTradeConfig {
+        let set = |value: &mut u64, over: Option<u64>| {
+            if let Some(over) = over {
+                *value = over;
+            }
+        };
+        set(&mut trade_config.initial_vwap_pre_block_us, self.initial_vwap_pre_us);
+        set(&mut trade_config.initial_vwap_post_block_us, self.initial_vwap_post_us);
+        set(&mut trade_config.max_vwap_pre_block_us, self.max_vwap_pre_us);
+        set(&mut trade_config.max_vwap_post_block_us, self.max_vwap_post_us);
+        set(&mut trade_config.initial_optimistic_pre_block_us, self.initial_optimistic_pre_us);
+        set(&mut trade_config.initial_optimistic_post_block_us, self.initial_optimistic_post_us);
+        set(&mut trade_config.max_optimistic_pre_block_us, self.max_optimistic_pre_us);
+        set(&mut trade_config.max_optimistic_post_block_us, self.max_optimistic_post_us);
+
+        trade_config
+    }
+
+    fn validate(&self) -> eyre::Result<()> {
+        let windows = [
+            ("vwap_pre", self.initial_vwap_pre_us, self.max_vwap_pre_us),
+            ("vwap_post", self.initial_vwap_post_us, self.max_vwap_post_us),
+            ("optimistic_pre", self.initial_optimistic_pre_us, self.max_optimistic_pre_us),
+            ("optimistic_post", self.initial_optimistic_post_us, self.max_optimistic_post_us),
+        ];
+        for (window, initial, max) in windows {
+            if let (Some(initial), Some(max)) = (initial, max) {
+                if initial > max {
+                    return Err(eyre::eyre!(
+                        "cex_dex_markout.initial_{window}_us must not be larger than \
+                         cex_dex_markout.max_{window}_us"
+                    ))
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FrontrunConfig {
+    /// The share of equal calldata words a call needs with the call of a
+    /// displaced transaction to count as a copy of it
+    pub calldata_similarity: f64,
+}
+
+impl Default for FrontrunConfig {
+    fn default() -> Self {
+        Self { calldata_similarity: 0.75 }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PricingConfig {
+    /// The max relative price movement of a pair within a block before its
+    /// prices are dropped as invalid
+    pub max_block_movement: f64,
+}
+
+impl Default for PricingConfig {
+    fn default() -> Self {
+        Self { max_block_movement: 0.9 }
+    }
+}
+
+/// Token symbols that count as stables on top of the built in ones
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StablePairs {
+    pub usd:  Vec<String>,
+    pub euro: Vec<String>,
+    pub gold: Vec<String>,
+}
+
+impl StablePairs {
+    fn stable_type(&self, symbol: &str) -> Option<StableType> {
+        get_stable_type(symbol).or_else(|| {
+            if self.usd.iter().any(|s| s == symbol) {
+                Some(StableType::USD)
+            } else if self.euro.iter().any(|s| s == symbol) {
+                Some(StableType::EURO)
+            } else if self.gold.iter().any(|s| s == symbol) {
+                Some(StableType::GOLD)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// whether both tokens are stables of the same type
+    pub fn is_stable_pair(&self, token_in: &str, token_out: &str) -> bool {
+        let Some(stable_type) = self.stable_type(token_in) else { return false };
+        match stable_type {
+            StableType::USD => is_usd_stable(token_out) || self.usd.iter().any(|s| s == token_out),
+            StableType::EURO => {
+                is_euro_stable(token_out) || self.euro.iter().any(|s| s == token_out)
+            }
+            StableType::GOLD => {
+                is_gold_stable(token_out) || self.gold.iter().any(|s| s == token_out)
+            }
+        }
+    }
+}
+
+/// Drops the bundles of the inner inspector that are below the profit floor.
+/// Bundles without a price are kept as their profit isn't known.
+pub struct ProfitFloor {
+    pub inspector: &'static (dyn Inspector<Result = Vec<Bundle>> + 'static),
+    pub floor:     f64,
+}
+
+impl Inspector for ProfitFloor {
+    type Result = Vec<Bundle>;
+
+    fn block_window(&self) -> usize {
+        self.inspector.block_window()
+    }
+
+    fn get_id(&self) -> &str {
+        self.inspector.get_id()
+    }
+
+    fn get_quote_token(&self) -> Address {
+        self.inspector.get_quote_token()
+    }
+
+    fn inspect_block(&self, data: MultiBlockData) -> Self::Result {
+        let mut bundles = self.inspector.inspect_block(data);
+        bundles.retain(|bundle| {
+            bundle.header.no_pricing_calculated || bundle.header.profit_usd >= self.floor
+        });
+
+        bundles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::mev::MevType;
+
+    use super::*;
+
+    fn parse(config: &str) -> eyre::Result<InspectorConfig> {
+        let config: InspectorConfig = toml::from_str(config)?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config = parse(
+            r#"
+            [atomic_arb]
+            triangle_searcher_threshold = 5
+            unpriced_threshold_multiplier = 3
+
+            [cex_dex_markout]
+            max_vwap_pre_us = 5000000
+
+            [frontrun]
+            calldata_similarity = 0.5
+
+            [pricing]
+            max_block_movement = 0.5
+
+            [profit_floors]
+            AtomicArb = 1.5
+
+            [stable_pairs]
+            usd = ["USDX"]
+
+            [searcher_eoas."0x1111111111111111111111111111111111111111"]
+            name = "searcher"
+            mev_types = ["AtomicArb"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.atomic_arb.triangle_searcher_threshold, 5);
+        assert_eq!(config.atomic_arb.unpriced_threshold_multiplier, 3);
+        // unset values keep their defaults
+        assert_eq!(config.atomic_arb.cross_pair_searcher_threshold, 10);
+        assert_eq!(config.cex_dex.searcher_threshold, 20);
+        assert_eq!(config.cex_dex_markout.max_vwap_pre_us, Some(5_000_000));
+        assert_eq!(config.cex_dex_markout.initial_vwap_pre_us, None);
+        assert_eq!(config.frontrun.calldata_similarity, 0.5);
+        assert_eq!(config.pricing.max_block_movement, 0.5);
+        assert_eq!(config.profit_floors.get(&Inspectors::AtomicArb), Some(&1.5));
+        assert!(config.stable_pairs.is_stable_pair("USDX", "USDC"));
+        assert!(!config.stable_pairs.is_stable_pair("USDX", "WETH"));
+
+        let (eoas, contracts) = config.searcher_labels().unwrap();
+        assert!(contracts.is_empty());
+        assert_eq!(eoas.len(), 1);
+        let (address, info) = &eoas[0];
+        assert_eq!(*address, Address::repeat_byte(0x11));
+        assert_eq!(info.name.as_deref(), Some("searcher"));
+        assert_eq!(info.config_labels, vec![MevType::AtomicArb]);
+    }
+
+    #[test]
+    fn test_empty_config_is_default() {
+        let config = parse("").unwrap();
+        let default = InspectorConfig::default();
+
+        assert_eq!(
+            config.atomic_arb.unpriced_threshold_multiplier,
+            default.atomic_arb.unpriced_threshold_multiplier
+        );
+        assert_eq!(config.frontrun.calldata_similarity, default.frontrun.calldata_similarity);
+        assert_eq!(config.pricing.max_block_movement, default.pricing.max_block_movement);
+        assert!(config.profit_floors.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_fields_are_rejected() {
+        assert!(parse("[frontrun]\ncalldata_similarty = 0.5").is_err());
+        assert!(parse("[unknown]\nvalue = 1").is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(parse("[atomic_arb]\nunpriced_threshold_multiplier = 0").is_err());
+
+        assert!(parse("[frontrun]\ncalldata_similarity = 0.0").is_err());
+        assert!(parse("[frontrun]\ncalldata_similarity = 1.5").is_err());
+        assert!(parse("[frontrun]\ncalldata_similarity = 1.0").is_ok());
+
+        assert!(parse("[pricing]\nmax_block_movement = 0.0").is_err());
+        assert!(parse("[pricing]\nmax_block_movement = 2.0").is_err());
+        assert!(parse("[pricing]\nmax_block_movement = nan").is_err());
+        assert!(parse("[pricing]\nmax_block_movement = 1.0").is_ok());
+
+        assert!(parse("[profit_floors]\nAtomicArb = inf").is_err());
+        assert!(parse("[profit_floors]\nAtomicArb = nan").is_err());
+        assert!(parse("[profit_floors]\nAtomicArb = -1.0").is_ok());
+
+        assert!(parse("[cex_dex_markout]\ninitial_vwap_pre_us = 2\nmax_vwap_pre_us = 1").is_err());
+        assert!(parse("[cex_dex_markout]\ninitial_vwap_pre_us = 1\nmax_vwap_pre_us = 1").is_ok());
+    }
+
+    #[test]
+    fn test_invalid_searcher_address() {
+        assert!(parse("[searcher_eoas.\"0x1234\"]\nname = \"searcher\"").is_err());
+        assert!(parse("[searcher_contracts.searcher]\nname = \"searcher\"").is_err());
+    }
+}
@@ -34,6 +34,7 @@
 //! - [`sandwich`](sandwich/index.html)
 //! - [`liquidations`](liquidations/index.html)
 //! - [`long_tail`](long_tail/index.html)
+//! - [`frontrun`](frontrun/index.html)
 //!
 //! Each inspector implements the `Inspector` trait and provides its own
 //! implementation of the `inspect_block` method.
@@ -58,6 +59,7 @@
 //! composition.
 
 pub mod composer;
+pub mod config;
 pub mod discovery;
 pub mod mev_inspectors;
 use brontes_metrics::inspectors::OutlierMetrics;
@@ -75,14 +77,17 @@ use brontes_types::{
         metadata::Metadata,
         traits::LibmdbxReader,
     },
-    mev::{Bundle, BundleData},
+    mev::{Bundle, BundleData, MevType},
     normalized_actions::Action,
     tree::BlockTree,
-    MultiBlockData,
+    ChainConfig, MultiBlockData,
 };
 use cex_dex::{markout::CexDexMarkoutInspector, quotes::CexDexQuotesInspector};
+use config::{InspectorConfig, ProfitFloor};
+use frontrun::FrontrunInspector;
 use jit::JitCexDex;
 use liquidations::LiquidationInspector;
+use long_tail::LongTailInspector;
 use sandwich::SandwichInspector;
 
 use crate::jit::jit_liquidity::JitInspector;
@@ -101,7 +106,16 @@ pub trait Inspector: Send + Sync {
 }
 
 #[derive(
-    Debug, PartialEq, Clone, Copy, Eq, Hash, strum::Display, strum::EnumString, strum::EnumIter,
+    Debug,
+    PartialEq,
+    Clone,
+    Copy,
+    Eq,
+    Hash,
+    strum::Display,
+    strum::EnumString,
+    strum::EnumIter,
+    serde::Deserialize,
 )]
 pub enum Inspectors {
     AtomicArb,
@@ -112,6 +126,8 @@ pub enum Inspectors {
     SearcherActivity,
     CexDexMarkout,
     JitCexDex,
+    LongTail,
+    Frontrun,
 }
 
 type DynMevInspector = &'static (dyn Inspector<Result = Vec<Bundle>> + 'static);
@@ -124,22 +140,28 @@ impl Inspectors {
         cex_exchanges: &[CexExchange],
         trade_config: CexDexTradeConfig,
         metrics: Option<OutlierMetrics>,
+        config: &InspectorConfig,
+        chain: &ChainConfig,
     ) -> DynMevInspector {
-        match &self {
-            Self::AtomicArb => {
-                static_object(AtomicArbInspector::new(quote_token, db, metrics)) as DynMevInspector
-            }
+        let inspector = match &self {
+            Self::AtomicArb => static_object(
+                AtomicArbInspector::new(quote_token, db, metrics)
+                    .with_config(config.atomic_arb.clone(), config.stable_pairs.clone()),
+            ) as DynMevInspector,
             Self::Jit => {
                 static_object(JitInspector::new(quote_token, db, metrics)) as DynMevInspector
             }
 
-            Self::CexDex => static_object(CexDexQuotesInspector::new(
-                quote_token,
-                db,
-                cex_exchanges,
-                trade_config.quote_offset_from_block_us,
-                metrics,
-            )) as DynMevInspector,
+            Self::CexDex => static_object(
+                CexDexQuotesInspector::new(
+                    quote_token,
+                    db,
+                    cex_exchanges,
+                    trade_config.quote_offset_from_block_us,
+                    metrics,
+                )
+                .with_config(config.cex_dex.clone()),
+            ) as DynMevInspector,
             Self::Sandwich => {
                 static_object(SandwichInspector::new(quote_token, db, metrics)) as DynMevInspector
             }
@@ -150,13 +172,10 @@ impl Inspectors {
             Self::SearcherActivity => {
                 static_object(SearcherActivity::new(quote_token, db, metrics)) as DynMevInspector
             }
-            Self::CexDexMarkout => static_object(CexDexMarkoutInspector::new(
-                quote_token,
-                db,
-                cex_exchanges,
-                trade_config,
-                metrics,
-            )) as DynMevInspector,
+            Self::CexDexMarkout => static_object(
+                CexDexMarkoutInspector::new(quote_token, db, cex_exchanges, trade_config, metrics)
+                    .with_config(config.cex_dex_markout.clone(), config.stable_pairs.clone()),
+            ) as DynMevInspector,
             Self::JitCexDex => static_object(JitCexDex {
                 cex_dex: CexDexMarkoutInspector::new(
                     quote_token,
@@ -164,9 +183,44 @@ impl Inspectors {
                     cex_exchanges,
                     trade_config,
                     metrics.clone(),
-                ),
+                )
+                .with_config(config.cex_dex_markout.clone(), config.stable_pairs.clone()),
                 jit:     JitInspector::new(quote_token, db, metrics),
             }) as DynMevInspector,
+            Self::LongTail => static_object(LongTailInspector::new(
+                quote_token,
+                chain.wrapped_native,
+                db,
+                metrics,
+            )) as DynMevInspector,
+            Self::Frontrun => static_object(
+                FrontrunInspector::new(quote_token, db, metrics)
+                    .with_config(config.frontrun.clone()),
+            ) as DynMevInspector,
+        };
+
+        match config.profit_floors.get(self) {
+            Some(floor) => {
+                static_object(ProfitFloor { inspector, floor: *floor }) as DynMevInspector
+            }
+            None => inspector,
+        }
+    }
+
+    /// The bundle types the inspector produces, including the types its
+    /// bundles get composed into
+    pub fn mev_types(&self) -> &'static [MevType] {
+        match self {
+            Self::AtomicArb => &[MevType::AtomicArb],
+            Self::CexDex => &[MevType::CexDexQuotes],
+            Self::Jit => &[MevType::Jit, MevType::JitSandwich],
+            Self::Liquidations => &[MevType::Liquidation],
+            Self::Sandwich => &[MevType::Sandwich, MevType::JitSandwich],
+            Self::SearcherActivity => &[MevType::SearcherTx],
+            Self::CexDexMarkout => &[MevType::CexDexTrades],
+            Self::JitCexDex => &[MevType::JitCexDex],
+            Self::LongTail => &[MevType::LongTail],
+            Self::Frontrun => &[MevType::Frontrun],
         }
     }
 }
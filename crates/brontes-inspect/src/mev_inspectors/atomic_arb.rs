@@ -3,7 +3,6 @@ use std::sync::Arc;
 use brontes_database::libmdbx::LibmdbxReader;
 use brontes_metrics::inspectors::OutlierMetrics;
 use brontes_types::{
-    constants::{get_stable_type, is_euro_stable, is_gold_stable, is_usd_stable, StableType},
     db::dex::PriceAt,
     mev::{AtomicArb, AtomicArbType, Bundle, BundleData, MevType},
     normalized_actions::{
@@ -18,19 +17,33 @@ use malachite::{num::basic::traits::Zero, Rational};
 use reth_primitives::{Address, B256};
 
 use crate::{
-    shared_utils::SharedInspectorUtils, BlockTree, Inspector, Metadata, MAX_PROFIT, MIN_PROFIT,
+    config::{AtomicArbConfig, StablePairs},
+    shared_utils::SharedInspectorUtils,
+    BlockTree, Inspector, Metadata, MAX_PROFIT, MIN_PROFIT,
 };
 
 const MAX_PRICE_DIFF: Rational = Rational::const_from_unsigneds(99995, 100000);
 
 // figure out why
 pub struct AtomicArbInspector<'db, DB: LibmdbxReader> {
-    utils: SharedInspectorUtils<'db, DB>,
+    utils:        SharedInspectorUtils<'db, DB>,
+    config:       AtomicArbConfig,
+    stable_pairs: StablePairs,
 }
 
 impl<'db, DB: LibmdbxReader> AtomicArbInspector<'db, DB> {
     pub fn new(quote: Address, db: &'db DB, metrics: Option<OutlierMetrics>) -> Self {
-        Self { utils: SharedInspectorUtils::new(quote, db, metrics) }
+        Self {
+            utils:        SharedInspectorUtils::new(quote, db, metrics),
+            config:       AtomicArbConfig::default(),
+            stable_pairs: StablePairs::default(),
+        }
+    }
+
+    pub fn with_config(mut self, config: AtomicArbConfig, stable_pairs: StablePairs) -> Self {
+        self.config = config;
+        self.stable_pairs = stable_pairs;
+        self
     }
 }
 
@@ -177,7 +190,8 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
 
         let is_profitable = profit > Rational::ZERO;
 
-        let requirement_multiplier = if has_dex_price { 1 } else { 2 };
+        let requirement_multiplier =
+            if has_dex_price { 1 } else { self.config.unpriced_threshold_multiplier };
 
         let profit = match possible_arb_type {
             AtomicArbType::Triangle => (is_profitable
@@ -235,7 +249,9 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
     }
 
     /// goes back through the tree until it finds a transaction that occurred
-    /// before the atomic arb that use the same liquidity pool for a swap.
+    /// before the atomic arb that use the same liquidity pool for a swap. If
+    /// there is none, falls back to an oracle update in the same block for one
+    /// of the arbed tokens.
     fn find_trigger_tx(
         &self,
         arb_info: &TxInfo,
@@ -243,6 +259,13 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
         swaps: &[NormalizedSwap],
     ) -> B256 {
         let this_tree = trees.pop().unwrap();
+        let tokens = swaps
+            .iter()
+            .flat_map(|swap| [swap.token_in.address, swap.token_out.address])
+            .collect::<FastHashSet<_>>();
+        let oracle_trigger = self
+            .utils
+            .find_oracle_trigger(&this_tree, arb_info.tx_index, &tokens);
 
         trees
             .into_iter()
@@ -308,6 +331,7 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
                 })
             })
             .map(|root| root.tx_hash)
+            .or(oracle_trigger)
             .unwrap_or_default()
     }
 
@@ -324,24 +348,30 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
                 if is_triangle && is_continuous {
                     return Some(AtomicArbType::Triangle)
                 } else if is_triangle
-                    && is_stable_pair(&swaps[0].token_out.symbol, &swaps[1].token_in.symbol)
+                    && self
+                        .stable_pairs
+                        .is_stable_pair(&swaps[0].token_out.symbol, &swaps[1].token_in.symbol)
                 {
                     return Some(AtomicArbType::StablecoinArb)
                 } else if is_triangle {
                     return Some(AtomicArbType::CrossPair(1))
-                } else if is_stable_pair(&swaps[0].token_in.symbol, &swaps[1].token_out.symbol) {
+                } else if self
+                    .stable_pairs
+                    .is_stable_pair(&swaps[0].token_in.symbol, &swaps[1].token_out.symbol)
+                {
                     return Some(AtomicArbType::StablecoinArb)
                 }
                 Some(AtomicArbType::LongTail)
             }
-            _ => identify_arb_sequence(swaps),
+            _ => identify_arb_sequence(swaps, &self.stable_pairs),
         }
     }
 
     fn process_triangle_arb(&self, tx_info: &TxInfo, multiplier: u64) -> bool {
-        let res = tx_info
-            .is_searcher_of_type_with_count_threshold(MevType::AtomicArb, 20 * multiplier)
-            || tx_info.is_labelled_searcher_of_type(MevType::AtomicArb)
+        let res = tx_info.is_searcher_of_type_with_count_threshold(
+            MevType::AtomicArb,
+            self.config.triangle_searcher_threshold * multiplier,
+        ) || tx_info.is_labelled_searcher_of_type(MevType::AtomicArb)
             || tx_info.gas_details.coinbase_transfer.is_some() && tx_info.is_private;
 
         if !res {
@@ -353,9 +383,10 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
     }
 
     fn is_cross_pair_or_stable_arb(&self, tx_info: &TxInfo, multiplier: u64) -> bool {
-        let res = tx_info
-            .is_searcher_of_type_with_count_threshold(MevType::AtomicArb, 10 * multiplier)
-            || tx_info.is_labelled_searcher_of_type(MevType::AtomicArb)
+        let res = tx_info.is_searcher_of_type_with_count_threshold(
+            MevType::AtomicArb,
+            self.config.cross_pair_searcher_threshold * multiplier,
+        ) || tx_info.is_labelled_searcher_of_type(MevType::AtomicArb)
             || tx_info.is_private
             || tx_info.gas_details.coinbase_transfer.is_some();
         if !res {
@@ -367,9 +398,10 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
     }
 
     fn is_long_tail(&self, tx_info: &TxInfo, multiplier: u64) -> bool {
-        let res = tx_info
-            .is_searcher_of_type_with_count_threshold(MevType::AtomicArb, 10 * multiplier)
-            || tx_info.is_labelled_searcher_of_type(MevType::AtomicArb)
+        let res = tx_info.is_searcher_of_type_with_count_threshold(
+            MevType::AtomicArb,
+            self.config.long_tail_searcher_threshold * multiplier,
+        ) || tx_info.is_labelled_searcher_of_type(MevType::AtomicArb)
             || tx_info.is_private && tx_info.gas_details.coinbase_transfer.is_some()
             || tx_info.mev_contract.is_some();
         if !res {
@@ -384,7 +416,7 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
         let token_bought = &swaps[jump_index - 1].token_out.symbol;
         let token_sold = &swaps[jump_index].token_in.symbol;
 
-        let res = is_stable_pair(token_sold, token_bought);
+        let res = self.stable_pairs.is_stable_pair(token_sold, token_bought);
         if !res {
             self.utils
                 .get_metrics()
@@ -395,7 +427,10 @@ impl<DB: LibmdbxReader> AtomicArbInspector<'_, DB> {
     }
 }
 
-fn identify_arb_sequence(swaps: &[NormalizedSwap]) -> Option<AtomicArbType> {
+fn identify_arb_sequence(
+    swaps: &[NormalizedSwap],
+    stable_pairs: &StablePairs,
+) -> Option<AtomicArbType> {
     let start_token = &swaps.first().unwrap().token_in.symbol;
     let end_token = &swaps.last().unwrap().token_out.symbol;
 
@@ -403,7 +438,7 @@ fn identify_arb_sequence(swaps: &[NormalizedSwap]) -> Option<AtomicArbType> {
     let end_address = &swaps.last().unwrap().token_out.address;
 
     if start_address != end_address {
-        if is_stable_pair(start_token, end_token) {
+        if stable_pairs.is_stable_pair(start_token, end_token) {
             return Some(AtomicArbType::StablecoinArb)
         } else {
             return Some(AtomicArbType::LongTail)
@@ -422,18 +457,6 @@ fn identify_arb_sequence(swaps: &[NormalizedSwap]) -> Option<AtomicArbType> {
     Some(AtomicArbType::Triangle)
 }
 
-pub fn is_stable_pair(token_in: &str, token_out: &str) -> bool {
-    if let Some(stable_type) = get_stable_type(token_in) {
-        match stable_type {
-            StableType::USD => is_usd_stable(token_out),
-            StableType::EURO => is_euro_stable(token_out),
-            StableType::GOLD => is_gold_stable(token_out),
-        }
-    } else {
-        false
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use alloy_primitives::hex;
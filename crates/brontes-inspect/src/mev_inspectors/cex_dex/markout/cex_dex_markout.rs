@@ -41,17 +41,18 @@ use super::{
     log_cex_trade_price_delta, ArbLeg, CexDexProcessing, CexPricesForSwaps, ExchangeLegCexPrice,
     OptimisticDetails, PossibleCexDex, PriceCalcType,
 };
-
-// The threshold for the number of CEX-DEX trades an address is required to make
-// to classify a a negative pnl cex-dex trade as a CEX-DEX trade
-pub const FILTER_THRESHOLD: u64 = 20;
-
-use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
+use crate::{
+    config::{CexDexMarkoutConfig, StablePairs},
+    shared_utils::SharedInspectorUtils,
+    Inspector, Metadata,
+};
 
 pub struct CexDexMarkoutInspector<'db, DB: LibmdbxReader> {
     pub utils:     SharedInspectorUtils<'db, DB>,
     trade_config:  CexDexTradeConfig,
     cex_exchanges: Vec<CexExchange>,
+    config:        CexDexMarkoutConfig,
+    stable_pairs:  StablePairs,
 }
 
 impl<'db, DB: LibmdbxReader> CexDexMarkoutInspector<'db, DB> {
@@ -66,8 +67,18 @@ impl<'db, DB: LibmdbxReader> CexDexMarkoutInspector<'db, DB> {
             utils: SharedInspectorUtils::new(quote, db, metrics),
             trade_config,
             cex_exchanges: cex_exchanges.to_owned(),
+            config: CexDexMarkoutConfig::default(),
+            stable_pairs: StablePairs::default(),
         }
     }
+
+    /// Applies the threshold and time window overrides of the config
+    pub fn with_config(mut self, config: CexDexMarkoutConfig, stable_pairs: StablePairs) -> Self {
+        self.trade_config = config.apply(self.trade_config);
+        self.config = config;
+        self.stable_pairs = stable_pairs;
+        self
+    }
 }
 
 impl<DB: LibmdbxReader> Inspector for CexDexMarkoutInspector<'_, DB> {
@@ -639,11 +650,14 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
         info: &TxInfo,
         metadata: Arc<Metadata>,
     ) -> Option<(f64, BundleData, Vec<ExchangeLegCexPrice>)> {
-        let sanity_check_arb = possible_cex_dex.arb_sanity_check();
+        let sanity_check_arb = possible_cex_dex.arb_sanity_check(&self.stable_pairs);
         let is_profitable_outlier = sanity_check_arb.is_profitable_outlier();
 
         let is_cex_dex_bot_with_significant_activity = info
-            .is_searcher_of_type_with_count_threshold(MevType::CexDexTrades, FILTER_THRESHOLD * 2);
+            .is_searcher_of_type_with_count_threshold(
+                MevType::CexDexTrades,
+                self.config.searcher_threshold * 2,
+            );
         let is_labelled_cex_dex_bot = info.is_labelled_searcher_of_type(MevType::CexDexTrades);
 
         let is_profitable_on_one_exchange = sanity_check_arb.profitable_exchanges_maker.len() == 1
@@ -662,12 +676,13 @@ impl<DB: LibmdbxReader> CexDexMarkoutInspector<'_, DB> {
 
         let tx_attributes_meet_cex_dex_criteria = !info.is_classified
             && info.is_private
-            && (info
-                .is_searcher_of_type_with_count_threshold(MevType::CexDexTrades, FILTER_THRESHOLD)
-                || info
-                    .contract_type
-                    .as_ref()
-                    .map_or(false, |contract_type| contract_type.could_be_mev_contract()));
+            && (info.is_searcher_of_type_with_count_threshold(
+                MevType::CexDexTrades,
+                self.config.searcher_threshold,
+            ) || info
+                .contract_type
+                .as_ref()
+                .map_or(false, |contract_type| contract_type.could_be_mev_contract()));
 
         let is_cex_dex_based_on_historical_activity =
             is_cex_dex_bot_with_significant_activity || is_labelled_cex_dex_bot;
@@ -21,7 +21,7 @@ use reth_primitives::Address;
 use strum::Display;
 use tracing::warn;
 
-use crate::atomic_arb::is_stable_pair;
+use crate::config::StablePairs;
 
 pub const HIGH_PROFIT_THRESHOLD: Rational = Rational::const_from_unsigned(10000);
 
@@ -334,7 +334,7 @@ impl CexDexProcessing {
         ))
     }
 
-    pub fn arb_sanity_check(&self) -> ArbSanityCheck {
+    pub fn arb_sanity_check(&self, stable_pairs: &StablePairs) -> ArbSanityCheck {
         let (profitable_exchanges_maker, profitable_exchanges_taker) = self
             .per_exchange_pnl
             .iter()
@@ -381,7 +381,7 @@ impl CexDexProcessing {
                     )
                 });
 
-        let is_stable_swaps = self.is_stable_swaps();
+        let is_stable_swaps = self.is_stable_swaps(stable_pairs);
 
         ArbSanityCheck {
             profitable_exchanges_maker,
@@ -392,10 +392,10 @@ impl CexDexProcessing {
         }
     }
 
-    fn is_stable_swaps(&self) -> bool {
-        self.dex_swaps
-            .iter()
-            .all(|swap| is_stable_pair(swap.token_in_symbol(), swap.token_out_symbol()))
+    fn is_stable_swaps(&self, stable_pairs: &StablePairs) -> bool {
+        self.dex_swaps.iter().all(|swap| {
+            stable_pairs.is_stable_pair(swap.token_in_symbol(), swap.token_out_symbol())
+        })
     }
 }
 
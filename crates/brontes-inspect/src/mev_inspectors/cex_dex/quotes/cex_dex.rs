@@ -59,6 +59,7 @@ use brontes_types::{
     BlockData, FastHashMap, MultiBlockData, ToFloatNearest, TreeCollector, TreeSearchBuilder,
     TxInfo,
 };
+use itertools::Itertools;
 use malachite::{
     num::{arithmetic::traits::Reciprocal, basic::traits::Zero},
     Rational,
@@ -68,16 +69,12 @@ use tracing::{debug, trace};
 use super::types::{
     log_cex_dex_quote_delta, CexDexProcessing, ExchangeLeg, ExchangeLegCexPrice, PossibleCexDex,
 };
-
-pub const FILTER_THRESHOLD: u64 = 20;
-
-use itertools::Itertools;
-
-use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
+use crate::{config::CexDexConfig, shared_utils::SharedInspectorUtils, Inspector, Metadata};
 pub struct CexDexQuotesInspector<'db, DB: LibmdbxReader> {
     utils:                SharedInspectorUtils<'db, DB>,
     _quotes_fetch_offset: u64,
     _cex_exchanges:       Vec<CexExchange>,
+    config:               CexDexConfig,
 }
 
 impl<'db, DB: LibmdbxReader> CexDexQuotesInspector<'db, DB> {
@@ -100,8 +97,14 @@ impl<'db, DB: LibmdbxReader> CexDexQuotesInspector<'db, DB> {
             utils:                SharedInspectorUtils::new(quote, db, metrics),
             _quotes_fetch_offset: quotes_fetch_offset,
             _cex_exchanges:       cex_exchanges.to_owned(),
+            config:               CexDexConfig::default(),
         }
     }
+
+    pub fn with_config(mut self, config: CexDexConfig) -> Self {
+        self.config = config;
+        self
+    }
 }
 
 impl<DB: LibmdbxReader> Inspector for CexDexQuotesInspector<'_, DB> {
@@ -452,8 +455,11 @@ impl<DB: LibmdbxReader> CexDexQuotesInspector<'_, DB> {
         info: &TxInfo,
         metadata: &Metadata,
     ) -> Option<(f64, BundleData)> {
-        let is_cex_dex_bot_with_significant_activity =
-            info.is_searcher_of_type_with_count_threshold(MevType::CexDexQuotes, FILTER_THRESHOLD);
+        let is_cex_dex_bot_with_significant_activity = info
+            .is_searcher_of_type_with_count_threshold(
+                MevType::CexDexQuotes,
+                self.config.searcher_threshold,
+            );
         let is_labelled_cex_dex_bot = info.is_labelled_searcher_of_type(MevType::CexDexQuotes);
 
         let should_include_based_on_pnl = possible_cex_dex.pnl.aggregate_pnl > 1.5;
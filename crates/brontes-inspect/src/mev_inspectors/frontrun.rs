@@ -0,0 +1,421 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, B256};
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_metrics::inspectors::OutlierMetrics;
+use brontes_types::{
+    db::dex::PriceAt,
+    mev::{Bundle, BundleData, Frontrun, MevType},
+    normalized_actions::{accounting::ActionAccounting, Action, NormalizedSwap},
+    tree::{BlockTree, GasDetails, Root, TxCall},
+    BlockData, FastHashMap, MultiBlockData, ToFloatNearest, TreeSearchBuilder, TxInfo,
+};
+use itertools::Itertools;
+use malachite::{num::basic::traits::Zero, Rational};
+
+use super::{MAX_PROFIT, MIN_PROFIT};
+use crate::{config::FrontrunConfig, shared_utils::SharedInspectorUtils, Inspector, Metadata};
+
+/// Finds generalized frontrunners that copy the call of a pending transaction
+/// and outbid it, so that the copied transaction reverts or gets nothing.
+pub struct FrontrunInspector<'db, DB: LibmdbxReader> {
+    utils:  SharedInspectorUtils<'db, DB>,
+    config: FrontrunConfig,
+}
+
+impl<'db, DB: LibmdbxReader> FrontrunInspector<'db, DB> {
+    pub fn new(quote: Address, db: &'db DB, metrics: Option<OutlierMetrics>) -> Self {
+        Self {
+            utils:  SharedInspectorUtils::new(quote, db, metrics),
+            config: FrontrunConfig::default(),
+        }
+    }
+
+    pub fn with_config(mut self, config: FrontrunConfig) -> Self {
+        self.config = config;
+        self
+    }
+}
+
+/// A transaction that reverted or succeeded without swapping or receiving
+/// anything.
+struct Displaced {
+    tx_hash:     B256,
+    position:    usize,
+    eoa:         Address,
+    call:        TxCall,
+    gas_details: GasDetails,
+    reverted:    bool,
+}
+
+impl<DB: LibmdbxReader> Inspector for FrontrunInspector<'_, DB> {
+    type Result = Vec<Bundle>;
+
+    fn block_window(&self) -> usize {
+        1
+    }
+
+    fn get_id(&self) -> &str {
+        "Frontrun"
+    }
+
+    fn get_quote_token(&self) -> Address {
+        self.utils.quote
+    }
+
+    fn inspect_block(&self, data: MultiBlockData) -> Self::Result {
+        let BlockData { metadata, tree } = data.get_most_recent_block();
+        let ex = || self.inspect_tree(tree.clone(), metadata.clone());
+
+        self.utils
+            .get_metrics()
+            .map(|m| m.run_inspector(MevType::Frontrun, ex))
+            .unwrap_or_else(ex)
+    }
+}
+
+impl<DB: LibmdbxReader> FrontrunInspector<'_, DB> {
+    fn inspect_tree(&self, tree: Arc<BlockTree<Action>>, metadata: Arc<Metadata>) -> Vec<Bundle> {
+        let displaced = displaced_txes(&tree);
+        if displaced.is_empty() {
+            return vec![]
+        }
+
+        let swaps: FastHashMap<B256, Vec<NormalizedSwap>> = tree
+            .tx_roots
+            .iter()
+            .map(|root| (root.tx_hash, self.root_swaps(root)))
+            .filter(|(_, swaps)| !swaps.is_empty())
+            .collect();
+
+        // every displaced transaction goes to the frontrun that copied its call the
+        // closest
+        let victims = displaced
+            .into_iter()
+            .filter_map(|victim| {
+                let (frontrun, _) = tree
+                    .tx_roots
+                    .iter()
+                    .filter(|root| {
+                        root.position < victim.position
+                            && root.head.address != victim.eoa
+                            && swaps.contains_key(&root.tx_hash)
+                            && outbids(&root.gas_details, &victim.gas_details)
+                    })
+                    .filter_map(|root| {
+                        let similarity = self.call_similarity(root, &victim.call);
+                        (similarity >= self.config.calldata_similarity)
+                            .then_some((root.tx_hash, similarity))
+                    })
+                    .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+                Some((frontrun, victim))
+            })
+            .into_group_map();
+
+        victims
+            .into_iter()
+            .filter_map(|(frontrun, victims)| {
+                let info = tree.get_tx_info(frontrun, self.utils.db)?;
+                let swaps = swaps.get(&frontrun)?.clone();
+                self.calculate_frontrun(info, tree.clone(), metadata.clone(), swaps, victims)
+            })
+            .collect()
+    }
+
+    fn root_swaps(&self, root: &Root<Action>) -> Vec<NormalizedSwap> {
+        let actions = root.collect(
+            &TreeSearchBuilder::default().with_actions([Action::is_swap, Action::is_nested_action]),
+        );
+
+        self.utils
+            .flatten_nested_actions(actions.into_iter(), &|action: &Action| action.is_swap())
+            .filter_map(Action::try_swaps_merged)
+            .collect()
+    }
+
+    /// The best similarity of the call with the top level call or one of the
+    /// unclassified inner calls of the transaction, generalized frontrunners
+    /// usually replay the copied call from their own contract.
+    fn call_similarity(&self, root: &Root<Action>, call: &TxCall) -> f64 {
+        root.collect(&TreeSearchBuilder::default().with_action(Action::is_unclassified))
+            .into_iter()
+            .filter_map(|action| match action {
+                Action::Unclassified(trace) => Some(TxCall::from_trace(&trace)),
+                _ => None,
+            })
+            .chain(std::iter::once(root.call.clone()))
+            .map(|inner| inner.calldata_similarity(call))
+            .fold(0.0, f64::max)
+    }
+
+    fn calculate_frontrun(
+        &self,
+        info: TxInfo,
+        tree: Arc<BlockTree<Action>>,
+        metadata: Arc<Metadata>,
+        swaps: Vec<NormalizedSwap>,
+        mut victims: Vec<Displaced>,
+    ) -> Option<Bundle> {
+        // copying a call only pays off for searchers, without any sign of one this
+        // is most likely two users doing the same thing
+        if info.mev_contract.is_none()
+            && !info.is_private
+            && info.gas_details.coinbase_transfer.is_none()
+            && !info.is_searcher_of_type(MevType::Frontrun)
+        {
+            return None
+        }
+
+        let deltas = tree
+            .collect(
+                &info.tx_hash,
+                TreeSearchBuilder::default().with_actions([
+                    Action::is_transfer,
+                    Action::is_eth_transfer,
+                    Action::is_nested_action,
+                ]),
+            )
+            .collect_vec();
+        let deltas = self
+            .utils
+            .flatten_nested_actions_default(deltas.into_iter())
+            .chain(info.get_total_eth_value().iter().cloned().map(Action::from))
+            .filter(|a| a.is_eth_transfer() || a.is_transfer())
+            .account_for_actions();
+
+        let (rev, mut has_dex_price) = match self.utils.get_deltas_usd(
+            info.tx_index,
+            PriceAt::Average,
+            &info.collect_address_set_for_accounting(),
+            &deltas,
+            metadata.clone(),
+            false,
+        ) {
+            Some(rev) => (rev, true),
+            None => (Rational::ZERO, false),
+        };
+
+        let gas_used_usd =
+            metadata.get_gas_price_usd(info.gas_details.gas_paid(), self.utils.quote);
+        let mut profit = if has_dex_price { rev - gas_used_usd } else { Rational::ZERO };
+
+        if profit >= MAX_PROFIT || profit <= MIN_PROFIT {
+            has_dex_price = false;
+            profit = Rational::ZERO;
+        }
+
+        if has_dex_price && profit <= Rational::ZERO {
+            return None
+        }
+
+        victims.sort_by_key(|victim| victim.position);
+
+        let header = self.utils.build_bundle_header(
+            vec![deltas],
+            vec![info.tx_hash],
+            &info,
+            profit.to_float(),
+            &[info.gas_details],
+            metadata.clone(),
+            MevType::Frontrun,
+            !has_dex_price,
+            |this, token, amount| {
+                this.get_token_value_dex(
+                    info.tx_index as usize,
+                    PriceAt::Average,
+                    token,
+                    &amount,
+                    &metadata,
+                )
+            },
+        );
+
+        let frontrun = Frontrun {
+            block_number:         metadata.block_num,
+            frontrun_tx_hash:     info.tx_hash,
+            frontrun_swaps:       swaps,
+            frontrun_gas_details: info.gas_details,
+            victim_tx_hashes:     victims.iter().map(|victim| victim.tx_hash).collect(),
+            victim_reverted:      victims.iter().map(|victim| victim.reverted).collect(),
+            victim_gas_details:   victims.iter().map(|victim| victim.gas_details).collect(),
+        };
+
+        Some(Bundle { header, data: BundleData::Frontrun(frontrun) })
+    }
+}
+
+/// Reverted transactions and transactions that didn't swap or receive
+/// anything. Plain eth transfers have no call to copy and are skipped.
+fn displaced_txes(tree: &BlockTree<Action>) -> Vec<Displaced> {
+    let reverted = tree
+        .failed_txs
+        .iter()
+        .filter(|tx| tx.call.selector().is_some())
+        .map(|tx| Displaced {
+            tx_hash:     tx.tx_hash,
+            position:    tx.position,
+            eoa:         tx.eoa,
+            call:        tx.call.clone(),
+            gas_details: tx.gas_details,
+            reverted:    true,
+        });
+
+    let no_ops = tree
+        .tx_roots
+        .iter()
+        .filter(|root| root.is_no_op())
+        .map(|root| Displaced {
+            tx_hash:     root.tx_hash,
+            position:    root.position,
+            eoa:         root.head.address,
+            call:        root.call.clone(),
+            gas_details: root.gas_details,
+            reverted:    false,
+        });
+
+    reverted.chain(no_ops).collect()
+}
+
+/// Whether the frontrun paid more for its position than the victim, either
+/// with its priority fee or with a coinbase transfer
+fn outbids(frontrun: &GasDetails, victim: &GasDetails) -> bool {
+    frontrun.priority_fee > victim.priority_fee || frontrun.coinbase_transfer.is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Log, U256};
+    use alloy_sol_types::{SolCall, SolEvent};
+    use brontes_classifier::{
+        test_utils::{call_trace, tx_trace},
+        UniswapV2::{swapCall, Swap},
+    };
+    use brontes_types::{structured_trace::TxTrace, Protocol};
+
+    use super::*;
+    use crate::{
+        test_utils::{InspectorTestUtils, USDC_ADDRESS, WETH_ADDRESS},
+        Inspectors,
+    };
+
+    const USDC_WETH_PAIR: Address = Address::new(hex!("B4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc"));
+    const TARGET: Address = Address::repeat_byte(0x11);
+    const BOT: Address = Address::repeat_byte(0x22);
+    const FRONTRUNNER: Address = Address::repeat_byte(0x33);
+    const VICTIM: Address = Address::repeat_byte(0x44);
+    const GWEI: u128 = 1_000_000_000;
+
+    /// A call to the target with a made up selector, the last word is the
+    /// recipient.
+    fn target_call(recipient: Address) -> Vec<u8> {
+        let mut input = hex!("4e71d92d").to_vec();
+        input.extend(U256::from(1_000).to_be_bytes::<32>());
+        input.extend(U256::from(7).to_be_bytes::<32>());
+        input.extend(U256::from(u64::MAX).to_be_bytes::<32>());
+        input.extend(recipient.into_word());
+        input
+    }
+
+    /// The bot replays the call of the victim with itself as the recipient and
+    /// sells the proceeds.
+    fn frontrun(priority_fee: u128) -> TxTrace {
+        let weth_out = U256::from(1_000_000_000_000_000_000_u128);
+        let usdc_in = U256::from(3_000_000_000_u64);
+
+        let mut tx = tx_trace(vec![
+            call_trace(vec![], FRONTRUNNER, BOT, hex!("deadbeef").to_vec(), vec![], vec![]),
+            call_trace(vec![0], BOT, TARGET, target_call(BOT), vec![], vec![]),
+            call_trace(
+                vec![1],
+                BOT,
+                USDC_WETH_PAIR,
+                swapCall {
+                    amount0Out: U256::ZERO,
+                    amount1Out: weth_out,
+                    to:         BOT,
+                    data:       Default::default(),
+                }
+                .abi_encode(),
+                vec![],
+                vec![Log {
+                    address: USDC_WETH_PAIR,
+                    data:    Swap {
+                        sender:     BOT,
+                        amount0In:  usdc_in,
+                        amount1In:  U256::ZERO,
+                        amount0Out: U256::ZERO,
+                        amount1Out: weth_out,
+                        to:         BOT,
+                    }
+                    .encode_log_data(),
+                }],
+            ),
+        ]);
+        tx.effective_price = priority_fee;
+        tx
+    }
+
+    fn reverted_victim(priority_fee: u128) -> TxTrace {
+        let mut tx =
+            tx_trace(vec![call_trace(vec![], VICTIM, TARGET, target_call(VICTIM), vec![], vec![])]);
+        tx.effective_price = priority_fee;
+        tx.is_success = false;
+        tx
+    }
+
+    async fn setup() -> InspectorTestUtils {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 0.0).await;
+        inspector_util.classifier_inspector.ensure_protocol(
+            Protocol::UniswapV2,
+            USDC_WETH_PAIR,
+            USDC_ADDRESS,
+            Some(WETH_ADDRESS),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        inspector_util
+    }
+
+    #[brontes_macros::test]
+    async fn test_frontrun_of_reverted_tx() {
+        let inspector_util = setup().await;
+
+        let bundles = inspector_util
+            .run_inspector_on_traces(
+                Inspectors::Frontrun,
+                vec![(19_000_000, vec![frontrun(2 * GWEI), reverted_victim(GWEI)])],
+            )
+            .await;
+
+        assert_eq!(bundles.len(), 1, "expected one frontrun bundle: {bundles:#?}");
+        let BundleData::Frontrun(frontrun) = &bundles[0].data else {
+            panic!("expected frontrun bundle data, got {:#?}", bundles[0].data)
+        };
+
+        assert_eq!(bundles[0].header.mev_type, MevType::Frontrun);
+        assert_eq!(bundles[0].header.eoa, FRONTRUNNER);
+        assert_eq!(frontrun.frontrun_swaps.len(), 1);
+        assert_eq!(frontrun.frontrun_swaps[0].pool, USDC_WETH_PAIR);
+        assert_eq!(frontrun.victim_tx_hashes.len(), 1);
+        assert_eq!(frontrun.victim_reverted, vec![true]);
+    }
+
+    #[brontes_macros::test]
+    async fn test_no_frontrun_without_outbidding() {
+        let inspector_util = setup().await;
+
+        // the victim paid more, so it landed after the copy for another reason
+        let bundles = inspector_util
+            .run_inspector_on_traces(
+                Inspectors::Frontrun,
+                vec![(19_000_000, vec![frontrun(GWEI), reverted_victim(2 * GWEI)])],
+            )
+            .await;
+
+        assert!(bundles.is_empty(), "found a frontrun that didn't outbid: {bundles:#?}");
+    }
+}
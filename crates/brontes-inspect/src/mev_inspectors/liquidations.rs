@@ -6,11 +6,12 @@ use brontes_types::{
     db::dex::PriceAt,
     mev::{Bundle, BundleData, Liquidation, MevType},
     normalized_actions::{accounting::ActionAccounting, Action},
-    ActionIter, BlockData, FastHashSet, MultiBlockData, ToFloatNearest, TreeSearchBuilder, TxInfo,
+    ActionIter, BlockData, BlockTree, FastHashSet, MultiBlockData, ToFloatNearest,
+    TreeSearchBuilder, TxInfo,
 };
 use itertools::multizip;
 use malachite::{num::basic::traits::Zero, Rational};
-use reth_primitives::{b256, Address};
+use reth_primitives::Address;
 
 use super::{MAX_PROFIT, MIN_PROFIT};
 use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
@@ -61,7 +62,7 @@ impl<DB: LibmdbxReader> Inspector for LiquidationInspector<'_, DB> {
                         .flatten_nested_actions_default(liq.into_iter())
                         .collect::<Vec<_>>();
 
-                    self.calculate_liquidation(info, metadata.clone(), actions)
+                    self.calculate_liquidation(&tree, info, metadata.clone(), actions)
                 })
                 .collect::<Vec<_>>()
         };
@@ -75,6 +76,7 @@ impl<DB: LibmdbxReader> Inspector for LiquidationInspector<'_, DB> {
 impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
     fn calculate_liquidation(
         &self,
+        tree: &BlockTree<Action>,
         info: TxInfo,
         metadata: Arc<Metadata>,
         actions: Vec<Action>,
@@ -143,10 +145,21 @@ impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
             },
         );
 
+        // liquidations are made possible by a price move of the collateral or the
+        // debt, which for oracle based lending is an update of the oracle
+        let assets = liqs
+            .iter()
+            .flat_map(|liq| [liq.collateral_asset.address, liq.debt_asset.address])
+            .collect::<FastHashSet<_>>();
+        let trigger_tx = self
+            .utils
+            .find_oracle_trigger(tree, info.tx_index, &assets)
+            .unwrap_or_default();
+
         let new_liquidation = Liquidation {
             block_number:        metadata.block_num,
             liquidation_tx_hash: info.tx_hash,
-            trigger:             b256!(),
+            trigger:             trigger_tx,
             liquidation_swaps:   swaps,
             liquidations:        liqs,
             gas_details:         info.gas_details,
@@ -158,14 +171,300 @@ impl<DB: LibmdbxReader> LiquidationInspector<'_, DB> {
 
 #[cfg(test)]
 mod tests {
+    use alloy_primitives::{hex, Bytes, Log, B256, I256, U256};
+    use alloy_sol_types::{SolCall, SolEvent};
+    use brontes_classifier::{
+        erc20::transferCall,
+        test_utils::{call_trace, tx_trace},
+        AaveV3::liquidationCallCall,
+        ChainlinkOCR2Aggregator::{transmitCall, AnswerUpdated},
+        MakerClipper::{takeCall, Take},
+        UniswapV2::{swapCall, Swap},
+    };
+    use brontes_types::{
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        structured_trace::TxTrace,
+        Protocol,
+    };
 
-    use alloy_primitives::hex;
-
+    use super::*;
     use crate::{
-        test_utils::{InspectorTestUtils, InspectorTxRunConfig, USDC_ADDRESS},
+        test_utils::{InspectorTestUtils, InspectorTxRunConfig, USDC_ADDRESS, WETH_ADDRESS},
         Inspectors,
     };
 
+    const SPARK_POOL: Address = Address::new(hex!("C13e21B648A5Ee794902342038FF3aDAB66BE987"));
+    const DAI: Address = Address::new(hex!("6B175474E89094C44Da98b954EedeAC495271d0F"));
+    const WBTC: Address = Address::new(hex!("2260FAC5E5542a773Aa44fBCfEDf7C193bc2C599"));
+    const ETH_USD_AGGREGATOR: Address =
+        Address::new(hex!("E62B71cf983019BFf55bC83B48601ce8419650CC"));
+    const BTC_USD_AGGREGATOR: Address =
+        Address::new(hex!("dBe1941BFbe4410D6865b9b7078e0b49af144D2d"));
+    const LIQUIDATOR: Address = Address::repeat_byte(0x11);
+    const BORROWER: Address = Address::repeat_byte(0x22);
+    const SP_WETH: Address = Address::repeat_byte(0x33);
+    const TRANSMITTER: Address = Address::repeat_byte(0x44);
+    const ETH_CLIPPER: Address = Address::new(hex!("c67963a140D9b4E9F2e16C93Ac7C8Ee4546dbD7C"));
+    const DAI_WETH_PAIR: Address = Address::new(hex!("A478c2975Ab1Ea89e8196811F51A7B7Ade33eB11"));
+    const BLOCK: u64 = 19_000_000;
+
+    fn transmit(aggregator: Address) -> TxTrace {
+        tx_trace(vec![call_trace(
+            vec![],
+            TRANSMITTER,
+            aggregator,
+            transmitCall {
+                reportContext: [B256::ZERO; 3],
+                report:        Bytes::new(),
+                rs:            vec![],
+                ss:            vec![],
+                rawVs:         B256::ZERO,
+            }
+            .abi_encode(),
+            vec![],
+            vec![Log {
+                address: aggregator,
+                data:    AnswerUpdated {
+                    current:   I256::try_from(300_000_000_000_u64).unwrap(),
+                    roundId:   U256::from(1),
+                    updatedAt: U256::from(1_700_000_000),
+                }
+                .encode_log_data(),
+            }],
+        )])
+    }
+
+    /// A spark liquidation that seizes weth for dai.
+    fn liquidation() -> TxTrace {
+        tx_trace(vec![
+            call_trace(
+                vec![],
+                LIQUIDATOR,
+                SPARK_POOL,
+                liquidationCallCall {
+                    collateralAsset: WETH_ADDRESS,
+                    debtAsset:       DAI,
+                    user:            BORROWER,
+                    debtToCover:     U256::from(3_000_000_000_000_000_000_000_u128),
+                    receiveAToken:   false,
+                }
+                .abi_encode(),
+                vec![],
+                vec![],
+            ),
+            call_trace(
+                vec![0],
+                SP_WETH,
+                WETH_ADDRESS,
+                transferCall { _0: LIQUIDATOR, _1: U256::from(1_050_000_000_000_000_000_u128) }
+                    .abi_encode(),
+                vec![],
+                vec![],
+            ),
+        ])
+    }
+
+    #[brontes_macros::test]
+    async fn test_liquidation_oracle_trigger() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 0.0).await;
+        let classifier = &inspector_util.classifier_inspector;
+        classifier.ensure_token(TokenInfoWithAddress::weth());
+        classifier.ensure_token(TokenInfoWithAddress {
+            address: WBTC,
+            inner:   TokenInfo { decimals: 8, symbol: "WBTC".to_string() },
+        });
+        classifier.ensure_token(TokenInfoWithAddress {
+            address: DAI,
+            inner:   TokenInfo { decimals: 18, symbol: "DAI".to_string() },
+        });
+        classifier.ensure_protocol(
+            Protocol::Spark,
+            SPARK_POOL,
+            Address::ZERO,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        for (aggregator, asset) in [(ETH_USD_AGGREGATOR, WETH_ADDRESS), (BTC_USD_AGGREGATOR, WBTC)]
+        {
+            classifier.ensure_protocol(
+                Protocol::ChainlinkAggregator,
+                aggregator,
+                asset,
+                Some(USDC_ADDRESS),
+                None,
+                None,
+                None,
+                None,
+            );
+        }
+
+        // the weth update in tx 1 is the latest update of an asset of the
+        // liquidation before it, the btc update and the update after the
+        // liquidation don't count
+        let bundles = inspector_util
+            .run_inspector_on_traces(
+                Inspectors::Liquidations,
+                vec![(
+                    BLOCK,
+                    vec![
+                        transmit(ETH_USD_AGGREGATOR),
+                        transmit(ETH_USD_AGGREGATOR),
+                        transmit(BTC_USD_AGGREGATOR),
+                        liquidation(),
+                        transmit(ETH_USD_AGGREGATOR),
+                    ],
+                )],
+            )
+            .await;
+
+        assert_eq!(bundles.len(), 1);
+        let BundleData::Liquidation(liquidation) = &bundles[0].data else {
+            panic!("not a liquidation bundle")
+        };
+        // the hashes of synthetic transactions are the block and their position
+        assert_eq!(
+            liquidation.liquidation_tx_hash,
+            B256::from((U256::from(BLOCK) << 64) | U256::from(3))
+        );
+        assert_eq!(liquidation.trigger, B256::from((U256::from(BLOCK) << 64) | U256::from(1)));
+    }
+
+    #[brontes_macros::test]
+    async fn test_liquidation_without_oracle_update() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 0.0).await;
+        let classifier = &inspector_util.classifier_inspector;
+        classifier.ensure_token(TokenInfoWithAddress::weth());
+        classifier.ensure_protocol(
+            Protocol::Spark,
+            SPARK_POOL,
+            Address::ZERO,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let bundles = inspector_util
+            .run_inspector_on_traces(Inspectors::Liquidations, vec![(BLOCK, vec![liquidation()])])
+            .await;
+
+        assert_eq!(bundles.len(), 1);
+        let BundleData::Liquidation(liquidation) = &bundles[0].data else {
+            panic!("not a liquidation bundle")
+        };
+        assert_eq!(liquidation.trigger, B256::ZERO);
+    }
+
+    #[brontes_macros::test]
+    async fn test_maker_take_callback_swaps() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 0.0).await;
+        let classifier = &inspector_util.classifier_inspector;
+        classifier.ensure_token(TokenInfoWithAddress::weth());
+        classifier.ensure_token(TokenInfoWithAddress {
+            address: DAI,
+            inner:   TokenInfo { decimals: 18, symbol: "DAI".to_string() },
+        });
+        classifier.ensure_protocol(
+            Protocol::MakerClipper,
+            ETH_CLIPPER,
+            DAI,
+            Some(WETH_ADDRESS),
+            None,
+            None,
+            None,
+            None,
+        );
+        classifier.ensure_protocol(
+            Protocol::UniswapV2,
+            DAI_WETH_PAIR,
+            DAI,
+            Some(WETH_ADDRESS),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let one_eth = U256::from(10).pow(U256::from(18));
+        let dai_out = U256::from(3_010) * one_eth;
+        // the keeper sells the eth it takes for 3000 dai in the take callback
+        let take = tx_trace(vec![
+            call_trace(
+                vec![],
+                LIQUIDATOR,
+                ETH_CLIPPER,
+                takeCall {
+                    id:   U256::from(1),
+                    amt:  one_eth,
+                    max:  U256::from(3_000) * U256::from(10).pow(U256::from(27)),
+                    who:  LIQUIDATOR,
+                    data: Bytes::from(vec![1]),
+                }
+                .abi_encode(),
+                vec![],
+                vec![Log {
+                    address: ETH_CLIPPER,
+                    data:    Take {
+                        id:    U256::from(1),
+                        max:   U256::from(3_000) * U256::from(10).pow(U256::from(27)),
+                        price: U256::from(3_000) * U256::from(10).pow(U256::from(27)),
+                        owe:   U256::from(3_000) * U256::from(10).pow(U256::from(45)),
+                        tab:   U256::ZERO,
+                        lot:   U256::ZERO,
+                        usr:   BORROWER,
+                    }
+                    .encode_log_data(),
+                }],
+            ),
+            call_trace(vec![0], ETH_CLIPPER, LIQUIDATOR, vec![], vec![], vec![]),
+            call_trace(
+                vec![0, 0],
+                LIQUIDATOR,
+                DAI_WETH_PAIR,
+                swapCall {
+                    amount0Out: dai_out,
+                    amount1Out: U256::ZERO,
+                    to:         LIQUIDATOR,
+                    data:       Bytes::new(),
+                }
+                .abi_encode(),
+                vec![],
+                vec![Log {
+                    address: DAI_WETH_PAIR,
+                    data:    Swap {
+                        sender:     LIQUIDATOR,
+                        amount0In:  U256::ZERO,
+                        amount1In:  one_eth,
+                        amount0Out: dai_out,
+                        amount1Out: U256::ZERO,
+                        to:         LIQUIDATOR,
+                    }
+                    .encode_log_data(),
+                }],
+            ),
+        ]);
+
+        let bundles = inspector_util
+            .run_inspector_on_traces(Inspectors::Liquidations, vec![(BLOCK, vec![take])])
+            .await;
+
+        assert_eq!(bundles.len(), 1);
+        let BundleData::Liquidation(liquidation) = &bundles[0].data else {
+            panic!("not a liquidation bundle")
+        };
+        assert_eq!(liquidation.liquidations.len(), 1);
+        assert_eq!(liquidation.liquidations[0].protocol, Protocol::MakerClipper);
+        assert_eq!(liquidation.liquidation_swaps.len(), 1);
+        let swap = &liquidation.liquidation_swaps[0];
+        assert_eq!(swap.pool, DAI_WETH_PAIR);
+        assert_eq!(swap.token_in.address, WETH_ADDRESS);
+        assert_eq!(swap.token_out.address, DAI);
+    }
+
     #[brontes_macros::test]
     async fn test_aave_v3_liquidation() {
         let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 6.0).await;
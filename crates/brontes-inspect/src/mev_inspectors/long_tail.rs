@@ -0,0 +1,564 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, B256};
+use brontes_database::libmdbx::LibmdbxReader;
+use brontes_metrics::inspectors::OutlierMetrics;
+use brontes_types::{
+    db::dex::PriceAt,
+    mev::{Bundle, BundleData, LongTail, MevType},
+    normalized_actions::{accounting::ActionAccounting, Action, NormalizedSwap},
+    BlockData, FastHashMap, FastHashSet, MultiBlockData, ToFloatNearest, TreeSearchBuilder, TxInfo,
+};
+use itertools::Itertools;
+use malachite::{num::basic::traits::Zero, Rational};
+
+use super::{MAX_PROFIT, MIN_PROFIT};
+use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata};
+
+/// Buys on a freshly launched pool up to this many blocks after the launch
+/// are counted as snipes.
+const SNIPE_BLOCKS: u64 = 2;
+/// Amount of blocks a sniper is followed for its exits, the launch included.
+const LONG_TAIL_BLOCK_WINDOW: usize = 25;
+
+/// (eoa, sniped token)
+type SniperKey = (Address, Address);
+
+pub struct LongTailInspector<'db, DB: LibmdbxReader> {
+    utils:          SharedInspectorUtils<'db, DB>,
+    /// buys of the wrapped native token are never snipes
+    wrapped_native: Address,
+}
+
+impl<'db, DB: LibmdbxReader> LongTailInspector<'db, DB> {
+    pub fn new(
+        quote: Address,
+        wrapped_native: Address,
+        db: &'db DB,
+        metrics: Option<OutlierMetrics>,
+    ) -> Self {
+        Self { utils: SharedInspectorUtils::new(quote, db, metrics), wrapped_native }
+    }
+}
+
+/// A buy of the launched token in one of the first blocks of a pool.
+struct Snipe {
+    pool:         Address,
+    launch_block: u64,
+    info:         TxInfo,
+    metadata:     Arc<Metadata>,
+    swaps:        Vec<NormalizedSwap>,
+}
+
+#[derive(Default)]
+struct SniperPosition {
+    snipes: Vec<Snipe>,
+    /// amount of the token sold since the first snipe
+    sold:   Rational,
+}
+
+impl SniperPosition {
+    fn bought(&self) -> Rational {
+        self.snipes
+            .iter()
+            .flat_map(|snipe| snipe.swaps.iter())
+            .map(|swap| &swap.amount_out)
+            .sum()
+    }
+}
+
+impl<DB: LibmdbxReader> Inspector for LongTailInspector<'_, DB> {
+    type Result = Vec<Bundle>;
+
+    // the snipes are in the older blocks of the window, the realized pnl is
+    // taken on the exits in the most recent block
+    fn block_window(&self) -> usize {
+        LONG_TAIL_BLOCK_WINDOW
+    }
+
+    fn get_id(&self) -> &str {
+        "LongTail"
+    }
+
+    fn get_quote_token(&self) -> Address {
+        self.utils.quote
+    }
+
+    fn inspect_block(&self, data: MultiBlockData) -> Self::Result {
+        let ex = || self.inspect_window(&data);
+
+        self.utils
+            .get_metrics()
+            .map(|m| m.run_inspector(MevType::LongTail, ex))
+            .unwrap_or_else(ex)
+    }
+}
+
+impl<DB: LibmdbxReader> LongTailInspector<'_, DB> {
+    fn inspect_window(&self, data: &MultiBlockData) -> Vec<Bundle> {
+        let Some((exit_block, prev_blocks)) = data.per_block_data.split_last() else {
+            return vec![]
+        };
+
+        let launches = self.launched_pools(data);
+        if launches.is_empty() {
+            return vec![]
+        }
+
+        let mut positions: FastHashMap<SniperKey, SniperPosition> = FastHashMap::default();
+
+        for block in prev_blocks {
+            let block_number = block.block_number();
+            let sniped_tokens = positions.keys().map(|(_, token)| *token).collect();
+
+            for (info, swaps) in self.candidate_txes(block, &launches, &sniped_tokens) {
+                for swap in &swaps {
+                    if let Some(position) = positions.get_mut(&(info.eoa, swap.token_in.address)) {
+                        position.sold += &swap.amount_in;
+                    }
+                }
+
+                let snipes = swaps
+                    .into_iter()
+                    .filter_map(|swap| {
+                        let launch_block = self.snipe_launch(&swap, block_number, &launches)?;
+                        Some((swap, launch_block))
+                    })
+                    .into_group_map_by(|(swap, _)| swap.token_out.address);
+
+                for (token, swaps) in snipes {
+                    let (swaps, launch_blocks): (Vec<_>, Vec<_>) = swaps.into_iter().unzip();
+                    positions
+                        .entry((info.eoa, token))
+                        .or_default()
+                        .snipes
+                        .push(Snipe {
+                            pool: swaps[0].pool,
+                            launch_block: launch_blocks[0],
+                            info: info.clone(),
+                            metadata: block.metadata.clone(),
+                            swaps,
+                        });
+                }
+            }
+        }
+
+        if positions.is_empty() {
+            return vec![]
+        }
+
+        let sniped_tokens = positions.keys().map(|(_, token)| *token).collect();
+        self.candidate_txes(exit_block, &FastHashMap::default(), &sniped_tokens)
+            .into_iter()
+            .filter_map(|(info, swaps)| {
+                let token = swaps
+                    .iter()
+                    .map(|swap| swap.token_in.address)
+                    .find(|token| positions.contains_key(&(info.eoa, *token)))?;
+                let exit_swaps = swaps
+                    .into_iter()
+                    .filter(|swap| swap.token_in.address == token)
+                    .collect_vec();
+
+                let position = positions.get_mut(&(info.eoa, token))?;
+                self.calculate_exit(info, exit_block, position, exit_swaps)
+            })
+            .collect()
+    }
+
+    /// Pools that were created in the window, keyed to the block they got
+    /// launched in. This is the block of the first liquidity add if we see one
+    /// after the creation.
+    fn launched_pools(&self, data: &MultiBlockData) -> FastHashMap<Address, u64> {
+        let start_block = data.per_block_data[0].block_number();
+        let end_block = data.get_most_recent_block().block_number();
+
+        let mut launches: FastHashMap<Address, u64> = self
+            .utils
+            .db
+            .protocols_created_range(start_block, end_block)
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|(block, pools)| pools.into_iter().map(move |(pool, ..)| (pool, block)))
+            .collect();
+
+        let search =
+            TreeSearchBuilder::default().with_actions([Action::is_new_pool, Action::is_mint]);
+        let mut funded = FastHashSet::default();
+        for block in &data.per_block_data {
+            let block_number = block.block_number();
+            for action in block
+                .tree
+                .tx_roots
+                .iter()
+                .flat_map(|root| root.collect(&search))
+            {
+                match action {
+                    Action::NewPool(pool) => {
+                        launches.entry(pool.pool_address).or_insert(block_number);
+                    }
+                    Action::Mint(mint) => {
+                        if let Some(launch) = launches.get_mut(&mint.pool) {
+                            if funded.insert(mint.pool) {
+                                *launch = block_number;
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        launches
+    }
+
+    /// Returns the launch block of the pool if the swap buys the launched token
+    /// from it within the snipe blocks.
+    fn snipe_launch(
+        &self,
+        swap: &NormalizedSwap,
+        block_number: u64,
+        launches: &FastHashMap<Address, u64>,
+    ) -> Option<u64> {
+        let launch_block = *launches.get(&swap.pool)?;
+        let token_out = swap.token_out.address;
+
+        (block_number >= launch_block
+            && block_number <= launch_block + SNIPE_BLOCKS
+            && token_out != self.wrapped_native
+            && token_out != self.utils.quote)
+            .then_some(launch_block)
+    }
+
+    /// Txes in the block that either buy from a launched pool or sell a sniped
+    /// token, in block order.
+    fn candidate_txes(
+        &self,
+        block: &BlockData,
+        launches: &FastHashMap<Address, u64>,
+        sniped_tokens: &FastHashSet<Address>,
+    ) -> Vec<(TxInfo, Vec<NormalizedSwap>)> {
+        let search =
+            TreeSearchBuilder::default().with_actions([Action::is_swap, Action::is_nested_action]);
+        let (tx_hashes, swaps): (Vec<B256>, Vec<Vec<NormalizedSwap>>) = block
+            .tree
+            .tx_roots
+            .iter()
+            .map(|root| {
+                let swaps = self
+                    .utils
+                    .flatten_nested_actions(
+                        root.collect(&search).into_iter(),
+                        &|action: &Action| action.is_swap(),
+                    )
+                    .filter_map(Action::try_swaps_merged)
+                    .collect_vec();
+                (root.tx_hash, swaps)
+            })
+            .filter(|(_, swaps)| {
+                swaps.iter().any(|swap| {
+                    launches.contains_key(&swap.pool)
+                        || sniped_tokens.contains(&swap.token_in.address)
+                })
+            })
+            .unzip();
+
+        block
+            .tree
+            .get_tx_info_batch(&tx_hashes, self.utils.db)
+            .into_iter()
+            .zip(swaps)
+            .filter_map(|(info, swaps)| Some((info?, swaps)))
+            .sorted_by_key(|(info, _)| info.tx_index)
+            .collect()
+    }
+
+    fn calculate_exit(
+        &self,
+        info: TxInfo,
+        exit_block: &BlockData,
+        position: &mut SniperPosition,
+        exit_swaps: Vec<NormalizedSwap>,
+    ) -> Option<Bundle> {
+        let BlockData { metadata, tree } = exit_block;
+
+        let bought = position.bought();
+        let remaining = &bought - &position.sold;
+        let sold_now = exit_swaps
+            .iter()
+            .map(|swap| &swap.amount_in)
+            .sum::<Rational>();
+        position.sold += &sold_now;
+
+        // the sniper already exited its whole position, this is a later buy
+        if remaining <= Rational::ZERO || bought == Rational::ZERO {
+            return None
+        }
+        let realized = if sold_now < remaining { sold_now } else { remaining };
+        let share = realized / &bought;
+
+        let entry_cost = position
+            .snipes
+            .iter()
+            .flat_map(|snipe| {
+                snipe.swaps.iter().map(|swap| {
+                    let cost = self.utils.get_token_value_dex(
+                        snipe.info.tx_index as usize,
+                        PriceAt::Average,
+                        swap.token_in.address,
+                        &swap.amount_in,
+                        &snipe.metadata,
+                    )?;
+                    let gas = snipe
+                        .metadata
+                        .get_gas_price_usd(snipe.info.gas_details.gas_paid(), self.utils.quote);
+
+                    // split the gas of the snipe over its swaps so it's only counted once
+                    Some(cost + gas / Rational::from(snipe.swaps.len()))
+                })
+            })
+            .sum::<Option<Rational>>();
+
+        let proceeds = exit_swaps
+            .iter()
+            .map(|swap| {
+                self.utils.get_token_value_dex(
+                    info.tx_index as usize,
+                    PriceAt::Average,
+                    swap.token_out.address,
+                    &swap.amount_out,
+                    metadata,
+                )
+            })
+            .sum::<Option<Rational>>();
+
+        let exit_gas = metadata.get_gas_price_usd(info.gas_details.gas_paid(), self.utils.quote);
+
+        let (mut profit_usd, mut has_dex_price) = match (entry_cost, proceeds) {
+            (Some(cost), Some(proceeds)) => (proceeds - cost * share - exit_gas, true),
+            _ => (Rational::ZERO, false),
+        };
+
+        if profit_usd >= MAX_PROFIT || profit_usd <= MIN_PROFIT {
+            has_dex_price = false;
+            profit_usd = Rational::ZERO;
+        }
+
+        let deltas = tree
+            .clone()
+            .collect(
+                &info.tx_hash,
+                TreeSearchBuilder::default().with_actions([
+                    Action::is_transfer,
+                    Action::is_eth_transfer,
+                    Action::is_nested_action,
+                ]),
+            )
+            .collect_vec();
+        let deltas = self
+            .utils
+            .flatten_nested_actions_default(deltas.into_iter())
+            .chain(info.get_total_eth_value().iter().cloned().map(Action::from))
+            .filter(|a| a.is_eth_transfer() || a.is_transfer())
+            .account_for_actions();
+
+        let entry_gas_details = position
+            .snipes
+            .iter()
+            .map(|snipe| snipe.info.gas_details)
+            .collect_vec();
+        let mut gas_details = entry_gas_details.clone();
+        gas_details.push(info.gas_details);
+
+        let header = self.utils.build_bundle_header(
+            vec![deltas],
+            vec![info.tx_hash],
+            &info,
+            profit_usd.to_float(),
+            &gas_details,
+            metadata.clone(),
+            MevType::LongTail,
+            !has_dex_price,
+            |this, token, amount| {
+                this.get_token_value_dex(
+                    info.tx_index as usize,
+                    PriceAt::Average,
+                    token,
+                    &amount,
+                    metadata,
+                )
+            },
+        );
+
+        let first_snipe = &position.snipes[0];
+        let long_tail = LongTail {
+            block_number: metadata.block_num,
+            pool: first_snipe.pool,
+            token: exit_swaps[0].token_in.address,
+            launch_block: first_snipe.launch_block,
+            entry_tx_hashes: position
+                .snipes
+                .iter()
+                .map(|snipe| snipe.info.tx_hash)
+                .collect(),
+            entry_swaps: position
+                .snipes
+                .iter()
+                .map(|snipe| snipe.swaps.clone())
+                .collect(),
+            entry_gas_details,
+            exit_tx_hash: info.tx_hash,
+            exit_swaps,
+            exit_gas_details: info.gas_details,
+        };
+
+        Some(Bundle { header, data: BundleData::LongTail(long_tail) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{hex, Log, U256};
+    use alloy_sol_types::{SolCall, SolEvent};
+    use brontes_classifier::{
+        test_utils::{call_trace, create_trace, tx_trace},
+        UniswapV2::{swapCall, Swap},
+        UniswapV2Factory::createPairCall,
+    };
+    use brontes_types::{
+        db::token_info::{TokenInfo, TokenInfoWithAddress},
+        structured_trace::TxTrace,
+    };
+
+    use super::*;
+    use crate::{
+        test_utils::{InspectorTestUtils, USDC_ADDRESS, WETH_ADDRESS},
+        Inspectors,
+    };
+
+    const FACTORY: Address = Address::new(hex!("5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f"));
+    // sorts before weth so it's token0 of the pair
+    const TOKEN: Address = Address::repeat_byte(0x11);
+    const PAIR: Address = Address::repeat_byte(0x22);
+    const SNIPER: Address = Address::repeat_byte(0x33);
+    const LAUNCH_BLOCK: u64 = 19_000_000;
+
+    fn e18(amount: u64) -> U256 {
+        U256::from(amount) * U256::from(10).pow(U256::from(18))
+    }
+
+    fn create_pair() -> TxTrace {
+        let deployer = Address::repeat_byte(0x44);
+        tx_trace(vec![
+            call_trace(
+                vec![],
+                deployer,
+                FACTORY,
+                createPairCall { tokenA: TOKEN, tokenB: WETH_ADDRESS }.abi_encode(),
+                PAIR.into_word().to_vec(),
+                vec![],
+            ),
+            create_trace(vec![0], FACTORY, PAIR),
+        ])
+    }
+
+    /// A swap of the sniper on the pair, buying the token if `buy` is set.
+    fn swap(buy: bool, token_amount: U256, weth_amount: U256) -> TxTrace {
+        let (amount0_in, amount1_in, amount0_out, amount1_out) = if buy {
+            (U256::ZERO, weth_amount, token_amount, U256::ZERO)
+        } else {
+            (token_amount, U256::ZERO, U256::ZERO, weth_amount)
+        };
+
+        tx_trace(vec![call_trace(
+            vec![],
+            SNIPER,
+            PAIR,
+            swapCall {
+                amount0Out: amount0_out,
+                amount1Out: amount1_out,
+                to:         SNIPER,
+                data:       Default::default(),
+            }
+            .abi_encode(),
+            vec![],
+            vec![Log {
+                address: PAIR,
+                data:    Swap {
+                    sender:     SNIPER,
+                    amount0In:  amount0_in,
+                    amount1In:  amount1_in,
+                    amount0Out: amount0_out,
+                    amount1Out: amount1_out,
+                    to:         SNIPER,
+                }
+                .encode_log_data(),
+            }],
+        )])
+    }
+
+    async fn setup() -> InspectorTestUtils {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 0.0).await;
+        inspector_util
+            .classifier_inspector
+            .ensure_token(TokenInfoWithAddress {
+                address: TOKEN,
+                inner:   TokenInfo { decimals: 18, symbol: "SNIPE".to_string() },
+            });
+
+        inspector_util
+    }
+
+    #[brontes_macros::test]
+    async fn test_long_tail_snipe_and_exit() {
+        let inspector_util = setup().await;
+
+        let bundles = inspector_util
+            .run_inspector_on_traces(
+                Inspectors::LongTail,
+                vec![
+                    (LAUNCH_BLOCK, vec![create_pair()]),
+                    (LAUNCH_BLOCK + 1, vec![swap(true, e18(1000), e18(1))]),
+                    (LAUNCH_BLOCK + 2, vec![]),
+                    (LAUNCH_BLOCK + 3, vec![swap(false, e18(1000), e18(2))]),
+                ],
+            )
+            .await;
+
+        assert_eq!(bundles.len(), 1, "expected one long tail bundle: {bundles:#?}");
+        let BundleData::LongTail(long_tail) = &bundles[0].data else {
+            panic!("expected long tail bundle data, got {:#?}", bundles[0].data)
+        };
+
+        assert_eq!(bundles[0].header.mev_type, MevType::LongTail);
+        assert_eq!(long_tail.block_number, LAUNCH_BLOCK + 3);
+        assert_eq!(long_tail.launch_block, LAUNCH_BLOCK);
+        assert_eq!(long_tail.pool, PAIR);
+        assert_eq!(long_tail.token, TOKEN);
+        assert_eq!(long_tail.entry_tx_hashes.len(), 1);
+        assert_ne!(long_tail.entry_tx_hashes[0], long_tail.exit_tx_hash);
+        assert_eq!(long_tail.entry_swaps[0][0].amount_out, Rational::from(1000));
+        assert_eq!(long_tail.exit_swaps[0].amount_out, Rational::from(2));
+    }
+
+    #[brontes_macros::test]
+    async fn test_long_tail_ignores_late_buys() {
+        let inspector_util = setup().await;
+
+        // the buy is after the snipe blocks
+        let bundles = inspector_util
+            .run_inspector_on_traces(
+                Inspectors::LongTail,
+                vec![
+                    (LAUNCH_BLOCK, vec![create_pair()]),
+                    (LAUNCH_BLOCK + 1, vec![]),
+                    (LAUNCH_BLOCK + 2, vec![]),
+                    (LAUNCH_BLOCK + 3, vec![swap(true, e18(1000), e18(1))]),
+                    (LAUNCH_BLOCK + 4, vec![swap(false, e18(1000), e18(2))]),
+                ],
+            )
+            .await;
+
+        assert!(bundles.is_empty(), "found long tail for a late buy: {bundles:#?}");
+    }
+}
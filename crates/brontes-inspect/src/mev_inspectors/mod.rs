@@ -1,8 +1,10 @@
 pub mod atomic_arb;
 pub mod cex_dex;
+pub mod frontrun;
 
 pub mod jit;
 pub mod liquidations;
+pub mod long_tail;
 pub mod sandwich;
 pub mod searcher_activity;
 pub mod shared_utils;
@@ -6,6 +6,7 @@ use std::{
 use alloy_primitives::TxHash;
 use tracing::trace;
 mod types;
+mod victim_loss;
 use brontes_database::libmdbx::LibmdbxReader;
 use brontes_metrics::inspectors::OutlierMetrics;
 use brontes_types::{
@@ -22,6 +23,7 @@ use itertools::Itertools;
 use malachite::{num::basic::traits::Zero, Rational};
 use reth_primitives::{Address, B256};
 use types::{PossibleSandwich, PossibleSandwichWithTxInfo};
+pub use victim_loss::simulate_victim_loss;
 
 use super::MAX_PROFIT;
 use crate::{shared_utils::SharedInspectorUtils, Inspector, Metadata, MIN_PROFIT};
@@ -431,6 +433,8 @@ impl<DB: LibmdbxReader> SandwichInspector<'_, DB> {
             victim_swaps_tx_hashes,
             victim_swaps_gas_details: victim_swaps_gas_details.into_iter().flatten().collect(),
             victim_swaps,
+            victim_counterfactual_out: vec![],
+            victim_loss_usd: vec![],
             backrun_tx_hash: backrun_info.tx_hash,
             backrun_swaps: back_run_swaps,
             backrun_gas_details: backrun_info.gas_details,
@@ -0,0 +1,200 @@
+use alloy_primitives::{b256, Address, Log, B256, U256};
+use brontes_types::{
+    db::dex::PriceAt, mev::Sandwich, normalized_actions::Action, pair::Pair,
+    traits::TracingProvider, tree::BlockTree, ToFloatNearest, ToScaledRational,
+};
+use malachite::{num::basic::traits::One, Rational};
+
+use crate::Metadata;
+
+const TRANSFER_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// Re-executes the victims of the sandwich on the block state without the
+/// frontruns and records what each victim swap would have received, along with
+/// the usd value lost to the sandwich.
+///
+/// The counterfactual output of a swap is the transfer of its token out from
+/// its pool in the replayed transaction. Swaps we can't match to a transfer,
+/// e.g because the pool doesn't custody its tokens, are recorded without a
+/// loss.
+pub async fn simulate_victim_loss<T: TracingProvider>(
+    tracer: &T,
+    quote: Address,
+    metadata: &Metadata,
+    tree: &BlockTree<Action>,
+    sandwich: &mut Sandwich,
+) {
+    let mut counterfactual_amount_out = Vec::with_capacity(sandwich.victim_swaps.len());
+    let mut loss_usd = Vec::with_capacity(sandwich.victim_swaps.len());
+
+    let victims = sandwich.victim_swaps_tx_hashes.iter().flatten();
+    for (tx_hash, swaps) in victims.zip(&sandwich.victim_swaps) {
+        let logs = tracer
+            .replay_transaction_without(
+                sandwich.block_number,
+                *tx_hash,
+                sandwich.frontrun_tx_hash.clone(),
+            )
+            .await
+            .inspect_err(|e| {
+                tracing::debug!(?tx_hash, err=%e, "failed to replay sandwich victim");
+            })
+            .unwrap_or_default();
+
+        let mut transfers = logs.iter().filter_map(decode_transfer).collect::<Vec<_>>();
+        let tx_index = tree.get_root(*tx_hash).map(|root| root.position);
+
+        let (amounts, losses): (Vec<_>, Vec<_>) = swaps
+            .iter()
+            .map(|swap| {
+                let Some(i) = transfers.iter().position(|(token, from, _)| {
+                    *token == swap.token_out.address && *from == swap.pool
+                }) else {
+                    return (swap.amount_out.clone().to_float(), 0.0)
+                };
+                let (.., amount) = transfers.remove(i);
+                let amount = amount.to_scaled_rational(swap.token_out.decimals);
+
+                let price = if swap.token_out.address == quote {
+                    Some(Rational::ONE)
+                } else {
+                    tx_index.and_then(|tx_index| {
+                        metadata
+                            .dex_quotes
+                            .as_ref()?
+                            .price_at(Pair(swap.token_out.address, quote), tx_index)
+                            .map(|price| price.get_price(PriceAt::Average))
+                    })
+                };
+                let loss = price
+                    .map(|price| (&amount - &swap.amount_out) * price)
+                    .map(ToFloatNearest::to_float)
+                    .unwrap_or_default();
+
+                (amount.to_float(), loss)
+            })
+            .unzip();
+
+        counterfactual_amount_out.push(amounts);
+        loss_usd.push(losses);
+    }
+
+    sandwich.victim_counterfactual_out = counterfactual_amount_out;
+    sandwich.victim_loss_usd = loss_usd;
+}
+
+/// token, from, amount of an erc20 transfer log
+fn decode_transfer(log: &Log) -> Option<(Address, Address, U256)> {
+    if log.topics().len() != 3 || log.topics()[0] != TRANSFER_TOPIC {
+        return None
+    }
+
+    let from = Address::from_slice(&log.topics()[1][12..]);
+    let amount = U256::try_from_be_slice(&log.data.data)?;
+
+    Some((log.address, from, amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use alloy_primitives::{hex, LogData};
+    use brontes_types::{mev::BundleData, BlockData, MultiBlockData};
+
+    use super::*;
+    use crate::{
+        mev_inspectors::sandwich::SandwichInspector,
+        test_utils::{InspectorTestUtils, USDC_ADDRESS},
+        Inspector,
+    };
+
+    #[test]
+    fn test_decode_transfer() {
+        let token = Address::repeat_byte(1);
+        let from = Address::repeat_byte(2);
+        let to = Address::repeat_byte(3);
+        let amount = U256::from(1_000_000);
+
+        let log = Log {
+            address: token,
+            data:    LogData::new_unchecked(
+                vec![TRANSFER_TOPIC, from.into_word(), to.into_word()],
+                amount.to_be_bytes_vec().into(),
+            ),
+        };
+        assert_eq!(decode_transfer(&log), Some((token, from, amount)));
+
+        // erc721 transfers index the token id
+        let mut nft = log.clone();
+        nft.data = LogData::new_unchecked(
+            vec![TRANSFER_TOPIC, from.into_word(), to.into_word(), B256::ZERO],
+            Default::default(),
+        );
+        assert_eq!(decode_transfer(&nft), None);
+
+        let mut approval = log;
+        approval.data = LogData::new_unchecked(
+            vec![B256::repeat_byte(9), from.into_word(), to.into_word()],
+            amount.to_be_bytes_vec().into(),
+        );
+        assert_eq!(decode_transfer(&approval), None);
+    }
+
+    #[brontes_macros::test]
+    async fn test_simulate_victim_loss() {
+        let inspector_util = InspectorTestUtils::new(USDC_ADDRESS, 1.0).await;
+        let classifier = &inspector_util.classifier_inspector;
+
+        let (tree, quotes) = classifier
+            .build_tree_txes_with_pricing(
+                vec![
+                    hex!("ff79c471b191c0021cfb62408cb1d7418d09334665a02106191f6ed16a47e36c").into(),
+                    hex!("19122ffe65a714f0551edbb16a24551031056df16ccaab39db87a73ac657b722").into(),
+                    hex!("67771f2e3b0ea51c11c5af156d679ccef6933db9a4d4d6cd7605b4eee27f9ac8").into(),
+                ],
+                USDC_ADDRESS,
+                vec![hex!("28cf5263108c1c40cf30e0fe390bd9ccf929bf82").into()],
+            )
+            .await
+            .unwrap()
+            .remove(0);
+        let metadata = Arc::new(Metadata { dex_quotes: Some(quotes), ..Default::default() });
+        let tree = Arc::new(tree);
+
+        let inspector = SandwichInspector::new(USDC_ADDRESS, classifier.libmdbx, None);
+        let data = BlockData { metadata: metadata.clone(), tree: tree.clone() };
+        let mut bundles = inspector
+            .inspect_block(MultiBlockData { blocks: 1, per_block_data: vec![data] });
+        assert_eq!(bundles.len(), 1);
+
+        let BundleData::Sandwich(mut sandwich) = bundles.remove(0).data else {
+            panic!("expected a sandwich");
+        };
+        let tracer = classifier.get_tracing_provider();
+        simulate_victim_loss(&**tracer, USDC_ADDRESS, &metadata, &tree, &mut sandwich).await;
+
+        assert_eq!(sandwich.victim_counterfactual_out.len(), sandwich.victim_swaps.len());
+        assert_eq!(sandwich.victim_loss_usd.len(), sandwich.victim_swaps.len());
+
+        let outcomes = sandwich
+            .victim_swaps
+            .iter()
+            .flatten()
+            .zip(sandwich.victim_counterfactual_out.iter().flatten())
+            .zip(sandwich.victim_loss_usd.iter().flatten());
+        for ((swap, counterfactual), loss) in outcomes {
+            assert!(
+                *counterfactual >= swap.amount_out.clone().to_float(),
+                "victim would have received less without the frontrun"
+            );
+            assert!(*loss >= 0.0, "negative victim loss {loss}");
+        }
+        assert!(sandwich
+            .victim_loss_usd
+            .iter()
+            .flatten()
+            .any(|loss| *loss > 0.0));
+    }
+}
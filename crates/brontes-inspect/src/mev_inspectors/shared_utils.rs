@@ -19,7 +19,7 @@ use brontes_types::{
     },
     pair::Pair,
     utils::ToFloatNearest,
-    ActionIter, FastHashMap, FastHashSet, GasDetails, TxInfo,
+    ActionIter, BlockTree, FastHashMap, FastHashSet, GasDetails, TreeSearchBuilder, TxInfo,
 };
 use itertools::Itertools;
 use malachite::{
@@ -550,6 +550,27 @@ impl<DB: LibmdbxReader> SharedInspectorUtils<'_, DB> {
         })
     }
 
+    /// Finds the latest transaction before `tx_index` in the block that pushed
+    /// an oracle update for one of the given assets.
+    pub fn find_oracle_trigger(
+        &self,
+        tree: &BlockTree<Action>,
+        tx_index: u64,
+        assets: &FastHashSet<Address>,
+    ) -> Option<TxHash> {
+        tree.tx_roots
+            .iter()
+            .take(tx_index as usize)
+            .rev()
+            .find(|root| {
+                root.collect(&TreeSearchBuilder::default().with_action(Action::is_oracle_update))
+                    .into_iter()
+                    .filter_map(Action::try_oracle_update)
+                    .any(|update| assets.contains(&update.asset.address))
+            })
+            .map(|root| root.tx_hash)
+    }
+
     /// Evaluates the validity of swap prices against DEX quoted prices within a
     /// given metadata context.
     ///
@@ -7,12 +7,12 @@ use brontes_types::{
         cex::{trades::CexDexTradeConfig, CexExchange},
         metadata::Metadata,
     },
-    BlockData, MultiBlockData,
+    BlockData, ChainConfig, MultiBlockData,
 };
 use criterion::{black_box, Criterion};
 
 use super::InspectorTestUtilsError;
-use crate::{composer::run_block_inspection, Inspectors};
+use crate::{composer::run_block_inspection, config::InspectorConfig, Inspectors};
 
 pub struct InspectorBenchUtils {
     classifier_inspector: ClassifierTestUtils,
@@ -49,6 +49,8 @@ impl InspectorBenchUtils {
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
                     None,
+                    &InspectorConfig::default(),
+                    ChainConfig::mainnet(),
                 )
             })
             .collect::<Vec<_>>();
@@ -100,6 +102,8 @@ impl InspectorBenchUtils {
             &[CexExchange::Binance],
             CexDexTradeConfig::default(),
             None,
+            &InspectorConfig::default(),
+            ChainConfig::mainnet(),
         );
 
         let mut trees =
@@ -163,6 +167,8 @@ impl InspectorBenchUtils {
             &[CexExchange::Binance],
             CexDexTradeConfig::default(),
             None,
+            &InspectorConfig::default(),
+            ChainConfig::mainnet(),
         );
 
         let (tree, prices) =
@@ -217,6 +223,8 @@ impl InspectorBenchUtils {
             &[CexExchange::Binance],
             CexDexTradeConfig::default(),
             None,
+            &InspectorConfig::default(),
+            ChainConfig::mainnet(),
         );
 
         let mut trees = self
@@ -264,6 +272,8 @@ impl InspectorBenchUtils {
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
                     None,
+                    &InspectorConfig::default(),
+                    ChainConfig::mainnet(),
                 )
             })
             .collect::<Vec<_>>();
@@ -323,6 +333,8 @@ impl InspectorBenchUtils {
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
                     None,
+                    &InspectorConfig::default(),
+                    ChainConfig::mainnet(),
                 )
             })
             .collect::<Vec<_>>();
@@ -33,12 +33,17 @@ use brontes_types::{
     },
     mev::{Bundle, MevType},
     normalized_actions::Action,
+    structured_trace::TxTrace,
     tree::BlockTree,
-    BlockData, MultiBlockData,
+    BlockData, ChainConfig, MultiBlockData,
 };
 use thiserror::Error;
 
-use crate::{composer::run_block_inspection, Inspectors};
+use crate::{
+    composer::{run_block_inspection, run_block_reinspection},
+    config::InspectorConfig,
+    Inspectors,
+};
 
 type StateTests = Option<Box<dyn for<'a> Fn(&'a Bundle)>>;
 
@@ -169,6 +174,8 @@ impl InspectorTestUtils {
             ],
             CexDexTradeConfig::default(),
             None,
+            &InspectorConfig::default(),
+            ChainConfig::mainnet(),
         );
         let data = BlockData { metadata: metadata.into(), tree: tree.into() };
         let multi = MultiBlockData { per_block_data: vec![data], blocks: 1 };
@@ -179,6 +186,40 @@ impl InspectorTestUtils {
         Ok(())
     }
 
+    /// Runs the inspector over a window of blocks that are built from the given
+    /// transactions, for mev that we don't have a transaction for in the test
+    /// db. The last block is the one that is inspected. There are no dex quotes
+    /// so profits are zero.
+    pub async fn run_inspector_on_traces(
+        &self,
+        mev_type: Inspectors,
+        blocks: Vec<(u64, Vec<TxTrace>)>,
+    ) -> Vec<Bundle> {
+        let mut per_block_data = Vec::with_capacity(blocks.len());
+        for (block, txes) in blocks {
+            let tree = self
+                .classifier_inspector
+                .build_block_tree_from_tx_traces(block, txes)
+                .await;
+            let mut metadata = Metadata::default();
+            metadata.block_metadata.block_num = block;
+
+            per_block_data.push(BlockData { metadata: metadata.into(), tree: tree.into() });
+        }
+
+        let inspector = mev_type.init_mev_inspector(
+            self.quote_address,
+            self.classifier_inspector.libmdbx,
+            &[],
+            CexDexTradeConfig::default(),
+            None,
+            &InspectorConfig::default(),
+            ChainConfig::mainnet(),
+        );
+
+        inspector.inspect_block(MultiBlockData { blocks: per_block_data.len(), per_block_data })
+    }
+
     pub async fn run_inspector(
         &self,
         config: InspectorTxRunConfig,
@@ -271,6 +312,8 @@ impl InspectorTestUtils {
             ],
             cex_trade_config,
             None,
+            &InspectorConfig::default(),
+            ChainConfig::mainnet(),
         );
 
         let data = BlockData { metadata: metadata.into(), tree: tree.into() };
@@ -391,6 +434,8 @@ impl InspectorTestUtils {
                     &[CexExchange::Binance],
                     CexDexTradeConfig::default(),
                     None,
+                    &InspectorConfig::default(),
+                    ChainConfig::mainnet(),
                 )
             })
             .collect::<Vec<_>>();
@@ -450,6 +495,65 @@ impl InspectorTestUtils {
 
         Ok(())
     }
+
+    /// Inspects the block with `inspectors`, then reinspects it with only
+    /// `reinspected` using the first results as the stored bundles. Returns
+    /// the stored and the reinspected bundles.
+    pub async fn run_reinspection(
+        &self,
+        block: u64,
+        inspectors: Vec<Inspectors>,
+        reinspected: Vec<Inspectors>,
+    ) -> Result<(Vec<Bundle>, Vec<Bundle>), InspectorTestUtilsError> {
+        let tree = self.get_block_tree(block).await?;
+        let metadata = self
+            .classifier_inspector
+            .get_metadata(block, false)
+            .await
+            .unwrap_or_else(|_| Metadata::default());
+        let data = BlockData { metadata: metadata.into(), tree: tree.into() };
+
+        let init = |inspectors: Vec<Inspectors>| {
+            inspectors
+                .into_iter()
+                .map(|i| {
+                    i.init_mev_inspector(
+                        self.quote_address,
+                        self.classifier_inspector.libmdbx,
+                        &[CexExchange::Binance],
+                        CexDexTradeConfig::default(),
+                        None,
+                        &InspectorConfig::default(),
+                        ChainConfig::mainnet(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let db = self.classifier_inspector.trace_loader.libmdbx;
+
+        let replaced = reinspected
+            .iter()
+            .flat_map(|i| i.mev_types().iter().copied())
+            .collect::<Vec<_>>();
+
+        let stored = run_block_inspection(
+            init(inspectors).as_slice(),
+            MultiBlockData { blocks: 1, per_block_data: vec![data.clone()] },
+            db,
+        )
+        .mev_details;
+
+        let reinspected = run_block_reinspection(
+            init(reinspected).as_slice(),
+            MultiBlockData { blocks: 1, per_block_data: vec![data] },
+            db,
+            stored.clone(),
+            &replaced,
+        )
+        .mev_details;
+
+        Ok((stored, reinspected))
+    }
 }
 
 /// This inspector test config is to configure an inspector test for a single
@@ -120,6 +120,7 @@ impl Parse for MacroParse {
 pub fn discovery_dispatch(input: TokenStream) -> syn::Result<TokenStream> {
     let DiscoveryDispatch { struct_name, rest } = syn::parse2(input)?;
 
+    let name_str = rest.iter().map(|n| n.to_string()).collect::<Vec<_>>();
     let (var_name, fn_name): (Vec<_>, Vec<_>) = rest
         .iter()
         .enumerate()
@@ -141,6 +142,23 @@ pub fn discovery_dispatch(input: TokenStream) -> syn::Result<TokenStream> {
         #[derive(Default, Debug)]
         pub struct #struct_name(#(pub #name,)*);
 
+        impl #struct_name {
+            /// Factories that are deployed to a different address on this chain are
+            /// mapped back to the mainnet address the discovery is keyed on
+            pub fn mainnet_factory(
+                chain_config: &::brontes_types::ChainConfig,
+                factory: ::alloy_primitives::Address,
+            ) -> ::alloy_primitives::Address {
+                #(
+                    if chain_config.factory(#name_str) == Some(factory) {
+                        return ::alloy_primitives::Address::from_slice(&#fn_name()[0..20])
+                    }
+                )*
+
+                factory
+            }
+        }
+
         impl crate::FactoryDiscoveryDispatch for #struct_name {
             async fn dispatch<T: ::brontes_types::traits::TracingProvider>(
                     &self,
@@ -160,6 +178,9 @@ pub fn discovery_dispatch(input: TokenStream) -> syn::Result<TokenStream> {
                             return Vec::new()
                         }
 
+                        let factory =
+                            Self::mainnet_factory(::brontes_types::ChainConfig::get(), factory);
+
                         let mut key = [0u8; 24];
                         key[0..20].copy_from_slice(&**factory);
                         key[20..].copy_from_slice(&parent_calldata[0..4]);
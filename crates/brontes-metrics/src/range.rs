@@ -192,3 +192,12 @@ pub struct FinishedRange {
     /// if at tip, then this is the range at init
     pub total_set_range: Counter,
 }
+
+#[derive(Metrics, Clone)]
+#[metrics(scope = "brontes_tip")]
+pub struct TipMetrics {
+    /// the amount of reorgs that rolled back processed blocks
+    pub reorgs:      Counter,
+    /// the amount of processed blocks that were orphaned by a reorg
+    pub reorg_depth: Histogram,
+}
@@ -6,7 +6,7 @@ use brontes_core::test_utils::*;
 use brontes_pricing::{types::DexPriceMsg, BrontesBatchPricer, GraphManager};
 use brontes_types::{
     normalized_actions::Action, traits::TracingProvider, tree::BlockTree, BrontesTaskManager,
-    FastHashMap, UnboundedYapperReceiver,
+    ChainConfig, FastHashMap, UnboundedYapperReceiver,
 };
 use thiserror::Error;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
@@ -47,7 +47,7 @@ impl PricingTestUtils {
                 .flat_map(|(_, pools)| {
                     pools
                         .into_iter()
-                        .map(|(addr, protocol, pair)| (addr, (protocol, pair)))
+                        .map(|(addr, protocol, pairs)| (addr, (protocol, pairs)))
                         .collect::<Vec<_>>()
                 })
                 .collect::<FastHashMap<_, _>>()
@@ -59,6 +59,7 @@ impl PricingTestUtils {
             0,
             Arc::new(AtomicBool::new(false)),
             self.quote_address,
+            ChainConfig::mainnet(),
             pair_graph,
             UnboundedYapperReceiver::new(rx, 100_000, "test".into()),
             self.tracer.get_provider(),
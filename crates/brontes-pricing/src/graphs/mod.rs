@@ -31,6 +31,10 @@ use crate::{
     Protocol,
 };
 
+/// The subgraph and pool state of a [`GraphManager`], as returned by
+/// [`GraphManager::snapshot_state`]
+pub type GraphSnapshot = (SubGraphRegistry, SubgraphVerifier, StateTracker);
+
 /// [`GraphManager`] Is the manager for everything graph related. It is
 /// responsible for creating, updating, and maintaining the main token graph as
 /// well as its derived subgraphs.
@@ -68,7 +72,7 @@ pub struct GraphManager {
 
 impl GraphManager {
     pub fn init_from_db_state(
-        all_pool_data: FastHashMap<(Address, Protocol), Pair>,
+        all_pool_data: FastHashMap<(Address, Protocol), Vec<Pair>>,
         metrics: Option<DexPricingMetrics>,
     ) -> Self {
         let graph = AllPairGraph::init_from_hash_map(all_pool_data);
@@ -100,6 +104,15 @@ impl GraphManager {
         self.graph_state = state;
     }
 
+    /// restores a snapshot taken with [`GraphManager::snapshot_state`] so that
+    /// the subgraphs don't need to be re-verified
+    pub fn restore_state(&mut self, snapshot: GraphSnapshot, metrics: Option<DexPricingMetrics>) {
+        let (mut sub_graph_registry, verifier, mut state) = snapshot;
+        sub_graph_registry.set_metrics(metrics.clone());
+        state.set_metrics(metrics);
+        self.set_state(sub_graph_registry, verifier, state);
+    }
+
     pub fn add_pool(&mut self, pair: Pair, pool_addr: Address, dex: Protocol, block: u64) {
         self.all_pair_graph.add_node(pair, pool_addr, dex, block);
     }
@@ -11,6 +11,7 @@ use malachite::{
     },
     Rational,
 };
+use serde::{Deserialize, Serialize};
 
 use super::{subgraph::PairSubGraph, PoolState};
 use crate::types::{PairWithFirstPoolHop, ProtocolState};
@@ -30,18 +31,19 @@ use crate::types::{PairWithFirstPoolHop, ProtocolState};
 /// Mainly functioning within the BrontesBatchPricer system, it plays a key role
 /// in providing up-to-date and reliable pricing data in the decentralized
 /// exchange context.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubGraphRegistry {
     /// all currently known sub-graphs
     sub_graphs:               FastHashMap<Pair, BTreeMap<Pair, PairSubGraph>>,
     /// the pending_subgrpahs that haven't been finalized yet.
     pending_finalized_graphs: FastHashMap<u64, PendingRegistry>,
     /// metrics
+    #[serde(skip)]
     metrics:                  Option<DexPricingMetrics>,
 }
 
 /// holder for subgraphs that aren't active yet to avoid race conditions
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PendingRegistry {
     sub_graphs: FastHashMap<Pair, BTreeMap<Pair, PairSubGraph>>,
 }
@@ -65,6 +67,10 @@ impl SubGraphRegistry {
         Self { sub_graphs, pending_finalized_graphs: FastHashMap::default(), metrics }
     }
 
+    pub fn set_metrics(&mut self, metrics: Option<DexPricingMetrics>) {
+        self.metrics = metrics;
+    }
+
     // for all subgraphs that haven't been used in a given time period, will
     // remove them from and return each pool with the amount to decrement.
     pub fn prune_dead_subgraphs(&mut self, block: u64) -> FastHashMap<Address, u64> {
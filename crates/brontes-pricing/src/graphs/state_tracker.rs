@@ -4,6 +4,7 @@ use alloy_primitives::Address;
 use brontes_metrics::pricing::DexPricingMetrics;
 use brontes_types::FastHashMap;
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use tracing::debug;
 
 use crate::{
@@ -29,13 +30,14 @@ use crate::{
 /// verification completion. This careful management of pool states is essential
 /// for the BrontesBatchPricer system to provide accurate and current pricing
 /// information for tokens on decentralized exchanges.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StateTracker {
     /// state that finalized subgraphs are dependent on.
     finalized_edge_state:    FastHashMap<Address, StateWithDependencies>,
     /// state that verification is using
     verification_edge_state: FastHashMap<Address, PoolStateWithBlock>,
     /// state count
+    #[serde(skip)]
     metrics:                 Option<DexPricingMetrics>,
 }
 
@@ -67,6 +69,10 @@ impl StateTracker {
         }
     }
 
+    pub fn set_metrics(&mut self, metrics: Option<DexPricingMetrics>) {
+        self.metrics = metrics;
+    }
+
     pub fn remove_finalized_state_dep(&mut self, pool: Address, amount: u64) {
         self.finalized_edge_state.retain(|i_pool, state| {
             if pool != *i_pool {
@@ -206,7 +212,7 @@ impl StateTracker {
     }
 }
 
-#[derive(Debug, Clone, derive_more::Deref)]
+#[derive(Debug, Clone, derive_more::Deref, Serialize, Deserialize)]
 pub struct StateWithDependencies {
     #[deref]
     pub state:      PoolState,
@@ -223,7 +229,7 @@ impl StateWithDependencies {
     }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct PoolStateWithBlock(Vec<(u64, StateWithDependencies)>);
 
 impl PoolStateWithBlock {
@@ -27,6 +27,7 @@ use petgraph::{
     prelude::*,
     visit::{VisitMap, Visitable},
 };
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use tracing::error;
 
 use crate::{types::ProtocolState, Pair};
@@ -38,7 +39,7 @@ pub struct VerificationOutcome {
     pub frayed_ends:    Vec<Address>,
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct BadEdge {
     pub pair:         Pair,
     pub pool_address: Address,
@@ -77,7 +78,7 @@ const INACTIVE_REMOVAL_PERIOD: u64 = 750;
 /// ensuring the integrity and reliability of each pool's data within the
 /// subgraph and recalculating prices based on up-to-date and verified
 /// information.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PairSubGraph {
     /// the pair represented
     pub(crate) pair:          Pair,
@@ -97,6 +98,7 @@ pub struct PairSubGraph {
     /// the last time this subgraph was used for pricing.
     /// if it has been more than a certain amount of blocks, we will
     /// remove this graph to save on memory
+    #[serde(with = "shared_block")]
     last_block_for_pricing: Arc<AtomicU64>,
     /// to avoid removing when there is a dependency
     /// we mark the removal time and all new subgraphs past this block,
@@ -104,6 +106,23 @@ pub struct PairSubGraph {
     remove_at:              Option<u64>,
 }
 
+mod shared_block {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        block: &Arc<AtomicU64>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        block.load(SeqCst).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Arc<AtomicU64>, D::Error> {
+        Ok(Arc::new(AtomicU64::new(u64::deserialize(deserializer)?)))
+    }
+}
+
 impl PairSubGraph {
     pub fn init(
         pair: Pair,
@@ -202,7 +221,7 @@ impl PairSubGraph {
             .edge_weights()
             .flat_map(|weight| {
                 weight.iter().filter_map(|edge| {
-                    let (r0, r1) = state.get(&edge.pool_addr)?.tvl(edge.token_0);
+                    let (r0, r1) = state.get(&edge.pool_addr)?.tvl(edge.token_0, edge.token_1);
                     let tvl_added = r0 + r1;
 
                     Some((edge.pool_addr, tvl_added))
@@ -222,7 +241,10 @@ impl PairSubGraph {
                 weight
                     .iter()
                     .map(|edge| {
-                        let (r0, r1) = state.get(&edge.pool_addr).unwrap().tvl(edge.token_0);
+                        let (r0, r1) = state
+                            .get(&edge.pool_addr)
+                            .unwrap()
+                            .tvl(edge.token_0, edge.token_1);
                         let tvl_added = r0 + r1;
                         let start_tvl = self.start_nodes_liq.get(&edge.pool_addr).unwrap();
 
@@ -499,14 +521,15 @@ impl PairSubGraph {
                         continue;
                     };
 
-                    let Ok(pool_price) =
-                        pool_state.price(info.get_token_with_direction(is_outgoing))
-                    else {
+                    let base = info.get_token_with_direction(is_outgoing);
+                    let quote = info.get_token_with_direction(!is_outgoing);
+
+                    let Ok(pool_price) = pool_state.price(base, quote) else {
                         Self::bad_state(pair, info, Rational::ZERO, &mut removal_map.removal_state);
                         continue;
                     };
 
-                    let (t0, t1) = pool_state.tvl(info.get_token_with_direction(is_outgoing));
+                    let (t0, t1) = pool_state.tvl(base, quote);
                     let liq0 = prev_price.clone().reciprocal() * &t0;
 
                     let goes_through_arg = if ignore_goes_through {
@@ -913,11 +936,13 @@ impl PairSubGraph {
                         continue;
                     };
 
-                    let Ok(pool_price) = pool_state.price(info.get_base_token()) else {
+                    let Ok(pool_price) =
+                        pool_state.price(info.get_base_token(), info.get_quote_token())
+                    else {
                         continue;
                     };
 
-                    let (t0, t1) = pool_state.tvl(info.get_base_token());
+                    let (t0, t1) = pool_state.tvl(info.get_base_token(), info.get_quote_token());
 
                     let t0xt1 = &t0 * &t1;
                     pxw += pool_price * &t0xt1;
@@ -1055,11 +1080,15 @@ pub mod test {
     }
 
     impl ProtocolState for MockPoolState {
-        fn price(&self, _base: Address) -> Result<Rational, crate::errors::ArithmeticError> {
+        fn price(
+            &self,
+            _base: Address,
+            _quote: Address,
+        ) -> Result<Rational, crate::errors::ArithmeticError> {
             Ok(self.price.clone())
         }
 
-        fn tvl(&self, _base: Address) -> (Rational, Rational) {
+        fn tvl(&self, _base: Address, _quote: Address) -> (Rational, Rational) {
             self.tvl.clone()
         }
     }
@@ -3,6 +3,7 @@ use brontes_types::{pair::Pair, FastHashMap, FastHashSet, ToFloatNearest};
 use itertools::Itertools;
 use malachite::{num::basic::traits::Zero, Rational};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
 use tracing::{error_span, instrument};
 
 use super::{
@@ -34,7 +35,7 @@ use crate::{types::PairWithFirstPoolHop, AllPairGraph, PoolPairInfoDirection, Su
 ///   the current state of the DEX, checking liquidity parameters and pool
 ///   states. This method is vital in maintaining the integrity of the pricing
 ///   system.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubgraphVerifier {
     pending_subgraphs:           FastHashMap<PairWithFirstPoolHop, Subgraph>,
     /// pruned edges of a subgraph that didn't meet liquidity params.
@@ -373,7 +374,7 @@ impl SubgraphVerifier {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Subgraph {
     pub subgraph:              PairSubGraph,
     pub frayed_end_extensions: FastHashMap<u64, Vec<SubGraphEdge>>,
@@ -420,7 +421,7 @@ pub enum VerificationResults {
     Abort(PairWithFirstPoolHop, u64),
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct SubgraphVerificationState {
     /// contains all fully removed edges. this is so that
     /// if we don't find a edge with the wanted amount of liquidity,
@@ -485,5 +486,5 @@ impl SubgraphVerificationState {
     }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct EdgesWithLiq(FastHashMap<Address, FastHashSet<BadEdge>>);
@@ -23,7 +23,7 @@
 use brontes_metrics::pricing::DexPricingMetrics;
 use brontes_types::{
     db::dex::PriceAt, execute_on, normalized_actions::pool::NormalizedPoolConfigUpdate,
-    BrontesTaskExecutor, UnboundedYapperReceiver,
+    BrontesTaskExecutor, ChainConfig, UnboundedYapperReceiver,
 };
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
@@ -54,7 +54,7 @@ use brontes_types::{
 };
 use futures::Stream;
 pub use graphs::{
-    AllPairGraph, GraphManager, StateTracker, SubGraphRegistry, SubgraphVerifier,
+    AllPairGraph, GraphManager, GraphSnapshot, StateTracker, SubGraphRegistry, SubgraphVerifier,
     VerificationResults,
 };
 use itertools::Itertools;
@@ -68,7 +68,7 @@ use subgraph_query::*;
 use tracing::{debug, error, info};
 use types::{DexPriceMsg, PairWithFirstPoolHop, PoolUpdate};
 
-use crate::types::PoolState;
+use crate::{types::PoolState, uniswap_v4::UniswapV4PoolKey};
 /// max movement of price in the block before its considered invalid.
 /// currently %90 movement from start price.
 /// If WETH was at 3000$usd. to trigger this. the final price
@@ -78,6 +78,7 @@ const MAX_BLOCK_MOVEMENT: Rational = Rational::const_from_unsigneds(9, 10);
 pub struct BrontesBatchPricer<T: TracingProvider> {
     range_id:        usize,
     quote_asset:     Address,
+    chain:           &'static ChainConfig,
     current_block:   u64,
     completed_block: u64,
     finished:        Arc<AtomicBool>,
@@ -85,33 +86,36 @@ pub struct BrontesBatchPricer<T: TracingProvider> {
 
     /// receiver from classifier, classifier is ran sequentially to guarantee
     /// order
-    update_rx:       UnboundedYapperReceiver<DexPriceMsg>,
+    update_rx:          UnboundedYapperReceiver<DexPriceMsg>,
     /// holds the state transfers and state void overrides for the given block.
     /// it works by processing all state transitions for a block and
     /// allowing lazy loading to occur. Once lazy loading has occurred and there
     /// are no more events for the current block, all the state transitions
     /// are applied in order with the price at the transaction index being
     /// calculated and inserted into the results and returned.
-    buffer:          StateBuffer,
+    buffer:             StateBuffer,
     /// holds new graph nodes / edges that can be added at every given block.
     /// this is done to ensure any route from a base to our quote asset will
     /// only pass though valid created pools.
-    new_graph_pairs: FastHashMap<Address, (Protocol, Pair)>,
+    new_graph_pairs:    FastHashMap<Address, (Protocol, Vec<Pair>)>,
     /// manages all graph related items
-    graph_manager:   GraphManager,
+    graph_manager:      GraphManager,
     /// lazy loads dex pairs so we only fetch init state that is needed
-    lazy_loader:     LazyExchangeLoader<T>,
-    dex_quotes:      FastHashMap<u64, DexQuotes>,
+    lazy_loader:        LazyExchangeLoader<T>,
+    dex_quotes:         FastHashMap<u64, DexQuotes>,
     /// pairs that failed to be verified. we use this to avoid the fallback for
     /// transfers
-    failed_pairs:    FastHashMap<u64, Vec<PairWithFirstPoolHop>>,
+    failed_pairs:       FastHashMap<u64, Vec<PairWithFirstPoolHop>>,
     /// when we are pulling from the channel, because its not peekable we always
     /// pull out one more than we want. this acts as a cache for it
-    overlap_update:  Option<PoolUpdate>,
+    overlap_update:     Option<PoolUpdate>,
     /// a queue of blocks that we should skip pricing for and just upkeep state
-    skip_pricing:    VecDeque<u64>,
+    skip_pricing:       VecDeque<u64>,
     /// metrics
-    metrics:         Option<DexPricingMetrics>,
+    metrics:            Option<DexPricingMetrics>,
+    /// the max relative price movement of a pair within a block before its
+    /// prices are dropped
+    max_block_movement: Rational,
 }
 
 impl<T: TracingProvider> BrontesBatchPricer<T> {
@@ -119,11 +123,12 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
         range_id: usize,
         finished: Arc<AtomicBool>,
         quote_asset: Address,
+        chain: &'static ChainConfig,
         graph_manager: GraphManager,
         update_rx: UnboundedYapperReceiver<DexPriceMsg>,
         provider: Arc<T>,
         current_block: u64,
-        new_graph_pairs: FastHashMap<Address, (Protocol, Pair)>,
+        new_graph_pairs: FastHashMap<Address, (Protocol, Vec<Pair>)>,
         needs_more_data: Arc<AtomicBool>,
         metrics: Option<DexPricingMetrics>,
         executor: BrontesTaskExecutor,
@@ -134,20 +139,27 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
             failed_pairs: FastHashMap::default(),
             new_graph_pairs,
             quote_asset,
+            chain,
             buffer: StateBuffer::new(),
             update_rx,
             graph_manager,
             dex_quotes: FastHashMap::default(),
-            lazy_loader: LazyExchangeLoader::new(provider, executor),
+            lazy_loader: LazyExchangeLoader::new(provider, executor, chain),
             current_block,
             completed_block: current_block,
             overlap_update: None,
             skip_pricing: VecDeque::new(),
             needs_more_data,
             metrics,
+            max_block_movement: MAX_BLOCK_MOVEMENT,
         }
     }
 
+    pub fn with_max_block_movement(mut self, max_block_movement: Rational) -> Self {
+        self.max_block_movement = max_block_movement;
+        self
+    }
+
     pub fn current_block_processing(&self) -> u64 {
         self.completed_block
     }
@@ -197,21 +209,26 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
                 self.current_block = msg.block;
             }
         }
+        self.register_uniswap_v4_pools(&updates);
 
         // insert new pools accessed on this block.
         updates
             .iter()
             .filter_map(|update| {
-                let (protocol, pair) = self.new_graph_pairs.remove(&update.get_pool_address())?;
-                Some((update.get_pool_address(), protocol, pair, update.block))
+                let (protocol, pairs) = self.new_graph_pairs.remove(&update.get_pool_address())?;
+                Some((update.get_pool_address(), protocol, pairs, update.block))
             })
-            .for_each(|(pool_addr, protocol, pair, block)| {
-                self.graph_manager
-                    .add_pool(pair, pool_addr, protocol, block);
+            .for_each(|(pool_addr, protocol, pairs, block)| {
+                for pair in pairs {
+                    self.graph_manager
+                        .add_pool(pair, pool_addr, protocol, block);
+                }
             });
 
         updates.iter().for_each(|msg| {
-            let Some(pair) = msg.get_pair(self.quote_asset) else { return };
+            let Some(pair) = msg.get_pair(self.quote_asset, self.chain.wrapped_native) else {
+                return
+            };
             let is_transfer = msg.is_transfer();
 
             let block = msg.block;
@@ -242,7 +259,12 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
 
         tracing::debug!("search triggered by pool updates");
         let (state, pools) = execute_on!(target = pricing, {
-            graph_search_par(&self.graph_manager, self.quote_asset, updates)
+            graph_search_par(
+                &self.graph_manager,
+                self.quote_asset,
+                self.chain.wrapped_native,
+                updates,
+            )
         });
         tracing::debug!("search triggered by on pool updates completed");
 
@@ -279,16 +301,19 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
                 self.current_block = msg.block;
             }
         }
+        self.register_uniswap_v4_pools(&updates);
 
         updates
             .iter()
             .filter_map(|update| {
-                let (protocol, pair) = self.new_graph_pairs.remove(&update.get_pool_address())?;
-                Some((update.get_pool_address(), protocol, pair, update.block))
+                let (protocol, pairs) = self.new_graph_pairs.remove(&update.get_pool_address())?;
+                Some((update.get_pool_address(), protocol, pairs, update.block))
             })
-            .for_each(|(pool_addr, protocol, pair, block)| {
-                self.graph_manager
-                    .add_pool(pair, pool_addr, protocol, block);
+            .for_each(|(pool_addr, protocol, pairs, block)| {
+                for pair in pairs {
+                    self.graph_manager
+                        .add_pool(pair, pool_addr, protocol, block);
+                }
             });
 
         updates.into_iter().for_each(|update| {
@@ -297,6 +322,19 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
         });
     }
 
+    /// v4 pools are addressed by their truncated pool id, the full id is taken
+    /// from the initialize or swap logs so that the pool state can be loaded
+    fn register_uniswap_v4_pools(&mut self, updates: &[PoolUpdate]) {
+        updates
+            .iter()
+            .filter_map(|update| {
+                Some((update.get_pool_address(), UniswapV4PoolKey::from_update(update)?))
+            })
+            .for_each(|(pool_addr, key)| {
+                self.lazy_loader.register_uniswap_v4_pool(pool_addr, key);
+            });
+    }
+
     fn get_dex_price(&mut self, pool_pair: Pair, goes_through: Pair) -> Option<Rational> {
         if pool_pair.0 == pool_pair.1 {
             return Some(Rational::ONE)
@@ -360,7 +398,7 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
         let block = msg.block;
         let is_transfer = msg.is_transfer();
 
-        let Some(pool_pair) = msg.get_pair(self.quote_asset) else {
+        let Some(pool_pair) = msg.get_pair(self.quote_asset, self.chain.wrapped_native) else {
             info!(?addr, "failed to get pair for pool");
             return;
         };
@@ -439,7 +477,7 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
         let tx_idx = msg.tx_idx;
         let block = msg.block;
         let is_transfer = msg.is_transfer();
-        let Some(pool_pair) = msg.get_pair(self.quote_asset) else {
+        let Some(pool_pair) = msg.get_pair(self.quote_asset, self.chain.wrapped_native) else {
             error!(?addr, "failed to get pair for pool");
             self.graph_manager.update_state(addr, msg);
             return;
@@ -591,7 +629,10 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
                 } = load_result
                 {
                     self.new_graph_pairs
-                        .insert(pool_address, (protocol, pool_pair));
+                        .entry(pool_address)
+                        .or_insert_with(|| (protocol, vec![]))
+                        .1
+                        .push(pool_pair);
                     self.graph_manager
                         .remove_pair_graph_address(pool_pair, pool_address);
 
@@ -653,7 +694,11 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
                                 .graph_manager
                                 .remove_pair_graph_address(bad_edge.pair, bad_edge.pool_address)
                             {
-                                self.new_graph_pairs.insert(addr, (protocol, pair));
+                                self.new_graph_pairs
+                                    .entry(addr)
+                                    .or_insert_with(|| (protocol, vec![]))
+                                    .1
+                                    .push(pair);
                             }
                         }
                     });
@@ -669,7 +714,11 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
                                 .graph_manager
                                 .remove_pair_graph_address(bad_edge.pair, bad_edge.pool_address)
                             {
-                                self.new_graph_pairs.insert(addr, (protocol, pair));
+                                self.new_graph_pairs
+                                    .entry(addr)
+                                    .or_insert_with(|| (protocol, vec![]))
+                                    .1
+                                    .push(pair);
                             }
                         }
                     });
@@ -1099,7 +1148,7 @@ impl<T: TracingProvider> BrontesBatchPricer<T> {
                     }
                     (&first_price - &last_price) / first_price
                 };
-                if block_movement > MAX_BLOCK_MOVEMENT {
+                if block_movement > self.max_block_movement {
                     Some(key)
                 } else {
                     None
@@ -1239,8 +1288,10 @@ impl<T: TracingProvider> Stream for BrontesBatchPricer<T> {
                             ..
                         }) => {
                             if protocol.has_state_updater() {
-                                self.new_graph_pairs
-                                    .insert(pool_address, (protocol, Pair(tokens[0], tokens[1])));
+                                self.new_graph_pairs.insert(
+                                    pool_address,
+                                    (protocol, Pair::from_pool_tokens(&tokens)),
+                                );
                             };
                             Some(PollResult::DiscoveredPool)
                         }
@@ -0,0 +1,120 @@
+pub mod stable;
+pub mod weighted;
+
+use std::sync::Arc;
+
+use alloy_primitives::{Address, FixedBytes};
+use alloy_sol_macro::sol;
+use brontes_types::{traits::TracingProvider, ToScaledRational};
+use malachite::Rational;
+
+use self::{stable::BalancerStablePool, weighted::BalancerWeightedPool};
+use crate::{errors::AmmError, make_call_request, types::PoolVariants, uniswap_v2::IErc20};
+
+sol!(
+    interface IBalancerVault {
+        function getPoolTokens(bytes32 poolId) external view returns (
+            address[] tokens,
+            uint256[] balances,
+            uint256 lastChangeBlock
+        );
+    }
+);
+
+sol!(
+    interface IBalancerV2Pool {
+        function getPoolId() external view returns (bytes32);
+        function getSwapFeePercentage() external view returns (uint256);
+    }
+);
+
+/// Token state of a balancer v2 pool as held by the vault.
+pub struct BalancerV2PoolTokens {
+    pub pool_id:   FixedBytes<32>,
+    pub tokens:    Vec<Address>,
+    pub decimals:  Vec<u8>,
+    pub balances:  Vec<Rational>,
+    /// composable pools register their own bpt as a token, it is removed from
+    /// `tokens` and its position in the vault token list is kept here
+    pub bpt_index: Option<usize>,
+}
+
+/// All balancer v2 pools hold their tokens in the vault, pool state is read
+/// from there keyed by the pool id.
+pub async fn load_v2_pool_tokens<T: TracingProvider>(
+    address: Address,
+    block: u64,
+    provider: &Arc<T>,
+    vault: Address,
+) -> Result<BalancerV2PoolTokens, AmmError> {
+    let pool_id =
+        make_call_request(IBalancerV2Pool::getPoolIdCall {}, provider, address, Some(block))
+            .await?
+            ._0;
+
+    let pool_tokens = make_call_request(
+        IBalancerVault::getPoolTokensCall { poolId: pool_id },
+        provider,
+        vault,
+        Some(block),
+    )
+    .await?;
+
+    let bpt_index = pool_tokens.tokens.iter().position(|t| *t == address);
+
+    let mut tokens = Vec::with_capacity(pool_tokens.tokens.len());
+    let mut decimals = Vec::with_capacity(pool_tokens.tokens.len());
+    let mut balances = Vec::with_capacity(pool_tokens.tokens.len());
+
+    for (token, balance) in pool_tokens
+        .tokens
+        .into_iter()
+        .zip(pool_tokens.balances)
+        .filter(|(token, _)| *token != address)
+    {
+        let dec = make_call_request(IErc20::decimalsCall {}, provider, token, Some(block))
+            .await?
+            ._0;
+
+        tokens.push(token);
+        decimals.push(dec);
+        balances.push(balance.to_scaled_rational(dec));
+    }
+
+    if tokens.len() < 2 {
+        return Err(AmmError::NoStateError(address))
+    }
+
+    Ok(BalancerV2PoolTokens { pool_id, tokens, decimals, balances, bpt_index })
+}
+
+/// Loads a balancer v2 pool. The vault doesn't tell us which math a pool
+/// uses, so we probe for the weighted pool getters first and fall back to the
+/// stable pool ones.
+pub async fn load_v2_pool<T: TracingProvider>(
+    address: Address,
+    provider: Arc<T>,
+    block: u64,
+    vault: Address,
+) -> Result<PoolVariants, AmmError> {
+    if let Ok(pool) =
+        BalancerWeightedPool::new_load_v2_on_block(address, provider.clone(), block, vault).await
+    {
+        return Ok(PoolVariants::BalancerWeighted(Box::new(pool)))
+    }
+
+    BalancerStablePool::new_load_on_block(address, provider, block, vault)
+        .await
+        .map(|pool| PoolVariants::BalancerStable(Box::new(pool)))
+}
+
+/// Loads a balancer v1 pool, these are all weighted pools.
+pub async fn load_v1_pool<T: TracingProvider>(
+    address: Address,
+    provider: Arc<T>,
+    block: u64,
+) -> Result<PoolVariants, AmmError> {
+    BalancerWeightedPool::new_load_v1_on_block(address, provider, block)
+        .await
+        .map(|pool| PoolVariants::BalancerWeighted(Box::new(pool)))
+}
@@ -0,0 +1,447 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, FixedBytes, Log, B256, U256};
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolEvent;
+use async_trait::async_trait;
+use brontes_types::{normalized_actions::Action, traits::TracingProvider, ToFloatNearest};
+use malachite::{num::basic::traits::Zero, Rational};
+use serde::{Deserialize, Serialize};
+
+use super::{load_v2_pool_tokens, IBalancerV2Pool};
+use crate::{
+    curve::{estimate_timestamp, stable_swap::CurveStableSwapPool},
+    errors::{AmmError, ArithmeticError, EventLogError},
+    make_call_request,
+    protocols::{apply_action_to_balances, token_index, u256_to_f64},
+    UpdatableProtocol,
+};
+
+sol!(
+    interface IBalancerStablePool {
+        function getAmplificationParameter() external view returns (
+            uint256 value,
+            bool isUpdating,
+            uint256 precision
+        );
+        function getScalingFactors() external view returns (uint256[]);
+
+        event AmpUpdateStarted(
+            uint256 startValue,
+            uint256 endValue,
+            uint256 startTime,
+            uint256 endTime
+        );
+        event AmpUpdateStopped(uint256 currentValue);
+    }
+);
+
+/// The pools don't expose an ongoing amplification update, only its current
+/// value. The update is read from the packed amplification data in storage,
+/// which is in one of the first slots for all stable pool versions.
+const MAX_AMP_DATA_SLOT: u64 = 32;
+
+/// State of a balancer v2 stable pool (stable, meta stable and composable
+/// stable pools).
+///
+/// Balancer's StableMath is the same invariant as curve's stableswap, so the
+/// solver is shared with [`CurveStableSwapPool`]. Rate providers (e.g wstETH)
+/// are applied through `rates`, which are the scaling factors without the
+/// decimal adjustment. Amplification updates are followed the same way as
+/// curve's `A` ramps.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct BalancerStablePool {
+    pub address:          Address,
+    pub pool_id:          FixedBytes<32>,
+    pub tokens:           Vec<Address>,
+    pub token_decimals:   Vec<u8>,
+    pub balances:         Vec<Rational>,
+    /// token rates, 1 for tokens without a rate provider
+    pub rates:            Vec<f64>,
+    /// amplification update, in `amp_precision` units. start and end are the
+    /// same when the amplification isn't being updated
+    pub start_amp:        U256,
+    pub end_amp:          U256,
+    pub start_amp_time:   u64,
+    pub end_amp_time:     u64,
+    pub amp_precision:    U256,
+    /// swap fee with 1e18 precision
+    pub swap_fee:         U256,
+    /// block and timestamp the state was loaded at, used to estimate the
+    /// timestamp of later blocks
+    pub loaded_block:     u64,
+    pub loaded_timestamp: u64,
+    pub current_block:    u64,
+}
+
+#[async_trait]
+impl UpdatableProtocol for BalancerStablePool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        self.tokens.clone()
+    }
+
+    /// prices the base token against the first other token in the pool. use
+    /// [`BalancerStablePool::calculate_price_for_pair`] when the pool has
+    /// more than two tokens
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        let quote = self
+            .tokens
+            .iter()
+            .find(|t| **t != base_token)
+            .copied()
+            .unwrap_or_default();
+
+        self.calculate_price_for_pair(base_token, quote)
+    }
+
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        apply_action_to_balances(self.address, &self.tokens, &mut self.balances, action)
+    }
+
+    fn sync_from_log(&mut self, log: Log) -> Result<(), AmmError> {
+        let event_signature = log.topics()[0];
+
+        if event_signature == IBalancerStablePool::AmpUpdateStarted::SIGNATURE_HASH {
+            let update = IBalancerStablePool::AmpUpdateStarted::decode_log_data(&log, false)?;
+            self.start_amp = update.startValue;
+            self.end_amp = update.endValue;
+            self.start_amp_time = update.startTime.saturating_to();
+            self.end_amp_time = update.endTime.saturating_to();
+        } else if event_signature == IBalancerStablePool::AmpUpdateStopped::SIGNATURE_HASH {
+            let stop = IBalancerStablePool::AmpUpdateStopped::decode_log_data(&log, false)?;
+            let timestamp =
+                estimate_timestamp(self.loaded_block, self.loaded_timestamp, self.current_block);
+            self.start_amp = stop.currentValue;
+            self.end_amp = stop.currentValue;
+            self.start_amp_time = timestamp;
+            self.end_amp_time = timestamp;
+        } else {
+            return Err(AmmError::EventLogError(EventLogError::InvalidEventSignature))
+        }
+
+        Ok(())
+    }
+}
+
+impl BalancerStablePool {
+    pub async fn new_load_on_block<T: TracingProvider>(
+        address: Address,
+        provider: Arc<T>,
+        block: u64,
+        vault: Address,
+    ) -> Result<Self, AmmError> {
+        let amp = make_call_request(
+            IBalancerStablePool::getAmplificationParameterCall {},
+            &provider,
+            address,
+            Some(block),
+        )
+        .await?;
+
+        let loaded_timestamp = provider
+            .header_by_number(block)
+            .await?
+            .map(|header| header.timestamp)
+            .ok_or(AmmError::BlockNumberNotFound)?;
+
+        let (start_amp, end_amp, start_amp_time, end_amp_time) = if amp.isUpdating {
+            Self::load_amp_update(address, block, &provider, amp.value, loaded_timestamp)
+                .await
+                .unwrap_or((amp.value, amp.value, 0, 0))
+        } else {
+            (amp.value, amp.value, 0, 0)
+        };
+
+        let pool_tokens = load_v2_pool_tokens(address, block, &provider, vault).await?;
+        let swap_fee = make_call_request(
+            IBalancerV2Pool::getSwapFeePercentageCall {},
+            &provider,
+            address,
+            Some(block),
+        )
+        .await?
+        ._0;
+
+        // older stable pools don't expose their scaling factors, these don't
+        // have rate providers either
+        let rates = match make_call_request(
+            IBalancerStablePool::getScalingFactorsCall {},
+            &provider,
+            address,
+            Some(block),
+        )
+        .await
+        {
+            Ok(factors) => {
+                let mut factors = factors._0;
+                if let Some(bpt_index) = pool_tokens.bpt_index {
+                    if bpt_index < factors.len() {
+                        factors.remove(bpt_index);
+                    }
+                }
+                if factors.len() != pool_tokens.tokens.len() {
+                    return Err(AmmError::PoolDataError)
+                }
+
+                factors
+                    .into_iter()
+                    .zip(pool_tokens.decimals.iter())
+                    .map(|(factor, dec)| Self::scaling_factor_to_rate(factor, *dec))
+                    .collect::<Result<_, _>>()?
+            }
+            Err(_) => vec![1.0; pool_tokens.tokens.len()],
+        };
+
+        let pool = Self {
+            address,
+            pool_id: pool_tokens.pool_id,
+            tokens: pool_tokens.tokens,
+            token_decimals: pool_tokens.decimals,
+            balances: pool_tokens.balances,
+            rates,
+            start_amp,
+            end_amp,
+            start_amp_time,
+            end_amp_time,
+            amp_precision: amp.precision.max(U256::from(1)),
+            swap_fee,
+            loaded_block: block,
+            loaded_timestamp,
+            current_block: block,
+        };
+
+        if !pool.data_is_populated() {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        Ok(pool)
+    }
+
+    /// Finds the ongoing amplification update in the pool storage. A slot is
+    /// only taken if the update it holds gives the current amplification.
+    async fn load_amp_update<T: TracingProvider>(
+        address: Address,
+        block: u64,
+        provider: &Arc<T>,
+        amp: U256,
+        timestamp: u64,
+    ) -> Option<(U256, U256, u64, u64)> {
+        for slot in 0..MAX_AMP_DATA_SLOT {
+            let Ok(Some(word)) = provider
+                .get_storage(Some(block), address, B256::from(U256::from(slot)))
+                .await
+            else {
+                continue
+            };
+
+            if let Some(update) = Self::decode_amp_update(word, amp, timestamp) {
+                return Some(update)
+            }
+        }
+
+        None
+    }
+
+    /// Decodes the packed amplification data, four 64 bit values of start
+    /// value, end value, start time and end time. Returns `None` if the word
+    /// isn't an update that is ongoing at `timestamp` with `amp` as its
+    /// current value.
+    fn decode_amp_update(word: U256, amp: U256, timestamp: u64) -> Option<(U256, U256, u64, u64)> {
+        let mask = U256::from(u64::MAX);
+        let start_amp = word & mask;
+        let end_amp = (word >> 64) & mask;
+        let start_time: u64 = ((word >> 128) & mask).saturating_to();
+        let end_time: u64 = (word >> 192).saturating_to();
+
+        if start_amp == end_amp
+            || start_amp.is_zero()
+            || end_amp.is_zero()
+            || !(start_time <= timestamp && timestamp < end_time)
+        {
+            return None
+        }
+
+        let elapsed = U256::from(timestamp - start_time);
+        let duration = U256::from(end_time - start_time);
+        let current = if end_amp > start_amp {
+            start_amp + (end_amp - start_amp) * elapsed / duration
+        } else {
+            start_amp - (start_amp - end_amp) * elapsed / duration
+        };
+
+        (current.max(amp) - current.min(amp) <= U256::from(1))
+            .then_some((start_amp, end_amp, start_time, end_time))
+    }
+
+    /// scaling factors are `10^(18 - decimals) * rate` with 1e18 precision.
+    fn scaling_factor_to_rate(factor: U256, decimals: u8) -> Result<f64, ArithmeticError> {
+        Ok(u256_to_f64(factor)? / 10f64.powi(36 - decimals as i32))
+    }
+
+    pub fn data_is_populated(&self) -> bool {
+        !(self.tokens.is_empty()
+            || self.end_amp.is_zero()
+            || self.rates.len() != self.tokens.len()
+            || self.balances.iter().any(|b| *b <= Rational::ZERO))
+    }
+
+    pub fn set_block(&mut self, block: u64) {
+        self.current_block = block;
+    }
+
+    /// amplification parameter at the current block, with the precision
+    /// removed. mirrors `_getAmplificationParameter` of the stable pools
+    pub fn amp(&self) -> Result<f64, ArithmeticError> {
+        let timestamp =
+            estimate_timestamp(self.loaded_block, self.loaded_timestamp, self.current_block);
+        let precision = u256_to_f64(self.amp_precision)?;
+        let start = u256_to_f64(self.start_amp)?;
+        let end = u256_to_f64(self.end_amp)?;
+
+        if timestamp >= self.end_amp_time || self.end_amp_time <= self.start_amp_time {
+            return Ok(end / precision)
+        }
+
+        let elapsed = timestamp.saturating_sub(self.start_amp_time) as f64;
+        let duration = (self.end_amp_time - self.start_amp_time) as f64;
+
+        Ok((start + (end - start) * elapsed / duration) / precision)
+    }
+
+    fn xp(&self) -> Vec<f64> {
+        self.balances
+            .iter()
+            .zip(self.rates.iter())
+            .map(|(b, rate)| b.clone().to_float() * rate)
+            .collect()
+    }
+
+    /// Spot price of `base` denominated in `quote`, excluding fees. The
+    /// marginal rate is computed on the rate adjusted balances and then
+    /// converted back to token units.
+    pub fn calculate_price_for_pair(
+        &self,
+        base: Address,
+        quote: Address,
+    ) -> Result<Rational, ArithmeticError> {
+        let i = token_index(&self.tokens, base)?;
+        let j = token_index(&self.tokens, quote)?;
+
+        let price = CurveStableSwapPool::spot_price(&self.xp(), self.amp()?, i, j)? * self.rates[i]
+            / self.rates[j];
+
+        Rational::try_from(price).map_err(|_| ArithmeticError::RoundingError)
+    }
+
+    pub fn get_tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
+        let balance = |token| {
+            token_index(&self.tokens, token)
+                .map(|i| self.balances[i].clone())
+                .unwrap_or_default()
+        };
+
+        (balance(base), balance(quote))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balanced_pool_follows_rates() {
+        let tokens = vec![Address::with_last_byte(1), Address::with_last_byte(2)];
+        // wstETH / WETH with a wstETH rate of 1.15, balanced in value
+        let pool = BalancerStablePool {
+            tokens: tokens.clone(),
+            token_decimals: vec![18, 18],
+            balances: vec![Rational::from(1_000_000), Rational::from(1_150_000)],
+            rates: vec![1.15, 1.0],
+            start_amp: U256::from(50_000),
+            end_amp: U256::from(50_000),
+            amp_precision: U256::from(1_000),
+            ..Default::default()
+        };
+
+        let price = pool
+            .calculate_price_for_pair(tokens[0], tokens[1])
+            .unwrap()
+            .to_float();
+
+        assert!((price - 1.15).abs() < 1e-9, "price: {price}");
+    }
+
+    fn ramping_pool() -> BalancerStablePool {
+        BalancerStablePool {
+            tokens: vec![Address::with_last_byte(1), Address::with_last_byte(2)],
+            token_decimals: vec![18, 18],
+            balances: vec![Rational::from(1_000_000), Rational::from(1_000_000)],
+            rates: vec![1.0, 1.0],
+            start_amp: U256::from(100_000),
+            end_amp: U256::from(200_000),
+            start_amp_time: 1_000,
+            end_amp_time: 1_000 + 100 * 12,
+            amp_precision: U256::from(1_000),
+            loaded_block: 10,
+            loaded_timestamp: 1_000,
+            current_block: 10,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_amp_update_is_followed() {
+        let mut pool = ramping_pool();
+        assert_eq!(pool.amp().unwrap(), 100.0);
+
+        pool.set_block(60);
+        assert_eq!(pool.amp().unwrap(), 150.0);
+
+        pool.set_block(200);
+        assert_eq!(pool.amp().unwrap(), 200.0);
+    }
+
+    #[test]
+    fn test_amp_update_stopped_by_log() {
+        let mut pool = ramping_pool();
+        pool.set_block(60);
+
+        let stop = IBalancerStablePool::AmpUpdateStopped { currentValue: U256::from(150_000) };
+        pool.sync_from_log(Log { address: pool.address, data: stop.encode_log_data() })
+            .unwrap();
+
+        pool.set_block(200);
+        assert_eq!(pool.amp().unwrap(), 150.0);
+    }
+
+    #[test]
+    fn test_decode_amp_update() {
+        let word = U256::from(100_000u64)
+            | U256::from(200_000u64) << 64
+            | U256::from(1_000u64) << 128
+            | U256::from(2_000u64) << 192;
+
+        assert_eq!(
+            BalancerStablePool::decode_amp_update(word, U256::from(150_000), 1_500),
+            Some((U256::from(100_000), U256::from(200_000), 1_000, 2_000))
+        );
+        // a slot that doesn't give the current amplification isn't taken
+        assert_eq!(BalancerStablePool::decode_amp_update(word, U256::from(120_000), 1_500), None);
+        // neither is an update that is already over
+        assert_eq!(BalancerStablePool::decode_amp_update(word, U256::from(200_000), 2_500), None);
+    }
+
+    #[test]
+    fn test_scaling_factor_overflow_is_an_error() {
+        assert_eq!(
+            BalancerStablePool::scaling_factor_to_rate(U256::from(10).pow(U256::from(18)), 18)
+                .unwrap(),
+            1.0
+        );
+        assert!(BalancerStablePool::scaling_factor_to_rate(U256::MAX, 18).is_err());
+    }
+}
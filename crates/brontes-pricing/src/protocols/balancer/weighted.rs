@@ -0,0 +1,263 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, FixedBytes, Log, U256};
+use alloy_sol_macro::sol;
+use async_trait::async_trait;
+use brontes_types::{normalized_actions::Action, traits::TracingProvider, ToScaledRational};
+use malachite::{num::basic::traits::Zero, Rational};
+use serde::{Deserialize, Serialize};
+
+use super::{load_v2_pool_tokens, IBalancerV2Pool};
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    make_call_request,
+    protocols::{apply_action_to_balances, token_index},
+    uniswap_v2::IErc20,
+    Protocol, UpdatableProtocol,
+};
+
+sol!(
+    interface IBalancerV1Pool {
+        function getCurrentTokens() external view returns (address[]);
+        function getBalance(address token) external view returns (uint256);
+        function getNormalizedWeight(address token) external view returns (uint256);
+        function getSwapFee() external view returns (uint256);
+    }
+);
+
+sol!(
+    interface IBalancerWeightedPool {
+        function getNormalizedWeights() external view returns (uint256[]);
+    }
+);
+
+/// State of a balancer weighted pool. Covers all balancer v1 pools as well as
+/// the v2 weighted pools (e.g the 80/20 governance token pools).
+///
+/// For v1 the pool holds its own balances. For v2 the balances live in the
+/// vault under `pool_id`, `pool_id` is zero for v1 pools.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BalancerWeightedPool {
+    pub address:        Address,
+    pub protocol:       Protocol,
+    pub pool_id:        FixedBytes<32>,
+    pub tokens:         Vec<Address>,
+    pub token_decimals: Vec<u8>,
+    pub balances:       Vec<Rational>,
+    /// normalized weights, these sum to 1
+    pub weights:        Vec<Rational>,
+    /// swap fee with 1e18 precision
+    pub swap_fee:       U256,
+}
+
+#[async_trait]
+impl UpdatableProtocol for BalancerWeightedPool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        self.tokens.clone()
+    }
+
+    /// prices the base token against the first other token in the pool. use
+    /// [`BalancerWeightedPool::calculate_price_for_pair`] when the pool has
+    /// more than two tokens
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        let quote = self
+            .tokens
+            .iter()
+            .find(|t| **t != base_token)
+            .copied()
+            .unwrap_or_default();
+
+        self.calculate_price_for_pair(base_token, quote)
+    }
+
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        apply_action_to_balances(self.address, &self.tokens, &mut self.balances, action)
+    }
+
+    fn sync_from_log(&mut self, _log: Log) -> Result<(), AmmError> {
+        Err(AmmError::EventLogError(EventLogError::InvalidEventSignature))
+    }
+}
+
+impl BalancerWeightedPool {
+    pub async fn new_load_v1_on_block<T: TracingProvider>(
+        address: Address,
+        provider: Arc<T>,
+        block: u64,
+    ) -> Result<Self, AmmError> {
+        let tokens = make_call_request(
+            IBalancerV1Pool::getCurrentTokensCall {},
+            &provider,
+            address,
+            Some(block),
+        )
+        .await?
+        ._0;
+        let swap_fee =
+            make_call_request(IBalancerV1Pool::getSwapFeeCall {}, &provider, address, Some(block))
+                .await?
+                ._0;
+
+        let mut token_decimals = Vec::with_capacity(tokens.len());
+        let mut balances = Vec::with_capacity(tokens.len());
+        let mut weights = Vec::with_capacity(tokens.len());
+
+        for token in &tokens {
+            let dec = make_call_request(IErc20::decimalsCall {}, &provider, *token, Some(block))
+                .await?
+                ._0;
+            let balance = make_call_request(
+                IBalancerV1Pool::getBalanceCall { token: *token },
+                &provider,
+                address,
+                Some(block),
+            )
+            .await?
+            ._0;
+            let weight = make_call_request(
+                IBalancerV1Pool::getNormalizedWeightCall { token: *token },
+                &provider,
+                address,
+                Some(block),
+            )
+            .await?
+            ._0;
+
+            token_decimals.push(dec);
+            balances.push(balance.to_scaled_rational(dec));
+            weights.push(weight.to_scaled_rational(18));
+        }
+
+        let pool = Self {
+            address,
+            protocol: Protocol::BalancerV1,
+            pool_id: FixedBytes::ZERO,
+            tokens,
+            token_decimals,
+            balances,
+            weights,
+            swap_fee,
+        };
+
+        if !pool.data_is_populated() {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        Ok(pool)
+    }
+
+    pub async fn new_load_v2_on_block<T: TracingProvider>(
+        address: Address,
+        provider: Arc<T>,
+        block: u64,
+        vault: Address,
+    ) -> Result<Self, AmmError> {
+        let weights = make_call_request(
+            IBalancerWeightedPool::getNormalizedWeightsCall {},
+            &provider,
+            address,
+            Some(block),
+        )
+        .await?
+        ._0
+        .into_iter()
+        .map(|w| w.to_scaled_rational(18))
+        .collect::<Vec<_>>();
+
+        let pool_tokens = load_v2_pool_tokens(address, block, &provider, vault).await?;
+        let swap_fee = make_call_request(
+            IBalancerV2Pool::getSwapFeePercentageCall {},
+            &provider,
+            address,
+            Some(block),
+        )
+        .await?
+        ._0;
+
+        if weights.len() != pool_tokens.tokens.len() {
+            return Err(AmmError::PoolDataError)
+        }
+
+        let pool = Self {
+            address,
+            protocol: Protocol::BalancerV2,
+            pool_id: pool_tokens.pool_id,
+            tokens: pool_tokens.tokens,
+            token_decimals: pool_tokens.decimals,
+            balances: pool_tokens.balances,
+            weights,
+            swap_fee,
+        };
+
+        if !pool.data_is_populated() {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        Ok(pool)
+    }
+
+    pub fn data_is_populated(&self) -> bool {
+        !(self.tokens.is_empty()
+            || self.weights.len() != self.tokens.len()
+            || self.weights.iter().any(|w| *w == Rational::ZERO)
+            || self.balances.iter().any(|b| *b <= Rational::ZERO))
+    }
+
+    /// Spot price of `base` denominated in `quote`, excluding fees.
+    ///
+    /// The weighted invariant is `prod(B_i^W_i)`, the marginal rate between
+    /// two tokens is `(B_quote / W_quote) / (B_base / W_base)`.
+    pub fn calculate_price_for_pair(
+        &self,
+        base: Address,
+        quote: Address,
+    ) -> Result<Rational, ArithmeticError> {
+        let i = token_index(&self.tokens, base)?;
+        let j = token_index(&self.tokens, quote)?;
+
+        if self.balances[i] <= Rational::ZERO || self.weights[j] == Rational::ZERO {
+            return Err(ArithmeticError::YIsZero)
+        }
+
+        Ok((&self.balances[j] * &self.weights[i]) / (&self.balances[i] * &self.weights[j]))
+    }
+
+    pub fn get_tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
+        let balance = |token| {
+            token_index(&self.tokens, token)
+                .map(|i| self.balances[i].clone())
+                .unwrap_or_default()
+        };
+
+        (balance(base), balance(quote))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eighty_twenty_pool_price() {
+        let tokens = vec![Address::with_last_byte(1), Address::with_last_byte(2)];
+        // 80% token0, 20% token1. 800 token0 at a price of 1 token1 per 4 token0
+        // means 200 token1 worth of token0, which matches 50 token1 at 20%.
+        let pool = BalancerWeightedPool {
+            tokens: tokens.clone(),
+            token_decimals: vec![18, 18],
+            balances: vec![Rational::from(800), Rational::from(50)],
+            weights: vec![Rational::from_signeds(4, 5), Rational::from_signeds(1, 5)],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            pool.calculate_price_for_pair(tokens[0], tokens[1]).unwrap(),
+            Rational::from_signeds(1, 4)
+        );
+        assert_eq!(pool.calculate_price_for_pair(tokens[1], tokens[0]).unwrap(), Rational::from(4));
+    }
+}
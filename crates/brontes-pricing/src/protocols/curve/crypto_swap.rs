@@ -0,0 +1,533 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, Log, U256};
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolEvent;
+use async_trait::async_trait;
+use brontes_types::{normalized_actions::Action, traits::TracingProvider, ToFloatNearest};
+use malachite::Rational;
+use serde::{Deserialize, Serialize};
+
+use super::{estimate_timestamp, load_pool_tokens};
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    make_call_request,
+    protocols::{apply_action_to_balances, token_index, u256_to_f64},
+    Protocol, UpdatableProtocol,
+};
+
+sol!(
+    interface ICurveCryptoSwap {
+        function A() external view returns (uint256);
+        function gamma() external view returns (uint256);
+        function price_scale() external view returns (uint256);
+        function initial_A_gamma() external view returns (uint256);
+        function future_A_gamma() external view returns (uint256);
+        function initial_A_gamma_time() external view returns (uint256);
+        function future_A_gamma_time() external view returns (uint256);
+
+        event RampAgamma(
+            uint256 initial_A,
+            uint256 future_A,
+            uint256 initial_gamma,
+            uint256 future_gamma,
+            uint256 initial_time,
+            uint256 future_time
+        );
+        event StopRampA(uint256 current_A, uint256 current_gamma, uint256 time);
+    }
+);
+
+sol!(
+    interface ICurveTriCrypto {
+        function price_scale(uint256 k) external view returns (uint256);
+
+        event TokenExchange(
+            address indexed buyer,
+            uint256 sold_id,
+            uint256 tokens_sold,
+            uint256 bought_id,
+            uint256 tokens_bought,
+            uint256 fee,
+            uint256 packed_price_scale
+        );
+        event AddLiquidity(
+            address indexed provider,
+            uint256[3] token_amounts,
+            uint256 fee,
+            uint256 token_supply,
+            uint256 packed_price_scale
+        );
+        event RemoveLiquidityOne(
+            address indexed provider,
+            uint256 token_amount,
+            uint256 coin_index,
+            uint256 coin_amount,
+            uint256 approx_fee,
+            uint256 packed_price_scale
+        );
+    }
+);
+
+/// A in cryptoswap pools is stored as `A * N^N * A_MULTIPLIER`
+const A_MULTIPLIER: f64 = 10_000.0;
+const MAX_ITERATIONS: usize = 255;
+/// A and gamma are packed into one word, A in the upper 128 bits
+const PACKED_SLOT_BITS: usize = 128;
+
+/// State of a curve cryptoswap pool (two coin cryptoswap and tricrypto).
+///
+/// The pool re-pegs internally through `price_scale`. Tricrypto-ng pools
+/// emit the new scale with every exchange and single sided liquidity change,
+/// which we follow. The legacy two coin pools don't emit it, for these the
+/// scale stays at the value it was loaded with and the spot price follows
+/// the balances through the invariant.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CurveCryptoSwapPool {
+    pub address:              Address,
+    pub protocol:             Protocol,
+    pub tokens:               Vec<Address>,
+    pub token_decimals:       Vec<u8>,
+    pub balances:             Vec<Rational>,
+    /// A and gamma ramp. A is raw, including the `N^N * A_MULTIPLIER`
+    /// factor, gamma has 1e18 precision
+    pub initial_a:            U256,
+    pub future_a:             U256,
+    pub initial_gamma:        U256,
+    pub future_gamma:         U256,
+    pub initial_a_gamma_time: u64,
+    pub future_a_gamma_time:  u64,
+    /// price of each coin in terms of coin 0, coin 0 is always 1
+    pub price_scale:          Vec<f64>,
+    /// block and timestamp the state was loaded at, used to estimate the
+    /// timestamp of later blocks
+    pub loaded_block:         u64,
+    pub loaded_timestamp:     u64,
+    pub current_block:        u64,
+}
+
+#[async_trait]
+impl UpdatableProtocol for CurveCryptoSwapPool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        self.tokens.clone()
+    }
+
+    /// prices the base token against the first other coin in the pool. use
+    /// [`CurveCryptoSwapPool::calculate_price_for_pair`] when the pool has
+    /// more than two coins
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        let quote = self
+            .tokens
+            .iter()
+            .find(|t| **t != base_token)
+            .copied()
+            .unwrap_or_default();
+
+        self.calculate_price_for_pair(base_token, quote)
+    }
+
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        apply_action_to_balances(self.address, &self.tokens, &mut self.balances, action)
+    }
+
+    fn sync_from_log(&mut self, log: Log) -> Result<(), AmmError> {
+        let event_signature = log.topics()[0];
+
+        if event_signature == ICurveCryptoSwap::RampAgamma::SIGNATURE_HASH {
+            let ramp = ICurveCryptoSwap::RampAgamma::decode_log_data(&log, false)?;
+            self.initial_a = ramp.initial_A;
+            self.future_a = ramp.future_A;
+            self.initial_gamma = ramp.initial_gamma;
+            self.future_gamma = ramp.future_gamma;
+            self.initial_a_gamma_time = ramp.initial_time.saturating_to();
+            self.future_a_gamma_time = ramp.future_time.saturating_to();
+        } else if event_signature == ICurveCryptoSwap::StopRampA::SIGNATURE_HASH {
+            let stop = ICurveCryptoSwap::StopRampA::decode_log_data(&log, false)?;
+            self.initial_a = stop.current_A;
+            self.future_a = stop.current_A;
+            self.initial_gamma = stop.current_gamma;
+            self.future_gamma = stop.current_gamma;
+            self.initial_a_gamma_time = stop.time.saturating_to();
+            self.future_a_gamma_time = stop.time.saturating_to();
+        } else if event_signature == ICurveTriCrypto::TokenExchange::SIGNATURE_HASH {
+            let exchange = ICurveTriCrypto::TokenExchange::decode_log_data(&log, false)?;
+            self.unpack_price_scale(exchange.packed_price_scale)?;
+        } else if event_signature == ICurveTriCrypto::AddLiquidity::SIGNATURE_HASH {
+            let add = ICurveTriCrypto::AddLiquidity::decode_log_data(&log, false)?;
+            self.unpack_price_scale(add.packed_price_scale)?;
+        } else if event_signature == ICurveTriCrypto::RemoveLiquidityOne::SIGNATURE_HASH {
+            let remove = ICurveTriCrypto::RemoveLiquidityOne::decode_log_data(&log, false)?;
+            self.unpack_price_scale(remove.packed_price_scale)?;
+        } else {
+            return Err(AmmError::EventLogError(EventLogError::InvalidEventSignature))
+        }
+
+        Ok(())
+    }
+}
+
+impl CurveCryptoSwapPool {
+    pub async fn new_load_on_block<T: TracingProvider>(
+        address: Address,
+        protocol: Protocol,
+        provider: Arc<T>,
+        block: u64,
+        wrapped_native: Address,
+    ) -> Result<Self, AmmError> {
+        let pool_tokens = load_pool_tokens(address, block, &provider, wrapped_native).await?;
+
+        let a = make_call_request(ICurveCryptoSwap::ACall {}, &provider, address, Some(block))
+            .await?
+            ._0;
+        let gamma =
+            make_call_request(ICurveCryptoSwap::gammaCall {}, &provider, address, Some(block))
+                .await?
+                ._0;
+        let (initial_a, future_a, initial_gamma, future_gamma, initial_time, future_time) =
+            Self::load_ramp(address, block, &provider)
+                .await
+                .unwrap_or((a, a, gamma, gamma, 0, 0));
+
+        let mut price_scale = vec![1.0];
+        if pool_tokens.tokens.len() == 2 {
+            let scale = make_call_request(
+                ICurveCryptoSwap::price_scaleCall {},
+                &provider,
+                address,
+                Some(block),
+            )
+            .await?
+            ._0;
+            price_scale.push(Self::scale_to_float(scale)?);
+        } else {
+            for k in 0..pool_tokens.tokens.len() - 1 {
+                let scale = make_call_request(
+                    ICurveTriCrypto::price_scaleCall { k: U256::from(k) },
+                    &provider,
+                    address,
+                    Some(block),
+                )
+                .await?
+                ._0;
+                price_scale.push(Self::scale_to_float(scale)?);
+            }
+        }
+
+        let loaded_timestamp = provider
+            .header_by_number(block)
+            .await?
+            .map(|header| header.timestamp)
+            .ok_or(AmmError::BlockNumberNotFound)?;
+
+        let pool = Self {
+            address,
+            protocol,
+            tokens: pool_tokens.tokens,
+            token_decimals: pool_tokens.decimals,
+            balances: pool_tokens.balances,
+            initial_a,
+            future_a,
+            initial_gamma,
+            future_gamma,
+            initial_a_gamma_time: initial_time,
+            future_a_gamma_time: future_time,
+            price_scale,
+            loaded_block: block,
+            loaded_timestamp,
+            current_block: block,
+        };
+
+        if !pool.data_is_populated() {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        Ok(pool)
+    }
+
+    #[allow(clippy::type_complexity)]
+    async fn load_ramp<T: TracingProvider>(
+        address: Address,
+        block: u64,
+        provider: &Arc<T>,
+    ) -> Result<(U256, U256, U256, U256, u64, u64), AmmError> {
+        let initial = make_call_request(
+            ICurveCryptoSwap::initial_A_gammaCall {},
+            provider,
+            address,
+            Some(block),
+        )
+        .await?
+        ._0;
+        let future = make_call_request(
+            ICurveCryptoSwap::future_A_gammaCall {},
+            provider,
+            address,
+            Some(block),
+        )
+        .await?
+        ._0;
+        let initial_time = make_call_request(
+            ICurveCryptoSwap::initial_A_gamma_timeCall {},
+            provider,
+            address,
+            Some(block),
+        )
+        .await?
+        ._0;
+        let future_time = make_call_request(
+            ICurveCryptoSwap::future_A_gamma_timeCall {},
+            provider,
+            address,
+            Some(block),
+        )
+        .await?
+        ._0;
+
+        let (initial_a, initial_gamma) = Self::unpack_a_gamma(initial);
+        let (future_a, future_gamma) = Self::unpack_a_gamma(future);
+
+        Ok((
+            initial_a,
+            future_a,
+            initial_gamma,
+            future_gamma,
+            initial_time.saturating_to(),
+            future_time.saturating_to(),
+        ))
+    }
+
+    fn unpack_a_gamma(packed: U256) -> (U256, U256) {
+        (packed >> PACKED_SLOT_BITS, packed & U256::from(u128::MAX))
+    }
+
+    /// tricrypto-ng packs the price scale of every coin but coin 0 into one
+    /// word, 128 bits each
+    fn unpack_price_scale(&mut self, packed: U256) -> Result<(), ArithmeticError> {
+        for k in 1..self.price_scale.len() {
+            let scale = (packed >> (PACKED_SLOT_BITS * (k - 1))) & U256::from(u128::MAX);
+            self.price_scale[k] = Self::scale_to_float(scale)?;
+        }
+
+        Ok(())
+    }
+
+    /// `price_scale` is 1e18 precision and already adjusted for decimals
+    fn scale_to_float(scale: U256) -> Result<f64, ArithmeticError> {
+        Ok(u256_to_f64(scale)? / 1e18)
+    }
+
+    pub fn set_block(&mut self, block: u64) {
+        self.current_block = block;
+    }
+
+    pub fn data_is_populated(&self) -> bool {
+        !(self.tokens.is_empty()
+            || self.future_a.is_zero()
+            || self.future_gamma.is_zero()
+            || self.price_scale.len() != self.tokens.len()
+            || self.balances.iter().any(|b| *b <= Rational::from(0)))
+    }
+
+    /// balances in coin 0 terms, normalized so that the mean is 1 to keep the
+    /// invariant well conditioned.
+    fn xp(&self) -> Vec<f64> {
+        let xp = self
+            .balances
+            .iter()
+            .zip(self.price_scale.iter())
+            .map(|(b, p)| b.clone().to_float() * p)
+            .collect::<Vec<_>>();
+        let mean = xp.iter().sum::<f64>() / xp.len() as f64;
+
+        xp.into_iter().map(|x| x / mean).collect()
+    }
+
+    /// A and gamma at the current block, mirrors `_A_gamma()` of the
+    /// cryptoswap contracts
+    fn amp_gamma(&self) -> Result<(f64, f64), ArithmeticError> {
+        let n = self.tokens.len() as i32;
+        let n_n = (n as f64).powi(n);
+
+        let timestamp =
+            estimate_timestamp(self.loaded_block, self.loaded_timestamp, self.current_block);
+        let mut a = u256_to_f64(self.future_a)?;
+        let mut gamma = u256_to_f64(self.future_gamma)?;
+
+        if timestamp < self.future_a_gamma_time
+            && self.future_a_gamma_time > self.initial_a_gamma_time
+        {
+            let a0 = u256_to_f64(self.initial_a)?;
+            let gamma0 = u256_to_f64(self.initial_gamma)?;
+            let elapsed = (timestamp.saturating_sub(self.initial_a_gamma_time)) as f64;
+            let duration = (self.future_a_gamma_time - self.initial_a_gamma_time) as f64;
+
+            a = a0 + (a - a0) * elapsed / duration;
+            gamma = gamma0 + (gamma - gamma0) * elapsed / duration;
+        }
+
+        Ok((a / (n_n * A_MULTIPLIER), gamma / 1e18))
+    }
+
+    /// the cryptoswap invariant
+    /// `K*D^(N-1)*S + prod(x) - K*D^N - (D/N)^N` where
+    /// `K0 = prod(x)*N^N/D^N` and `K = A*K0*gamma^2/(gamma+1-K0)^2`
+    fn invariant(xp: &[f64], d: f64, amp: f64, gamma: f64) -> f64 {
+        let n = xp.len() as f64;
+        let n_i = xp.len() as i32;
+        let s: f64 = xp.iter().sum();
+        let prod: f64 = xp.iter().product();
+
+        let k0 = prod * n.powi(n_i) / d.powi(n_i);
+        let g = gamma + 1.0 - k0;
+        let k = amp * k0 * gamma * gamma / (g * g);
+
+        k * d.powi(n_i - 1) * s + prod - k * d.powi(n_i) - (d / n).powi(n_i)
+    }
+
+    /// newton's method on D, starting from `N * geometric_mean(x)` like the
+    /// on-chain `newton_D`.
+    fn get_d(xp: &[f64], amp: f64, gamma: f64) -> Result<f64, ArithmeticError> {
+        let n = xp.len() as f64;
+        let mut d = n * xp.iter().product::<f64>().powf(1.0 / n);
+
+        for _ in 0..MAX_ITERATIONS {
+            let h = d * 1e-7;
+            let f = Self::invariant(xp, d, amp, gamma);
+            let f_prime = (Self::invariant(xp, d + h, amp, gamma)
+                - Self::invariant(xp, d - h, amp, gamma))
+                / (2.0 * h);
+
+            if f_prime == 0.0 || !f_prime.is_finite() {
+                break
+            }
+
+            let d_next = d - f / f_prime;
+            if (d_next - d).abs() <= d * 1e-12 {
+                return Ok(d_next)
+            }
+            d = d_next;
+        }
+
+        Err(ArithmeticError::InvariantDidNotConverge)
+    }
+
+    /// Spot price of `base` denominated in `quote`, excluding fees. The
+    /// marginal rate in the scaled space is `dF/dx_base / dF/dx_quote` which
+    /// we then convert back with the price scale of both coins.
+    pub fn calculate_price_for_pair(
+        &self,
+        base: Address,
+        quote: Address,
+    ) -> Result<Rational, ArithmeticError> {
+        let i = token_index(&self.tokens, base)?;
+        let j = token_index(&self.tokens, quote)?;
+
+        let xp = self.xp();
+        let (amp, gamma) = self.amp_gamma()?;
+        let d = Self::get_d(&xp, amp, gamma)?;
+
+        let partial = |idx: usize| {
+            let h = xp[idx] * 1e-7;
+            let mut up = xp.clone();
+            let mut down = xp.clone();
+            up[idx] += h;
+            down[idx] -= h;
+
+            (Self::invariant(&up, d, amp, gamma) - Self::invariant(&down, d, amp, gamma))
+                / (2.0 * h)
+        };
+
+        let price = partial(i) / partial(j) * self.price_scale[i] / self.price_scale[j];
+        if !price.is_finite() || price <= 0.0 {
+            return Err(ArithmeticError::InvariantDidNotConverge)
+        }
+
+        Rational::try_from(price).map_err(|_| ArithmeticError::RoundingError)
+    }
+
+    pub fn get_tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
+        let balance = |token| {
+            token_index(&self.tokens, token)
+                .map(|i| self.balances[i].clone())
+                .unwrap_or_default()
+        };
+
+        (balance(base), balance(quote))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_follows_price_scale_when_balanced() {
+        let tokens = vec![Address::with_last_byte(1), Address::with_last_byte(2)];
+        // 1 token1 == 2000 token0, pool balanced in value
+        let pool = CurveCryptoSwapPool {
+            tokens: tokens.clone(),
+            token_decimals: vec![18, 18],
+            balances: vec![Rational::from(2_000_000), Rational::from(1_000)],
+            future_a: U256::from(400_000 * 4 * 10_000u64),
+            future_gamma: U256::from(145_000_000_000_000u64),
+            price_scale: vec![1.0, 2000.0],
+            ..Default::default()
+        };
+
+        let price = pool
+            .calculate_price_for_pair(tokens[1], tokens[0])
+            .unwrap()
+            .to_float();
+
+        assert!((price - 2000.0).abs() / 2000.0 < 1e-6, "price: {price}");
+    }
+
+    #[test]
+    fn test_unpack_price_scale() {
+        let mut pool = CurveCryptoSwapPool { price_scale: vec![1.0; 3], ..Default::default() };
+        let e18 = U256::from(10u64.pow(18));
+        let packed = ((U256::from(2_000) * e18) << 128) + U256::from(30_000) * e18;
+
+        pool.unpack_price_scale(packed).unwrap();
+
+        assert_eq!(pool.price_scale, vec![1.0, 30_000.0, 2_000.0]);
+    }
+
+    #[test]
+    fn test_a_gamma_ramp() {
+        let mut pool = CurveCryptoSwapPool {
+            tokens: vec![Address::with_last_byte(1), Address::with_last_byte(2)],
+            initial_a: U256::from(4 * 10_000 * 100u64),
+            future_a: U256::from(4 * 10_000 * 200u64),
+            initial_gamma: U256::from(10u64.pow(16)),
+            future_gamma: U256::from(2 * 10u64.pow(16)),
+            initial_a_gamma_time: 1_000,
+            future_a_gamma_time: 1_000 + 100 * 12,
+            loaded_block: 10,
+            loaded_timestamp: 1_000,
+            ..Default::default()
+        };
+
+        pool.set_block(60);
+        assert_eq!(pool.amp_gamma().unwrap(), (150.0, 0.015));
+
+        pool.set_block(200);
+        assert_eq!(pool.amp_gamma().unwrap(), (200.0, 0.02));
+    }
+
+    #[test]
+    fn test_a_gamma_overflow_is_an_error() {
+        let pool = CurveCryptoSwapPool {
+            tokens: vec![Address::with_last_byte(1), Address::with_last_byte(2)],
+            future_a: U256::MAX,
+            future_gamma: U256::from(1),
+            ..Default::default()
+        };
+
+        assert!(pool.amp_gamma().is_err());
+    }
+}
@@ -0,0 +1,129 @@
+pub mod crypto_swap;
+pub mod stable_swap;
+
+use std::sync::Arc;
+
+use alloy_primitives::{Address, U256};
+use alloy_sol_macro::sol;
+use brontes_types::{constants::ETH_ADDRESS, traits::TracingProvider, ToScaledRational};
+use malachite::Rational;
+
+use super::make_call_request;
+use crate::{errors::AmmError, uniswap_v2::IErc20};
+
+/// Curve pools hold at most 8 coins, we use this as the upper bound when
+/// walking the `coins` getter.
+const MAX_COINS: usize = 8;
+
+/// Post-merge block time. Used to estimate the timestamp of a block so that
+/// amplification ramps can be applied without fetching every header.
+pub const SECONDS_PER_BLOCK: u64 = 12;
+
+sol!(
+    interface ICurvePool {
+        function coins(uint256 i) external view returns (address);
+        function balances(uint256 i) external view returns (uint256);
+        function fee() external view returns (uint256);
+    }
+);
+
+sol!(
+    interface ICurvePoolLegacy {
+        function coins(int128 i) external view returns (address);
+        function balances(int128 i) external view returns (uint256);
+    }
+);
+
+/// Raw token state of a curve pool as read on chain.
+pub struct CurvePoolTokens {
+    pub tokens:   Vec<Address>,
+    pub decimals: Vec<u8>,
+    pub balances: Vec<Rational>,
+}
+
+/// Loads the coins, decimals and balances of a curve pool. Both the `uint256`
+/// and the legacy `int128` getters are tried. The native eth placeholder is
+/// mapped to the wrapped native token as that is the token the classifier
+/// reports.
+pub async fn load_pool_tokens<T: TracingProvider>(
+    address: Address,
+    block: u64,
+    provider: &Arc<T>,
+    wrapped_native: Address,
+) -> Result<CurvePoolTokens, AmmError> {
+    let mut tokens = Vec::new();
+    let mut balances = Vec::new();
+
+    for i in 0..MAX_COINS {
+        let (token, balance) = if let Ok(token) = make_call_request(
+            ICurvePool::coinsCall { i: U256::from(i) },
+            provider,
+            address,
+            Some(block),
+        )
+        .await
+        {
+            let balance = make_call_request(
+                ICurvePool::balancesCall { i: U256::from(i) },
+                provider,
+                address,
+                Some(block),
+            )
+            .await?;
+            (token._0, balance._0)
+        } else if let Ok(token) = make_call_request(
+            ICurvePoolLegacy::coinsCall { i: i as i128 },
+            provider,
+            address,
+            Some(block),
+        )
+        .await
+        {
+            let balance = make_call_request(
+                ICurvePoolLegacy::balancesCall { i: i as i128 },
+                provider,
+                address,
+                Some(block),
+            )
+            .await?;
+            (token._0, balance._0)
+        } else {
+            break
+        };
+
+        tokens.push(token);
+        balances.push(balance);
+    }
+
+    if tokens.len() < 2 {
+        return Err(AmmError::NoStateError(address))
+    }
+
+    let mut decimals = Vec::with_capacity(tokens.len());
+    for token in tokens.iter_mut() {
+        if *token == ETH_ADDRESS {
+            *token = wrapped_native;
+            decimals.push(18);
+            continue
+        }
+        let dec = make_call_request(IErc20::decimalsCall {}, provider, *token, Some(block)).await?;
+        decimals.push(dec._0);
+    }
+
+    let balances = balances
+        .into_iter()
+        .zip(decimals.iter())
+        .map(|(balance, dec)| balance.to_scaled_rational(*dec))
+        .collect();
+
+    Ok(CurvePoolTokens { tokens, decimals, balances })
+}
+
+/// Estimates the timestamp of `block` from a known block / timestamp pair.
+pub fn estimate_timestamp(known_block: u64, known_timestamp: u64, block: u64) -> u64 {
+    if block >= known_block {
+        known_timestamp + (block - known_block) * SECONDS_PER_BLOCK
+    } else {
+        known_timestamp.saturating_sub((known_block - block) * SECONDS_PER_BLOCK)
+    }
+}
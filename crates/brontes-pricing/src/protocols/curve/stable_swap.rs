@@ -0,0 +1,387 @@
+use std::sync::Arc;
+
+use alloy_primitives::{Address, Log, U256};
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolEvent;
+use async_trait::async_trait;
+use brontes_types::{normalized_actions::Action, traits::TracingProvider, ToFloatNearest};
+use malachite::Rational;
+use serde::{Deserialize, Serialize};
+
+use super::{estimate_timestamp, load_pool_tokens, ICurvePool};
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    make_call_request,
+    protocols::{apply_action_to_balances, token_index, u256_to_f64},
+    Protocol, UpdatableProtocol,
+};
+
+sol!(
+    interface ICurveStableSwap {
+        function A() external view returns (uint256);
+        function A_precise() external view returns (uint256);
+        function initial_A() external view returns (uint256);
+        function future_A() external view returns (uint256);
+        function initial_A_time() external view returns (uint256);
+        function future_A_time() external view returns (uint256);
+
+        event RampA(uint256 old_A, uint256 new_A, uint256 initial_time, uint256 future_time);
+        event StopRampA(uint256 A, uint256 t);
+    }
+);
+
+/// max newton iterations, same as the on-chain implementation
+const MAX_ITERATIONS: usize = 255;
+
+/// State of a curve stableswap pool (base, plain, meta and crvUSD pools).
+///
+/// Balances are tracked decimal adjusted so that they can be updated directly
+/// from the classified actions. The invariant and spot price are evaluated in
+/// floating point, which is plenty for price derivation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CurveStableSwapPool {
+    pub address:          Address,
+    pub protocol:         Protocol,
+    pub tokens:           Vec<Address>,
+    pub token_decimals:   Vec<u8>,
+    pub balances:         Vec<Rational>,
+    /// fee with 1e10 precision
+    pub fee:              U256,
+    /// amplification coefficient ramp, in `a_precision` units
+    pub initial_a:        U256,
+    pub future_a:         U256,
+    pub initial_a_time:   u64,
+    pub future_a_time:    u64,
+    pub a_precision:      U256,
+    /// block and timestamp the state was loaded at, used to estimate the
+    /// timestamp of later blocks
+    pub loaded_block:     u64,
+    pub loaded_timestamp: u64,
+    pub current_block:    u64,
+}
+
+#[async_trait]
+impl UpdatableProtocol for CurveStableSwapPool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        self.tokens.clone()
+    }
+
+    /// prices the base token against the first other coin in the pool. use
+    /// [`CurveStableSwapPool::calculate_price_for_pair`] when the pool has
+    /// more than two coins
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        let quote = self
+            .tokens
+            .iter()
+            .find(|t| **t != base_token)
+            .copied()
+            .unwrap_or_default();
+
+        self.calculate_price_for_pair(base_token, quote)
+    }
+
+    fn sync_from_action(&mut self, action: Action) -> Result<(), AmmError> {
+        apply_action_to_balances(self.address, &self.tokens, &mut self.balances, action)
+    }
+
+    fn sync_from_log(&mut self, log: Log) -> Result<(), AmmError> {
+        let event_signature = log.topics()[0];
+
+        if event_signature == ICurveStableSwap::RampA::SIGNATURE_HASH {
+            let ramp = ICurveStableSwap::RampA::decode_log_data(&log, false)?;
+            self.initial_a = ramp.old_A;
+            self.future_a = ramp.new_A;
+            self.initial_a_time = ramp.initial_time.saturating_to();
+            self.future_a_time = ramp.future_time.saturating_to();
+        } else if event_signature == ICurveStableSwap::StopRampA::SIGNATURE_HASH {
+            let stop = ICurveStableSwap::StopRampA::decode_log_data(&log, false)?;
+            self.initial_a = stop.A;
+            self.future_a = stop.A;
+            self.initial_a_time = stop.t.saturating_to();
+            self.future_a_time = stop.t.saturating_to();
+        } else {
+            return Err(AmmError::EventLogError(EventLogError::InvalidEventSignature))
+        }
+
+        Ok(())
+    }
+}
+
+impl CurveStableSwapPool {
+    pub async fn new_load_on_block<T: TracingProvider>(
+        address: Address,
+        protocol: Protocol,
+        provider: Arc<T>,
+        block: u64,
+        wrapped_native: Address,
+    ) -> Result<Self, AmmError> {
+        let pool_tokens = load_pool_tokens(address, block, &provider, wrapped_native).await?;
+
+        let fee = make_call_request(ICurvePool::feeCall {}, &provider, address, Some(block))
+            .await?
+            ._0;
+
+        let a = make_call_request(ICurveStableSwap::ACall {}, &provider, address, Some(block))
+            .await?
+            ._0;
+        // older pools don't have `A_precise` and store A without precision
+        let a_precision =
+            make_call_request(ICurveStableSwap::A_preciseCall {}, &provider, address, Some(block))
+                .await
+                .ok()
+                .filter(|precise| !a.is_zero() && precise._0 != a)
+                .map(|precise| precise._0 / a)
+                .unwrap_or(U256::from(1));
+
+        let (initial_a, future_a, initial_a_time, future_a_time) = Self::load_ramp(
+            address, block, &provider,
+        )
+        .await
+        .unwrap_or((a * a_precision, a * a_precision, 0, 0));
+
+        let loaded_timestamp = provider
+            .header_by_number(block)
+            .await?
+            .map(|header| header.timestamp)
+            .ok_or(AmmError::BlockNumberNotFound)?;
+
+        let pool = Self {
+            address,
+            protocol,
+            tokens: pool_tokens.tokens,
+            token_decimals: pool_tokens.decimals,
+            balances: pool_tokens.balances,
+            fee,
+            initial_a,
+            future_a,
+            initial_a_time,
+            future_a_time,
+            a_precision,
+            loaded_block: block,
+            loaded_timestamp,
+            current_block: block,
+        };
+
+        if !pool.data_is_populated() {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        Ok(pool)
+    }
+
+    async fn load_ramp<T: TracingProvider>(
+        address: Address,
+        block: u64,
+        provider: &Arc<T>,
+    ) -> Result<(U256, U256, u64, u64), AmmError> {
+        let initial_a =
+            make_call_request(ICurveStableSwap::initial_ACall {}, provider, address, Some(block))
+                .await?
+                ._0;
+        let future_a =
+            make_call_request(ICurveStableSwap::future_ACall {}, provider, address, Some(block))
+                .await?
+                ._0;
+        let initial_a_time = make_call_request(
+            ICurveStableSwap::initial_A_timeCall {},
+            provider,
+            address,
+            Some(block),
+        )
+        .await?
+        ._0;
+        let future_a_time = make_call_request(
+            ICurveStableSwap::future_A_timeCall {},
+            provider,
+            address,
+            Some(block),
+        )
+        .await?
+        ._0;
+
+        Ok((initial_a, future_a, initial_a_time.saturating_to(), future_a_time.saturating_to()))
+    }
+
+    pub fn data_is_populated(&self) -> bool {
+        !(self.tokens.is_empty()
+            || self.initial_a.is_zero()
+            || self.balances.iter().any(|b| *b <= Rational::from(0)))
+    }
+
+    pub fn set_block(&mut self, block: u64) {
+        self.current_block = block;
+    }
+
+    /// amplification coefficient at the current block, with the precision
+    /// removed. mirrors `_A()` of the stableswap contracts
+    pub fn a(&self) -> Result<f64, ArithmeticError> {
+        let timestamp =
+            estimate_timestamp(self.loaded_block, self.loaded_timestamp, self.current_block);
+        let precision = u256_to_f64(self.a_precision)?;
+        let a0 = u256_to_f64(self.initial_a)?;
+        let a1 = u256_to_f64(self.future_a)?;
+
+        if timestamp >= self.future_a_time || self.future_a_time <= self.initial_a_time {
+            return Ok(a1 / precision)
+        }
+
+        let elapsed = (timestamp.saturating_sub(self.initial_a_time)) as f64;
+        let duration = (self.future_a_time - self.initial_a_time) as f64;
+
+        Ok((a0 + (a1 - a0) * elapsed / duration) / precision)
+    }
+
+    fn xp(&self) -> Vec<f64> {
+        self.balances.iter().map(|b| b.clone().to_float()).collect()
+    }
+
+    /// solves the stableswap invariant for D. mirrors `get_D` on chain
+    pub fn get_d(xp: &[f64], amp: f64) -> Result<f64, ArithmeticError> {
+        let n = xp.len() as f64;
+        let s: f64 = xp.iter().sum();
+        if s == 0.0 {
+            return Ok(0.0)
+        }
+
+        let ann = amp * n;
+        let mut d = s;
+        for _ in 0..MAX_ITERATIONS {
+            let d_p = Self::d_p(xp, d);
+            let d_prev = d;
+            d = (ann * s + d_p * n) * d / ((ann - 1.0) * d + (n + 1.0) * d_p);
+
+            if (d - d_prev).abs() <= d * f64::EPSILON * 4.0 {
+                return Ok(d)
+            }
+        }
+
+        Err(ArithmeticError::InvariantDidNotConverge)
+    }
+
+    /// D^(n+1) / (n^n * prod(x))
+    fn d_p(xp: &[f64], d: f64) -> f64 {
+        let n = xp.len() as f64;
+        xp.iter().fold(d, |d_p, x| d_p * d / (x * n))
+    }
+
+    /// Spot price of `base` denominated in `quote`, excluding fees.
+    ///
+    /// With the invariant `F = Ann*S + D - Ann*D - D^(n+1)/(n^n*prod(x))`,
+    /// the marginal rate between two coins is `dF/dx_base / dF/dx_quote`,
+    /// where `dF/dx_i = Ann + D_P / x_i`.
+    pub fn calculate_price_for_pair(
+        &self,
+        base: Address,
+        quote: Address,
+    ) -> Result<Rational, ArithmeticError> {
+        let i = token_index(&self.tokens, base)?;
+        let j = token_index(&self.tokens, quote)?;
+
+        let price = Self::spot_price(&self.xp(), self.a()?, i, j)?;
+
+        Rational::try_from(price).map_err(|_| ArithmeticError::RoundingError)
+    }
+
+    /// marginal rate of coin `i` in terms of coin `j` for the given balances.
+    /// shared with the balancer stable pools which use the same invariant
+    pub fn spot_price(xp: &[f64], amp: f64, i: usize, j: usize) -> Result<f64, ArithmeticError> {
+        if xp[i] <= 0.0 || xp[j] <= 0.0 {
+            return Err(ArithmeticError::YIsZero)
+        }
+
+        let d = Self::get_d(xp, amp)?;
+        let ann = amp * xp.len() as f64;
+        let d_p = Self::d_p(xp, d);
+
+        Ok((ann + d_p / xp[i]) / (ann + d_p / xp[j]))
+    }
+
+    pub fn get_tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
+        let balance = |token| {
+            token_index(&self.tokens, token)
+                .map(|i| self.balances[i].clone())
+                .unwrap_or_default()
+        };
+
+        (balance(base), balance(quote))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(balances: Vec<u64>, a: u64) -> CurveStableSwapPool {
+        let tokens = (0..balances.len())
+            .map(|i| Address::with_last_byte(i as u8 + 1))
+            .collect::<Vec<_>>();
+
+        CurveStableSwapPool {
+            tokens,
+            token_decimals: vec![18; balances.len()],
+            balances: balances.into_iter().map(Rational::from).collect(),
+            initial_a: U256::from(a),
+            future_a: U256::from(a),
+            a_precision: U256::from(1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_balanced_pool_is_pegged() {
+        let pool = pool(vec![1_000_000, 1_000_000, 1_000_000], 2000);
+        let price = pool
+            .calculate_price_for_pair(pool.tokens[0], pool.tokens[2])
+            .unwrap()
+            .to_float();
+
+        assert!((price - 1.0).abs() < 1e-9, "price: {price}");
+    }
+
+    #[test]
+    fn test_imbalanced_pool_discounts_abundant_token() {
+        let pool = pool(vec![1_500_000, 500_000], 100);
+        let abundant = pool
+            .calculate_price_for_pair(pool.tokens[0], pool.tokens[1])
+            .unwrap()
+            .to_float();
+        let scarce = pool
+            .calculate_price_for_pair(pool.tokens[1], pool.tokens[0])
+            .unwrap()
+            .to_float();
+
+        assert!(abundant < 1.0 && abundant > 0.95, "price: {abundant}");
+        assert!((abundant * scarce - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_a_ramp() {
+        let mut pool = pool(vec![1_000_000, 1_000_000], 100);
+        pool.future_a = U256::from(200);
+        pool.initial_a_time = 1_000;
+        pool.future_a_time = 1_000 + 100 * 12;
+        pool.loaded_block = 10;
+        pool.loaded_timestamp = 1_000;
+
+        pool.set_block(60);
+        assert_eq!(pool.a().unwrap(), 150.0);
+
+        pool.set_block(200);
+        assert_eq!(pool.a().unwrap(), 200.0);
+    }
+
+    #[test]
+    fn test_a_overflow_is_an_error() {
+        let mut pool = pool(vec![1_000_000, 1_000_000], 100);
+        pool.future_a = U256::MAX;
+
+        assert!(pool.a().is_err());
+        assert!(pool
+            .calculate_price_for_pair(pool.tokens[0], pool.tokens[1])
+            .is_err());
+    }
+}
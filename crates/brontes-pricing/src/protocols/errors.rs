@@ -73,6 +73,10 @@ pub enum ArithmeticError {
     UniswapV3MathError(#[from] UniswapV3MathError),
     #[error("v2 div by zero")]
     UniV2DivZero,
+    #[error("Token {0:?} is not part of the pool")]
+    TokenNotInPool(Address),
+    #[error("Pool invariant did not converge")]
+    InvariantDidNotConverge,
 }
 
 #[derive(Error, Debug)]
@@ -3,8 +3,8 @@ use std::{collections::hash_map::Entry, pin::Pin, sync::Arc, task::Poll};
 use alloy_primitives::Address;
 use brontes_metrics::pricing::DexPricingMetrics;
 use brontes_types::{
-    pair::Pair, traits::TracingProvider, unzip_either::IterExt, BrontesTaskExecutor, FastHashMap,
-    FastHashSet,
+    pair::Pair, traits::TracingProvider, unzip_either::IterExt, BrontesTaskExecutor, ChainConfig,
+    FastHashMap, FastHashSet,
 };
 use futures::{stream::FuturesOrdered, Future, Stream, StreamExt};
 use itertools::Itertools;
@@ -14,6 +14,7 @@ use crate::{
     errors::AmmError,
     protocols::LoadState,
     types::{PairWithFirstPoolHop, PoolState},
+    uniswap_v4::{load_pool_state, UniswapV4PoolKey},
     Protocol,
 };
 
@@ -71,10 +72,14 @@ pub struct LazyExchangeLoader<T: TracingProvider> {
     req_per_block:     FastHashMap<BlockNumber, u64>,
     state_tracking:    LoadingStateTracker,
     ex:                BrontesTaskExecutor,
+    /// uniswap v4 pools can't be loaded from their address alone, these are
+    /// registered from the pool updates as they come in
+    uniswap_v4_pools:  FastHashMap<Address, UniswapV4PoolKey>,
+    chain:             &'static ChainConfig,
 }
 
 impl<T: TracingProvider> LazyExchangeLoader<T> {
-    pub fn new(provider: Arc<T>, ex: BrontesTaskExecutor) -> Self {
+    pub fn new(provider: Arc<T>, ex: BrontesTaskExecutor, chain: &'static ChainConfig) -> Self {
         Self {
             state_tracking: LoadingStateTracker::default(),
             pool_buf: FastHashMap::default(),
@@ -82,9 +87,15 @@ impl<T: TracingProvider> LazyExchangeLoader<T> {
             provider,
             req_per_block: FastHashMap::default(),
             ex,
+            uniswap_v4_pools: FastHashMap::default(),
+            chain,
         }
     }
 
+    pub fn register_uniswap_v4_pool(&mut self, address: Address, key: UniswapV4PoolKey) {
+        self.uniswap_v4_pools.insert(address, key);
+    }
+
     pub fn is_loading(&self, k: &Address) -> bool {
         self.pool_buf.contains_key(k)
     }
@@ -165,12 +176,27 @@ impl<T: TracingProvider> LazyExchangeLoader<T> {
         let provider = self.provider.clone();
         self.add_state_trackers(block_number, id, address, pair);
 
-        let fut = ex_type.try_load_state(address, provider, block_number, pool_pair, pair);
+        let fut: BoxedFuture<Result<PoolFetchSuccess, PoolFetchError>> = match self
+            .uniswap_v4_pools
+            .get(&address)
+        {
+            Some(key) if ex_type == Protocol::UniswapV4 => {
+                Box::pin(load_pool_state(*key, address, provider, block_number, pool_pair, pair))
+            }
+            _ => Box::pin(ex_type.try_load_state(
+                address,
+                provider,
+                block_number,
+                pool_pair,
+                pair,
+                self.chain,
+            )),
+        };
         self.pool_load_futures.add_future(
             block_number,
             Box::pin(self.ex.handle().spawn(async move {
                 if let Some(metrics) = metrics {
-                    metrics.meter_state_load(|| Box::pin(fut)).await
+                    metrics.meter_state_load(|| fut).await
                 } else {
                     fut.await
                 }
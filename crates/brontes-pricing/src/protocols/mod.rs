@@ -1,18 +1,26 @@
+pub mod balancer;
+pub mod curve;
 pub mod errors;
 pub mod lazy;
 pub mod uniswap_v2;
 pub mod uniswap_v3;
+pub mod uniswap_v4;
 
 use std::{future::Future, sync::Arc};
 
-use alloy_primitives::{Address, Log};
+use alloy_primitives::{Address, Log, U256};
 use async_trait::async_trait;
-use brontes_types::{normalized_actions::Action, pair::Pair, traits::TracingProvider};
+use brontes_types::{
+    db::token_info::TokenInfoWithAddress, normalized_actions::Action, pair::Pair,
+    traits::TracingProvider, ChainConfig,
+};
 pub use brontes_types::{queries::make_call_request, Protocol};
 use malachite::Rational;
 use tracing::{debug, warn};
 
 use crate::{
+    balancer::{load_v1_pool, load_v2_pool},
+    curve::{crypto_swap::CurveCryptoSwapPool, stable_swap::CurveStableSwapPool},
     lazy::{PoolFetchError, PoolFetchSuccess},
     protocols::errors::{AmmError, ArithmeticError},
     types::PairWithFirstPoolHop,
@@ -39,9 +47,100 @@ pub trait LoadState {
         block_number: u64,
         pool_pair: Pair,
         full_pair: PairWithFirstPoolHop,
+        chain: &'static ChainConfig,
     ) -> impl Future<Output = Result<PoolFetchSuccess, PoolFetchError>> + Send;
 }
 
+/// curve pools that use the stableswap invariant
+fn is_curve_stable_swap(protocol: &Protocol) -> bool {
+    matches!(
+        protocol,
+        Protocol::CurveBasePool2
+            | Protocol::CurveBasePool3
+            | Protocol::CurveBasePool4
+            | Protocol::CurveV1MetaPool
+            | Protocol::CurveV1MetapoolImpl
+            | Protocol::CurveV2MetaPool
+            | Protocol::CurveV2MetapoolImpl
+            | Protocol::CurveV2PlainPool
+            | Protocol::CurveV2PlainPoolImpl
+            | Protocol::CurvecrvUSDMetaPool
+            | Protocol::CurvecrvUSDMetapoolImpl
+            | Protocol::CurvecrvUSDPlainPool
+            | Protocol::CurvecrvUSDPlainPoolImpl
+    )
+}
+
+/// curve pools that use the cryptoswap invariant
+fn is_curve_crypto_swap(protocol: &Protocol) -> bool {
+    matches!(protocol, Protocol::CurveCryptoSwapPool | Protocol::CurveTriCryptoPool)
+}
+
+/// Converts a raw on-chain value to a float, erroring instead of panicking
+/// when it doesn't fit in a u128
+pub(crate) fn u256_to_f64(value: U256) -> Result<f64, ArithmeticError> {
+    u128::try_from(value)
+        .map(|value| value as f64)
+        .map_err(|_| ArithmeticError::U128ConversionError)
+}
+
+pub(crate) fn token_index(tokens: &[Address], token: Address) -> Result<usize, ArithmeticError> {
+    tokens
+        .iter()
+        .position(|t| *t == token)
+        .ok_or(ArithmeticError::TokenNotInPool(token))
+}
+
+fn token_indices(
+    tokens: &[Address],
+    action_tokens: &[TokenInfoWithAddress],
+) -> Result<Vec<usize>, ArithmeticError> {
+    action_tokens
+        .iter()
+        .map(|token| token_index(tokens, token.address))
+        .collect()
+}
+
+/// Applies the token deltas of a classified action to the balances of a
+/// multi-token pool. Curve and Balancer pools don't emit their balances, so
+/// we track them from the swaps, mints and burns that touch the pool. All
+/// tokens are resolved before any balance is touched so that an action with
+/// an unknown token leaves the balances as they were.
+pub(crate) fn apply_action_to_balances(
+    pool: Address,
+    tokens: &[Address],
+    balances: &mut [Rational],
+    action: Action,
+) -> Result<(), AmmError> {
+    match action {
+        Action::Swap(s) if s.pool == pool => {
+            let i = token_index(tokens, s.token_in.address)?;
+            let j = token_index(tokens, s.token_out.address)?;
+            balances[i] += s.amount_in;
+            balances[j] -= s.amount_out;
+        }
+        Action::SwapWithFee(s) if s.pool == pool => {
+            let i = token_index(tokens, s.token_in.address)?;
+            let j = token_index(tokens, s.token_out.address)?;
+            balances[i] += s.swap.amount_in;
+            balances[j] -= s.swap.amount_out;
+        }
+        Action::Mint(m) if m.pool == pool => {
+            for (i, amount) in token_indices(tokens, &m.token)?.into_iter().zip(m.amount) {
+                balances[i] += amount;
+            }
+        }
+        Action::Burn(b) if b.pool == pool => {
+            for (i, amount) in token_indices(tokens, &b.token)?.into_iter().zip(b.amount) {
+                balances[i] -= amount;
+            }
+        }
+        _ => return Err(AmmError::SyncError(pool)),
+    }
+
+    Ok(())
+}
+
 impl LoadState for Protocol {
     fn has_state_updater(&self) -> bool {
         matches!(
@@ -52,7 +151,11 @@ impl LoadState for Protocol {
                 | Self::SushiSwapV3
                 | Self::PancakeSwapV2
                 | Self::PancakeSwapV3
-        )
+                | Self::BalancerV1
+                | Self::BalancerV2
+                | Self::UniswapV4
+        ) || is_curve_stable_swap(self)
+            || is_curve_crypto_swap(self)
     }
 
     async fn try_load_state<T: TracingProvider>(
@@ -62,6 +165,7 @@ impl LoadState for Protocol {
         block_number: u64,
         pool_pair: Pair,
         fp: PairWithFirstPoolHop,
+        chain: &'static ChainConfig,
     ) -> Result<PoolFetchSuccess, PoolFetchError> {
         match self {
             Self::UniswapV2 | Self::SushiSwapV2 | Self::PancakeSwapV2 => {
@@ -120,6 +224,117 @@ impl LoadState for Protocol {
                     res,
                 ))
             }
+            protocol if is_curve_stable_swap(&protocol) => {
+                let (pool, res) = if let Ok(pool) = CurveStableSwapPool::new_load_on_block(
+                    address,
+                    protocol,
+                    provider.clone(),
+                    block_number - 1,
+                    chain.wrapped_native,
+                )
+                .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        CurveStableSwapPool::new_load_on_block(
+                            address,
+                            protocol,
+                            provider,
+                            block_number,
+                            chain.wrapped_native,
+                        )
+                        .await
+                        .map_err(|e| {
+                                debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                                (address, protocol, block_number, pool_pair, fp, e)
+                            })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::CurveStableSwap(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
+            protocol if is_curve_crypto_swap(&protocol) => {
+                let (pool, res) = if let Ok(pool) = CurveCryptoSwapPool::new_load_on_block(
+                    address,
+                    protocol,
+                    provider.clone(),
+                    block_number - 1,
+                    chain.wrapped_native,
+                )
+                .await
+                {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        CurveCryptoSwapPool::new_load_on_block(
+                            address,
+                            protocol,
+                            provider,
+                            block_number,
+                            chain.wrapped_native,
+                        )
+                        .await
+                        .map_err(|e| {
+                                debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                                (address, protocol, block_number, pool_pair, fp, e)
+                            })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((
+                    block_number,
+                    address,
+                    PoolState::new(
+                        crate::types::PoolVariants::CurveCryptoSwap(Box::new(pool)),
+                        block_number,
+                    ),
+                    res,
+                ))
+            }
+            Self::BalancerV1 | Self::BalancerV2 => {
+                let vault = chain.balancer_v2_vault;
+                let load = |block| {
+                    let provider = provider.clone();
+                    async move {
+                        if self == Self::BalancerV1 {
+                            load_v1_pool(address, provider, block).await
+                        } else {
+                            load_v2_pool(address, provider, block, vault).await
+                        }
+                    }
+                };
+
+                let (pool, res) = if let Ok(pool) = load(block_number - 1).await {
+                    (pool, LoadResult::Ok)
+                } else {
+                    (
+                        load(block_number).await.map_err(|e| {
+                            debug!(?pool_pair, protocol=%self, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                            (address, self, block_number, pool_pair, fp, e)
+                        })?,
+                        LoadResult::PoolInitOnBlock,
+                    )
+                };
+
+                Ok((block_number, address, PoolState::new(pool, block_number), res))
+            }
+            // v4 pools are loaded through their pool id, see
+            // `LazyExchangeLoader::register_uniswap_v4_pool`
+            Self::UniswapV4 => {
+                debug!(?pool_pair, %block_number, pool_address=?address, "uniswap v4 pool id is unknown");
+                Err((address, self, block_number, pool_pair, fp, AmmError::NoStateError(address)))
+            }
             rest => {
                 warn!(protocol=?rest, "no state updater is build for");
                 Err((address, self, block_number, pool_pair, fp, AmmError::UnsupportedProtocol))
@@ -127,3 +342,29 @@ impl LoadState for Protocol {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::normalized_actions::NormalizedMint;
+
+    use super::*;
+
+    #[test]
+    fn test_mint_with_unknown_token_leaves_balances() {
+        let pool = Address::with_last_byte(10);
+        let tokens = vec![Address::with_last_byte(1), Address::with_last_byte(2)];
+        let mut balances = vec![Rational::from(100), Rational::from(100)];
+
+        let token = |address| TokenInfoWithAddress { address, ..Default::default() };
+        let mint = NormalizedMint {
+            pool,
+            token: vec![token(tokens[0]), token(Address::with_last_byte(3))],
+            amount: vec![Rational::from(5), Rational::from(5)],
+            ..Default::default()
+        };
+
+        let res = apply_action_to_balances(pool, &tokens, &mut balances, Action::Mint(mint));
+        assert!(res.is_err());
+        assert_eq!(balances, vec![Rational::from(100), Rational::from(100)]);
+    }
+}
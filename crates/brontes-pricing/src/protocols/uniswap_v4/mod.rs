@@ -0,0 +1,385 @@
+use std::{cmp::Ordering, sync::Arc};
+
+use alloy_primitives::{keccak256, Address, Log, B256, U256};
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolEvent;
+use async_trait::async_trait;
+use brontes_types::{
+    normalized_actions::{Action, NormalizedSwap},
+    pair::Pair,
+    traits::TracingProvider,
+    ToScaledRational,
+};
+use malachite::{num::arithmetic::traits::Pow, Rational};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::make_call_request;
+use crate::{
+    errors::{AmmError, ArithmeticError, EventLogError},
+    lazy::{PoolFetchError, PoolFetchSuccess},
+    types::{PairWithFirstPoolHop, PoolUpdate, PoolVariants},
+    uniswap_v2::IErc20,
+    uniswap_v3::uniswap_v3_math,
+    LoadResult, PoolState, Protocol, UpdatableProtocol,
+};
+
+/// storage slot of the `PoolId => Pool.State` mapping in the pool manager
+const POOLS_SLOT: U256 = U256::from_limbs([6, 0, 0, 0]);
+/// offset of `liquidity` from the start of `Pool.State`
+const LIQUIDITY_OFFSET: U256 = U256::from_limbs([3, 0, 0, 0]);
+
+sol!(
+    interface IUniswapV4PoolManager {
+        function extsload(bytes32 slot) external view returns (bytes32);
+
+        event Initialize(
+            bytes32 indexed id,
+            address indexed currency0,
+            address indexed currency1,
+            uint24 fee,
+            int24 tickSpacing,
+            address hooks,
+            uint160 sqrtPriceX96,
+            int24 tick
+        );
+
+        event Swap(
+            bytes32 indexed id,
+            address indexed sender,
+            int128 amount0,
+            int128 amount1,
+            uint160 sqrtPriceX96,
+            uint128 liquidity,
+            int24 tick,
+            uint24 fee
+        );
+    }
+);
+
+/// The classifier only gives us the truncated pool id as the pool address.
+/// The full id and the currency0 side are recovered from the initialize or
+/// swap logs, this is what we need to read the pool state. Every v4 pool lives
+/// in the pool manager, which is the emitter of these logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniswapV4PoolKey {
+    pub pool_id:      B256,
+    pub currency_0:   Address,
+    pub pool_manager: Address,
+}
+
+impl UniswapV4PoolKey {
+    pub fn from_update(update: &PoolUpdate) -> Option<Self> {
+        match &update.action {
+            Action::Swap(swap) if swap.protocol == Protocol::UniswapV4 => {
+                Self::from_swap_logs(swap, &update.logs)
+            }
+            Action::PoolConfigUpdate(init) if init.protocol == Protocol::UniswapV4 => {
+                update.logs.iter().find_map(|log| {
+                    let event =
+                        IUniswapV4PoolManager::Initialize::decode_log_data(log, false).ok()?;
+                    if event.id[0..20] != init.pool_address[..] {
+                        return None
+                    }
+
+                    // native eth is already mapped to the wrapped native token by the
+                    // classifier
+                    Some(Self {
+                        pool_id:      event.id,
+                        currency_0:   *init.tokens.first()?,
+                        pool_manager: log.address,
+                    })
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn from_swap_logs(swap: &NormalizedSwap, logs: &[Log]) -> Option<Self> {
+        logs.iter().find_map(|log| {
+            let event = IUniswapV4PoolManager::Swap::decode_log_data(log, false).ok()?;
+            if event.id[0..20] != swap.pool[..] {
+                return None
+            }
+
+            // amount0 is from the perspective of the swapper
+            let currency_0 = if event.amount0.is_negative() {
+                swap.token_in.address
+            } else {
+                swap.token_out.address
+            };
+
+            Some(Self { pool_id: event.id, currency_0, pool_manager: log.address })
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UniswapV4Pool {
+    pub address:          Address,
+    pub pool_manager:     Address,
+    pub pool_id:          B256,
+    pub token_a:          Address,
+    pub token_a_decimals: u8,
+    pub token_b:          Address,
+    pub token_b_decimals: u8,
+    pub liquidity:        u128,
+    pub sqrt_price:       U256,
+    pub tick:             i32,
+    pub fee:              u32,
+}
+
+#[async_trait]
+impl UpdatableProtocol for UniswapV4Pool {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn tokens(&self) -> Vec<Address> {
+        vec![self.token_a, self.token_b]
+    }
+
+    fn calculate_price(&self, base_token: Address) -> Result<Rational, ArithmeticError> {
+        if self.liquidity <= 10_000 {
+            return Err(ArithmeticError::UniswapV3MathError(
+                uniswap_v3_math::error::UniswapV3MathError::LiquidityTooLow(self.liquidity),
+            ))
+        }
+
+        let tick = uniswap_v3_math::tick_math::get_tick_at_sqrt_ratio(self.sqrt_price)?;
+        let shift = self.token_a_decimals as i8 - self.token_b_decimals as i8;
+        let price = match shift.cmp(&0) {
+            Ordering::Less => 1.0001_f64.powi(tick) / 10_f64.powi(-shift as i32),
+            Ordering::Greater => 1.0001_f64.powi(tick) * 10_f64.powi(shift as i32),
+            Ordering::Equal => 1.0001_f64.powi(tick),
+        };
+
+        let price = if base_token == self.token_a { price } else { 1.0 / price };
+
+        Rational::try_from(price).map_err(|_| ArithmeticError::RoundingError)
+    }
+
+    /// the pool state is only synced from the swap logs
+    fn sync_from_action(&mut self, _action: Action) -> Result<(), AmmError> {
+        Err(AmmError::SyncError(self.address))
+    }
+
+    /// liquidity changes are not classified on their own frame, so the active
+    /// liquidity is taken from the latest swap
+    fn sync_from_log(&mut self, log: Log) -> Result<(), AmmError> {
+        if log.topics().first() != Some(&IUniswapV4PoolManager::Swap::SIGNATURE_HASH) {
+            Err(EventLogError::InvalidEventSignature)?
+        }
+
+        let swap_event = IUniswapV4PoolManager::Swap::decode_log_data(&log, false)?;
+        if swap_event.id != self.pool_id {
+            return Err(AmmError::SyncError(self.address))
+        }
+
+        self.sqrt_price = swap_event.sqrtPriceX96;
+        self.liquidity = swap_event.liquidity;
+        self.tick = swap_event.tick;
+        self.fee = swap_event.fee;
+
+        Ok(())
+    }
+}
+
+impl UniswapV4Pool {
+    pub async fn new_load_on_block<T: TracingProvider>(
+        address: Address,
+        key: UniswapV4PoolKey,
+        token_b: Address,
+        provider: Arc<T>,
+        block: u64,
+    ) -> Result<Self, AmmError> {
+        let state_slot =
+            keccak256([key.pool_id.as_slice(), &POOLS_SLOT.to_be_bytes::<32>()].concat());
+        let slot0 = Self::extsload(key.pool_manager, state_slot, &provider, block).await?;
+        let liquidity_slot =
+            B256::from(U256::from_be_bytes(state_slot.0).wrapping_add(LIQUIDITY_OFFSET));
+        let liquidity = Self::extsload(key.pool_manager, liquidity_slot, &provider, block).await?;
+
+        let token_a_decimals =
+            make_call_request(IErc20::decimalsCall {}, &provider, key.currency_0, Some(block))
+                .await?
+                ._0;
+        let token_b_decimals =
+            make_call_request(IErc20::decimalsCall {}, &provider, token_b, Some(block))
+                .await?
+                ._0;
+
+        // slot0 packs | lpFee (24) | protocolFee (24) | tick (24) | sqrtPriceX96 (160)
+        // |
+        let sqrt_price = slot0 & ((U256::from(1) << 160) - U256::from(1));
+        let tick = (((slot0 >> 160) & U256::from(0xffffff)).to::<u32>() << 8) as i32 >> 8;
+        let fee = ((slot0 >> 208) & U256::from(0xffffff)).to::<u32>();
+        let liquidity = (liquidity & U256::from(u128::MAX)).to::<u128>();
+
+        let pool = Self {
+            address,
+            pool_manager: key.pool_manager,
+            pool_id: key.pool_id,
+            token_a: key.currency_0,
+            token_a_decimals,
+            token_b,
+            token_b_decimals,
+            liquidity,
+            sqrt_price,
+            tick,
+            fee,
+        };
+
+        if !pool.data_is_populated() {
+            return Err(AmmError::NoStateError(address))
+        }
+
+        Ok(pool)
+    }
+
+    async fn extsload<T: TracingProvider>(
+        pool_manager: Address,
+        slot: B256,
+        provider: &Arc<T>,
+        block: u64,
+    ) -> Result<U256, AmmError> {
+        let value = make_call_request(
+            IUniswapV4PoolManager::extsloadCall { slot },
+            provider,
+            pool_manager,
+            Some(block),
+        )
+        .await?
+        ._0;
+
+        Ok(U256::from_be_bytes(value.0))
+    }
+
+    pub fn data_is_populated(&self) -> bool {
+        !self.sqrt_price.is_zero()
+    }
+
+    /// v4 pools don't hold their own balances, we use the virtual reserves
+    /// at the current price instead. `x = L / sqrt(P)`, `y = L * sqrt(P)`
+    pub fn get_tvl(&self, base: Address) -> (Rational, Rational) {
+        let sqrt_price = self.sqrt_price.to_scaled_rational(0) / Rational::from(2u8).pow(96u64);
+        let liquidity = Rational::from(self.liquidity);
+
+        let (reserve_0, reserve_1) = if self.sqrt_price.is_zero() {
+            (Rational::default(), Rational::default())
+        } else {
+            (
+                (&liquidity / &sqrt_price).to_scaled_rational(self.token_a_decimals),
+                (liquidity * sqrt_price).to_scaled_rational(self.token_b_decimals),
+            )
+        };
+
+        if self.token_a == base {
+            (reserve_0, reserve_1)
+        } else {
+            (reserve_1, reserve_0)
+        }
+    }
+}
+
+pub async fn load_pool_state<T: TracingProvider>(
+    key: UniswapV4PoolKey,
+    address: Address,
+    provider: Arc<T>,
+    block_number: u64,
+    pool_pair: Pair,
+    fp: PairWithFirstPoolHop,
+) -> Result<PoolFetchSuccess, PoolFetchError> {
+    let token_b = if pool_pair.0 == key.currency_0 { pool_pair.1 } else { pool_pair.0 };
+
+    let (pool, res) = if let Ok(pool) =
+        UniswapV4Pool::new_load_on_block(address, key, token_b, provider.clone(), block_number - 1)
+            .await
+    {
+        (pool, LoadResult::Ok)
+    } else {
+        (
+            UniswapV4Pool::new_load_on_block(address, key, token_b, provider, block_number)
+                .await
+                .map_err(|e| {
+                    debug!(?pool_pair, protocol=%Protocol::UniswapV4, %block_number, pool_address=?address, err=%e, "lazy load failed");
+                    (address, Protocol::UniswapV4, block_number, pool_pair, fp, e)
+                })?,
+            LoadResult::PoolInitOnBlock,
+        )
+    };
+
+    Ok((
+        block_number,
+        address,
+        PoolState::new(PoolVariants::UniswapV4(Box::new(pool)), block_number),
+        res,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use brontes_types::normalized_actions::NormalizedPoolConfigUpdate;
+
+    use super::*;
+
+    #[test]
+    fn test_virtual_reserves() {
+        // sqrt price of 2, so 4 token_b per token_a
+        let pool = UniswapV4Pool {
+            token_a: Address::with_last_byte(1),
+            token_b: Address::with_last_byte(2),
+            liquidity: 1_000,
+            sqrt_price: U256::from(2) << 96,
+            ..Default::default()
+        };
+
+        let (reserve_a, reserve_b) = pool.get_tvl(pool.token_a);
+        assert_eq!(reserve_a, Rational::from(500));
+        assert_eq!(reserve_b, Rational::from(2_000));
+    }
+
+    #[test]
+    fn test_key_from_initialize() {
+        let pool_manager = Address::with_last_byte(0x44);
+        let pool_id = B256::repeat_byte(0xab);
+        let (currency_0, currency_1) = (Address::with_last_byte(1), Address::with_last_byte(2));
+
+        let initialize = IUniswapV4PoolManager::Initialize {
+            id:           pool_id,
+            currency0:    currency_0,
+            currency1:    currency_1,
+            fee:          3000,
+            tickSpacing:  60,
+            hooks:        Address::ZERO,
+            sqrtPriceX96: U256::from(1) << 96,
+            tick:         0,
+        };
+        let update = PoolUpdate {
+            block:  1,
+            tx_idx: 0,
+            logs:   vec![Log { address: pool_manager, data: initialize.encode_log_data() }],
+            action: Action::PoolConfigUpdate(NormalizedPoolConfigUpdate {
+                trace_index:  0,
+                protocol:     Protocol::UniswapV4,
+                pool_address: Address::from_slice(&pool_id[0..20]),
+                tokens:       vec![currency_0, currency_1],
+            }),
+        };
+
+        assert_eq!(
+            UniswapV4PoolKey::from_update(&update),
+            Some(UniswapV4PoolKey { pool_id, currency_0, pool_manager })
+        );
+    }
+
+    #[test]
+    fn test_sync_from_action_errors() {
+        let mut pool = UniswapV4Pool { address: Address::with_last_byte(1), ..Default::default() };
+
+        assert!(matches!(
+            pool.sync_from_action(Action::Revert),
+            Err(AmmError::SyncError(address)) if address == pool.address
+        ));
+    }
+}
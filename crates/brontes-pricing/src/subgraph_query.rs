@@ -16,12 +16,13 @@ type GraphSeachParRes = (Vec<Vec<(Address, PoolUpdate)>>, Vec<Vec<NewGraphDetail
 pub fn graph_search_par(
     graph: &GraphManager,
     quote: Address,
+    wrapped_native: Address,
     updates: Vec<PoolUpdate>,
 ) -> GraphSeachParRes {
     let (state, pools): (Vec<_>, Vec<_>) = updates
         .into_par_iter()
         .filter_map(|msg| {
-            let pair = msg.get_pair(quote)?;
+            let pair = msg.get_pair(quote, wrapped_native)?;
             let is_transfer = msg.is_transfer();
 
             let pair0 = Pair(pair.0, quote);
@@ -2,15 +2,20 @@ use std::fmt::{Debug, Display};
 
 use alloy_primitives::{wrap_fixed_bytes, Address, FixedBytes, Log};
 use brontes_types::{
-    constants::WETH_ADDRESS,
     normalized_actions::{pool::NormalizedPoolConfigUpdate, Action},
     pair::Pair,
 };
 use malachite::Rational;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    errors::ArithmeticError, uniswap_v2::UniswapV2Pool, uniswap_v3::UniswapV3Pool, LoadState,
-    Protocol, UpdatableProtocol,
+    balancer::{stable::BalancerStablePool, weighted::BalancerWeightedPool},
+    curve::{crypto_swap::CurveCryptoSwapPool, stable_swap::CurveStableSwapPool},
+    errors::ArithmeticError,
+    uniswap_v2::UniswapV2Pool,
+    uniswap_v3::UniswapV3Pool,
+    uniswap_v4::UniswapV4Pool,
+    LoadState, Protocol, UpdatableProtocol,
 };
 
 wrap_fixed_bytes!(extra_derives:[],
@@ -52,32 +57,33 @@ impl PairWithFirstPoolHop {
 }
 
 pub trait ProtocolState: Debug {
-    fn price(&self, base: Address) -> Result<Rational, ArithmeticError>;
-    fn tvl(&self, base: Address) -> (Rational, Rational);
+    fn price(&self, base: Address, quote: Address) -> Result<Rational, ArithmeticError>;
+    fn tvl(&self, base: Address, quote: Address) -> (Rational, Rational);
 }
 
 impl ProtocolState for PoolState {
-    fn tvl(&self, base: Address) -> (Rational, Rational) {
-        self.get_tvl(base)
+    fn tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
+        self.get_tvl(base, quote)
     }
 
-    fn price(&self, base: Address) -> Result<Rational, ArithmeticError> {
-        self.get_price(base)
+    fn price(&self, base: Address, quote: Address) -> Result<Rational, ArithmeticError> {
+        self.get_price(base, quote)
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PoolState {
     variant:         PoolVariants,
     pub last_update: u64,
 }
 impl Debug for PoolState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pair = self.pair();
         f.debug_struct("Pool State")
             .field("addr", &self.address())
-            .field("pair", &self.pair())
-            .field("tvl 0", &self.get_tvl(self.pair().0).0)
-            .field("tvl 1", &self.get_tvl(self.pair().0).1)
+            .field("pair", &pair)
+            .field("tvl 0", &self.get_tvl(pair.0, pair.1).0)
+            .field("tvl 1", &self.get_tvl(pair.0, pair.1).1)
             .field("block", &self.last_update)
             .finish()
     }
@@ -88,10 +94,17 @@ impl PoolState {
         Self { variant, last_update }
     }
 
+    /// the first pair of the pool. multi-token pools have an edge for every
+    /// pair of their tokens
     pub fn pair(&self) -> Pair {
         match &self.variant {
             PoolVariants::UniswapV2(v) => Pair(v.token_a, v.token_b),
             PoolVariants::UniswapV3(v) => Pair(v.token_a, v.token_b),
+            PoolVariants::UniswapV4(v) => Pair(v.token_a, v.token_b),
+            PoolVariants::CurveStableSwap(v) => Pair(v.tokens[0], v.tokens[1]),
+            PoolVariants::CurveCryptoSwap(v) => Pair(v.tokens[0], v.tokens[1]),
+            PoolVariants::BalancerWeighted(v) => Pair(v.tokens[0], v.tokens[1]),
+            PoolVariants::BalancerStable(v) => Pair(v.tokens[0], v.tokens[1]),
         }
     }
 
@@ -99,6 +112,11 @@ impl PoolState {
         match &self.variant {
             PoolVariants::UniswapV2(_) => Protocol::UniswapV2,
             PoolVariants::UniswapV3(_) => Protocol::UniswapV3,
+            PoolVariants::UniswapV4(_) => Protocol::UniswapV4,
+            PoolVariants::CurveStableSwap(v) => v.protocol,
+            PoolVariants::CurveCryptoSwap(v) => v.protocol,
+            PoolVariants::BalancerWeighted(v) => v.protocol,
+            PoolVariants::BalancerStable(_) => Protocol::BalancerV2,
         }
     }
 
@@ -108,44 +126,103 @@ impl PoolState {
             return
         }
         self.last_update = state.block;
-        self.variant.increment_state(state.logs);
+        self.variant.increment_state(state);
     }
 
     pub fn address(&self) -> Address {
         match &self.variant {
             PoolVariants::UniswapV2(v) => v.address(),
             PoolVariants::UniswapV3(v) => v.address(),
+            PoolVariants::UniswapV4(v) => v.address(),
+            PoolVariants::CurveStableSwap(v) => v.address(),
+            PoolVariants::CurveCryptoSwap(v) => v.address(),
+            PoolVariants::BalancerWeighted(v) => v.address(),
+            PoolVariants::BalancerStable(v) => v.address(),
         }
     }
 
-    pub fn get_tvl(&self, base: Address) -> (Rational, Rational) {
+    pub fn get_tvl(&self, base: Address, quote: Address) -> (Rational, Rational) {
         match &self.variant {
             PoolVariants::UniswapV2(v) => v.get_tvl(base),
             PoolVariants::UniswapV3(v) => v.get_tvl(base),
+            PoolVariants::UniswapV4(v) => v.get_tvl(base),
+            PoolVariants::CurveStableSwap(v) => v.get_tvl(base, quote),
+            PoolVariants::CurveCryptoSwap(v) => v.get_tvl(base, quote),
+            PoolVariants::BalancerWeighted(v) => v.get_tvl(base, quote),
+            PoolVariants::BalancerStable(v) => v.get_tvl(base, quote),
         }
     }
 
-    pub fn get_price(&self, base: Address) -> Result<Rational, ArithmeticError> {
+    pub fn get_price(&self, base: Address, quote: Address) -> Result<Rational, ArithmeticError> {
         match &self.variant {
             PoolVariants::UniswapV2(v) => v.calculate_price(base),
             PoolVariants::UniswapV3(v) => v.calculate_price(base),
+            PoolVariants::UniswapV4(v) => v.calculate_price(base),
+            PoolVariants::CurveStableSwap(v) => v.calculate_price_for_pair(base, quote),
+            PoolVariants::CurveCryptoSwap(v) => v.calculate_price_for_pair(base, quote),
+            PoolVariants::BalancerWeighted(v) => v.calculate_price_for_pair(base, quote),
+            PoolVariants::BalancerStable(v) => v.calculate_price_for_pair(base, quote),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PoolVariants {
     UniswapV2(Box<UniswapV2Pool>),
     UniswapV3(Box<UniswapV3Pool>),
+    UniswapV4(Box<UniswapV4Pool>),
+    CurveStableSwap(Box<CurveStableSwapPool>),
+    CurveCryptoSwap(Box<CurveCryptoSwapPool>),
+    BalancerWeighted(Box<BalancerWeightedPool>),
+    BalancerStable(Box<BalancerStablePool>),
 }
 
 impl PoolVariants {
-    fn increment_state(&mut self, logs: Vec<Log>) {
-        for log in logs {
-            let _ = match self {
-                PoolVariants::UniswapV3(a) => a.sync_from_log(log),
-                PoolVariants::UniswapV2(a) => a.sync_from_log(log),
-            };
+    fn increment_state(&mut self, update: PoolUpdate) {
+        match self {
+            PoolVariants::UniswapV3(a) => {
+                for log in update.logs {
+                    let _ = a.sync_from_log(log);
+                }
+            }
+            PoolVariants::UniswapV2(a) => {
+                for log in update.logs {
+                    let _ = a.sync_from_log(log);
+                }
+            }
+            PoolVariants::UniswapV4(a) => {
+                for log in update.logs {
+                    let _ = a.sync_from_log(log);
+                }
+            }
+            // curve doesn't emit balances, so we track them through the classified
+            // action and only use the logs for amplification and price scale changes
+            PoolVariants::CurveStableSwap(a) => {
+                a.set_block(update.block);
+                for log in update.logs {
+                    let _ = a.sync_from_log(log);
+                }
+                let _ = a.sync_from_action(update.action);
+            }
+            PoolVariants::CurveCryptoSwap(a) => {
+                a.set_block(update.block);
+                for log in update.logs {
+                    let _ = a.sync_from_log(log);
+                }
+                let _ = a.sync_from_action(update.action);
+            }
+            // balancer v2 balances live in the vault, the classified actions carry
+            // the pool address so these are applied the same way as curve
+            PoolVariants::BalancerWeighted(a) => {
+                let _ = a.sync_from_action(update.action);
+            }
+            PoolVariants::BalancerStable(a) => {
+                a.set_block(update.block);
+                for log in update.logs {
+                    let _ = a.sync_from_log(log);
+                }
+                let _ = a.sync_from_action(update.action);
+            }
         }
     }
 }
@@ -224,7 +301,7 @@ impl PoolUpdate {
 
     // we currently only use this in order to fetch the pair for when its new or to
     // fetch all pairs of it. this
-    pub fn get_pair(&self, quote: Address) -> Option<Pair> {
+    pub fn get_pair(&self, quote: Address, wrapped_native: Address) -> Option<Pair> {
         match &self.action {
             Action::Swap(s) => Some(Pair(s.token_in.address, s.token_out.address)),
             Action::Mint(m) => Some(Pair(
@@ -240,7 +317,7 @@ impl PoolUpdate {
                 b.token.get(1).map(|t| t.address).unwrap_or(quote),
             )),
             Action::Transfer(t) => Some(Pair(t.token.address, quote)),
-            Action::EthTransfer(_) => Some(Pair(WETH_ADDRESS, quote)),
+            Action::EthTransfer(_) => Some(Pair(wrapped_native, quote)),
             Action::Liquidation(l) => Some(Pair(l.collateral_asset.address, l.debt_asset.address)),
             Action::SwapWithFee(s) => Some(Pair(s.token_in.address, s.token_out.address)),
             rest => {
@@ -20,14 +20,31 @@ where
     S: Subscriber,
     for<'a> S: LookupSpan<'a>,
 {
-    let filter = EnvFilter::builder()
-        .with_default_directive(default_directive.to_string().parse().unwrap())
-        .from_env_lossy()
-        .add_directive("hyper::proto::h1=off".parse().unwrap());
+    tracing_subscriber::fmt::layer()
+        .with_ansi(true)
+        .with_target(true)
+        .with_filter(filter(default_directive))
+        .boxed()
+}
 
+/// Same as [`stdout`] but writes to stderr, for when stdout is used to stream
+/// results
+pub fn stderr<S>(default_directive: impl Display) -> BoxedLayer<S>
+where
+    S: Subscriber,
+    for<'a> S: LookupSpan<'a>,
+{
     tracing_subscriber::fmt::layer()
         .with_ansi(true)
         .with_target(true)
-        .with_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_filter(filter(default_directive))
         .boxed()
 }
+
+fn filter(default_directive: impl Display) -> EnvFilter {
+    EnvFilter::builder()
+        .with_default_directive(default_directive.to_string().parse().unwrap())
+        .from_env_lossy()
+        .add_directive("hyper::proto::h1=off".parse().unwrap())
+}
@@ -3,11 +3,14 @@ use std::hash::Hash;
 use ::relays_openapi::models::{
     GetDeliveredPayloads200ResponseInner, GetReceivedBids200ResponseInner,
 };
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
 
 use super::Relays;
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct RelayBid {
     pub relay:                  Relays,
     pub slot:                   u64,
@@ -26,21 +29,39 @@ pub struct RelayBid {
 
 impl RelayBid {
     pub fn new(bid: GetReceivedBids200ResponseInner, relay: Relays) -> Self {
-        Self {
-            relay,
-            slot: bid.slot.unwrap().parse().unwrap(),
-            parent_hash: bid.parent_hash.unwrap(),
-            block_hash: bid.block_hash.unwrap(),
-            builder_pubkey: bid.builder_pubkey.unwrap(),
-            proposer_fee_recipient: bid.proposer_fee_recipient.unwrap(),
-            gas_limit: bid.gas_limit.unwrap().parse().unwrap(),
-            gas_used: bid.gas_used.unwrap().parse().unwrap(),
-            value: bid.value.unwrap().parse().unwrap(),
-            block_number: bid.block_number.unwrap().parse().unwrap(),
-            num_tx: bid.num_tx.unwrap().parse().unwrap(),
-            timestamp: bid.timestamp.unwrap().parse().unwrap(),
-            timestamp_ms: bid.timestamp_ms.unwrap().parse().unwrap(),
+        Self::try_new(bid, relay).expect("relay returned an invalid bid")
+    }
+
+    /// Fails on missing or malformed fields, for bids that don't come from a
+    /// relay directly such as bid dumps
+    pub fn try_new(bid: GetReceivedBids200ResponseInner, relay: Relays) -> eyre::Result<Self> {
+        fn field<T: std::str::FromStr>(value: Option<String>, name: &str) -> eyre::Result<T> {
+            value
+                .ok_or_else(|| eyre::eyre!("bid is missing the {name}"))?
+                .parse()
+                .map_err(|_| eyre::eyre!("bid has an invalid {name}"))
         }
+
+        Ok(Self {
+            relay,
+            slot: field(bid.slot, "slot")?,
+            parent_hash: field(bid.parent_hash, "parent_hash")?,
+            block_hash: field(bid.block_hash, "block_hash")?,
+            builder_pubkey: field(bid.builder_pubkey, "builder_pubkey")?,
+            proposer_fee_recipient: field(bid.proposer_fee_recipient, "proposer_fee_recipient")?,
+            gas_limit: field(bid.gas_limit, "gas_limit")?,
+            gas_used: field(bid.gas_used, "gas_used")?,
+            value: field(bid.value, "value")?,
+            block_number: field(bid.block_number, "block_number")?,
+            num_tx: field(bid.num_tx, "num_tx")?,
+            timestamp: field(bid.timestamp, "timestamp")?,
+            timestamp_ms: field(bid.timestamp_ms, "timestamp_ms")?,
+        })
+    }
+
+    /// Parses a bid in the json format of the relay data api
+    pub fn from_json(json: &str, relay: Relays) -> eyre::Result<Self> {
+        Self::try_new(serde_json::from_str(json)?, relay)
     }
 
     pub fn calculate_epoch(&self) -> u64 {
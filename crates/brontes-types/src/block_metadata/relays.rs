@@ -1,3 +1,4 @@
+use redefined::self_convert_redefined;
 use relays_openapi::apis::{
     configuration::Configuration,
     data_api::{get_delivered_payloads, get_received_bids},
@@ -13,7 +14,8 @@ macro_rules! relays {
 
         #[derive(
             Debug, Copy, Clone, serde::Serialize, serde::Deserialize, Eq, PartialEq,
-            std::hash::Hash, PartialOrd, strum::EnumIter
+            std::hash::Hash, PartialOrd, strum::EnumIter, rkyv::Serialize, rkyv::Deserialize,
+            rkyv::Archive
         )]
         pub enum Relays {
             $($relay),*
@@ -42,6 +44,20 @@ macro_rules! relays {
                 write!(f, "{:?}", self)
             }
         }
+
+        impl std::str::FromStr for Relays {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $(
+                    if s.eq_ignore_ascii_case(stringify!($relay)) {
+                        return Ok(Relays::$relay)
+                    }
+                )*
+
+                Err(format!("unknown relay: {s}"))
+            }
+        }
     };
 }
 
@@ -89,6 +105,8 @@ relays!(
     ]
 );
 
+self_convert_redefined!(Relays);
+
 impl Relays {
     fn configuration(&self) -> Configuration {
         Configuration { base_path: self.url().to_string(), ..Default::default() }
@@ -146,6 +164,31 @@ impl Relays {
         Ok(None)
     }
 
+    /// All bids the relays received for the block, the relays that fail are
+    /// skipped
+    pub async fn get_block_bids(block_number: u64) -> Vec<RelayBid> {
+        futures::future::join_all(
+            Relays::iter()
+                .filter(|relay| relay.min_block_with_data() <= block_number)
+                .map(|relay| async move {
+                    match relay
+                        .get_received_bids(None, None, Some(block_number.to_string()), None, None)
+                        .await
+                    {
+                        Ok(bids) => bids,
+                        Err(e) => {
+                            tracing::error!(%relay, "error getting bids - {:?}", e);
+                            vec![]
+                        }
+                    }
+                }),
+        )
+        .await
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
     async fn get_winning_bid(
         self,
         block_number: u64,
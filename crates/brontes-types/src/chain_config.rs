@@ -0,0 +1,155 @@
+use std::{path::PathBuf, sync::OnceLock};
+
+use alloy_primitives::Address;
+use serde::Deserialize;
+
+use crate::{
+    constants::{BALANCER_V2_VAULT_ADDRESS, USDT_ADDRESS, USD_STABLES_BY_ADDRESS, WETH_ADDRESS},
+    FastHashMap,
+};
+
+static CHAIN_CONFIG: OnceLock<ChainConfig> = OnceLock::new();
+static MAINNET_CONFIG: OnceLock<ChainConfig> = OnceLock::new();
+
+/// The chain specific values brontes needs to analyse a chain. Loaded once at
+/// startup from the file passed with `--chain-config`, mainnet is used if no
+/// file is given.
+///
+/// ```toml
+/// chain = "holesky"
+/// wrapped_native = "0x..."
+/// quote_asset = "0x..."
+/// usd_stables = ["0x..."]
+/// classifier_config = "config/holesky_classifier_config.toml"
+/// balancer_v2_vault = "0x..."
+///
+/// [factories]
+/// UniswapV3Discovery = "0x..."
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChainConfig {
+    /// A chain known to reth (`mainnet`, `sepolia`, `holesky`, `goerli`) or
+    /// the path to a genesis file
+    pub chain:             String,
+    /// The wrapped native token. Native transfers are accounted in this token
+    pub wrapped_native:    Address,
+    /// The default asset profits are quoted in
+    pub quote_asset:       Address,
+    /// Usd stables, used to detect stable arbs
+    #[serde(default)]
+    pub usd_stables:       Vec<Address>,
+    /// The protocol deployments on this chain, relative to the workspace dir.
+    /// Defaults to `config/classifier_config.toml`
+    pub classifier_config: Option<PathBuf>,
+    /// Factory addresses keyed by the name of their discovery classifier, for
+    /// factories that are deployed to a different address than on mainnet
+    #[serde(default)]
+    pub factories:         FastHashMap<String, Address>,
+    /// The balancer v2 vault, which holds the balances of all v2 pools.
+    /// Defaults to the address it is deployed to on mainnet
+    #[serde(default = "default_balancer_v2_vault")]
+    pub balancer_v2_vault: Address,
+}
+
+fn default_balancer_v2_vault() -> Address {
+    BALANCER_V2_VAULT_ADDRESS
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self {
+            chain:             "mainnet".to_string(),
+            wrapped_native:    WETH_ADDRESS,
+            quote_asset:       USDT_ADDRESS,
+            usd_stables:       USD_STABLES_BY_ADDRESS.to_vec(),
+            classifier_config: None,
+            factories:         FastHashMap::default(),
+            balancer_v2_vault: BALANCER_V2_VAULT_ADDRESS,
+        }
+    }
+}
+
+impl ChainConfig {
+    /// Sets the config for the process. Needs to happen before anything reads
+    /// the config, returns the config back if it was already set.
+    pub fn init(self) -> Result<(), Self> {
+        CHAIN_CONFIG.set(self)
+    }
+
+    /// The config of the chain being analysed. The commands read it once and
+    /// hand it to the parser, pricer and inspectors, this is for the types that
+    /// can't be handed the config. Falls back to mainnet if it is read before
+    /// it was initialized.
+    pub fn get() -> &'static Self {
+        CHAIN_CONFIG.get_or_init(|| {
+            tracing::warn!(
+                target: "brontes",
+                "chain config read before it was initialized, using mainnet"
+            );
+            Self::default()
+        })
+    }
+
+    /// The mainnet config, for tests and tools that only run against mainnet
+    pub fn mainnet() -> &'static Self {
+        MAINNET_CONFIG.get_or_init(Self::default)
+    }
+
+    pub fn is_usd_stable(&self, address: &Address) -> bool {
+        self.usd_stables.contains(address)
+    }
+
+    /// The factory address on this chain for the given discovery classifier
+    pub fn factory(&self, discovery: &str) -> Option<Address> {
+        self.factories.get(discovery).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOLESKY_CONFIG: &str = r#"
+chain = "holesky"
+wrapped_native = "0x0101010101010101010101010101010101010101"
+quote_asset = "0x0202020202020202020202020202020202020202"
+usd_stables = ["0x0202020202020202020202020202020202020202"]
+classifier_config = "config/holesky_classifier_config.toml"
+
+[factories]
+UniswapV3Discovery = "0x0303030303030303030303030303030303030303"
+UniswapV2Discovery = "0x0404040404040404040404040404040404040404"
+"#;
+
+    #[test]
+    fn test_load_chain_config() {
+        let config: ChainConfig = toml::from_str(HOLESKY_CONFIG).unwrap();
+
+        assert_eq!(config.chain, "holesky");
+        assert_eq!(config.wrapped_native, Address::repeat_byte(1));
+        assert_eq!(config.quote_asset, Address::repeat_byte(2));
+        assert!(config.is_usd_stable(&Address::repeat_byte(2)));
+        assert!(!config.is_usd_stable(&USDT_ADDRESS));
+        assert_eq!(
+            config.classifier_config,
+            Some(PathBuf::from("config/holesky_classifier_config.toml"))
+        );
+        // the vault is at the same address on every chain
+        assert_eq!(config.balancer_v2_vault, BALANCER_V2_VAULT_ADDRESS);
+
+        assert_eq!(config.factory("UniswapV3Discovery"), Some(Address::repeat_byte(3)));
+        assert_eq!(config.factory("UniswapV2Discovery"), Some(Address::repeat_byte(4)));
+        assert_eq!(config.factory("SushiSwapV2Discovery"), None);
+    }
+
+    #[test]
+    fn test_mainnet_defaults() {
+        let config = ChainConfig::mainnet();
+
+        assert_eq!(config.chain, "mainnet");
+        assert_eq!(config.wrapped_native, WETH_ADDRESS);
+        assert_eq!(config.quote_asset, USDT_ADDRESS);
+        assert!(config.is_usd_stable(&USDT_ADDRESS));
+        assert!(config.factories.is_empty());
+    }
+}
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     db::redefined_types::primitives::AddressRedefined,
     implement_table_value_codecs_with_zc,
+    pair::Pair,
     serde_utils::{addresss, option_addresss, protocol},
     Protocol,
 };
@@ -59,6 +60,11 @@ impl ProtocolInfo {
 
         tokens
     }
+
+    /// all pairs that can be routed through this pool
+    pub fn get_pairs(&self) -> Vec<Pair> {
+        Pair::from_pool_tokens(&self.get_tokens())
+    }
 }
 
 impl IntoIterator for ProtocolInfo {
@@ -1,6 +1,6 @@
 use alloy_primitives::Address;
 use clickhouse::Row;
-use redefined::Redefined;
+use redefined::{self_convert_redefined, Redefined};
 use reth_rpc_types::beacon::BlsPublicKey;
 use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
@@ -91,6 +91,65 @@ impl BuilderInfo {
 
 implement_table_value_codecs_with_zc!(BuilderInfoRedefined);
 
+/// How the builder paid the proposers of its mev boost blocks compared to the
+/// bids the relays reported. Tracked by brontes from the processed blocks and
+/// stored apart from the builder metadata in the `BuilderProposerPayments`
+/// table
+#[derive(
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    rSerialize,
+    rDeserialize,
+    Archive,
+)]
+pub struct ProposerPaymentStats {
+    /// Blocks where the proposer payment was verified
+    pub blocks:              u64,
+    /// Blocks where the proposer received less than the relay bid
+    pub underpaid_blocks:    u64,
+    /// Sum of the shortfalls of the underpaid blocks (in wei)
+    pub total_underpaid:     u128,
+    /// Sum of the payment deltas of all blocks (in wei)
+    pub total_payment_delta: i128,
+}
+
+impl ProposerPaymentStats {
+    /// The stats of a single block, see `MevBlock::payment_delta`
+    pub fn from_payment_delta(payment_delta: i128) -> Self {
+        let underpaid = payment_delta < 0;
+        Self {
+            blocks:              1,
+            underpaid_blocks:    underpaid as u64,
+            total_underpaid:     if underpaid { payment_delta.unsigned_abs() } else { 0 },
+            total_payment_delta: payment_delta,
+        }
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.blocks += other.blocks;
+        self.underpaid_blocks += other.underpaid_blocks;
+        self.total_underpaid += other.total_underpaid;
+        self.total_payment_delta += other.total_payment_delta;
+    }
+
+    /// Undoes a [`Self::merge`] of `other`
+    pub fn remove(&mut self, other: Self) {
+        self.blocks = self.blocks.saturating_sub(other.blocks);
+        self.underpaid_blocks = self.underpaid_blocks.saturating_sub(other.underpaid_blocks);
+        self.total_underpaid = self.total_underpaid.saturating_sub(other.total_underpaid);
+        self.total_payment_delta -= other.total_payment_delta;
+    }
+}
+
+self_convert_redefined!(ProposerPaymentStats);
+implement_table_value_codecs_with_zc!(ProposerPaymentStats);
+
 #[serde_as]
 #[derive(Debug, Default, Row, PartialEq, Clone, Serialize, Deserialize)]
 pub struct BuilderInfoWithAddress {
@@ -122,3 +181,19 @@ impl BuilderInfoWithAddress {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_proposer_payment() {
+        let mut stats = ProposerPaymentStats::from_payment_delta(100);
+        let expected = stats;
+
+        stats.merge(ProposerPaymentStats::from_payment_delta(-40));
+        stats.remove(ProposerPaymentStats::from_payment_delta(-40));
+
+        assert_eq!(stats, expected);
+    }
+}
@@ -21,11 +21,11 @@ use serde::{Deserialize, Serialize};
 use tracing::debug;
 
 use crate::{
-    constants::{ETH_ADDRESS, WETH_ADDRESS},
+    constants::ETH_ADDRESS,
     db::{clickhouse_serde::dex::dex_quote, redefined_types::malachite::RationalRedefined},
     implement_table_value_codecs_with_zc,
     pair::{Pair, PairRedefined},
-    FastHashMap,
+    ChainConfig, FastHashMap,
 };
 
 /// Represents the DEX prices of a token pair before (`pre_state`) and after a
@@ -109,10 +109,10 @@ impl DexQuotes {
     #[cfg(feature = "test_pricing")]
     pub fn price_at(&self, mut pair: Pair, mut tx: usize) -> Option<DexPrices> {
         if pair.0 == ETH_ADDRESS {
-            pair.0 = WETH_ADDRESS;
+            pair.0 = ChainConfig::get().wrapped_native;
         }
         if pair.1 == ETH_ADDRESS {
-            pair.1 = WETH_ADDRESS;
+            pair.1 = ChainConfig::get().wrapped_native;
         }
         let s_idx = tx;
 
@@ -146,10 +146,10 @@ impl DexQuotes {
     #[cfg(not(feature = "test_pricing"))]
     pub fn price_at(&self, mut pair: Pair, tx: usize) -> Option<DexPrices> {
         if pair.0 == ETH_ADDRESS {
-            pair.0 = WETH_ADDRESS;
+            pair.0 = ChainConfig::get().wrapped_native;
         }
         if pair.1 == ETH_ADDRESS {
-            pair.1 = WETH_ADDRESS;
+            pair.1 = ChainConfig::get().wrapped_native;
         }
         let s_idx = tx;
 
@@ -173,10 +173,10 @@ impl DexQuotes {
 
     pub fn price_at_or_before(&self, mut pair: Pair, mut tx: usize) -> Option<DexPrices> {
         if pair.0 == ETH_ADDRESS {
-            pair.0 = WETH_ADDRESS;
+            pair.0 = ChainConfig::get().wrapped_native;
         }
         if pair.1 == ETH_ADDRESS {
-            pair.1 = WETH_ADDRESS;
+            pair.1 = ChainConfig::get().wrapped_native;
         }
         let s_idx = tx;
 
@@ -207,10 +207,10 @@ impl DexQuotes {
 
     pub fn price_for_block(&self, mut pair: Pair, price_at: BlockPrice) -> Option<Rational> {
         if pair.0 == ETH_ADDRESS {
-            pair.0 = WETH_ADDRESS;
+            pair.0 = ChainConfig::get().wrapped_native;
         }
         if pair.1 == ETH_ADDRESS {
-            pair.1 = WETH_ADDRESS;
+            pair.1 = ChainConfig::get().wrapped_native;
         }
 
         match price_at {
@@ -262,10 +262,10 @@ impl DexQuotes {
 
     fn get_price(&self, mut pair: Pair, tx: usize) -> Option<&DexPrices> {
         if pair.0 == ETH_ADDRESS {
-            pair.0 = WETH_ADDRESS;
+            pair.0 = ChainConfig::get().wrapped_native;
         }
         if pair.1 == ETH_ADDRESS {
-            pair.1 = WETH_ADDRESS;
+            pair.1 = ChainConfig::get().wrapped_native;
         }
         self.0.get(tx)?.as_ref()?.get(&pair)
     }
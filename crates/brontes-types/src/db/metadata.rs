@@ -15,12 +15,11 @@ use super::{
 };
 use crate::{
     block_metadata::RelayBlockMetadata,
-    constants::WETH_ADDRESS,
     db::{dex::BlockPrice, redefined_types::primitives::*},
     implement_table_value_codecs_with_zc,
     pair::Pair,
     serde_utils::{option_addresss, u256, vec_txhash},
-    FastHashSet,
+    ChainConfig, FastHashSet,
 };
 #[allow(unused_imports)]
 use crate::{db::cex::CexExchange, normalized_actions::NormalizedSwap};
@@ -123,7 +122,10 @@ impl Metadata {
         self.dex_quotes
             .as_ref()
             .and_then(|dex_quotes| {
-                dex_quotes.price_for_block(Pair(WETH_ADDRESS, quote_token), BlockPrice::Average)
+                dex_quotes.price_for_block(
+                    Pair(ChainConfig::get().wrapped_native, quote_token),
+                    BlockPrice::Average,
+                )
             })
             .unwrap_or(Rational::ZERO)
     }
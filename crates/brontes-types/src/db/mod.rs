@@ -20,6 +20,7 @@ pub mod mev_block;
 pub mod normalized_actions;
 pub mod pool_creation_block;
 pub mod redefined_types;
+pub mod relay_bids;
 pub mod searcher;
 pub mod token_info;
 pub mod traces;
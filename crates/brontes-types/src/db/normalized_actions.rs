@@ -157,6 +157,7 @@ pub enum ActionKind {
     NewPool,
     PoolConfigUpdate,
     Aggregator,
+    OracleUpdate,
     Revert,
 }
 
@@ -178,6 +179,7 @@ impl From<&Action> for ActionKind {
             Action::NewPool(_) => ActionKind::NewPool,
             Action::PoolConfigUpdate(_) => ActionKind::PoolConfigUpdate,
             Action::Aggregator(_) => ActionKind::Aggregator,
+            Action::OracleUpdate(_) => ActionKind::OracleUpdate,
             Action::Revert => ActionKind::Revert,
         }
     }
@@ -0,0 +1,16 @@
+use clickhouse::Row;
+use redefined::Redefined;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    block_metadata::{RelayBid, RelayBidRedefined},
+    implement_table_value_codecs_with_zc,
+};
+
+/// All bids the relays received for a block
+#[derive(Debug, Default, Clone, Row, PartialEq, Serialize, Deserialize, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct BlockRelayBids(pub Vec<RelayBid>);
+
+implement_table_value_codecs_with_zc!(BlockRelayBidsRedefined);
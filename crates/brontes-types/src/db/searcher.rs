@@ -12,7 +12,7 @@ use strum::AsRefStr;
 use crate::{
     db::redefined_types::primitives::AddressRedefined,
     implement_table_value_codecs_with_zc,
-    mev::{BundleHeader, MevCount, MevType},
+    mev::{BundleHeader, FailedAttempt, MevCount, MevType},
     serde_utils::{addresss, option_addresss, vec_address},
 };
 
@@ -74,6 +74,8 @@ impl SearcherInfo {
             MevType::AtomicArb => self.mev_count.atomic_backrun_count,
             MevType::Liquidation => self.mev_count.liquidation_count,
             MevType::SearcherTx => self.mev_count.searcher_tx_count,
+            MevType::LongTail => self.mev_count.long_tail_count,
+            MevType::Frontrun => self.mev_count.frontrun_count,
             MevType::Unknown => None,
         }
     }
@@ -172,10 +174,59 @@ impl SearcherInfo {
         self.mev_count.increment_count(header.mev_type);
         self.gas_bids.account_gas(header);
     }
+
+    /// Undoes [`Self::update_with_bundle`] for a bundle of a reorged block
+    pub fn revert_bundle(&mut self, header: &BundleHeader) {
+        self.pnl.remove_pnl(header);
+        self.mev_count.decrement_count(header.mev_type);
+        self.gas_bids.remove_gas(header);
+    }
 }
 
 implement_table_value_codecs_with_zc!(SearcherInfoRedefined);
 
+/// Reverted and no-op transactions of a searcher eoa or contract, the gas of
+/// these is burnt on mev attempts that didn't land. Tracked by brontes from the
+/// processed blocks and stored apart from the searcher info in the
+/// `SearcherFailedAttempts` table
+#[derive(
+    Debug,
+    Default,
+    PartialEq,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    rkyv::Serialize,
+    rDeserialize,
+    Archive,
+)]
+pub struct FailedAttemptStats {
+    pub count:        u64,
+    /// Gas paid for the failed attempts (in usd)
+    pub gas_paid_usd: f64,
+}
+
+impl FailedAttemptStats {
+    pub fn from_attempt(attempt: &FailedAttempt) -> Self {
+        Self { count: 1, gas_paid_usd: attempt.gas_paid_usd }
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.count += other.count;
+        self.gas_paid_usd += other.gas_paid_usd;
+    }
+
+    /// Undoes a [`Self::merge`] of `other`
+    pub fn remove(&mut self, other: Self) {
+        self.count = self.count.saturating_sub(other.count);
+        self.gas_paid_usd -= other.gas_paid_usd;
+    }
+}
+
+self_convert_redefined!(FailedAttemptStats);
+implement_table_value_codecs_with_zc!(FailedAttemptStats);
+
 #[serde_as]
 #[derive(
     Debug,
@@ -205,87 +256,37 @@ self_convert_redefined!(TollByType);
 
 impl TollByType {
     pub fn account_pnl(&mut self, header: &BundleHeader) {
-        self.total += header.profit_usd;
-        match header.mev_type {
-            MevType::CexDexTrades => {
-                self.cex_dex_trades = Some(
-                    self.cex_dex_trades
-                        .unwrap_or_default()
-                        .add(header.profit_usd),
-                )
-            }
-            MevType::CexDexQuotes => {
-                self.cex_dex_quotes = Some(
-                    self.cex_dex_quotes
-                        .unwrap_or_default()
-                        .add(header.profit_usd),
-                )
-            }
-            MevType::Sandwich => {
-                self.sandwich = Some(self.sandwich.unwrap_or_default().add(header.profit_usd))
-            }
-            MevType::AtomicArb => {
-                self.atomic_backrun = Some(
-                    self.atomic_backrun
-                        .unwrap_or_default()
-                        .add(header.profit_usd),
-                )
-            }
-            MevType::Jit => self.jit = Some(self.jit.unwrap_or_default().add(header.profit_usd)),
-            MevType::JitSandwich => {
-                self.jit_sandwich =
-                    Some(self.jit_sandwich.unwrap_or_default().add(header.profit_usd))
-            }
-            MevType::Liquidation => {
-                self.liquidation = Some(self.liquidation.unwrap_or_default().add(header.profit_usd))
-            }
-            MevType::SearcherTx => {
-                self.searcher_tx = Some(self.searcher_tx.unwrap_or_default().add(header.profit_usd))
-            }
-            _ => (),
-        }
+        self.account(header.mev_type, header.profit_usd);
     }
 
     pub fn account_gas(&mut self, header: &BundleHeader) {
-        self.total += header.bribe_usd;
-        match header.mev_type {
-            MevType::CexDexQuotes => {
-                self.cex_dex_quotes = Some(
-                    self.cex_dex_quotes
-                        .unwrap_or_default()
-                        .add(header.bribe_usd),
-                )
-            }
-            MevType::CexDexTrades => {
-                self.cex_dex_trades = Some(
-                    self.cex_dex_trades
-                        .unwrap_or_default()
-                        .add(header.bribe_usd),
-                )
-            }
-            MevType::Sandwich => {
-                self.sandwich = Some(self.sandwich.unwrap_or_default().add(header.bribe_usd))
-            }
-            MevType::AtomicArb => {
-                self.atomic_backrun = Some(
-                    self.atomic_backrun
-                        .unwrap_or_default()
-                        .add(header.bribe_usd),
-                )
-            }
-            MevType::Jit => self.jit = Some(self.jit.unwrap_or_default().add(header.bribe_usd)),
-            MevType::JitSandwich => {
-                self.jit_sandwich =
-                    Some(self.jit_sandwich.unwrap_or_default().add(header.bribe_usd))
-            }
-            MevType::Liquidation => {
-                self.liquidation = Some(self.liquidation.unwrap_or_default().add(header.bribe_usd))
-            }
-            MevType::SearcherTx => {
-                self.searcher_tx = Some(self.searcher_tx.unwrap_or_default().add(header.bribe_usd))
-            }
-            _ => (),
-        }
+        self.account(header.mev_type, header.bribe_usd);
+    }
+
+    /// Undoes [`Self::account_pnl`], used when a block is reorged out
+    pub fn remove_pnl(&mut self, header: &BundleHeader) {
+        self.account(header.mev_type, -header.profit_usd);
+    }
+
+    /// Undoes [`Self::account_gas`], used when a block is reorged out
+    pub fn remove_gas(&mut self, header: &BundleHeader) {
+        self.account(header.mev_type, -header.bribe_usd);
+    }
+
+    fn account(&mut self, mev_type: MevType, amount: f64) {
+        self.total += amount;
+        let toll = match mev_type {
+            MevType::CexDexTrades => &mut self.cex_dex_trades,
+            MevType::CexDexQuotes => &mut self.cex_dex_quotes,
+            MevType::Sandwich => &mut self.sandwich,
+            MevType::AtomicArb => &mut self.atomic_backrun,
+            MevType::Jit => &mut self.jit,
+            MevType::JitSandwich => &mut self.jit_sandwich,
+            MevType::Liquidation => &mut self.liquidation,
+            MevType::SearcherTx => &mut self.searcher_tx,
+            _ => return,
+        };
+        *toll = Some(toll.unwrap_or_default().add(amount));
     }
 }
 
@@ -434,3 +435,49 @@ pub enum SearcherEoaContract {
     EOA      = 0,
     Contract = 1,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(mev_type: MevType, profit_usd: f64, bribe_usd: f64) -> BundleHeader {
+        BundleHeader { mev_type, profit_usd, bribe_usd, ..Default::default() }
+    }
+
+    #[test]
+    fn test_revert_bundle() {
+        let kept = header(MevType::Sandwich, 10.0, 2.0);
+        let reorged = header(MevType::Sandwich, 5.0, 1.0);
+
+        let mut info = SearcherInfo::default();
+        info.update_with_bundle(&kept);
+        let expected = info.clone();
+
+        info.update_with_bundle(&reorged);
+        info.revert_bundle(&reorged);
+
+        assert_eq!(info, expected);
+    }
+
+    #[test]
+    fn test_revert_bundle_of_new_type() {
+        let mut info = SearcherInfo::default();
+        info.update_with_bundle(&header(MevType::AtomicArb, 3.0, 1.0));
+        info.revert_bundle(&header(MevType::AtomicArb, 3.0, 1.0));
+
+        assert_eq!(info.mev_count.bundle_count, 0);
+        assert_eq!(info.mev_count.atomic_backrun_count, Some(0));
+        assert_eq!(info.pnl.total, 0.0);
+        assert_eq!(info.gas_bids.atomic_backrun, Some(0.0));
+    }
+
+    #[test]
+    fn test_remove_failed_attempts() {
+        let attempt = FailedAttempt { gas_paid_usd: 4.0, ..Default::default() };
+        let mut stats = FailedAttemptStats::from_attempt(&attempt);
+        stats.merge(FailedAttemptStats::from_attempt(&attempt));
+        stats.remove(FailedAttemptStats::from_attempt(&attempt));
+
+        assert_eq!(stats, FailedAttemptStats::from_attempt(&attempt));
+    }
+}
@@ -11,10 +11,11 @@ use serde::{ser::SerializeStruct, Deserialize, Serialize};
 
 use super::clickhouse_serde::token_info::token_info_des;
 use crate::{
-    constants::{USDC_ADDRESS, USDT_ADDRESS, WETH_ADDRESS},
+    constants::{USDC_ADDRESS, USDT_ADDRESS},
     db::redefined_types::primitives::AddressRedefined,
     implement_table_value_codecs_with_zc,
     serde_utils::addresss,
+    ChainConfig,
 };
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Deserialize, Redefined)]
@@ -31,14 +32,14 @@ impl TokenInfoWithAddress {
     pub fn native_eth() -> Self {
         Self {
             inner:   TokenInfo { decimals: 18, symbol: "ETH".to_string() },
-            address: WETH_ADDRESS,
+            address: ChainConfig::get().wrapped_native,
         }
     }
 
     pub fn weth() -> Self {
         Self {
             inner:   TokenInfo { decimals: 18, symbol: "WETH".to_string() },
-            address: WETH_ADDRESS,
+            address: ChainConfig::get().wrapped_native,
         }
     }
 
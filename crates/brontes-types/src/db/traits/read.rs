@@ -2,9 +2,15 @@ use alloy_primitives::Address;
 
 use crate::{
     db::{
-        address_metadata::AddressMetadata, address_to_protocol_info::ProtocolInfo,
-        builder::BuilderInfo, cex::trades::CexTradeMap, dex::DexQuotes, metadata::Metadata,
-        mev_block::MevBlockWithClassified, searcher::SearcherInfo,
+        address_metadata::AddressMetadata,
+        address_to_protocol_info::ProtocolInfo,
+        builder::{BuilderInfo, ProposerPaymentStats},
+        cex::{quotes::CexPriceMap, trades::CexTradeMap},
+        dex::DexQuotes,
+        metadata::{BlockMetadataInner, Metadata},
+        mev_block::MevBlockWithClassified,
+        relay_bids::BlockRelayBids,
+        searcher::{FailedAttemptStats, SearcherInfo},
         token_info::TokenInfoWithAddress,
     },
     pair::Pair,
@@ -12,7 +18,8 @@ use crate::{
     FastHashMap, Protocol,
 };
 pub type AllSearcherInfo = (Vec<(Address, SearcherInfo)>, Vec<(Address, SearcherInfo)>);
-pub type ProtocolCreatedRange = FastHashMap<u64, Vec<(Address, Protocol, Pair)>>;
+pub type ProtocolCreatedBefore = FastHashMap<(Address, Protocol), Vec<Pair>>;
+pub type ProtocolCreatedRange = FastHashMap<u64, Vec<(Address, Protocol, Vec<Pair>)>>;
 
 #[auto_impl::auto_impl(&, Box)]
 pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
@@ -89,6 +96,16 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
         searcher_contract: Vec<Address>,
     ) -> eyre::Result<FastHashMap<Address, SearcherInfo>>;
 
+    /// The reverted and no-op transactions of a searcher eoa or contract
+    fn try_fetch_searcher_failed_attempts(
+        &self,
+        searcher: Address,
+    ) -> eyre::Result<Option<FailedAttemptStats>>;
+
+    fn fetch_all_searcher_failed_attempts(
+        &self,
+    ) -> eyre::Result<Vec<(Address, FailedAttemptStats)>>;
+
     fn try_fetch_builder_info(
         &self,
         builder_coinbase_addr: Address,
@@ -96,6 +113,16 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
 
     fn fetch_all_builder_info(&self) -> eyre::Result<Vec<(Address, BuilderInfo)>>;
 
+    /// How the builder paid the proposers of the processed blocks it built
+    fn try_fetch_builder_proposer_payments(
+        &self,
+        builder_coinbase_addr: Address,
+    ) -> eyre::Result<Option<ProposerPaymentStats>>;
+
+    fn fetch_all_builder_proposer_payments(
+        &self,
+    ) -> eyre::Result<Vec<(Address, ProposerPaymentStats)>>;
+
     fn get_metadata(&self, block_num: u64, quote_asset: Address) -> eyre::Result<Metadata>;
 
     fn get_cex_trades(&self, block: u64) -> eyre::Result<CexTradeMap>;
@@ -119,15 +146,22 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
         end_block: u64,
     ) -> eyre::Result<Vec<MevBlockWithClassified>>;
 
+    /// The stored mev block and bundles of the block, if it was processed
+    fn try_fetch_mev_block(&self, block_num: u64) -> eyre::Result<Option<MevBlockWithClassified>>;
+
+    /// The stored mev blocks of `start_block..=end_block`
+    fn fetch_mev_block_range(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> eyre::Result<Vec<MevBlockWithClassified>>;
+
     fn fetch_all_mev_blocks(
         &self,
         start_block: Option<u64>,
     ) -> eyre::Result<Vec<MevBlockWithClassified>>;
 
-    fn protocols_created_before(
-        &self,
-        start_block: u64,
-    ) -> eyre::Result<FastHashMap<(Address, Protocol), Pair>>;
+    fn protocols_created_before(&self, start_block: u64) -> eyre::Result<ProtocolCreatedBefore>;
 
     fn protocols_created_range(
         &self,
@@ -155,4 +189,20 @@ pub trait LibmdbxReader: Send + Sync + Unpin + 'static {
     }
 
     fn load_trace(&self, block_num: u64) -> eyre::Result<Vec<TxTrace>>;
+
+    /// The stored block info of the block, none if it isn't stored
+    fn try_fetch_block_info(&self, block_num: u64) -> eyre::Result<Option<BlockMetadataInner>>;
+
+    /// The stored cex quotes of the block, none if they aren't stored
+    fn try_fetch_cex_quotes(&self, block_num: u64) -> eyre::Result<Option<CexPriceMap>>;
+
+    /// The cex trades stored for the block. Unlike [`Self::get_cex_trades`]
+    /// the trades of the following blocks aren't merged in
+    fn try_fetch_block_cex_trades(&self, block_num: u64) -> eyre::Result<Option<CexTradeMap>>;
+
+    /// The stored traces of the block, none if they aren't stored
+    fn try_fetch_traces(&self, block_num: u64) -> eyre::Result<Option<Vec<TxTrace>>>;
+
+    /// The imported relay bids of the block, none if they aren't stored
+    fn try_fetch_relay_bids(&self, block_num: u64) -> eyre::Result<Option<BlockRelayBids>>;
 }
@@ -1,3 +1,5 @@
+use std::ops::RangeInclusive;
+
 use alloy_primitives::Address;
 use futures::Future;
 
@@ -6,7 +8,7 @@ use crate::{
         address_metadata::AddressMetadata, block_analysis::BlockAnalysis, builder::BuilderInfo,
         dex::DexQuotes, searcher::SearcherInfo,
     },
-    mev::{Bundle, MevBlock},
+    mev::{Bundle, FailedAttempt, MevBlock},
     normalized_actions::Action,
     structured_trace::TxTrace,
     BlockTree, Protocol,
@@ -81,6 +83,17 @@ pub trait DBWriter: Send + Unpin + 'static {
             .write_searcher_contract_info(searcher_contract, searcher_info)
     }
 
+    /// Adds the failed attempts of a processed block to the stats of their
+    /// searcher eoas and contracts
+    fn write_searcher_failed_attempts(
+        &self,
+        block_number: u64,
+        attempts: Vec<FailedAttempt>,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner()
+            .write_searcher_failed_attempts(block_number, attempts)
+    }
+
     fn write_builder_info(
         &self,
         builder_address: Address,
@@ -90,6 +103,17 @@ pub trait DBWriter: Send + Unpin + 'static {
             .write_builder_info(builder_address, builder_info)
     }
 
+    /// Adds the proposer payment of a processed block to the payment stats of
+    /// the builder that built it
+    fn write_builder_proposer_payment(
+        &self,
+        builder_address: Address,
+        payment_delta: i128,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner()
+            .write_builder_proposer_payment(builder_address, payment_delta)
+    }
+
     fn write_address_meta(
         &self,
         address: Address,
@@ -124,4 +148,14 @@ pub trait DBWriter: Send + Unpin + 'static {
     ) -> impl Future<Output = eyre::Result<()>> + Send {
         self.inner().save_traces(block, traces)
     }
+
+    /// removes all results that were written for the given blocks and reverts
+    /// what they added to the searcher and builder stats so that they can be
+    /// re-run after a reorg
+    fn rollback_blocks(
+        &self,
+        blocks: RangeInclusive<u64>,
+    ) -> impl Future<Output = eyre::Result<()>> + Send {
+        self.inner().rollback_blocks(blocks)
+    }
 }
@@ -146,6 +146,12 @@ pub fn display_sandwich(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result
             .to_string()
             .bright_red()
     )?;
+    writeln!(
+        f,
+        " - {}: {}",
+        "Victim Loss (USD)".bright_white(),
+        format!("{:.2}", sandwich_data.total_victim_loss_usd()).bright_red()
+    )?;
 
     bundle
         .header
@@ -1014,6 +1020,156 @@ pub fn display_searcher_tx(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Resu
     Ok(())
 }
 
+pub fn display_long_tail(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result {
+    let ascii_header = indoc! {r#"
+
+         _                         _____     _ _
+        | |                       |_   _|   (_) |
+        | |     ___  _ __   __ _    | | __ _ _| |
+        | |    / _ \| '_ \ / _` |   | |/ _` | | |
+        | |___| (_) | | | | (_| |   | | (_| | | |
+        \_____/\___/|_| |_|\__, |   \_/\__,_|_|_|
+                            __/ |
+                           |___/
+
+    "#};
+
+    for line in ascii_header.lines() {
+        writeln!(f, "{}", line.bright_red())?;
+    }
+
+    let long_tail_data = match &bundle.data {
+        BundleData::LongTail(data) => data,
+        _ => panic!("Wrong bundle type"),
+    };
+
+    // MEV Bot Details
+    writeln!(f, "{}: \n", "Transaction Details".bold().underline().bright_yellow())?;
+    writeln!(f, "   - EOA: {}", bundle.header.eoa)?;
+
+    match bundle.header.mev_contract {
+        Some(contract) => {
+            writeln!(f, "   - Mev Contract: {}", formate_etherscan_address_url(&contract))?;
+        }
+        None => {
+            writeln!(f, "   - Mev Contract: None")?;
+        }
+    }
+
+    writeln!(f, "   - Pool: {}", formate_etherscan_address_url(&long_tail_data.pool))?;
+    writeln!(f, "   - Token: {}", formate_etherscan_address_url(&long_tail_data.token))?;
+    writeln!(f, "   - Launch Block: {}", long_tail_data.launch_block.to_string().bold())?;
+
+    // Entries
+    writeln!(f, "\n{}\n", "Entries".bright_yellow().underline())?;
+    for (i, (tx_hash, swaps)) in long_tail_data
+        .entry_tx_hashes
+        .iter()
+        .zip(long_tail_data.entry_swaps.iter())
+        .enumerate()
+    {
+        writeln!(
+            f,
+            " - {}: {}",
+            format!("Entry {}", i + 1).bright_blue(),
+            format_etherscan_url(tx_hash)
+        )?;
+        for swap in swaps {
+            writeln!(f, "    - {}", swap)?;
+        }
+    }
+
+    // Exit
+    writeln!(f, "\n{}\n", "Exit".bright_yellow().underline())?;
+    writeln!(
+        f,
+        " - {}: {}",
+        "Transaction".bright_blue(),
+        format_etherscan_url(&long_tail_data.exit_tx_hash)
+    )?;
+    for swap in &long_tail_data.exit_swaps {
+        writeln!(f, "    - {}", swap)?;
+    }
+
+    writeln!(f, "\n{}: \n", "PnL".bold().underline().bright_yellow())?;
+    writeln!(f, "   - Realized Profit (USD): {}", format_profit(bundle.header.profit_usd))?;
+    writeln!(f, "   - Bribe (USD): {}", (format_bribe(bundle.header.bribe_usd)).to_string().red())?;
+
+    Ok(())
+}
+
+pub fn display_frontrun(bundle: &Bundle, f: &mut fmt::Formatter) -> fmt::Result {
+    let ascii_header = indoc! {r#"
+
+         ______                _
+        |  ____|              | |
+        | |__ _ __ ___  _ __ | |_ _ __ _   _ _ __
+        |  __| '__/ _ \| '_ \| __| '__| | | | '_ \
+        | |  | | | (_) | | | | |_| |  | |_| | | | |
+        |_|  |_|  \___/|_| |_|\__|_|   \__,_|_| |_|
+
+    "#};
+
+    for line in ascii_header.lines() {
+        writeln!(f, "{}", line.bright_red())?;
+    }
+
+    let frontrun_data = match &bundle.data {
+        BundleData::Frontrun(data) => data,
+        _ => panic!("Wrong bundle type"),
+    };
+
+    // MEV Bot Details
+    writeln!(f, "{}: \n", "Transaction Details".bold().underline().bright_yellow())?;
+    writeln!(f, "   - EOA: {}", bundle.header.eoa)?;
+
+    match bundle.header.mev_contract {
+        Some(contract) => {
+            writeln!(f, "   - Mev Contract: {}", formate_etherscan_address_url(&contract))?;
+        }
+        None => {
+            writeln!(f, "   - Mev Contract: None")?;
+        }
+    }
+
+    // Frontrun
+    writeln!(f, "\n{}\n", "Frontrun".bright_yellow().underline())?;
+    writeln!(
+        f,
+        " - {}: {}",
+        "Transaction".bright_blue(),
+        format_etherscan_url(&frontrun_data.frontrun_tx_hash)
+    )?;
+    for swap in &frontrun_data.frontrun_swaps {
+        writeln!(f, "    - {}", swap)?;
+    }
+
+    // Victims
+    writeln!(f, "\n{}\n", "Displaced Transactions".bright_yellow().underline())?;
+    for (i, ((tx_hash, reverted), outbid)) in frontrun_data
+        .victim_tx_hashes
+        .iter()
+        .zip(frontrun_data.victim_reverted.iter())
+        .zip(frontrun_data.priority_fee_outbids())
+        .enumerate()
+    {
+        writeln!(
+            f,
+            " - {}: {}",
+            format!("Victim {}", i + 1).bright_blue(),
+            format_etherscan_url(tx_hash)
+        )?;
+        writeln!(f, "    - Reverted: {}", reverted)?;
+        writeln!(f, "    - Priority Fee Outbid: {} wei", outbid)?;
+    }
+
+    writeln!(f, "\n{}: \n", "PnL".bold().underline().bright_yellow())?;
+    writeln!(f, "   - Realized Profit (USD): {}", format_profit(bundle.header.profit_usd))?;
+    writeln!(f, "   - Bribe (USD): {}", (format_bribe(bundle.header.bribe_usd)).to_string().red())?;
+
+    Ok(())
+}
+
 // Helper function to format profit values
 fn format_profit(value: f64) -> ColoredString {
     if value < 0.0 {
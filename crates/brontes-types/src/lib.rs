@@ -21,6 +21,8 @@ pub mod action_iter;
 pub use action_iter::*;
 pub mod executor;
 pub use executor::*;
+pub mod chain_config;
+pub use chain_config::ChainConfig;
 pub mod constants;
 pub mod db;
 pub mod display;
@@ -0,0 +1,309 @@
+use std::{cmp::Reverse, fmt};
+
+use colored::Colorize;
+use itertools::Itertools;
+use redefined::{self_convert_redefined, Redefined};
+use reth_primitives::B256;
+use rkyv::{Archive, Deserialize as rDeser, Serialize as rSer};
+use serde::{Deserialize, Serialize};
+
+use crate::block_metadata::RelayBid;
+
+/// How the relay auction of the block played out, built from all bids the
+/// relays received for the slot.
+///
+/// All times are in milliseconds after the start of the slot, which is the
+/// block timestamp.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSer, rDeser, Archive))]
+pub struct BidAnalysis {
+    pub slot:                     u64,
+    /// Bids for the slot, the same bid is counted once per relay
+    pub bid_count:                u64,
+    pub builder_count:            u64,
+    pub winning_builder_pubkey:   String,
+    pub winning_bid:              u128,
+    /// When the winning block was first received by a relay
+    pub winning_bid_time_ms:      i64,
+    /// The best bid of another builder received before the winning bid was
+    /// last received, of equal bids the one received first
+    pub runner_up_builder_pubkey: Option<String>,
+    pub runner_up_bid:            Option<u128>,
+    pub winning_margin:           Option<i128>,
+    /// Share of the block value the winning builder didn't bid to the
+    /// proposer
+    pub bid_shading:              Option<f64>,
+    /// When the block was seen on the p2p network, otherwise when the last
+    /// bid of the slot was received. Proposers playing timing games delay
+    /// asking for the header to collect higher bids
+    pub proposer_delay_ms:        i64,
+    /// Every increase of the best bid of the slot
+    #[redefined(same_fields)]
+    pub bid_curve:                Vec<BidCurvePoint>,
+}
+
+#[derive(
+    Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Default, rSer, rDeser, Archive,
+)]
+pub struct BidCurvePoint {
+    pub time_ms: i64,
+    pub value:   u128,
+}
+
+self_convert_redefined!(BidCurvePoint);
+
+impl BidAnalysis {
+    /// None if none of the bids is for the block
+    pub fn new(
+        bids: &[RelayBid],
+        block_hash: B256,
+        parent_hash: B256,
+        block_timestamp: u64,
+        p2p_timestamp: Option<u64>,
+        block_value: u128,
+    ) -> Option<Self> {
+        let block_hash = format!("{:?}", block_hash);
+        let parent_hash = format!("{:?}", parent_hash);
+        let slot_start = block_timestamp as i64 * 1000;
+
+        let bids = bids
+            .iter()
+            .filter(|bid| bid.parent_hash.to_lowercase() == parent_hash)
+            .sorted_by_key(|bid| bid.timestamp_ms)
+            .collect_vec();
+
+        let winning = bids
+            .iter()
+            .filter(|bid| bid.block_hash.to_lowercase() == block_hash)
+            .collect_vec();
+        let first_winning = winning.first()?;
+        let last_winning = winning.last()?;
+
+        let runner_up = bids
+            .iter()
+            .filter(|bid| {
+                bid.builder_pubkey != first_winning.builder_pubkey
+                    && bid.timestamp_ms <= last_winning.timestamp_ms
+            })
+            .max_by_key(|bid| (bid.value, Reverse(bid.timestamp_ms)));
+
+        let mut best = 0;
+        let bid_curve = bids
+            .iter()
+            .filter(|bid| {
+                let increase = bid.value > best;
+                best = best.max(bid.value);
+                increase
+            })
+            .map(|bid| BidCurvePoint {
+                time_ms: bid.timestamp_ms as i64 - slot_start,
+                value:   bid.value,
+            })
+            .collect_vec();
+
+        let proposer_seen_ms = p2p_timestamp.unwrap_or(bids.last()?.timestamp_ms);
+
+        Some(Self {
+            slot: first_winning.slot,
+            bid_count: bids.len() as u64,
+            builder_count: bids.iter().map(|bid| &bid.builder_pubkey).unique().count() as u64,
+            winning_builder_pubkey: first_winning.builder_pubkey.clone(),
+            winning_bid: first_winning.value,
+            winning_bid_time_ms: first_winning.timestamp_ms as i64 - slot_start,
+            runner_up_builder_pubkey: runner_up.map(|bid| bid.builder_pubkey.clone()),
+            runner_up_bid: runner_up.map(|bid| bid.value),
+            winning_margin: runner_up.map(|bid| first_winning.value as i128 - bid.value as i128),
+            bid_shading: (block_value > 0)
+                .then(|| 1.0 - first_winning.value as f64 / block_value as f64),
+            proposer_delay_ms: proposer_seen_ms as i64 - slot_start,
+            bid_curve,
+        })
+    }
+}
+
+impl fmt::Display for BidAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "  - {} {} bids from {} builders in slot {}",
+            "Bids:".bold(),
+            self.bid_count,
+            self.builder_count,
+            self.slot
+        )?;
+        writeln!(
+            f,
+            "  - {} {:.6} ETH at {}ms",
+            "Winning Bid:".bold(),
+            self.winning_bid as f64 * 1e-18,
+            self.winning_bid_time_ms
+        )?;
+        if let (Some(runner_up), Some(margin)) = (self.runner_up_bid, self.winning_margin) {
+            writeln!(
+                f,
+                "  - {} {:.6} ETH, won by {:.6} ETH",
+                "Runner Up:".bold(),
+                runner_up as f64 * 1e-18,
+                margin as f64 * 1e-18
+            )?;
+        }
+        if let Some(shading) = self.bid_shading {
+            writeln!(f, "  - {} {:.2}%", "Bid Shading:".bold(), shading * 100.0)?;
+        }
+        writeln!(f, "  - {} {}ms", "Proposer Delay:".bold(), self.proposer_delay_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_metadata::Relays;
+
+    fn bid(builder: &str, block_hash: B256, value: u128, timestamp_ms: u64) -> RelayBid {
+        RelayBid {
+            relay: Relays::Flashbots,
+            slot: 1,
+            parent_hash: format!("{:?}", B256::repeat_byte(1)),
+            block_hash: format!("{:?}", block_hash),
+            builder_pubkey: builder.to_string(),
+            proposer_fee_recipient: String::new(),
+            gas_limit: 30_000_000,
+            gas_used: 15_000_000,
+            value,
+            block_number: 1,
+            num_tx: 100,
+            timestamp: timestamp_ms / 1000,
+            timestamp_ms,
+        }
+    }
+
+    #[test]
+    fn test_bid_analysis() {
+        let winning_hash = B256::repeat_byte(2);
+        let bids = vec![
+            bid("a", B256::repeat_byte(3), 100, 12_100),
+            bid("b", B256::repeat_byte(4), 150, 12_500),
+            bid("a", winning_hash, 200, 13_000),
+            bid("b", B256::repeat_byte(5), 120, 13_200),
+            // received after the winning bid
+            bid("b", B256::repeat_byte(6), 300, 14_000),
+        ];
+
+        let analysis =
+            BidAnalysis::new(&bids, winning_hash, B256::repeat_byte(1), 12, Some(13_500), 400)
+                .unwrap();
+
+        assert_eq!(analysis.bid_count, 5);
+        assert_eq!(analysis.builder_count, 2);
+        assert_eq!(analysis.winning_bid, 200);
+        assert_eq!(analysis.winning_bid_time_ms, 1000);
+        assert_eq!(analysis.runner_up_bid, Some(150));
+        assert_eq!(analysis.winning_margin, Some(50));
+        assert_eq!(analysis.bid_shading, Some(0.5));
+        assert_eq!(analysis.proposer_delay_ms, 1500);
+        assert_eq!(
+            analysis
+                .bid_curve
+                .iter()
+                .map(|point| point.value)
+                .collect_vec(),
+            vec![100, 150, 200, 300]
+        );
+    }
+
+    #[test]
+    fn test_single_bid_slot() {
+        let winning_hash = B256::repeat_byte(2);
+        let bids = vec![bid("a", winning_hash, 200, 12_800)];
+
+        let analysis =
+            BidAnalysis::new(&bids, winning_hash, B256::repeat_byte(1), 12, None, 0).unwrap();
+
+        assert_eq!(analysis.bid_count, 1);
+        assert_eq!(analysis.builder_count, 1);
+        assert_eq!(analysis.winning_bid_time_ms, 800);
+        assert_eq!(analysis.runner_up_builder_pubkey, None);
+        assert_eq!(analysis.runner_up_bid, None);
+        assert_eq!(analysis.winning_margin, None);
+        assert_eq!(analysis.bid_shading, None);
+        // without a p2p timestamp the last bid of the slot is used
+        assert_eq!(analysis.proposer_delay_ms, 800);
+        assert_eq!(analysis.bid_curve, vec![BidCurvePoint { time_ms: 800, value: 200 }]);
+    }
+
+    #[test]
+    fn test_runner_up_ties() {
+        let winning_hash = B256::repeat_byte(2);
+        let bids = vec![
+            bid("c", B256::repeat_byte(4), 150, 12_600),
+            bid("b", B256::repeat_byte(3), 150, 12_400),
+            // the winning builder's own bids are never the runner up
+            bid("a", B256::repeat_byte(5), 180, 12_500),
+            bid("a", winning_hash, 150, 13_000),
+        ];
+
+        let analysis =
+            BidAnalysis::new(&bids, winning_hash, B256::repeat_byte(1), 12, Some(13_100), 0)
+                .unwrap();
+
+        assert_eq!(analysis.runner_up_builder_pubkey.as_deref(), Some("b"));
+        assert_eq!(analysis.runner_up_bid, Some(150));
+        assert_eq!(analysis.winning_margin, Some(0));
+        assert_eq!(
+            analysis
+                .bid_curve
+                .iter()
+                .map(|point| (point.time_ms, point.value))
+                .collect_vec(),
+            vec![(400, 150), (500, 180)]
+        );
+    }
+
+    #[test]
+    fn test_runner_up_outbidding_winner() {
+        let winning_hash = B256::repeat_byte(2);
+        let bids = vec![
+            bid("a", winning_hash, 100, 12_300),
+            bid("b", B256::repeat_byte(3), 130, 12_200),
+            // ignored as it builds on another parent
+            RelayBid {
+                parent_hash: format!("{:?}", B256::repeat_byte(9)),
+                ..bid("c", B256::repeat_byte(4), 500, 12_250)
+            },
+        ];
+
+        let analysis =
+            BidAnalysis::new(&bids, winning_hash, B256::repeat_byte(1), 12, None, 0).unwrap();
+
+        assert_eq!(analysis.bid_count, 2);
+        assert_eq!(analysis.runner_up_bid, Some(130));
+        assert_eq!(analysis.winning_margin, Some(-30));
+    }
+
+    #[test]
+    fn test_proposer_delay() {
+        let winning_hash = B256::repeat_byte(2);
+        let bids =
+            vec![bid("a", winning_hash, 200, 12_900), bid("b", B256::repeat_byte(3), 250, 14_250)];
+
+        // the block was seen on the p2p network before the last bid
+        let analysis =
+            BidAnalysis::new(&bids, winning_hash, B256::repeat_byte(1), 12, Some(13_050), 0)
+                .unwrap();
+        assert_eq!(analysis.proposer_delay_ms, 1_050);
+        // a bid received after the winning bid isn't the runner up
+        assert_eq!(analysis.runner_up_bid, None);
+
+        let analysis =
+            BidAnalysis::new(&bids, winning_hash, B256::repeat_byte(1), 12, None, 0).unwrap();
+        assert_eq!(analysis.proposer_delay_ms, 2_250);
+    }
+
+    #[test]
+    fn test_bid_analysis_without_winning_bid() {
+        let bids = vec![bid("a", B256::repeat_byte(3), 100, 12_100)];
+
+        assert!(BidAnalysis::new(&bids, B256::repeat_byte(2), B256::repeat_byte(1), 12, None, 0)
+            .is_none());
+    }
+}
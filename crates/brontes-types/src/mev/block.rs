@@ -14,7 +14,7 @@ use rkyv::{Archive, Deserialize as rDeser, Serialize as rSer};
 use serde::{ser::SerializeStruct, Deserialize, Serialize};
 use serde_with::serde_as;
 
-use super::MevType;
+use super::{BidAnalysis, MevType};
 use crate::{
     db::redefined_types::primitives::{AddressRedefined, B256Redefined},
     display::utils::formate_etherscan_address_url,
@@ -56,8 +56,16 @@ pub struct MevBlock {
     pub proposer_fee_recipient:      Option<Address>,
     pub proposer_mev_reward:         Option<u128>,
     pub proposer_profit_usd:         Option<f64>,
+    /// Whether the proposer received at least the bid the relay promised,
+    /// None if it isn't an mev boost block
+    pub payment_verified:            Option<bool>,
+    /// Paid to the proposer on chain minus the promised bid (in wei)
+    pub payment_delta:               Option<i128>,
     pub total_mev_profit_usd:        f64,
     pub possible_mev:                PossibleMevCollection,
+    pub failed_attempts:             FailedAttemptCollection,
+    /// None if the relays received no bid for the block
+    pub bid_analysis:                Option<BidAnalysis>,
 }
 
 impl fmt::Display for MevBlock {
@@ -169,8 +177,22 @@ impl fmt::Display for MevBlock {
                 format_profit(self.proposer_profit_usd.unwrap()).green()
             )?;
         }
+        if let (Some(verified), Some(delta)) = (self.payment_verified, self.payment_delta) {
+            let delta = format!("{:.6} ETH", delta as f64 / 10f64.powf(18.0));
+            if verified {
+                writeln!(f, "  - Payment Verified: {}", delta.green())?;
+            } else {
+                writeln!(f, "  - Proposer Underpaid: {}", delta.red())?;
+            }
+        }
 
         writeln!(f, "\n{}: {}", "Missed Mev".bold().red().underline(), self.possible_mev)?;
+        writeln!(f, "{}: {}", "Failed Attempts".bold().red().underline(), self.failed_attempts)?;
+
+        if let Some(bid_analysis) = &self.bid_analysis {
+            writeln!(f, "{}", "Relay Bids:".bold().red().underline())?;
+            write!(f, "{}", bid_analysis)?;
+        }
 
         Ok(())
     }
@@ -199,6 +221,8 @@ pub struct MevCount {
     pub atomic_backrun_count: Option<u64>,
     pub liquidation_count:    Option<u64>,
     pub searcher_tx_count:    Option<u64>,
+    pub long_tail_count:      Option<u64>,
+    pub frontrun_count:       Option<u64>,
 }
 
 impl MevCount {
@@ -231,9 +255,35 @@ impl MevCount {
             MevType::JitCexDex => {
                 self.jit_cex_dex_count = Some(self.jit_cex_dex_count.unwrap_or_default().add(1))
             }
+            MevType::LongTail => {
+                self.long_tail_count = Some(self.long_tail_count.unwrap_or_default().add(1))
+            }
+            MevType::Frontrun => {
+                self.frontrun_count = Some(self.frontrun_count.unwrap_or_default().add(1))
+            }
             _ => {}
         }
     }
+
+    /// Undoes [`Self::increment_count`], used when a block is reorged out
+    pub fn decrement_count(&mut self, mev_type: MevType) {
+        self.bundle_count = self.bundle_count.saturating_sub(1);
+        let count = match mev_type {
+            MevType::CexDexTrades => &mut self.cex_dex_trade_count,
+            MevType::CexDexQuotes => &mut self.cex_dex_quote_count,
+            MevType::Sandwich => &mut self.sandwich_count,
+            MevType::AtomicArb => &mut self.atomic_backrun_count,
+            MevType::Jit => &mut self.jit_count,
+            MevType::JitSandwich => &mut self.jit_sandwich_count,
+            MevType::Liquidation => &mut self.liquidation_count,
+            MevType::SearcherTx => &mut self.searcher_tx_count,
+            MevType::JitCexDex => &mut self.jit_cex_dex_count,
+            MevType::LongTail => &mut self.long_tail_count,
+            MevType::Frontrun => &mut self.frontrun_count,
+            _ => return,
+        };
+        *count = count.map(|count| count.saturating_sub(1));
+    }
 }
 self_convert_redefined!(MevCount);
 
@@ -268,6 +318,12 @@ impl fmt::Display for MevCount {
         if let Some(count) = self.searcher_tx_count {
             writeln!(f, "    - Searcher TXs: {}", count.to_string().bold())?;
         }
+        if let Some(count) = self.long_tail_count {
+            writeln!(f, "    - Long Tail: {}", count.to_string().bold())?;
+        }
+        if let Some(count) = self.frontrun_count {
+            writeln!(f, "    - Frontrun: {}", count.to_string().bold())?;
+        }
 
         Ok(())
     }
@@ -347,6 +403,57 @@ impl fmt::Display for PossibleMevTriggers {
     }
 }
 
+/// A reverted or no-op transaction sent to a searcher contract
+#[serde_as]
+#[derive(Debug, Deserialize, PartialEq, Row, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSer, rDeser, Archive))]
+pub struct FailedAttempt {
+    pub tx_hash:      B256,
+    pub tx_idx:       u64,
+    pub eoa:          Address,
+    pub mev_contract: Address,
+    /// Whether the transaction reverted, otherwise it succeeded without
+    /// swapping or receiving anything
+    pub reverted:     bool,
+    #[redefined(same_fields)]
+    pub gas_details:  GasDetails,
+    pub gas_paid_usd: f64,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, PartialEq, Row, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSer, rDeser, Archive))]
+pub struct FailedAttemptCollection(pub Vec<FailedAttempt>);
+
+impl FailedAttemptCollection {
+    pub fn total_gas_paid_usd(&self) -> f64 {
+        self.0.iter().map(|attempt| attempt.gas_paid_usd).sum()
+    }
+}
+
+impl fmt::Display for FailedAttemptCollection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} burning {}",
+            format!("Found {} reverted or no-op searcher transactions", self.0.len())
+                .bright_yellow(),
+            format_profit(self.total_gas_paid_usd()).red()
+        )?;
+        for attempt in self.0.iter() {
+            writeln!(
+                f,
+                "    - {} {:?} to {}, {} paid in gas",
+                if attempt.reverted { "Reverted" } else { "No-op" },
+                attempt.tx_hash,
+                formate_etherscan_address_url(&attempt.mev_contract),
+                format_profit(attempt.gas_paid_usd)
+            )?;
+        }
+        Ok(())
+    }
+}
+
 impl PossibleMevTriggers {
     pub fn was_triggered(&self) -> bool {
         self.coinbase_transfer || self.high_priority_fee
@@ -358,7 +465,7 @@ impl Serialize for MevBlock {
     where
         S: serde::Serializer,
     {
-        let mut ser_struct = serializer.serialize_struct("MevBlock", 33)?;
+        let mut ser_struct = serializer.serialize_struct("MevBlock", 62)?;
 
         ser_struct.serialize_field("block_hash", &format!("{:?}", self.block_hash))?;
         ser_struct.serialize_field("block_number", &self.block_number)?;
@@ -425,6 +532,8 @@ impl Serialize for MevBlock {
         )?;
         ser_struct.serialize_field("proposer_mev_reward", &self.proposer_mev_reward)?;
         ser_struct.serialize_field("proposer_profit_usd", &self.proposer_profit_usd)?;
+        ser_struct.serialize_field("payment_verified", &self.payment_verified)?;
+        ser_struct.serialize_field("payment_delta", &self.payment_delta)?;
         ser_struct.serialize_field("total_mev_profit_usd", &self.total_mev_profit_usd)?;
 
         let mut possible_tx_hashes = Vec::new();
@@ -498,6 +607,156 @@ impl Serialize for MevBlock {
             &possible_high_priority_fee,
         )?;
 
+        let failed_attempts = &self.failed_attempts.0;
+        ser_struct.serialize_field(
+            "failed_attempts.tx_hash",
+            &failed_attempts
+                .iter()
+                .map(|tx| format!("{:?}", tx.tx_hash))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "failed_attempts.tx_idx",
+            &failed_attempts
+                .iter()
+                .map(|tx| tx.tx_idx)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "failed_attempts.eoa",
+            &failed_attempts
+                .iter()
+                .map(|tx| format!("{:?}", tx.eoa))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "failed_attempts.mev_contract",
+            &failed_attempts
+                .iter()
+                .map(|tx| format!("{:?}", tx.mev_contract))
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "failed_attempts.reverted",
+            &failed_attempts
+                .iter()
+                .map(|tx| tx.reverted)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "failed_attempts.gas_details.priority_fee",
+            &failed_attempts
+                .iter()
+                .map(|tx| tx.gas_details.priority_fee)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "failed_attempts.gas_details.gas_used",
+            &failed_attempts
+                .iter()
+                .map(|tx| tx.gas_details.gas_used)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "failed_attempts.gas_details.effective_gas_price",
+            &failed_attempts
+                .iter()
+                .map(|tx| tx.gas_details.effective_gas_price)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "failed_attempts.gas_paid_usd",
+            &failed_attempts
+                .iter()
+                .map(|tx| tx.gas_paid_usd)
+                .collect::<Vec<_>>(),
+        )?;
+
+        let bid_analysis = self.bid_analysis.iter().collect::<Vec<_>>();
+        ser_struct.serialize_field(
+            "bid_analysis.slot",
+            &bid_analysis.iter().map(|a| a.slot).collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "bid_analysis.bid_count",
+            &bid_analysis.iter().map(|a| a.bid_count).collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "bid_analysis.builder_count",
+            &bid_analysis
+                .iter()
+                .map(|a| a.builder_count)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "bid_analysis.winning_builder_pubkey",
+            &bid_analysis
+                .iter()
+                .map(|a| a.winning_builder_pubkey.clone())
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "bid_analysis.winning_bid",
+            &bid_analysis
+                .iter()
+                .map(|a| a.winning_bid)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "bid_analysis.winning_bid_time_ms",
+            &bid_analysis
+                .iter()
+                .map(|a| a.winning_bid_time_ms)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "bid_analysis.runner_up_builder_pubkey",
+            &bid_analysis
+                .iter()
+                .map(|a| a.runner_up_builder_pubkey.clone())
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "bid_analysis.runner_up_bid",
+            &bid_analysis
+                .iter()
+                .map(|a| a.runner_up_bid)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "bid_analysis.winning_margin",
+            &bid_analysis
+                .iter()
+                .map(|a| a.winning_margin)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "bid_analysis.bid_shading",
+            &bid_analysis
+                .iter()
+                .map(|a| a.bid_shading)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "bid_analysis.proposer_delay_ms",
+            &bid_analysis
+                .iter()
+                .map(|a| a.proposer_delay_ms)
+                .collect::<Vec<_>>(),
+        )?;
+        ser_struct.serialize_field(
+            "bid_analysis.bid_curve",
+            &bid_analysis
+                .iter()
+                .map(|a| {
+                    a.bid_curve
+                        .iter()
+                        .map(|point| (point.time_ms, point.value))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>(),
+        )?;
+
         ser_struct.end()
     }
 }
@@ -533,6 +792,8 @@ impl DbRow for MevBlock {
         "proposer_fee_recipient",
         "proposer_mev_reward",
         "proposer_profit_usd",
+        "payment_verified",
+        "payment_delta",
         "total_mev_profit_usd",
         "possible_mev.tx_hash",
         "possible_mev.tx_idx",
@@ -543,5 +804,26 @@ impl DbRow for MevBlock {
         "possible_mev.triggers.is_private",
         "possible_mev.triggers.coinbase_transfer",
         "possible_mev.triggers.high_priority_fee",
+        "failed_attempts.tx_hash",
+        "failed_attempts.tx_idx",
+        "failed_attempts.eoa",
+        "failed_attempts.mev_contract",
+        "failed_attempts.reverted",
+        "failed_attempts.gas_details.priority_fee",
+        "failed_attempts.gas_details.gas_used",
+        "failed_attempts.gas_details.effective_gas_price",
+        "failed_attempts.gas_paid_usd",
+        "bid_analysis.slot",
+        "bid_analysis.bid_count",
+        "bid_analysis.builder_count",
+        "bid_analysis.winning_builder_pubkey",
+        "bid_analysis.winning_bid",
+        "bid_analysis.winning_bid_time_ms",
+        "bid_analysis.runner_up_builder_pubkey",
+        "bid_analysis.runner_up_bid",
+        "bid_analysis.winning_margin",
+        "bid_analysis.bid_shading",
+        "bid_analysis.proposer_delay_ms",
+        "bid_analysis.bid_curve",
     ];
 }
@@ -32,6 +32,8 @@ pub enum BundleData {
     CexDex(CexDex),
     Liquidation(Liquidation),
     Unknown(SearcherTx),
+    LongTail(LongTail),
+    Frontrun(Frontrun),
 }
 
 impl Default for BundleData {
@@ -51,6 +53,8 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.mev_type(),
             BundleData::Liquidation(m) => m.mev_type(),
             BundleData::Unknown(m) => m.mev_type(),
+            BundleData::LongTail(m) => m.mev_type(),
+            BundleData::Frontrun(m) => m.mev_type(),
         }
     }
 
@@ -64,6 +68,8 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.total_gas_paid(),
             BundleData::Liquidation(m) => m.total_gas_paid(),
             BundleData::Unknown(s) => s.total_gas_paid(),
+            BundleData::LongTail(m) => m.total_gas_paid(),
+            BundleData::Frontrun(m) => m.total_gas_paid(),
         }
     }
 
@@ -77,6 +83,8 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.total_priority_fee_paid(base_fee),
             BundleData::Liquidation(m) => m.total_priority_fee_paid(base_fee),
             BundleData::Unknown(s) => s.total_priority_fee_paid(base_fee),
+            BundleData::LongTail(m) => m.total_priority_fee_paid(base_fee),
+            BundleData::Frontrun(m) => m.total_priority_fee_paid(base_fee),
         }
     }
 
@@ -90,6 +98,8 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.bribe(),
             BundleData::Liquidation(m) => m.bribe(),
             BundleData::Unknown(s) => s.bribe(),
+            BundleData::LongTail(m) => m.bribe(),
+            BundleData::Frontrun(m) => m.bribe(),
         }
     }
 
@@ -103,6 +113,8 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.mev_transaction_hashes(),
             BundleData::Liquidation(m) => m.mev_transaction_hashes(),
             BundleData::Unknown(s) => s.mev_transaction_hashes(),
+            BundleData::LongTail(m) => m.mev_transaction_hashes(),
+            BundleData::Frontrun(m) => m.mev_transaction_hashes(),
         }
     }
 
@@ -116,6 +128,8 @@ impl Mev for BundleData {
             BundleData::CexDexQuote(m) => m.protocols(),
             BundleData::Liquidation(m) => m.protocols(),
             BundleData::Unknown(s) => s.protocols(),
+            BundleData::LongTail(m) => m.protocols(),
+            BundleData::Frontrun(m) => m.protocols(),
         }
     }
 }
@@ -162,6 +176,18 @@ impl From<Liquidation> for BundleData {
     }
 }
 
+impl From<LongTail> for BundleData {
+    fn from(value: LongTail) -> Self {
+        Self::LongTail(value)
+    }
+}
+
+impl From<Frontrun> for BundleData {
+    fn from(value: Frontrun) -> Self {
+        Self::Frontrun(value)
+    }
+}
+
 impl Serialize for BundleData {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -176,6 +202,8 @@ impl Serialize for BundleData {
             BundleData::CexDexQuote(cex_dex) => cex_dex.serialize(serializer),
             BundleData::Liquidation(liquidation) => liquidation.serialize(serializer),
             BundleData::Unknown(s) => s.serialize(serializer),
+            BundleData::LongTail(long_tail) => long_tail.serialize(serializer),
+            BundleData::Frontrun(frontrun) => frontrun.serialize(serializer),
         }
     }
 }
@@ -191,6 +219,8 @@ impl InsertRow for BundleData {
             BundleData::CexDexQuote(cex_dex) => cex_dex.get_column_names(),
             BundleData::Liquidation(liquidation) => liquidation.get_column_names(),
             BundleData::Unknown(s) => s.get_column_names(),
+            BundleData::LongTail(long_tail) => long_tail.get_column_names(),
+            BundleData::Frontrun(frontrun) => frontrun.get_column_names(),
         }
     }
 }
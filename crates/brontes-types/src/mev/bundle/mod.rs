@@ -68,6 +68,8 @@ impl fmt::Display for Bundle {
             MevType::Liquidation => display_liquidation(self, f)?,
             MevType::JitSandwich => display_jit_liquidity_sandwich(self, f)?,
             MevType::SearcherTx => display_searcher_tx(self, f)?,
+            MevType::LongTail => display_long_tail(self, f)?,
+            MevType::Frontrun => display_frontrun(self, f)?,
             MevType::Unknown => (),
         }
 
@@ -102,6 +104,8 @@ pub enum MevType {
     Liquidation,
     AtomicArb,
     SearcherTx,
+    LongTail,
+    Frontrun,
     #[default]
     Unknown,
 }
@@ -115,6 +119,8 @@ impl MevType {
             | MevType::AtomicArb
             | MevType::Liquidation
             | MevType::SearcherTx
+            | MevType::LongTail
+            | MevType::Frontrun
             | MevType::Unknown => false,
             MevType::CexDexRfq
             | MevType::CexDexTrades
@@ -135,6 +141,8 @@ impl MevType {
             MevType::JitSandwich => "jit-sandwich",
             MevType::SearcherTx => "searcher-tx",
             MevType::Liquidation => "liquidation",
+            MevType::LongTail => "long-tail",
+            MevType::Frontrun => "frontrun",
             MevType::Unknown => "header",
         }
     }
@@ -154,6 +162,8 @@ impl From<String> for MevType {
             "JitSandwich" => MevType::JitSandwich,
             "AtomicArb" => MevType::AtomicArb,
             "SearcherTx" => MevType::SearcherTx,
+            "LongTail" => MevType::LongTail,
+            "Frontrun" => MevType::Frontrun,
             _ => MevType::Unknown,
         }
     }
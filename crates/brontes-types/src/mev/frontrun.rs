@@ -0,0 +1,170 @@
+use std::fmt::Debug;
+
+use ::clickhouse::DbRow;
+use ::serde::ser::{SerializeStruct, Serializer};
+use ahash::HashSet;
+use redefined::Redefined;
+use reth_primitives::B256;
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use super::{Mev, MevType};
+use crate::{
+    db::redefined_types::primitives::*,
+    normalized_actions::{ClickhouseVecNormalizedSwap, NormalizedSwap, NormalizedSwapRedefined},
+    ClickhouseVecGasDetails, GasDetails, Protocol,
+};
+
+/// A generalized frontrunner copying the call of a pending transaction and
+/// outbidding it, so that the displaced transaction reverts or receives
+/// nothing.
+///
+/// Only the frontrun is a mev transaction, the victims are kept to show the
+/// displaced calls and the priority fee the frontrun outbid them with.
+#[serde_as]
+#[derive(Debug, Deserialize, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct Frontrun {
+    pub block_number:         u64,
+    pub frontrun_tx_hash:     B256,
+    pub frontrun_swaps:       Vec<NormalizedSwap>,
+    #[redefined(same_fields)]
+    pub frontrun_gas_details: GasDetails,
+    pub victim_tx_hashes:     Vec<B256>,
+    /// Whether the victim reverted, otherwise it succeeded without receiving
+    /// anything
+    pub victim_reverted:      Vec<bool>,
+    #[redefined(same_fields)]
+    pub victim_gas_details:   Vec<GasDetails>,
+}
+
+impl Frontrun {
+    /// The priority fee per gas the frontrun paid over each victim
+    pub fn priority_fee_outbids(&self) -> Vec<i128> {
+        self.victim_gas_details
+            .iter()
+            .map(|victim| {
+                self.frontrun_gas_details.priority_fee as i128 - victim.priority_fee as i128
+            })
+            .collect()
+    }
+}
+
+impl Mev for Frontrun {
+    fn mev_type(&self) -> MevType {
+        MevType::Frontrun
+    }
+
+    fn total_gas_paid(&self) -> u128 {
+        self.frontrun_gas_details.gas_paid()
+    }
+
+    fn total_priority_fee_paid(&self, base_fee: u128) -> u128 {
+        self.frontrun_gas_details.priority_fee_paid(base_fee)
+    }
+
+    fn bribe(&self) -> u128 {
+        self.frontrun_gas_details
+            .coinbase_transfer
+            .unwrap_or_default()
+    }
+
+    fn mev_transaction_hashes(&self) -> Vec<B256> {
+        vec![self.frontrun_tx_hash]
+    }
+
+    fn protocols(&self) -> HashSet<Protocol> {
+        self.frontrun_swaps
+            .iter()
+            .map(|swap| swap.protocol)
+            .collect()
+    }
+}
+
+impl Serialize for Frontrun {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut ser_struct = serializer.serialize_struct("Frontrun", 19)?;
+        ser_struct.serialize_field("block_number", &self.block_number)?;
+
+        // frontrun
+        let frontrun_tx_hash = format!("{:?}", &self.frontrun_tx_hash);
+        ser_struct.serialize_field("frontrun_tx_hash", &frontrun_tx_hash)?;
+
+        let frontrun_swaps: ClickhouseVecNormalizedSwap = self
+            .frontrun_swaps
+            .clone()
+            .try_into()
+            .map_err(serde::ser::Error::custom)?;
+        ser_struct.serialize_field("frontrun_swaps.trace_idx", &frontrun_swaps.trace_index)?;
+        ser_struct.serialize_field("frontrun_swaps.from", &frontrun_swaps.from)?;
+        ser_struct.serialize_field("frontrun_swaps.recipient", &frontrun_swaps.recipient)?;
+        ser_struct.serialize_field("frontrun_swaps.pool", &frontrun_swaps.pool)?;
+        ser_struct.serialize_field("frontrun_swaps.token_in", &frontrun_swaps.token_in)?;
+        ser_struct.serialize_field("frontrun_swaps.token_out", &frontrun_swaps.token_out)?;
+        ser_struct.serialize_field("frontrun_swaps.amount_in", &frontrun_swaps.amount_in)?;
+        ser_struct.serialize_field("frontrun_swaps.amount_out", &frontrun_swaps.amount_out)?;
+
+        let frontrun_gas_details = (
+            self.frontrun_gas_details.coinbase_transfer,
+            self.frontrun_gas_details.priority_fee,
+            self.frontrun_gas_details.gas_used,
+            self.frontrun_gas_details.effective_gas_price,
+        );
+        ser_struct.serialize_field("frontrun_gas_details", &frontrun_gas_details)?;
+
+        // victims
+        let victim_tx_hashes = self
+            .victim_tx_hashes
+            .iter()
+            .map(|hash| format!("{:?}", hash))
+            .collect::<Vec<_>>();
+        ser_struct.serialize_field("victim_tx_hashes", &victim_tx_hashes)?;
+        ser_struct.serialize_field("victim_reverted", &self.victim_reverted)?;
+
+        let victim_gas_details: ClickhouseVecGasDetails =
+            (self.victim_tx_hashes.clone(), self.victim_gas_details.clone()).into();
+        ser_struct.serialize_field("victim_gas_details.tx_hash", &victim_gas_details.tx_hash)?;
+        ser_struct.serialize_field(
+            "victim_gas_details.coinbase_transfer",
+            &victim_gas_details.coinbase_transfer,
+        )?;
+        ser_struct
+            .serialize_field("victim_gas_details.priority_fee", &victim_gas_details.priority_fee)?;
+        ser_struct.serialize_field("victim_gas_details.gas_used", &victim_gas_details.gas_used)?;
+        ser_struct.serialize_field(
+            "victim_gas_details.effective_gas_price",
+            &victim_gas_details.effective_gas_price,
+        )?;
+        ser_struct.serialize_field("priority_fee_outbids", &self.priority_fee_outbids())?;
+
+        ser_struct.end()
+    }
+}
+
+impl DbRow for Frontrun {
+    const COLUMN_NAMES: &'static [&'static str] = &[
+        "block_number",
+        "frontrun_tx_hash",
+        "frontrun_swaps.trace_idx",
+        "frontrun_swaps.from",
+        "frontrun_swaps.recipient",
+        "frontrun_swaps.pool",
+        "frontrun_swaps.token_in",
+        "frontrun_swaps.token_out",
+        "frontrun_swaps.amount_in",
+        "frontrun_swaps.amount_out",
+        "frontrun_gas_details",
+        "victim_tx_hashes",
+        "victim_reverted",
+        "victim_gas_details.tx_hash",
+        "victim_gas_details.coinbase_transfer",
+        "victim_gas_details.priority_fee",
+        "victim_gas_details.gas_used",
+        "victim_gas_details.effective_gas_price",
+        "priority_fee_outbids",
+    ];
+}
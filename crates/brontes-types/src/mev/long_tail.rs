@@ -0,0 +1,199 @@
+use std::fmt::Debug;
+
+use ::clickhouse::DbRow;
+use ::serde::ser::{SerializeStruct, Serializer};
+use ahash::HashSet;
+use redefined::Redefined;
+use reth_primitives::{Address, B256};
+use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use super::{Mev, MevType};
+use crate::{
+    db::redefined_types::primitives::*,
+    normalized_actions::{
+        ClickhouseDoubleVecNormalizedSwap, ClickhouseVecNormalizedSwap, NormalizedSwap,
+        NormalizedSwapRedefined,
+    },
+    ClickhouseVecGasDetails, GasDetails, Protocol,
+};
+
+/// A searcher buying into a freshly launched pool and selling the bought token
+/// within the inspector block window.
+///
+/// The bundle is emitted in the block of the exit, with the entries being the
+/// buys made in the first blocks after the pool was created or had its first
+/// liquidity added. Entries can be spread over multiple blocks, the profit in
+/// the header is the pnl realized by this exit.
+#[serde_as]
+#[derive(Debug, Deserialize, PartialEq, Clone, Default, Redefined)]
+#[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
+pub struct LongTail {
+    pub block_number:      u64,
+    /// The sniped pool
+    pub pool:              Address,
+    /// The token bought from the sniped pool
+    pub token:             Address,
+    /// Block the pool was created or had its first liquidity added
+    pub launch_block:      u64,
+    pub entry_tx_hashes:   Vec<B256>,
+    /// Buys of the token in each entry transaction
+    pub entry_swaps:       Vec<Vec<NormalizedSwap>>,
+    #[redefined(same_fields)]
+    pub entry_gas_details: Vec<GasDetails>,
+    pub exit_tx_hash:      B256,
+    /// Sells of the token in the exit transaction
+    pub exit_swaps:        Vec<NormalizedSwap>,
+    #[redefined(same_fields)]
+    pub exit_gas_details:  GasDetails,
+}
+
+impl Mev for LongTail {
+    fn mev_type(&self) -> MevType {
+        MevType::LongTail
+    }
+
+    fn total_gas_paid(&self) -> u128 {
+        self.entry_gas_details
+            .iter()
+            .map(|gd| gd.gas_paid())
+            .sum::<u128>()
+            + self.exit_gas_details.gas_paid()
+    }
+
+    fn total_priority_fee_paid(&self, base_fee: u128) -> u128 {
+        self.entry_gas_details
+            .iter()
+            .map(|gd| gd.priority_fee_paid(base_fee))
+            .sum::<u128>()
+            + self.exit_gas_details.priority_fee_paid(base_fee)
+    }
+
+    fn bribe(&self) -> u128 {
+        self.entry_gas_details
+            .iter()
+            .filter_map(|gd| gd.coinbase_transfer)
+            .sum::<u128>()
+            + self.exit_gas_details.coinbase_transfer.unwrap_or_default()
+    }
+
+    fn mev_transaction_hashes(&self) -> Vec<B256> {
+        let mut txs = self.entry_tx_hashes.clone();
+        txs.push(self.exit_tx_hash);
+        txs
+    }
+
+    fn protocols(&self) -> HashSet<Protocol> {
+        self.entry_swaps
+            .iter()
+            .flatten()
+            .chain(self.exit_swaps.iter())
+            .map(|swap| swap.protocol)
+            .collect()
+    }
+}
+
+impl Serialize for LongTail {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut ser_struct = serializer.serialize_struct("LongTail", 32)?;
+        ser_struct.serialize_field("block_number", &self.block_number)?;
+        ser_struct.serialize_field("pool", &format!("{:?}", self.pool))?;
+        ser_struct.serialize_field("token", &format!("{:?}", self.token))?;
+        ser_struct.serialize_field("launch_block", &self.launch_block)?;
+
+        // entries
+        let entry_swaps: ClickhouseDoubleVecNormalizedSwap =
+            (self.entry_tx_hashes.clone(), self.entry_swaps.clone())
+                .try_into()
+                .map_err(serde::ser::Error::custom)?;
+        ser_struct.serialize_field("entry_swaps.tx_hash", &entry_swaps.tx_hash)?;
+        ser_struct.serialize_field("entry_swaps.trace_idx", &entry_swaps.trace_index)?;
+        ser_struct.serialize_field("entry_swaps.from", &entry_swaps.from)?;
+        ser_struct.serialize_field("entry_swaps.recipient", &entry_swaps.recipient)?;
+        ser_struct.serialize_field("entry_swaps.pool", &entry_swaps.pool)?;
+        ser_struct.serialize_field("entry_swaps.token_in", &entry_swaps.token_in)?;
+        ser_struct.serialize_field("entry_swaps.token_out", &entry_swaps.token_out)?;
+        ser_struct.serialize_field("entry_swaps.amount_in", &entry_swaps.amount_in)?;
+        ser_struct.serialize_field("entry_swaps.amount_out", &entry_swaps.amount_out)?;
+
+        let entry_gas_details: ClickhouseVecGasDetails =
+            (self.entry_tx_hashes.clone(), self.entry_gas_details.clone()).into();
+        ser_struct.serialize_field("entry_gas_details.tx_hash", &entry_gas_details.tx_hash)?;
+        ser_struct.serialize_field(
+            "entry_gas_details.coinbase_transfer",
+            &entry_gas_details.coinbase_transfer,
+        )?;
+        ser_struct
+            .serialize_field("entry_gas_details.priority_fee", &entry_gas_details.priority_fee)?;
+        ser_struct.serialize_field("entry_gas_details.gas_used", &entry_gas_details.gas_used)?;
+        ser_struct.serialize_field(
+            "entry_gas_details.effective_gas_price",
+            &entry_gas_details.effective_gas_price,
+        )?;
+
+        // exit
+        let exit_tx_hash = format!("{:?}", &self.exit_tx_hash);
+        ser_struct.serialize_field("exit_tx_hash", &exit_tx_hash)?;
+
+        let exit_swaps: ClickhouseVecNormalizedSwap = self
+            .exit_swaps
+            .clone()
+            .try_into()
+            .map_err(serde::ser::Error::custom)?;
+        ser_struct.serialize_field("exit_swaps.trace_idx", &exit_swaps.trace_index)?;
+        ser_struct.serialize_field("exit_swaps.from", &exit_swaps.from)?;
+        ser_struct.serialize_field("exit_swaps.recipient", &exit_swaps.recipient)?;
+        ser_struct.serialize_field("exit_swaps.pool", &exit_swaps.pool)?;
+        ser_struct.serialize_field("exit_swaps.token_in", &exit_swaps.token_in)?;
+        ser_struct.serialize_field("exit_swaps.token_out", &exit_swaps.token_out)?;
+        ser_struct.serialize_field("exit_swaps.amount_in", &exit_swaps.amount_in)?;
+        ser_struct.serialize_field("exit_swaps.amount_out", &exit_swaps.amount_out)?;
+
+        let exit_gas_details = (
+            self.exit_gas_details.coinbase_transfer,
+            self.exit_gas_details.priority_fee,
+            self.exit_gas_details.gas_used,
+            self.exit_gas_details.effective_gas_price,
+        );
+        ser_struct.serialize_field("exit_gas_details", &exit_gas_details)?;
+
+        ser_struct.end()
+    }
+}
+
+impl DbRow for LongTail {
+    const COLUMN_NAMES: &'static [&'static str] = &[
+        "block_number",
+        "pool",
+        "token",
+        "launch_block",
+        "entry_swaps.tx_hash",
+        "entry_swaps.trace_idx",
+        "entry_swaps.from",
+        "entry_swaps.recipient",
+        "entry_swaps.pool",
+        "entry_swaps.token_in",
+        "entry_swaps.token_out",
+        "entry_swaps.amount_in",
+        "entry_swaps.amount_out",
+        "entry_gas_details.tx_hash",
+        "entry_gas_details.coinbase_transfer",
+        "entry_gas_details.priority_fee",
+        "entry_gas_details.gas_used",
+        "entry_gas_details.effective_gas_price",
+        "exit_tx_hash",
+        "exit_swaps.trace_idx",
+        "exit_swaps.from",
+        "exit_swaps.recipient",
+        "exit_swaps.pool",
+        "exit_swaps.token_in",
+        "exit_swaps.token_out",
+        "exit_swaps.amount_in",
+        "exit_swaps.amount_out",
+        "exit_gas_details",
+    ];
+}
@@ -19,3 +19,9 @@ pub use searcher_tx::*;
 
 pub mod cex_dex_quotes;
 pub use cex_dex_quotes::*;
+pub mod long_tail;
+pub use long_tail::*;
+pub mod frontrun;
+pub use frontrun::*;
+pub mod bid_analysis;
+pub use bid_analysis::*;
@@ -14,7 +14,7 @@ use super::{Mev, MevType};
 use crate::{
     db::{redefined_types::primitives::*, token_info::TokenInfoWithAddress},
     normalized_actions::*,
-    ClickhouseVecGasDetails, Protocol,
+    ClickhouseVecGasDetails, Protocol, ToFloatNearest,
 };
 #[allow(unused_imports)]
 use crate::{
@@ -60,34 +60,40 @@ use crate::{
 #[derive(Debug, Deserialize, PartialEq, Clone, Default, Redefined)]
 #[redefined_attr(derive(Debug, PartialEq, Clone, Serialize, rSerialize, rDeserialize, Archive))]
 pub struct Sandwich {
-    pub block_number:             u64,
+    pub block_number:              u64,
     /// Transaction hashes of the frontrunning transactions.
     /// Supports multiple transactions for complex sandwich scenarios.
-    pub frontrun_tx_hash:         Vec<B256>,
+    pub frontrun_tx_hash:          Vec<B256>,
     /// Swaps executed in each frontrunning transaction.
     /// Nested vectors represent multiple swaps within each transaction.
-    pub frontrun_swaps:           Vec<Vec<NormalizedSwap>>,
+    pub frontrun_swaps:            Vec<Vec<NormalizedSwap>>,
     /// Gas details for each frontrunning transaction.
     #[redefined(same_fields)]
-    pub frontrun_gas_details:     Vec<GasDetails>,
+    pub frontrun_gas_details:      Vec<GasDetails>,
     /// Transaction hashes of the victim transactions, logically grouped by
     /// their corresponding frontrunning transaction. Each outer vector
     /// index corresponds to a frontrun transaction, grouping victims targeted
     /// by that specific frontrun.
-    pub victim_swaps_tx_hashes:   Vec<Vec<B256>>,
+    pub victim_swaps_tx_hashes:    Vec<Vec<B256>>,
     /// Swaps executed by victims, each outer vector corresponds to a victim
     /// transaction.
-    pub victim_swaps:             Vec<Vec<NormalizedSwap>>,
+    pub victim_swaps:              Vec<Vec<NormalizedSwap>>,
     /// Gas details for each victim transaction.
     #[redefined(same_fields)]
-    pub victim_swaps_gas_details: Vec<GasDetails>,
+    pub victim_swaps_gas_details:  Vec<GasDetails>,
+    /// What each victim swap would have received without the frontruns,
+    /// mirrors `victim_swaps`. Empty if the victims weren't simulated.
+    pub victim_counterfactual_out: Vec<Vec<f64>>,
+    /// The usd value each victim swap lost to the sandwich, mirrors
+    /// `victim_swaps`.
+    pub victim_loss_usd:           Vec<Vec<f64>>,
     /// Transaction hashes of the backrunning transactions.
-    pub backrun_tx_hash:          B256,
+    pub backrun_tx_hash:           B256,
     /// Swaps executed in each backrunning transaction.
-    pub backrun_swaps:            Vec<NormalizedSwap>,
+    pub backrun_swaps:             Vec<NormalizedSwap>,
     /// Gas details for each backrunning transaction.
     #[redefined(same_fields)]
-    pub backrun_gas_details:      GasDetails,
+    pub backrun_gas_details:       GasDetails,
 }
 
 /// calcuation for the loss per user
@@ -101,6 +107,40 @@ pub struct VictimLossAmount {
     pub amount_lost_usd:   Rational,
 }
 
+impl Sandwich {
+    /// The counterfactual amount out and usd loss of every victim swap, in the
+    /// order of the flattened `victim_swaps`. Swaps that weren't simulated
+    /// keep their amount out and have no loss.
+    pub fn victim_swap_losses(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.victim_swaps
+            .iter()
+            .enumerate()
+            .flat_map(move |(i, swaps)| {
+                swaps.iter().enumerate().map(move |(j, swap)| {
+                    let amount_out = self
+                        .victim_counterfactual_out
+                        .get(i)
+                        .and_then(|amounts| amounts.get(j))
+                        .copied()
+                        .unwrap_or_else(|| swap.amount_out.clone().to_float());
+                    let loss_usd = self
+                        .victim_loss_usd
+                        .get(i)
+                        .and_then(|losses| losses.get(j))
+                        .copied()
+                        .unwrap_or_default();
+
+                    (amount_out, loss_usd)
+                })
+            })
+    }
+
+    /// Total usd value the victims lost to the sandwich
+    pub fn total_victim_loss_usd(&self) -> f64 {
+        self.victim_loss_usd.iter().flatten().sum()
+    }
+}
+
 impl Mev for Sandwich {
     fn mev_type(&self) -> MevType {
         MevType::Sandwich
@@ -223,6 +263,14 @@ impl Serialize for Sandwich {
         ser_struct.serialize_field("victim_swaps.amount_in", &victim_swaps.amount_in)?;
         ser_struct.serialize_field("victim_swaps.amount_out", &victim_swaps.amount_out)?;
 
+        let (counterfactual_amount_out, loss_usd): (Vec<f64>, Vec<f64>) =
+            self.victim_swap_losses().unzip();
+        ser_struct.serialize_field(
+            "victim_swaps.counterfactual_amount_out",
+            &counterfactual_amount_out,
+        )?;
+        ser_struct.serialize_field("victim_swaps.loss_usd", &loss_usd)?;
+
         let victim_gas_details: ClickhouseVecGasDetails =
             (self.victim_swaps_tx_hashes.clone(), self.victim_swaps_gas_details.clone()).into();
         ser_struct.serialize_field("victim_gas_details.tx_hash", &victim_gas_details.tx_hash)?;
@@ -313,6 +361,8 @@ impl DbRow for Sandwich {
         "victim_swaps.token_out",
         "victim_swaps.amount_in",
         "victim_swaps.amount_out",
+        "victim_swaps.counterfactual_amount_out",
+        "victim_swaps.loss_usd",
         "victim_gas_details.tx_hash",
         "victim_gas_details.coinbase_transfer",
         "victim_gas_details.priority_fee",
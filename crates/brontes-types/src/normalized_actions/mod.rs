@@ -8,6 +8,7 @@ pub mod lending;
 pub mod liquidation;
 pub mod liquidity;
 pub mod multi_callframe;
+pub mod oracle;
 pub mod pool;
 pub mod self_destruct;
 pub mod swaps;
@@ -26,6 +27,7 @@ pub use lending::*;
 pub use liquidation::*;
 pub use liquidity::*;
 pub use multi_callframe::*;
+pub use oracle::*;
 pub use pool::*;
 use reth_rpc_types::trace::parity::Action as TraceAction;
 pub use self_destruct::*;
@@ -97,6 +99,7 @@ impl NormalizedAction for Action {
             Self::NewPool(p) => p.trace_index,
             Self::PoolConfigUpdate(p) => p.trace_index,
             Self::Aggregator(a) => a.trace_index,
+            Self::OracleUpdate(o) => o.trace_index,
             Self::Revert => unreachable!("no trace index for revert"),
         }
     }
@@ -119,6 +122,7 @@ pub enum Action {
     NewPool(NormalizedNewPool),
     PoolConfigUpdate(NormalizedPoolConfigUpdate),
     Aggregator(NormalizedAggregator),
+    OracleUpdate(NormalizedOracleUpdate),
     Unclassified(TransactionTraceWithLogs),
     Revert,
 }
@@ -141,6 +145,7 @@ impl InsertRow for Action {
             Action::PoolConfigUpdate(_) => todo!(),
             Action::Unclassified(..) | Action::Revert => panic!(),
             Action::Aggregator(_) => NormalizedAggregator::COLUMN_NAMES,
+            Action::OracleUpdate(_) => NormalizedOracleUpdate::COLUMN_NAMES,
         }
     }
 }
@@ -163,6 +168,7 @@ impl serde::Serialize for Action {
             Action::Liquidation(c) => c.serialize(serializer),
             Action::SelfDestruct(sd) => sd.serialize(serializer),
             Action::EthTransfer(et) => et.serialize(serializer),
+            Action::OracleUpdate(o) => o.serialize(serializer),
             Action::Unclassified(trace) => (trace).serialize(serializer),
             action => format!("{:?}", action).serialize(serializer),
             //action => unreachable!("no action serialization for {action:?}"),
@@ -226,6 +232,7 @@ impl Action {
                 Self::EthTransfer(_) => None,
                 Self::NewPool(_) => None,
                 Self::PoolConfigUpdate(_) => None,
+                Self::OracleUpdate(_) => None,
                 Self::Revert => None,
             };
         if res.is_some() {
@@ -258,6 +265,7 @@ impl Action {
             Self::NewPool(p) => p.trace_index,
             Self::PoolConfigUpdate(p) => p.trace_index,
             Self::Aggregator(a) => a.trace_index,
+            Self::OracleUpdate(o) => o.trace_index,
             Self::Revert => return None,
         })
     }
@@ -335,6 +343,7 @@ impl Action {
             Action::EthTransfer(t) => t.to,
             Action::NewPool(p) => p.pool_address,
             Action::PoolConfigUpdate(p) => p.pool_address,
+            Action::OracleUpdate(o) => o.oracle,
             Action::Revert => Address::ZERO,
         }
     }
@@ -362,6 +371,7 @@ impl Action {
             Action::Revert => unreachable!(),
             Action::NewPool(_) => Address::ZERO,
             Action::PoolConfigUpdate(_) => Address::ZERO,
+            Action::OracleUpdate(o) => o.from,
         }
     }
 
@@ -431,6 +441,10 @@ impl Action {
         matches!(self, Action::PoolConfigUpdate(_))
     }
 
+    pub const fn is_oracle_update(&self) -> bool {
+        matches!(self, Action::OracleUpdate(_))
+    }
+
     pub const fn is_unclassified(&self) -> bool {
         matches!(self, Action::Unclassified(_))
     }
@@ -448,6 +462,7 @@ impl Action {
             Action::NewPool(p) => p.protocol,
             Action::PoolConfigUpdate(p) => p.protocol,
             Action::Aggregator(a) => a.protocol,
+            Action::OracleUpdate(o) => o.protocol,
             _ => Protocol::Unknown,
         }
     }
@@ -526,7 +541,8 @@ extra_impls!(
     (FlashLoan, NormalizedFlashLoan),
     (Aggregator, NormalizedAggregator),
     (Batch, NormalizedBatch),
-    (NewPool, NormalizedNewPool)
+    (NewPool, NormalizedNewPool),
+    (OracleUpdate, NormalizedOracleUpdate)
 );
 
 /// Custom impl for itering over swaps and swap with fee
@@ -583,6 +599,7 @@ impl TokenAccounting for Action {
             Action::SelfDestruct(_self_destruct) => (),
             Action::NewPool(_new_pool) => (),
             Action::PoolConfigUpdate(_pool_update) => (),
+            Action::OracleUpdate(_oracle_update) => (),
             Action::Revert => (), // No token deltas to apply for a revert
         }
     }
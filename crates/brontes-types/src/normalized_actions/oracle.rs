@@ -0,0 +1,38 @@
+use std::fmt::{self, Debug};
+
+use alloy_primitives::{Address, U256};
+use clickhouse::Row;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::{db::token_info::TokenInfoWithAddress, Protocol};
+
+/// A price update pushed on chain by an oracle, e.g a Chainlink aggregator
+/// `transmit` or a Maker OSM `poke`.
+#[derive(Default, Debug, Serialize, Clone, Row, PartialEq, Eq, Deserialize)]
+pub struct NormalizedOracleUpdate {
+    pub protocol:    Protocol,
+    pub trace_index: u64,
+    /// The feed contract that was updated
+    pub oracle:      Address,
+    /// The account that pushed the update
+    pub from:        Address,
+    /// The asset priced by the feed
+    pub asset:       TokenInfoWithAddress,
+    /// The new answer, in the decimals of the feed
+    pub answer:      U256,
+}
+
+impl fmt::Display for NormalizedOracleUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Protocol {} - Oracle: {}, From: {}, Asset: {}, Answer: {}",
+            self.protocol.to_string().bold(),
+            format!("{}", self.oracle).cyan(),
+            format!("{}", self.from).cyan(),
+            self.asset.inner.symbol.bold(),
+            self.answer.to_string().green()
+        )
+    }
+}
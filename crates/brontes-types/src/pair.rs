@@ -2,12 +2,13 @@ use std::{hash::Hash, str::FromStr};
 
 use alloy_primitives::Address;
 use alloy_rlp::{BufMut, Decodable, Encodable};
+use itertools::Itertools;
 use redefined::Redefined;
 use reth_db::table::{Decode, Encode};
 use rkyv::{Archive, Deserialize as rDeserialize, Serialize as rSerialize};
 use serde::{Deserialize, Serialize};
 
-use crate::{constants::USD_STABLES_BY_ADDRESS, db::redefined_types::primitives::AddressRedefined};
+use crate::{db::redefined_types::primitives::AddressRedefined, ChainConfig};
 
 #[derive(
     Debug,
@@ -91,7 +92,18 @@ impl Pair {
     }
 
     pub fn is_usd_stable_pair(&self) -> bool {
-        USD_STABLES_BY_ADDRESS.contains(&self.0) && USD_STABLES_BY_ADDRESS.contains(&self.1)
+        ChainConfig::get().is_usd_stable(&self.0) && ChainConfig::get().is_usd_stable(&self.1)
+    }
+
+    /// returns every pair that can be traded through a pool with the given
+    /// tokens. for two token pools this is just the single pair
+    pub fn from_pool_tokens(tokens: &[Address]) -> Vec<Self> {
+        tokens
+            .iter()
+            .unique()
+            .tuple_combinations()
+            .map(|(t0, t1)| Pair(*t0, *t1))
+            .collect()
     }
 }
 
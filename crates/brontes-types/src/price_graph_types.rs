@@ -1,14 +1,14 @@
 use std::ops::{Deref, DerefMut};
 
 use alloy_primitives::Address;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{pair::Pair, FastHashMap, Protocol};
 
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct SubGraphsEntry(pub FastHashMap<u64, Vec<SubGraphEdge>>);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SubGraphEdge {
     pub info: PoolPairInfoDirection,
 }
@@ -57,6 +57,21 @@ impl PoolPairInfoDirection {
     }
 }
 
+impl Serialize for PoolPairInfoDirection {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.info, self.token_0_in).serialize(serializer)
+    }
+}
+
+/// the pool info is leaked in the same way as when the edge is first added to
+/// the graph
+impl<'de> Deserialize<'de> for PoolPairInfoDirection {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (info, token_0_in) = <(PoolPairInformation, bool)>::deserialize(deserializer)?;
+        Ok(Self::new(Box::leak(Box::new(info)), token_0_in))
+    }
+}
+
 impl Deref for PoolPairInfoDirection {
     type Target = PoolPairInformation;
 
@@ -102,6 +102,17 @@ utils!(
         ClipperExchange,
         PropellerLabsSolver,
         Dodo,
+        UniswapV4,
+        MorphoBlue,
+        CompoundV3,
+        Spark,
+        MakerClipper,
+        MakerOSM,
+        ChainlinkAggregator,
+        ParaswapV5,
+        KyberSwap,
+        OdosV2,
+        MakerDog,
         #[default]
         Unknown,
     }
@@ -147,6 +158,17 @@ impl Protocol {
             Protocol::ClipperExchange => ("ClipperExchange", ""),
             Protocol::PropellerLabsSolver => ("Propeller Labs Solver", ""),
             Protocol::Dodo => ("Dodo", "V1/V2"),
+            Protocol::UniswapV4 => ("Uniswap", "V4"),
+            Protocol::MorphoBlue => ("Morpho", "Blue"),
+            Protocol::CompoundV3 => ("Compound", "V3"),
+            Protocol::Spark => ("Spark", ""),
+            Protocol::MakerClipper => ("Maker", "Clipper"),
+            Protocol::MakerOSM => ("Maker", "OSM"),
+            Protocol::ChainlinkAggregator => ("Chainlink", "Aggregator"),
+            Protocol::ParaswapV5 => ("Paraswap", "V5"),
+            Protocol::KyberSwap => ("KyberSwap", "MetaAggregationV2"),
+            Protocol::OdosV2 => ("Odos", "V2"),
+            Protocol::MakerDog => ("Maker", "Dog"),
             Protocol::Unknown => ("Unknown", "Unknown"),
         }
     }
@@ -156,6 +178,7 @@ impl Protocol {
             "uniswapv2" => Protocol::UniswapV2,
             "sushiswapv2" => Protocol::SushiSwapV2,
             "uniswapv3" => Protocol::UniswapV3,
+            "uniswapv4" => Protocol::UniswapV4,
             "sushiswapv3" => Protocol::SushiSwapV3,
             "curve.fibase2" => Protocol::CurveBasePool2,
             "curve.fibase3" => Protocol::CurveBasePool3,
@@ -222,6 +245,17 @@ impl fmt::Display for Protocol {
                 Protocol::ClipperExchange => "Clipper",
                 Protocol::PropellerLabsSolver => "Propeller Labs",
                 Protocol::Dodo => "Dodo",
+                Protocol::UniswapV4 => "Uni V4",
+                Protocol::MorphoBlue => "Morpho Blue",
+                Protocol::CompoundV3 => "Compound V3",
+                Protocol::Spark => "Spark",
+                Protocol::MakerClipper => "Maker Clipper",
+                Protocol::MakerOSM => "Maker OSM",
+                Protocol::ChainlinkAggregator => "Chainlink",
+                Protocol::ParaswapV5 => "Paraswap V5",
+                Protocol::KyberSwap => "KyberSwap",
+                Protocol::OdosV2 => "Odos V2",
+                Protocol::MakerDog => "Maker Dog",
                 Protocol::Unknown => "Unknown",
             }
         )
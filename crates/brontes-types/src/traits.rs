@@ -52,6 +52,15 @@ pub trait TracingProvider: Send + Sync + 'static {
 
     async fn block_and_tx_index(&self, hash: TxHash) -> eyre::Result<(u64, usize)>;
 
+    /// Replays the block up to `tx_hash` leaving out the transactions in
+    /// `skip`, returns the logs `tx_hash` emits on that state
+    async fn replay_transaction_without(
+        &self,
+        block_number: u64,
+        tx_hash: TxHash,
+        skip: Vec<TxHash>,
+    ) -> eyre::Result<Vec<alloy_primitives::Log>>;
+
     // DB Access Methods
     async fn get_storage(
         &self,
@@ -17,9 +17,11 @@ mod types;
 pub mod util;
 pub use util::*;
 pub mod root;
+pub mod tx_call;
 pub mod tx_info;
 pub use node::*;
 pub use root::*;
+pub use tx_call::*;
 pub use tx_info::*;
 pub mod search_args;
 pub use search_args::*;
@@ -32,6 +34,8 @@ type ClassifyData<V> = Option<(usize, Vec<MultiCallFrameClassification<V>>)>;
 #[derive(Debug, Clone)]
 pub struct BlockTree<V: NormalizedAction> {
     pub tx_roots:             Vec<Root<V>>,
+    /// transactions whose top level call reverted
+    pub failed_txs:           Vec<FailedTx>,
     pub header:               Header,
     pub priority_fee_std_dev: f64,
     pub avg_priority_fee:     f64,
@@ -41,6 +45,7 @@ impl<V: NormalizedAction> BlockTree<V> {
     pub fn new(header: Header, tx_num: usize) -> Self {
         Self {
             tx_roots: Vec::with_capacity(tx_num),
+            failed_txs: Vec::new(),
             header,
             priority_fee_std_dev: 0.0,
             avg_priority_fee: 0.0,
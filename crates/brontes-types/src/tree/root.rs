@@ -8,7 +8,7 @@ use redefined::self_convert_redefined;
 use reth_primitives::{Address, B256};
 use serde::{Deserialize, Serialize};
 
-use super::Node;
+use super::{Node, TxCall};
 use crate::{
     db::{
         address_metadata::AddressMetadata, metadata::Metadata, searcher::SearcherInfo,
@@ -55,6 +55,8 @@ pub struct Root<V: NormalizedAction> {
     pub position: usize,
     pub tx_hash: B256,
     pub private: bool,
+    /// the top level call of the transaction
+    pub call: TxCall,
     pub gas_details: GasDetails,
     /// all msg.value transfers that aren't classified as
     /// eth transfers
@@ -237,6 +239,26 @@ impl<V: NormalizedAction> Root<V> {
         self.position
     }
 
+    /// Whether the transaction made a call but didn't swap, transfer a token
+    /// or receive eth
+    pub fn is_no_op(&self) -> bool {
+        self.call.selector().is_some()
+            && self.total_msg_value_transfers.is_empty()
+            && !self
+                .data_store
+                .0
+                .iter()
+                .flatten()
+                .flatten()
+                .map(|action| action.get_action())
+                .any(|action| {
+                    action.is_swap()
+                        || action.is_transfer()
+                        || action.is_eth_transfer()
+                        || action.is_nested_action()
+                })
+    }
+
     pub fn insert(&mut self, node: Node, data: Vec<V>) {
         self.head.insert(node, data, &mut self.data_store);
     }
@@ -488,6 +510,57 @@ pub enum FalsePositiveEntity {
     MaestroBots,
 }
 
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Bytes, U256};
+
+    use super::*;
+    use crate::normalized_actions::NormalizedSwap;
+
+    fn root(input: &[u8], actions: Vec<Action>) -> Root<Action> {
+        let mut data_store = NodeData(vec![]);
+        let mut head = Node::new(0, Address::ZERO, vec![]);
+        head.data = data_store.add(actions);
+
+        Root {
+            head,
+            position: 0,
+            tx_hash: B256::ZERO,
+            private: false,
+            call: TxCall {
+                to:    Address::with_last_byte(1),
+                value: U256::ZERO,
+                input: Bytes::copy_from_slice(input),
+            },
+            gas_details: GasDetails::default(),
+            total_msg_value_transfers: vec![],
+            data_store,
+        }
+    }
+
+    #[test]
+    fn test_is_no_op() {
+        let selector = [0x12, 0x34, 0x56, 0x78];
+
+        // a call that didn't move anything
+        assert!(root(&selector, vec![]).is_no_op());
+        assert!(root(&selector, vec![Action::Revert]).is_no_op());
+
+        // a plain eth transfer isn't a contract call
+        assert!(!root(&[], vec![]).is_no_op());
+
+        assert!(!root(&selector, vec![Action::Swap(NormalizedSwap::default())]).is_no_op());
+        assert!(!root(&selector, vec![Action::Transfer(Default::default())]).is_no_op());
+        assert!(!root(&selector, vec![Action::EthTransfer(Default::default())]).is_no_op());
+
+        let mut received_eth = root(&selector, vec![]);
+        received_eth
+            .total_msg_value_transfers
+            .push(NormalizedEthTransfer::default());
+        assert!(!received_eth.is_no_op());
+    }
+}
+
 /*
 #[cfg(test)]
 pub mod test {
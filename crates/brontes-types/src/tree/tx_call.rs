@@ -0,0 +1,88 @@
+use alloy_primitives::{Address, Bytes, B256, U256};
+
+use super::GasDetails;
+use crate::structured_trace::{TraceActions, TransactionTraceWithLogs, TxTrace};
+
+/// The top level call of a transaction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TxCall {
+    pub to:    Address,
+    pub value: U256,
+    pub input: Bytes,
+}
+
+impl TxCall {
+    pub fn from_trace(trace: &TransactionTraceWithLogs) -> Self {
+        Self {
+            to:    trace.get_to_address(),
+            value: trace.get_msg_value(),
+            input: trace.get_calldata(),
+        }
+    }
+
+    pub fn selector(&self) -> Option<[u8; 4]> {
+        self.input.get(0..4).map(|s| s.try_into().unwrap())
+    }
+
+    /// The share of the 32 byte calldata words of both calls that are equal,
+    /// zero if the calls go to different functions.
+    pub fn calldata_similarity(&self, other: &TxCall) -> f64 {
+        if self.to != other.to || self.selector().is_none() || self.selector() != other.selector() {
+            return 0.0
+        }
+
+        let words = |input: &Bytes| {
+            input[4..]
+                .chunks(32)
+                .map(|w| w.to_vec())
+                .collect::<Vec<_>>()
+        };
+        let (ours, theirs) = (words(&self.input), words(&other.input));
+        let max_len = ours.len().max(theirs.len());
+        if max_len == 0 {
+            return 1.0
+        }
+
+        let equal = ours
+            .iter()
+            .zip(theirs.iter())
+            .filter(|(a, b)| a == b)
+            .count();
+
+        equal as f64 / max_len as f64
+    }
+}
+
+/// A transaction whose top level call reverted. Reverted transactions aren't
+/// classified, so only their call and gas are kept in the block tree.
+#[derive(Debug, Clone)]
+pub struct FailedTx {
+    pub tx_hash:     B256,
+    pub position:    usize,
+    pub eoa:         Address,
+    pub call:        TxCall,
+    pub gas_details: GasDetails,
+}
+
+impl FailedTx {
+    /// None if the transaction succeeded or has no trace
+    pub fn from_trace(trace: &TxTrace, base_fee: u128) -> Option<Self> {
+        if trace.is_success {
+            return None
+        }
+        let root = trace.trace.first()?;
+
+        Some(Self {
+            tx_hash:     trace.tx_hash,
+            position:    trace.tx_index as usize,
+            eoa:         root.get_from_addr(),
+            call:        TxCall::from_trace(root),
+            gas_details: GasDetails {
+                coinbase_transfer:   None,
+                gas_used:            trace.gas_used,
+                effective_gas_price: trace.effective_price,
+                priority_fee:        trace.effective_price.saturating_sub(base_fee),
+            },
+        })
+    }
+}
@@ -12,7 +12,7 @@ use reth_blockchain_tree::{
 use reth_db::{mdbx::DatabaseArguments, DatabaseEnv};
 use reth_network_api::noop::NoopNetwork;
 use reth_node_ethereum::EthEvmConfig;
-use reth_primitives::{BlockId, PruneModes, MAINNET};
+use reth_primitives::{BlockId, ChainSpec, Genesis, PruneModes, GOERLI, HOLESKY, MAINNET, SEPOLIA};
 use reth_provider::{providers::BlockchainProvider, ProviderFactory};
 use reth_revm::{inspectors::GasInspector, EvmProcessorFactory};
 use reth_rpc::{
@@ -62,8 +62,8 @@ impl TracingClient {
         max_tasks: u64,
         task_executor: BrontesTaskExecutor,
         static_files_path: PathBuf,
+        chain: Arc<ChainSpec>,
     ) -> Self {
-        let chain = MAINNET.clone();
         let provider_factory =
             ProviderFactory::new(Arc::clone(&db), Arc::clone(&chain), static_files_path)
                 .expect("failed to start provider factory");
@@ -135,12 +135,17 @@ impl TracingClient {
         Self { api, trace, provider_factory }
     }
 
-    pub fn new(db_path: &Path, max_tasks: u64, task_executor: BrontesTaskExecutor) -> Self {
+    pub fn new(
+        db_path: &Path,
+        max_tasks: u64,
+        task_executor: BrontesTaskExecutor,
+        chain: Arc<ChainSpec>,
+    ) -> Self {
         let db = Arc::new(init_db(db_path).unwrap());
         let mut static_files = db_path.to_path_buf();
         static_files.pop();
         static_files.push("static_files");
-        Self::new_with_db(db, max_tasks, task_executor, static_files)
+        Self::new_with_db(db, max_tasks, task_executor, static_files, chain)
     }
 
     /// Replays all transactions in a block using a custom inspector for each
@@ -181,6 +186,24 @@ pub struct StackStep {
     _step_idx:  usize,
 }
 
+/// Resolves the chain of the chain config, either a chain known to reth or the
+/// path to a genesis file.
+pub fn chain_spec(chain: &str) -> eyre::Result<Arc<ChainSpec>> {
+    Ok(match chain {
+        "mainnet" => MAINNET.clone(),
+        "sepolia" => SEPOLIA.clone(),
+        "holesky" => HOLESKY.clone(),
+        "goerli" => GOERLI.clone(),
+        genesis_path => {
+            let genesis = std::fs::read_to_string(genesis_path)
+                .map_err(|e| eyre::eyre!("failed to read genesis file {genesis_path}: {e}"))?;
+            let genesis: Genesis = serde_json::from_str(&genesis)
+                .map_err(|e| eyre::eyre!("invalid genesis file {genesis_path}: {e}"))?;
+            Arc::new(genesis.into())
+        }
+    })
+}
+
 /// Opens up an existing database at the specified path.
 pub fn init_db<P: AsRef<Path> + Debug>(path: P) -> eyre::Result<DatabaseEnv> {
     reth_db::open_db_read_only(path.as_ref(), DatabaseArguments::new(Default::default()))
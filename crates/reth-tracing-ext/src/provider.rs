@@ -4,10 +4,12 @@ use alloy_rpc_types::AnyReceiptEnvelope;
 use brontes_types::{structured_trace::TxTrace, traits::TracingProvider};
 use eyre::eyre;
 use reth_primitives::{
-    Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Bytes, Header, StorageValue, TxHash,
-    B256, U256,
+    revm::env::tx_env_with_recovered, Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode,
+    Bytes, Header, StorageValue, TxHash, B256, U256,
+};
+use reth_provider::{
+    BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, TransactionVariant,
 };
-use reth_provider::{BlockIdReader, BlockNumReader, HeaderProvider};
 use reth_revm::{database::StateProviderDatabase, db::CacheDB};
 use reth_rpc::eth::{
     error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
@@ -21,7 +23,7 @@ use revm::{
     primitives::{
         db::DatabaseRef, BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, TransactTo, TxEnv,
     },
-    Database,
+    Database, DatabaseCommit,
 };
 use revm_primitives::ExecutionResult;
 
@@ -107,6 +109,54 @@ impl TracingProvider for TracingClient {
         Ok((tx.block_number.unwrap(), tx.transaction_index.unwrap() as usize))
     }
 
+    async fn replay_transaction_without(
+        &self,
+        block_number: u64,
+        tx_hash: TxHash,
+        skip: Vec<TxHash>,
+    ) -> eyre::Result<Vec<alloy_primitives::Log>> {
+        let block = self
+            .provider_factory
+            .block_with_senders(block_number.into(), TransactionVariant::WithHash)?
+            .ok_or_else(|| eyre!("no block found"))?;
+        let (cfg, block_env, _) = self.api.evm_env_at(block_number.into()).await?;
+        let state = self.api.state_at(block.parent_hash.into())?;
+        let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+        for tx in block.into_transactions_ecrecovered() {
+            let hash = tx.hash();
+            if skip.contains(&hash) {
+                continue
+            }
+
+            let env = EnvWithHandlerCfg::new_with_cfg_env(
+                cfg.clone(),
+                block_env.clone(),
+                tx_env_with_recovered(&tx),
+            );
+            let res = match self.api.transact(&mut db, env) {
+                Ok((res, _)) => res,
+                // leaving out the skipped txs can invalidate later txs of the same
+                // senders, e.g. through a nonce gap. These are left out as well
+                Err(EthApiError::InvalidTransaction(e)) if hash != tx_hash => {
+                    tracing::debug!(?hash, error = %e, "skipping invalid replayed transaction");
+                    continue
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            if hash == tx_hash {
+                return match res.result {
+                    ExecutionResult::Success { logs, .. } => Ok(logs),
+                    _ => Err(eyre!("transaction failed on the replayed state")),
+                }
+            }
+            db.commit(res.state);
+        }
+
+        Err(eyre!("transaction not found in block"))
+    }
+
     async fn header_by_number(&self, number: BlockNumber) -> eyre::Result<Option<Header>> {
         self.trace
             .provider()